@@ -17,6 +17,7 @@
 //! Polkadot CLI library.
 
 use clap::Parser;
+use std::path::PathBuf;
 
 #[allow(missing_docs)]
 #[derive(Debug, Parser)]
@@ -59,6 +60,10 @@ pub enum Subcommand {
 	/// capabilities of running a validator.
 	HostPerfCheck,
 
+	/// Precheck and compile a PVF the way validators do, and optionally execute a candidate with
+	/// it. Useful to check whether a validation code upgrade will pass PVF pre-checking.
+	Pvf(PvfCmd),
+
 	/// Try some command against runtime state.
 	#[cfg(feature = "try-runtime")]
 	TryRuntime(try_runtime_cli::TryRuntimeCmd),
@@ -82,6 +87,33 @@ pub struct ValidationWorkerCommand {
 	pub socket_path: String,
 }
 
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct PvfCmd {
+	/// The path to the validation code, which may be compressed.
+	pub code: PathBuf,
+
+	/// The path to the SCALE-encoded PoV of a candidate to execute, which may be compressed.
+	#[arg(long, requires = "parent_head")]
+	pub pov: Option<PathBuf>,
+
+	/// The path to the head data of the parent of the candidate to execute.
+	#[arg(long, requires = "pov")]
+	pub parent_head: Option<PathBuf>,
+
+	/// The number of the relay parent the candidate is executed at.
+	#[arg(long, default_value_t = 0)]
+	pub relay_parent_number: u32,
+
+	/// The storage root of the relay parent the candidate is executed at. Defaults to zero.
+	#[arg(long)]
+	pub relay_parent_storage_root: Option<sp_core::H256>,
+
+	/// The execution timeout in seconds. Defaults to the timeout used for approval checking.
+	#[arg(long)]
+	pub execution_timeout: Option<u64>,
+}
+
 #[allow(missing_docs)]
 #[derive(Debug, Parser)]
 #[group(skip)]
@@ -143,6 +175,85 @@ pub struct RunCmd {
 	/// **Dangerous!** Do not touch unless explicitly adviced to.
 	#[arg(long)]
 	pub overseer_channel_capacity_override: Option<usize>,
+
+	/// Sandbox the PVF preparation and execution workers (Linux only).
+	///
+	/// The workers are denied networking and their filesystem access is restricted, as far as
+	/// supported by the system. The enforced measures are printed out in the logs.
+	#[arg(long)]
+	pub pvf_sandbox: bool,
+
+	/// Consider blocks which are not approved within the given number of seconds as stagnant
+	/// and stop building on them.
+	///
+	/// By default, stagnant blocks are not detected.
+	#[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+	pub stagnant_timeout: Option<u64>,
+
+	/// Remove blocks which are still stagnant after the given number of seconds from the chain
+	/// selection database, along with their descendants.
+	#[arg(
+		long,
+		value_name = "SECONDS",
+		requires = "stagnant_timeout",
+		value_parser = clap::value_parser!(u64).range(1..),
+	)]
+	pub prune_stagnant_after: Option<u64>,
+
+	/// Keep the availability data of candidates which are not included in any block for the
+	/// given number of seconds. Defaults to one hour.
+	#[arg(long, value_name = "SECONDS")]
+	pub availability_keep_unavailable_for: Option<u64>,
+
+	/// Keep the availability data of finalized candidates for the given number of seconds.
+	/// Defaults to and must be at least 25 hours, so the data is available for disputes.
+	#[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(90_000..))]
+	pub availability_keep_finalized_for: Option<u64>,
+
+	/// Prune the availability store every given number of seconds. Defaults to five minutes.
+	#[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+	pub availability_pruning_interval: Option<u64>,
+
+	/// Limit the size of the availability store. Once the limit is exceeded, the data of the
+	/// candidates finalized the longest time ago is evicted early, on every pruning.
+	///
+	/// Data finalized less than 25 hours ago is never evicted, as it may still be disputed, so
+	/// the store may grow beyond the limit. The limit is therefore only effective together with a
+	/// longer `--availability-keep-finalized-for`, e.g. on archive nodes.
+	#[arg(long, value_name = "MiB", requires = "availability_keep_finalized_for")]
+	pub availability_max_size: Option<u64>,
+
+	/// Append every dispute concluding on this node, with all its votes and the resulting
+	/// slashes, to `dispute-audit.jsonl` in the database directory.
+	///
+	/// Requires the node to be built with the `dispute-audit-log` feature.
+	#[arg(long)]
+	pub dispute_audit_log: bool,
+
+	/// Rotate the dispute audit log once it exceeds the given size. Defaults to 100 MiB.
+	#[arg(
+		long,
+		value_name = "MiB",
+		requires = "dispute_audit_log",
+		value_parser = clap::value_parser!(u64).range(1..),
+	)]
+	pub dispute_audit_log_max_size: Option<u64>,
+
+	/// Sign and distribute local approvals of up to the given number of candidates in the same
+	/// relay chain block as a single vote. Defaults to 1, which disables coalescing.
+	///
+	/// Coalesced approvals can't be understood by nodes which only support version 1 of the
+	/// approval distribution protocol.
+	#[arg(long, value_name = "COUNT", value_parser = clap::value_parser!(u32).range(1..))]
+	pub max_approval_coalesce_count: Option<u32>,
+
+	/// Produce compact assignment certificates, which claim all cores assigned to this node by
+	/// the relay chain VRF at once.
+	///
+	/// Compact assignments can't be understood by nodes which only support version 1 of the
+	/// approval distribution protocol.
+	#[arg(long)]
+	pub enable_compact_assignments: bool,
 }
 
 #[allow(missing_docs)]
@@ -25,7 +25,7 @@ use sc_cli::{RuntimeVersion, SubstrateCli};
 use service::{self, HeaderBackend, IdentifyVariant};
 use sp_core::crypto::Ss58AddressFormatRegistry;
 use sp_keyring::Sr25519Keyring;
-use std::net::ToSocketAddrs;
+use std::{net::ToSocketAddrs, time::Duration};
 
 pub use crate::{error::Error, service::BlockId};
 #[cfg(feature = "hostperfcheck")]
@@ -311,6 +311,46 @@ where
 		Some((cli.run.grandpa_pause[0], cli.run.grandpa_pause[1]))
 	};
 
+	let stagnant_check = cli.run.stagnant_timeout.map(|timeout| {
+		let mode = match cli.run.prune_stagnant_after {
+			None => service::StagnantCheckMode::CheckAndPrune,
+			Some(grace_period) => service::StagnantCheckMode::CheckAndPruneSubtrees {
+				grace_period: Duration::from_secs(grace_period),
+			},
+		};
+		(mode, Duration::from_secs(timeout))
+	});
+
+	let availability_pruning = {
+		let default = service::AvailabilityPruningConfig::default();
+		service::AvailabilityPruningConfig {
+			keep_unavailable_for: cli
+				.run
+				.availability_keep_unavailable_for
+				.map_or(default.keep_unavailable_for, Duration::from_secs),
+			keep_finalized_for: cli
+				.run
+				.availability_keep_finalized_for
+				.map_or(default.keep_finalized_for, Duration::from_secs),
+			pruning_interval: cli
+				.run
+				.availability_pruning_interval
+				.map_or(default.pruning_interval, Duration::from_secs),
+			max_size: cli.run.availability_max_size.map(|mib| mib.saturating_mul(1024 * 1024)),
+		}
+	};
+
+	let approval_voting = {
+		let default = service::ApprovalVotingOptions::default();
+		service::ApprovalVotingOptions {
+			max_approval_coalesce_count: cli
+				.run
+				.max_approval_coalesce_count
+				.unwrap_or(default.max_approval_coalesce_count),
+			enable_compact_assignments: cli.run.enable_compact_assignments,
+		}
+	};
+
 	if chain_spec.is_kusama() {
 		info!("----------------------------");
 		info!("This chain is not in any way");
@@ -339,6 +379,15 @@ where
 			}))
 			.flatten();
 
+		let dispute_audit_log =
+			config.database.path().filter(|_| cli.run.dispute_audit_log).map(|path| {
+				let mut audit_log =
+					service::DisputeAuditLogConfig::new(path.join("dispute-audit.jsonl"));
+				if let Some(mib) = cli.run.dispute_audit_log_max_size {
+					audit_log.max_file_size = mib.saturating_mul(1024 * 1024);
+				}
+				audit_log
+			});
 		let database_source = config.database.clone();
 		let task_manager = service::build_full(
 			config,
@@ -347,6 +396,12 @@ where
 			cli.run.beefy,
 			jaeger_agent,
 			None,
+			cli.run.pvf_sandbox,
+			stagnant_check,
+			availability_pruning,
+			None,
+			dispute_audit_log,
+			approval_voting,
 			false,
 			overseer_gen,
 			cli.run.overseer_channel_capacity_override,
@@ -645,6 +700,24 @@ pub fn run() -> Result<()> {
 
 			host_perf_check()
 		},
+		Some(Subcommand::Pvf(cmd)) => {
+			let mut builder = sc_cli::LoggerBuilder::new("");
+			builder.with_colors(true);
+			builder.init()?;
+
+			#[cfg(target_os = "android")]
+			{
+				return Err(sc_cli::Error::Input(
+					"PVF workers are not supported under this platform".into(),
+				)
+				.into())
+			}
+
+			#[cfg(not(target_os = "android"))]
+			{
+				crate::pvf::run(cmd)
+			}
+		},
 		Some(Subcommand::Key(cmd)) => Ok(cmd.run(&cli)?),
 		#[cfg(feature = "try-runtime")]
 		Some(Subcommand::TryRuntime(cmd)) => {
@@ -26,6 +26,8 @@ mod command;
 mod error;
 #[cfg(all(feature = "hostperfcheck", build_type = "release"))]
 mod host_perf_check;
+#[cfg(all(feature = "cli", not(target_os = "android")))]
+mod pvf;
 
 #[cfg(feature = "full-node")]
 pub use service::RuntimeApiCollection;
@@ -0,0 +1,154 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Checking a PVF offline, the way validators prepare and execute it.
+
+use crate::{cli::PvfCmd, error::Error};
+use futures::channel::oneshot;
+use log::info;
+use parity_scale_codec::{Decode, Encode};
+use polkadot_node_core_pvf::{
+	sp_maybe_compressed_blob, start, Config, Metrics, PrepareStats, Priority,
+	PvfWithExecutorParams, CHECKSUM_SUFFIX,
+};
+use polkadot_node_primitives::{
+	PoV, APPROVAL_EXECUTION_TIMEOUT, POV_BOMB_LIMIT, VALIDATION_CODE_BOMB_LIMIT,
+};
+use polkadot_parachain::primitives::{HeadData, ValidationParams};
+use polkadot_primitives::vstaging::ExecutorParams;
+use std::{
+	path::Path,
+	time::{Duration, Instant},
+};
+
+/// Prechecks the PVF with a throwaway validation host and, if a PoV is given, executes it.
+///
+/// The workers are spawned from the current executable, just like a validator does.
+pub fn run(cmd: &PvfCmd) -> Result<(), Error> {
+	let code = read(&cmd.code)?;
+	let code = sp_maybe_compressed_blob::decompress(&code, VALIDATION_CODE_BOMB_LIMIT)
+		.map_err(|e| format!("Failed to decompress the validation code: {}", e))?
+		.into_owned();
+	let validation_params = match (&cmd.pov, &cmd.parent_head) {
+		(Some(pov), Some(parent_head)) => Some(validation_params(cmd, pov, parent_head)?),
+		_ => None,
+	};
+
+	let program_path = std::env::current_exe()
+		.map_err(|e| format!("Failed to get the path of the executable: {}", e))?;
+	let cache_dir = tempfile::tempdir()
+		.map_err(|e| format!("Failed to create the artifact cache directory: {}", e))?;
+	let runtime =
+		sc_cli::build_runtime().map_err(|e| format!("Failed to build the tokio runtime: {}", e))?;
+
+	let (mut host, task) =
+		start(Config::new(cache_dir.path().to_owned(), program_path), Metrics::default());
+	runtime.spawn(task);
+
+	runtime.block_on(async move {
+		let pvf = PvfWithExecutorParams::from_code(code, ExecutorParams::default());
+
+		info!("Prechecking the PVF...");
+		let (result_tx, result_rx) = oneshot::channel();
+		host.precheck_pvf(pvf.clone(), result_tx).await?;
+		let stats = result_rx
+			.await
+			.map_err(|_| "The validation host hung up".to_owned())?
+			.map_err(|e| format!("PVF prechecking failed: {}", e))?;
+		report_prepare_stats(&stats, artifact_size(cache_dir.path())?);
+
+		let validation_params = match validation_params {
+			Some(validation_params) => validation_params,
+			None => return Ok(()),
+		};
+
+		info!("Executing the candidate...");
+		let timeout = cmd.execution_timeout.map_or(APPROVAL_EXECUTION_TIMEOUT, Duration::from_secs);
+		let (result_tx, result_rx) = oneshot::channel();
+		let start = Instant::now();
+		host.execute_pvf(pvf, timeout, validation_params.encode(), Priority::Critical, result_tx)
+			.await?;
+		let result = result_rx
+			.await
+			.map_err(|_| "The validation host hung up".to_owned())?
+			.map_err(|e| format!("PVF execution failed: {:?}", e))?;
+
+		info!("✅ Candidate executed in {:?}", start.elapsed());
+		info!("  new head hash: {:?}", result.head_data.hash());
+		info!("  new validation code: {}", result.new_validation_code.is_some());
+		info!("  upward messages: {}", result.upward_messages.len());
+		info!("  horizontal messages: {}", result.horizontal_messages.len());
+		info!("  processed downward messages: {}", result.processed_downward_messages);
+		info!("  HRMP watermark: {}", result.hrmp_watermark);
+
+		Ok(())
+	})
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, Error> {
+	std::fs::read(path)
+		.map_err(|e| Error::Other(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+fn validation_params(
+	cmd: &PvfCmd,
+	pov: &Path,
+	parent_head: &Path,
+) -> Result<ValidationParams, Error> {
+	let pov = read(pov)?;
+	let pov = sp_maybe_compressed_blob::decompress(&pov, POV_BOMB_LIMIT)
+		.map_err(|e| format!("Failed to decompress the PoV: {}", e))?;
+	let pov = PoV::decode(&mut &pov[..]).map_err(|e| format!("Failed to decode the PoV: {}", e))?;
+
+	Ok(ValidationParams {
+		parent_head: HeadData(read(parent_head)?),
+		block_data: pov.block_data,
+		relay_parent_number: cmd.relay_parent_number,
+		relay_parent_storage_root: cmd.relay_parent_storage_root.unwrap_or_default(),
+	})
+}
+
+/// Returns the size of the artifact prepared into the otherwise empty `cache_dir`.
+fn artifact_size(cache_dir: &Path) -> Result<u64, Error> {
+	let read_err = |e| Error::Other(format!("Failed to read the artifact cache: {}", e));
+	let mut size = 0;
+	for entry in std::fs::read_dir(cache_dir).map_err(read_err)? {
+		let entry = entry.map_err(read_err)?;
+		if !entry.file_name().to_string_lossy().ends_with(CHECKSUM_SUFFIX) {
+			size += entry.metadata().map_err(read_err)?.len();
+		}
+	}
+	Ok(size)
+}
+
+fn report_prepare_stats(stats: &PrepareStats, artifact_size: u64) {
+	info!("✅ PVF prechecking passed");
+	info!("  compile time (CPU): {:?}", stats.cpu_time_elapsed());
+	info!("  artifact size: {} bytes", artifact_size);
+
+	let memory_stats = stats.memory_stats();
+	match memory_stats.max_rss_kb() {
+		Some(max_rss) => info!("  peak resident memory (ru_maxrss): {} KiB", max_rss),
+		None => info!("  peak resident memory (ru_maxrss): not available"),
+	}
+	match memory_stats.max_allocation_stats() {
+		Some(max_stats) => {
+			info!("  peak resident memory (jemalloc): {} KiB", max_stats.resident / 1024);
+			info!("  peak allocated memory (jemalloc): {} KiB", max_stats.allocated / 1024);
+		},
+		None => info!("  peak memory (jemalloc): not available"),
+	}
+}
@@ -96,6 +96,17 @@ pub const fn recovery_threshold(n_validators: usize) -> Result<usize, Error> {
 	Ok(needed + 1)
 }
 
+/// Obtain the number of systematic chunks that should be enough to recover the data.
+///
+/// The systematic chunks are the first `systematic_recovery_threshold` chunks, which hold the
+/// encoded payload itself. This is never larger than [`recovery_threshold`], as the underlying
+/// code rounds the number of data shards down to a power of two.
+pub fn systematic_recovery_threshold(n_validators: usize) -> Result<usize, Error> {
+	let k_wanted = recovery_threshold(n_validators)?;
+	// the number of data shards used by `novelpoly` is the next lower power of 2.
+	Ok(1 << (usize::BITS - 1 - k_wanted.leading_zeros()))
+}
+
 fn code_params(n_validators: usize) -> Result<CodeParams, Error> {
 	// we need to be able to reconstruct from 1/3 - eps
 
@@ -204,6 +215,58 @@ where
 	Decode::decode(&mut &payload_bytes[..]).or_else(|_e| Err(Error::BadPayload))
 }
 
+/// Reconstruct the v1 available data from the set of systematic chunks.
+///
+/// Provide a vector containing chunk data, ordered by chunk index. The first
+/// [`systematic_recovery_threshold`] chunks must be present; any further chunks are ignored.
+///
+/// Works only up to 65536 validators, and `n_validators` must be non-zero.
+pub fn reconstruct_from_systematic_v1(
+	n_validators: usize,
+	chunks: Vec<Vec<u8>>,
+) -> Result<AvailableData, Error> {
+	reconstruct_from_systematic(n_validators, chunks)
+}
+
+/// Reconstruct decodable data from the set of systematic chunks.
+///
+/// As the erasure code is systematic, the first `k` chunks contain the encoded payload itself,
+/// interleaved two bytes at a time. This means the data can be recovered by concatenating those
+/// chunks, skipping the much more expensive decoding of [`reconstruct`].
+///
+/// Provide a vector containing chunk data, ordered by chunk index. If too few chunks are
+/// provided, recovery is not possible.
+///
+/// Works only up to 65536 validators, and `n_validators` must be non-zero.
+pub fn reconstruct_from_systematic<T: Decode>(
+	n_validators: usize,
+	chunks: Vec<Vec<u8>>,
+) -> Result<T, Error> {
+	let k = systematic_recovery_threshold(n_validators)?;
+
+	if chunks.len() < k {
+		return Err(Error::NotEnoughChunks)
+	}
+
+	let shard_len = chunks[0].len();
+	if shard_len % 2 != 0 {
+		return Err(Error::UnevenLength)
+	}
+
+	if chunks.iter().take(k).any(|chunk| chunk.len() != shard_len || chunk.is_empty()) {
+		return Err(Error::NonUniformChunks)
+	}
+
+	let mut payload_bytes = Vec::with_capacity(shard_len * k);
+	for i in (0..shard_len).step_by(2) {
+		for chunk in chunks.iter().take(k) {
+			payload_bytes.extend_from_slice(&chunk[i..i + 2]);
+		}
+	}
+
+	Decode::decode(&mut &payload_bytes[..]).or_else(|_e| Err(Error::BadPayload))
+}
+
 /// An iterator that yields merkle branches and chunk data for all chunks to
 /// be sent to other validators.
 pub struct Branches<'a, I> {
@@ -379,6 +442,40 @@ mod tests {
 		assert_eq!(reconstructed, available_data);
 	}
 
+	#[test]
+	fn systematic_round_trip_works() {
+		let pov = PoV { block_data: BlockData((0..255).collect()) };
+
+		let available_data = AvailableData { pov: pov.into(), validation_data: Default::default() };
+
+		for n_validators in [2, 5, 10, 100, 1000] {
+			let chunks = obtain_chunks(n_validators, &available_data).unwrap();
+			let k = systematic_recovery_threshold(n_validators).unwrap();
+
+			assert!(k <= recovery_threshold(n_validators).unwrap());
+
+			let reconstructed: AvailableData =
+				reconstruct_from_systematic(n_validators, chunks.into_iter().take(k).collect())
+					.unwrap();
+
+			assert_eq!(reconstructed, available_data);
+		}
+	}
+
+	#[test]
+	fn systematic_reconstruct_needs_enough_chunks() {
+		let pov = PoV { block_data: BlockData((0..255).collect()) };
+
+		let available_data = AvailableData { pov: pov.into(), validation_data: Default::default() };
+		let chunks = obtain_chunks(10, &available_data).unwrap();
+		let k = systematic_recovery_threshold(10).unwrap();
+
+		assert_eq!(
+			reconstruct_from_systematic_v1(10, chunks.into_iter().take(k - 1).collect()),
+			Err(Error::NotEnoughChunks),
+		);
+	}
+
 	#[test]
 	fn reconstruct_does_not_panic_on_low_validator_count() {
 		let reconstructed = reconstruct_v1(1, [].iter().cloned());
@@ -18,6 +18,7 @@
 
 use bitvec::{order::Lsb0 as BitOrderLsb0, slice::BitSlice};
 use polkadot_node_primitives::approval::DelayTranche;
+use polkadot_node_subsystem::messages::RequiredTranchesDetails;
 use polkadot_primitives::ValidatorIndex;
 
 use crate::{
@@ -63,6 +64,36 @@ pub enum RequiredTranches {
 	},
 }
 
+impl From<RequiredTranches> for RequiredTranchesDetails {
+	fn from(required: RequiredTranches) -> Self {
+		match required {
+			RequiredTranches::All => RequiredTranchesDetails::All,
+			RequiredTranches::Pending {
+				considered,
+				next_no_show,
+				maximum_broadcast,
+				clock_drift,
+			} => RequiredTranchesDetails::Pending {
+				considered,
+				next_no_show,
+				maximum_broadcast,
+				clock_drift,
+			},
+			RequiredTranches::Exact {
+				needed,
+				tolerated_missing,
+				next_no_show,
+				last_assignment_tick,
+			} => RequiredTranchesDetails::Exact {
+				needed,
+				tolerated_missing,
+				next_no_show,
+				last_assignment_tick,
+			},
+		}
+	}
+}
+
 /// The result of a check.
 #[derive(Debug, Clone, Copy)]
 pub enum Check {
@@ -364,6 +395,31 @@ fn count_no_shows(
 	(no_shows, next_no_show)
 }
 
+/// Lists the assigned validators which have not approved within `no_show_duration` of their
+/// assignment, up to `tick_now`.
+///
+/// Unlike the no-shows counted by `tranches_to_approve`, this considers the assignments of all
+/// tranches and ignores the clock drift of covering no-shows, so it is meant for inspection only.
+pub fn no_show_validators(
+	approval_entry: &ApprovalEntry,
+	approvals: &BitSlice<u8, BitOrderLsb0>,
+	tick_now: Tick,
+	block_tick: Tick,
+	no_show_duration: Tick,
+) -> Vec<ValidatorIndex> {
+	approval_entry
+		.tranches()
+		.iter()
+		.flat_map(|tranche_entry| tranche_entry.assignments())
+		.filter(|(v_index, tick)| {
+			// Validators out of range of the approvals are ignored, as in `count_no_shows`.
+			let has_approved = approvals.get(v_index.0 as usize).map_or(true, |a| *a);
+			!has_approved && tick.max(&block_tick) + no_show_duration <= tick_now
+		})
+		.map(|(v_index, _)| *v_index)
+		.collect()
+}
+
 /// Determine the amount of tranches of assignments needed to determine approval of a candidate.
 pub fn tranches_to_approve(
 	approval_entry: &ApprovalEntry,
@@ -1335,6 +1391,44 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn no_show_validators_lists_late_non_approving_validators() {
+		let approval_entry: ApprovalEntry = approval_db::v1::ApprovalEntry {
+			tranches: vec![
+				approval_db::v1::TrancheEntry {
+					tranche: 0,
+					assignments: vec![(ValidatorIndex(0), 5.into()), (ValidatorIndex(1), 5.into())],
+				},
+				approval_db::v1::TrancheEntry {
+					tranche: 1,
+					assignments: vec![
+						(ValidatorIndex(2), 20.into()),
+						(ValidatorIndex(9), 5.into()),
+					],
+				},
+			],
+			assignments: bitvec![u8, BitOrderLsb0; 0; 4],
+			our_assignment: None,
+			our_approval_sig: None,
+			backing_group: GroupIndex(0),
+			approved: false,
+		}
+		.into();
+
+		// Validator 0 approved, validator 2 is not late yet and validator 9 is out of range.
+		let approvals = bitvec![u8, BitOrderLsb0; 1, 0, 0, 0];
+
+		assert_eq!(
+			no_show_validators(&approval_entry, &approvals, 25, 10, 10),
+			vec![ValidatorIndex(1)],
+		);
+		assert_eq!(
+			no_show_validators(&approval_entry, &approvals, 30, 10, 10),
+			vec![ValidatorIndex(1), ValidatorIndex(2)],
+		);
+		assert!(no_show_validators(&approval_entry, &approvals, 19, 10, 10).is_empty());
+	}
+
 	#[test]
 	fn depth_0_covering_not_treated_as_such() {
 		let state = State {
@@ -18,7 +18,9 @@
 
 use parity_scale_codec::{Decode, Encode};
 use polkadot_node_primitives::approval::{
-	self as approval_types, AssignmentCert, AssignmentCertKind, DelayTranche, RelayVRFStory,
+	self as approval_types,
+	v2::{AssignmentCertKindV2, AssignmentCertV2, CoreBitfield},
+	DelayTranche, RelayVRFStory,
 };
 use polkadot_primitives::{
 	AssignmentId, AssignmentPair, CandidateHash, CoreIndex, GroupIndex, IndexedVec, SessionInfo,
@@ -37,7 +39,7 @@ use super::LOG_TARGET;
 /// Details pertaining to our assignment on a block.
 #[derive(Debug, Clone, Encode, Decode, PartialEq)]
 pub struct OurAssignment {
-	cert: AssignmentCert,
+	cert: AssignmentCertV2,
 	tranche: DelayTranche,
 	validator_index: ValidatorIndex,
 	// Whether the assignment has been triggered already.
@@ -45,7 +47,7 @@ pub struct OurAssignment {
 }
 
 impl OurAssignment {
-	pub(crate) fn cert(&self) -> &AssignmentCert {
+	pub(crate) fn cert(&self) -> &AssignmentCertV2 {
 		&self.cert
 	}
 
@@ -105,6 +107,34 @@ fn relay_vrf_modulo_core(vrf_in_out: &VRFInOut, n_cores: u32) -> CoreIndex {
 	CoreIndex(random_core)
 }
 
+fn relay_vrf_modulo_compact_transcript(relay_vrf_story: RelayVRFStory) -> Transcript {
+	let mut t = Transcript::new(approval_types::v2::RELAY_VRF_MODULO_COMPACT_CONTEXT);
+	t.append_message(b"RC-VRF", &relay_vrf_story.0);
+	t
+}
+
+// Derives up to `num_samples` distinct cores from a single VRF output.
+fn relay_vrf_modulo_cores(vrf_in_out: &VRFInOut, num_samples: u32, n_cores: u32) -> Vec<CoreIndex> {
+	let seed: [u8; 32] = vrf_in_out.make_bytes(approval_types::CORE_RANDOMNESS_CONTEXT);
+
+	let mut t = Transcript::new(approval_types::v2::RELAY_VRF_MODULO_COMPACT_CONTEXT);
+	t.append_message(b"seed", &seed);
+
+	let mut cores = Vec::with_capacity(num_samples as usize);
+	for _ in 0..num_samples {
+		let mut bytes = [0u8; 4];
+		t.challenge_bytes(b"core", &mut bytes);
+
+		// interpret as little-endian u32.
+		let core = CoreIndex(u32::from_le_bytes(bytes) % n_cores);
+		if !cores.contains(&core) {
+			cores.push(core);
+		}
+	}
+
+	cores
+}
+
 fn relay_vrf_delay_transcript(relay_vrf_story: RelayVRFStory, core_index: CoreIndex) -> Transcript {
 	let mut t = Transcript::new(approval_types::RELAY_VRF_DELAY_CONTEXT);
 	t.append_message(b"RC-VRF", &relay_vrf_story.0);
@@ -133,6 +163,12 @@ fn assigned_core_transcript(core_index: CoreIndex) -> Transcript {
 	t
 }
 
+fn assigned_cores_transcript(core_bitfield: &CoreBitfield) -> Transcript {
+	let mut t = Transcript::new(approval_types::ASSIGNED_CORE_CONTEXT);
+	core_bitfield.using_encoded(|s| t.append_message(b"cores", s));
+	t
+}
+
 /// Information about the world assignments are being produced in.
 #[derive(Clone)]
 pub(crate) struct Config {
@@ -148,6 +184,17 @@ pub(crate) struct Config {
 	relay_vrf_modulo_samples: u32,
 	/// The number of delay tranches in total.
 	n_delay_tranches: u32,
+	/// Whether to produce a single compact `RelayVRFModulo` assignment claiming all assigned
+	/// cores, instead of one assignment per sample.
+	enable_compact_assignments: bool,
+}
+
+impl Config {
+	/// Set whether compact assignments are produced.
+	pub(crate) fn with_compact_assignments(mut self, enable_compact_assignments: bool) -> Self {
+		self.enable_compact_assignments = enable_compact_assignments;
+		self
+	}
 }
 
 impl<'a> From<&'a SessionInfo> for Config {
@@ -159,6 +206,7 @@ impl<'a> From<&'a SessionInfo> for Config {
 			zeroth_delay_tranche_width: s.zeroth_delay_tranche_width,
 			relay_vrf_modulo_samples: s.relay_vrf_modulo_samples,
 			n_delay_tranches: s.n_delay_tranches,
+			enable_compact_assignments: false,
 		}
 	}
 }
@@ -175,12 +223,13 @@ pub(crate) trait AssignmentCriteria {
 
 	fn check_assignment_cert(
 		&self,
-		claimed_core_index: CoreIndex,
+		claimed_core_indices: CoreBitfield,
 		validator_index: ValidatorIndex,
 		config: &Config,
 		relay_vrf_story: RelayVRFStory,
-		assignment: &AssignmentCert,
-		backing_group: GroupIndex,
+		assignment: &AssignmentCertV2,
+		// Backing groups for each of the claimed cores, in ascending core index order.
+		backing_groups: Vec<GroupIndex>,
 	) -> Result<DelayTranche, InvalidAssignment>;
 }
 
@@ -199,20 +248,20 @@ impl AssignmentCriteria for RealAssignmentCriteria {
 
 	fn check_assignment_cert(
 		&self,
-		claimed_core_index: CoreIndex,
+		claimed_core_indices: CoreBitfield,
 		validator_index: ValidatorIndex,
 		config: &Config,
 		relay_vrf_story: RelayVRFStory,
-		assignment: &AssignmentCert,
-		backing_group: GroupIndex,
+		assignment: &AssignmentCertV2,
+		backing_groups: Vec<GroupIndex>,
 	) -> Result<DelayTranche, InvalidAssignment> {
 		check_assignment_cert(
-			claimed_core_index,
+			claimed_core_indices,
 			validator_index,
 			config,
 			relay_vrf_story,
 			assignment,
-			backing_group,
+			backing_groups,
 		)
 	}
 }
@@ -226,6 +275,9 @@ impl AssignmentCriteria for RealAssignmentCriteria {
 /// The current description of the protocol assigns every validator to check every core. But at different times.
 /// The idea is that most assignments are never triggered and fall by the wayside.
 ///
+/// With compact assignments enabled, all cores assigned through `RelayVRFModulo` share a single
+/// certificate, which is stored for each of them.
+///
 /// This will not assign to anything the local validator was part of the backing group for.
 pub(crate) fn compute_assignments(
 	keystore: &LocalKeystore,
@@ -289,15 +341,26 @@ pub(crate) fn compute_assignments(
 
 	let mut assignments = HashMap::new();
 
-	// First run `RelayVRFModulo` for each sample.
-	compute_relay_vrf_modulo_assignments(
-		&assignments_key,
-		index,
-		config,
-		relay_vrf_story.clone(),
-		leaving_cores.iter().cloned(),
-		&mut assignments,
-	);
+	// First run `RelayVRFModulo`, either once for all samples or for each sample.
+	if config.enable_compact_assignments {
+		compute_relay_vrf_modulo_compact_assignment(
+			&assignments_key,
+			index,
+			config,
+			relay_vrf_story.clone(),
+			&leaving_cores,
+			&mut assignments,
+		);
+	} else {
+		compute_relay_vrf_modulo_assignments(
+			&assignments_key,
+			index,
+			config,
+			relay_vrf_story.clone(),
+			leaving_cores.iter().cloned(),
+			&mut assignments,
+		);
+	}
 
 	// Then run `RelayVRFDelay` once for the whole block.
 	compute_relay_vrf_delay_assignments(
@@ -354,8 +417,8 @@ fn compute_relay_vrf_modulo_assignments(
 		if let Some((vrf_in_out, vrf_proof, _)) = maybe_assignment {
 			// Sanity: `core` is always initialized to non-default here, as the closure above
 			// has been executed.
-			let cert = AssignmentCert {
-				kind: AssignmentCertKind::RelayVRFModulo { sample: rvm_sample },
+			let cert = AssignmentCertV2 {
+				kind: AssignmentCertKindV2::RelayVRFModulo { sample: rvm_sample },
 				vrf: (
 					approval_types::VRFOutput(vrf_in_out.to_output()),
 					approval_types::VRFProof(vrf_proof),
@@ -373,6 +436,75 @@ fn compute_relay_vrf_modulo_assignments(
 	}
 }
 
+fn compute_relay_vrf_modulo_compact_assignment(
+	assignments_key: &schnorrkel::Keypair,
+	validator_index: ValidatorIndex,
+	config: &Config,
+	relay_vrf_story: RelayVRFStory,
+	leaving_cores: &[(CandidateHash, CoreIndex)],
+	assignments: &mut HashMap<CoreIndex, OurAssignment>,
+) {
+	let mut assigned_cores = Vec::new();
+
+	let maybe_assignment = {
+		// Extra scope to ensure borrowing instead of moving `assigned_cores`
+		// into closure.
+		let assigned_cores = &mut assigned_cores;
+		assignments_key.vrf_sign_extra_after_check(
+			relay_vrf_modulo_compact_transcript(relay_vrf_story),
+			|vrf_in_out| {
+				*assigned_cores = relay_vrf_modulo_cores(
+					&vrf_in_out,
+					config.relay_vrf_modulo_samples,
+					config.n_cores,
+				)
+				.into_iter()
+				.filter(|core| leaving_cores.iter().any(|(_, c)| c == core))
+				.collect();
+
+				CoreBitfield::try_from(assigned_cores.clone())
+					.ok()
+					.map(|core_bitfield| assigned_cores_transcript(&core_bitfield))
+			},
+		)
+	};
+
+	if let Some((vrf_in_out, vrf_proof, _)) = maybe_assignment {
+		// Sanity: `assigned_cores` is non-empty here, as the closure above only
+		// produces an extra transcript for a non-empty bitfield.
+		let core_bitfield = match CoreBitfield::try_from(assigned_cores.clone()) {
+			Ok(b) => b,
+			Err(_) => return,
+		};
+
+		gum::trace!(
+			target: LOG_TARGET,
+			?assigned_cores,
+			?validator_index,
+			tranche = 0,
+			"RelayVRFModuloCompact Assignment."
+		);
+
+		let cert = AssignmentCertV2 {
+			kind: AssignmentCertKindV2::RelayVRFModuloCompact { core_bitfield },
+			vrf: (
+				approval_types::VRFOutput(vrf_in_out.to_output()),
+				approval_types::VRFProof(vrf_proof),
+			),
+		};
+
+		// All assignments of type RelayVRFModulo have tranche 0.
+		for core in assigned_cores {
+			assignments.entry(core).or_insert(OurAssignment {
+				cert: cert.clone(),
+				tranche: 0,
+				validator_index,
+				triggered: false,
+			});
+		}
+	}
+}
+
 fn compute_relay_vrf_delay_assignments(
 	assignments_key: &schnorrkel::Keypair,
 	validator_index: ValidatorIndex,
@@ -391,8 +523,8 @@ fn compute_relay_vrf_delay_assignments(
 			config.zeroth_delay_tranche_width,
 		);
 
-		let cert = AssignmentCert {
-			kind: AssignmentCertKind::RelayVRFDelay { core_index: core },
+		let cert = AssignmentCertV2 {
+			kind: AssignmentCertKindV2::RelayVRFDelay { core_index: core },
 			vrf: (
 				approval_types::VRFOutput(vrf_in_out.to_output()),
 				approval_types::VRFProof(vrf_proof),
@@ -452,26 +584,31 @@ pub(crate) enum InvalidAssignmentReason {
 	VRFModuloOutputMismatch,
 	VRFDelayCoreIndexMismatch,
 	VRFDelayOutputMismatch,
+	VRFModuloCompactCoreIndexMismatch,
+	VRFModuloCompactOutputMismatch,
+	NullAssignment,
+	InvalidArguments,
 }
 
 /// Checks the crypto of an assignment cert. Failure conditions:
 ///   * Validator index out of bounds
 ///   * VRF signature check fails
-///   * VRF output doesn't match assigned core
-///   * Core is not covered by extra data in signature
+///   * VRF output doesn't match assigned cores
+///   * Cores are not covered by extra data in signature
 ///   * Core index out of bounds
 ///   * Sample is out of bounds
-///   * Validator is present in backing group.
+///   * Validator is present in a backing group
+///   * No core or, for single-core certs, more than one core is claimed.
 ///
 /// This function does not check whether the core is actually a valid assignment or not. That should be done
 /// outside the scope of this function.
 pub(crate) fn check_assignment_cert(
-	claimed_core_index: CoreIndex,
+	claimed_core_indices: CoreBitfield,
 	validator_index: ValidatorIndex,
 	config: &Config,
 	relay_vrf_story: RelayVRFStory,
-	assignment: &AssignmentCert,
-	backing_group: GroupIndex,
+	assignment: &AssignmentCertV2,
+	backing_groups: Vec<GroupIndex>,
 ) -> Result<DelayTranche, InvalidAssignment> {
 	use InvalidAssignmentReason as Reason;
 
@@ -483,22 +620,69 @@ pub(crate) fn check_assignment_cert(
 	let public = schnorrkel::PublicKey::from_bytes(validator_public.as_slice())
 		.map_err(|_| InvalidAssignment(Reason::InvalidAssignmentKey))?;
 
-	if claimed_core_index.0 >= config.n_cores {
+	let claimed_cores: Vec<CoreIndex> =
+		claimed_core_indices.iter_ones().map(|i| CoreIndex(i as u32)).collect();
+
+	if claimed_cores.is_empty() {
+		return Err(InvalidAssignment(Reason::NullAssignment))
+	}
+
+	if claimed_cores.len() != backing_groups.len() {
+		return Err(InvalidAssignment(Reason::InvalidArguments))
+	}
+
+	if claimed_cores.iter().any(|core| core.0 >= config.n_cores) {
 		return Err(InvalidAssignment(Reason::CoreIndexOutOfBounds))
 	}
 
-	// Check that the validator was not part of the backing group
+	// Check that the validator was not part of any of the backing groups
 	// and not already assigned.
-	let is_in_backing =
-		is_in_backing_group(&config.validator_groups, validator_index, backing_group);
+	let is_in_backing = backing_groups
+		.iter()
+		.any(|group| is_in_backing_group(&config.validator_groups, validator_index, *group));
 
 	if is_in_backing {
 		return Err(InvalidAssignment(Reason::IsInBackingGroup))
 	}
 
 	let &(ref vrf_output, ref vrf_proof) = &assignment.vrf;
+
+	// Single-core certs only ever claim one core.
+	let claimed_core_index = match assignment.kind {
+		AssignmentCertKindV2::RelayVRFModuloCompact { .. } => claimed_cores[0],
+		_ if claimed_cores.len() == 1 => claimed_cores[0],
+		_ => return Err(InvalidAssignment(Reason::InvalidArguments)),
+	};
+
 	match assignment.kind {
-		AssignmentCertKind::RelayVRFModulo { sample } => {
+		AssignmentCertKindV2::RelayVRFModuloCompact { ref core_bitfield } => {
+			// The claimed cores must be exactly those committed to by the cert.
+			if !core_bitfield.iter_ones().eq(claimed_core_indices.iter_ones()) {
+				return Err(InvalidAssignment(Reason::VRFModuloCompactCoreIndexMismatch))
+			}
+
+			let (vrf_in_out, _) = public
+				.vrf_verify_extra(
+					relay_vrf_modulo_compact_transcript(relay_vrf_story),
+					&vrf_output.0,
+					&vrf_proof.0,
+					assigned_cores_transcript(core_bitfield),
+				)
+				.map_err(|_| InvalidAssignment(Reason::VRFModuloCompactOutputMismatch))?;
+
+			// ensure that the `vrf_in_out` actually gives us all of the claimed cores.
+			let cores = relay_vrf_modulo_cores(
+				&vrf_in_out,
+				config.relay_vrf_modulo_samples,
+				config.n_cores,
+			);
+			if claimed_cores.iter().all(|core| cores.contains(core)) {
+				Ok(0)
+			} else {
+				Err(InvalidAssignment(Reason::VRFModuloCompactCoreIndexMismatch))
+			}
+		},
+		AssignmentCertKindV2::RelayVRFModulo { sample } => {
 			if sample >= config.relay_vrf_modulo_samples {
 				return Err(InvalidAssignment(Reason::SampleOutOfBounds))
 			}
@@ -519,7 +703,7 @@ pub(crate) fn check_assignment_cert(
 				Err(InvalidAssignment(Reason::VRFModuloCoreIndexMismatch))
 			}
 		},
-		AssignmentCertKind::RelayVRFDelay { core_index } => {
+		AssignmentCertKindV2::RelayVRFDelay { core_index } => {
 			if core_index != claimed_core_index {
 				return Err(InvalidAssignment(Reason::VRFDelayCoreIndexMismatch))
 			}
@@ -643,6 +827,7 @@ mod tests {
 				zeroth_delay_tranche_width: 10,
 				relay_vrf_modulo_samples: 3,
 				n_delay_tranches: 40,
+				enable_compact_assignments: false,
 			},
 			vec![(c_a, CoreIndex(0), GroupIndex(1)), (c_b, CoreIndex(1), GroupIndex(0))],
 		);
@@ -678,6 +863,7 @@ mod tests {
 				zeroth_delay_tranche_width: 10,
 				relay_vrf_modulo_samples: 3,
 				n_delay_tranches: 40,
+				enable_compact_assignments: false,
 			},
 			vec![(c_a, CoreIndex(0), GroupIndex(0)), (c_b, CoreIndex(1), GroupIndex(1))],
 		);
@@ -705,6 +891,7 @@ mod tests {
 				zeroth_delay_tranche_width: 10,
 				relay_vrf_modulo_samples: 3,
 				n_delay_tranches: 40,
+				enable_compact_assignments: false,
 			},
 			vec![],
 		);
@@ -714,7 +901,7 @@ mod tests {
 
 	struct MutatedAssignment {
 		core: CoreIndex,
-		cert: AssignmentCert,
+		cert: AssignmentCertV2,
 		group: GroupIndex,
 		own_group: GroupIndex,
 		val_index: ValidatorIndex,
@@ -742,6 +929,7 @@ mod tests {
 			zeroth_delay_tranche_width: 10,
 			relay_vrf_modulo_samples: 3,
 			n_delay_tranches: 40,
+			enable_compact_assignments: false,
 		};
 
 		let relay_vrf_story = RelayVRFStory([42u8; 32]);
@@ -779,12 +967,12 @@ mod tests {
 			counted += 1;
 
 			let is_good = check_assignment_cert(
-				mutated.core,
+				mutated.core.into(),
 				mutated.val_index,
 				&mutated.config,
 				relay_vrf_story.clone(),
 				&mutated.cert,
-				mutated.group,
+				vec![mutated.group],
 			)
 			.is_ok();
 
@@ -827,7 +1015,7 @@ mod tests {
 	fn check_rejects_delay_bad_vrf() {
 		check_mutated_assignments(40, 10, 8, |m| {
 			match m.cert.kind.clone() {
-				AssignmentCertKind::RelayVRFDelay { .. } => {
+				AssignmentCertKindV2::RelayVRFDelay { .. } => {
 					m.cert.vrf = garbage_vrf();
 					Some(false)
 				},
@@ -840,7 +1028,7 @@ mod tests {
 	fn check_rejects_modulo_bad_vrf() {
 		check_mutated_assignments(200, 100, 25, |m| {
 			match m.cert.kind.clone() {
-				AssignmentCertKind::RelayVRFModulo { .. } => {
+				AssignmentCertKindV2::RelayVRFModulo { .. } => {
 					m.cert.vrf = garbage_vrf();
 					Some(false)
 				},
@@ -853,7 +1041,7 @@ mod tests {
 	fn check_rejects_modulo_sample_out_of_bounds() {
 		check_mutated_assignments(200, 100, 25, |m| {
 			match m.cert.kind.clone() {
-				AssignmentCertKind::RelayVRFModulo { sample } => {
+				AssignmentCertKindV2::RelayVRFModulo { sample } => {
 					m.config.relay_vrf_modulo_samples = sample;
 					Some(false)
 				},
@@ -866,7 +1054,7 @@ mod tests {
 	fn check_rejects_delay_claimed_core_wrong() {
 		check_mutated_assignments(200, 100, 25, |m| {
 			match m.cert.kind.clone() {
-				AssignmentCertKind::RelayVRFDelay { .. } => {
+				AssignmentCertKindV2::RelayVRFDelay { .. } => {
 					m.core = CoreIndex((m.core.0 + 1) % 100);
 					Some(false)
 				},
@@ -879,7 +1067,7 @@ mod tests {
 	fn check_rejects_modulo_core_wrong() {
 		check_mutated_assignments(200, 100, 25, |m| {
 			match m.cert.kind.clone() {
-				AssignmentCertKind::RelayVRFModulo { .. } => {
+				AssignmentCertKindV2::RelayVRFModulo { .. } => {
 					m.core = CoreIndex((m.core.0 + 1) % 100);
 					Some(false)
 				},
@@ -887,4 +1075,91 @@ mod tests {
 			}
 		});
 	}
+
+	#[test]
+	fn computed_compact_assignments_pass_checks() {
+		let keystore = futures::executor::block_on(make_keystore(&[Sr25519Keyring::Alice]));
+
+		let n_validators = 200;
+		let n_cores = 100;
+		let group_for_core = |i| GroupIndex(((i + 25) % n_cores) as _);
+
+		let config = Config {
+			assignment_keys: assignment_keys_plus_random(
+				&[Sr25519Keyring::Alice],
+				n_validators - 1,
+			),
+			validator_groups: basic_groups(n_validators, n_cores),
+			n_cores: n_cores as u32,
+			zeroth_delay_tranche_width: 10,
+			relay_vrf_modulo_samples: 6,
+			n_delay_tranches: 40,
+			enable_compact_assignments: true,
+		};
+
+		let relay_vrf_story = RelayVRFStory([42u8; 32]);
+		let assignments = compute_assignments(
+			&keystore,
+			relay_vrf_story.clone(),
+			&config,
+			(0..n_cores)
+				.map(|i| {
+					(
+						CandidateHash(Hash::repeat_byte(i as u8)),
+						CoreIndex(i as u32),
+						group_for_core(i),
+					)
+				})
+				.collect::<Vec<_>>(),
+		);
+
+		let compact: Vec<_> = assignments
+			.values()
+			.filter_map(|a| match a.cert().kind {
+				AssignmentCertKindV2::RelayVRFModuloCompact { ref core_bitfield } =>
+					Some((core_bitfield.clone(), a.cert().clone())),
+				_ => None,
+			})
+			.collect();
+
+		assert!(!compact.is_empty());
+
+		for (core_bitfield, cert) in compact {
+			let backing_groups: Vec<_> =
+				core_bitfield.iter_ones().map(|core| group_for_core(core)).collect();
+
+			// All cores of the cert must be claimed together.
+			assert_eq!(
+				check_assignment_cert(
+					core_bitfield.clone(),
+					ValidatorIndex(0),
+					&config,
+					relay_vrf_story.clone(),
+					&cert,
+					backing_groups.clone(),
+				),
+				Ok(0),
+			);
+
+			let mut extra_core =
+				core_bitfield.iter_ones().map(|c| CoreIndex(c as u32)).collect::<Vec<_>>();
+			let unclaimed = (0..n_cores as u32)
+				.map(CoreIndex)
+				.find(|c| !extra_core.contains(c) && group_for_core(c.0 as usize) != GroupIndex(0))
+				.unwrap();
+			extra_core.push(unclaimed);
+			let mut extra_groups = backing_groups.clone();
+			extra_groups.push(group_for_core(unclaimed.0 as usize));
+
+			assert!(check_assignment_cert(
+				extra_core.try_into().unwrap(),
+				ValidatorIndex(0),
+				&config,
+				relay_vrf_story.clone(),
+				&cert,
+				extra_groups,
+			)
+			.is_err());
+		}
+	}
 }
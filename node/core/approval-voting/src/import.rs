@@ -81,6 +81,7 @@ struct ImportedBlockInfoEnv<'a> {
 	session_window: &'a Option<RollingSessionWindow>,
 	assignment_criteria: &'a (dyn AssignmentCriteria + Send + Sync),
 	keystore: &'a LocalKeystore,
+	enable_compact_assignments: bool,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -238,7 +239,8 @@ async fn imported_block_info<Context>(
 						let assignments = env.assignment_criteria.compute_assignments(
 							&env.keystore,
 							relay_vrf.clone(),
-							&crate::criteria::Config::from(session_info),
+							&crate::criteria::Config::from(session_info)
+								.with_compact_assignments(env.enable_compact_assignments),
 							included_candidates
 								.iter()
 								.map(|(c_hash, _, core, group)| (*c_hash, *core, *group))
@@ -408,6 +410,7 @@ pub(crate) async fn handle_new_head<Context, B: Backend>(
 				session_window: &state.session_window,
 				assignment_criteria: &*state.assignment_criteria,
 				keystore: &state.keystore,
+				enable_compact_assignments: state.enable_compact_assignments,
 			};
 
 			match imported_block_info(ctx, env, block_hash, &block_header).await {
@@ -661,6 +664,9 @@ pub(crate) mod tests {
 			assignment_criteria: Box::new(MockAssignmentCriteria),
 			db,
 			db_config: TEST_CONFIG,
+			max_approval_coalesce_count: 1,
+			enable_compact_assignments: false,
+			approvals_to_coalesce: HashMap::new(),
 		}
 	}
 
@@ -690,13 +696,14 @@ pub(crate) mod tests {
 
 		fn check_assignment_cert(
 			&self,
-			_claimed_core_index: polkadot_primitives::CoreIndex,
+			_claimed_core_indices: polkadot_node_primitives::approval::v2::CoreBitfield,
 			_validator_index: polkadot_primitives::ValidatorIndex,
 			_config: &criteria::Config,
 			_relay_vrf_story: polkadot_node_primitives::approval::RelayVRFStory,
-			_assignment: &polkadot_node_primitives::approval::AssignmentCert,
-			_backing_group: polkadot_primitives::GroupIndex,
-		) -> Result<polkadot_node_primitives::approval::DelayTranche, criteria::InvalidAssignment> {
+			_assignment: &polkadot_node_primitives::approval::v2::AssignmentCertV2,
+			_backing_groups: Vec<polkadot_primitives::GroupIndex>,
+		) -> Result<polkadot_node_primitives::approval::DelayTranche, criteria::InvalidAssignment>
+		{
 			Ok(0)
 		}
 	}
@@ -788,6 +795,7 @@ pub(crate) mod tests {
 					session_window: &Some(session_window),
 					assignment_criteria: &MockAssignmentCriteria,
 					keystore: &LocalKeystore::in_memory(),
+					enable_compact_assignments: false,
 				};
 
 				let info = imported_block_info(&mut ctx, env, hash, &header).await.unwrap();
@@ -894,6 +902,7 @@ pub(crate) mod tests {
 					session_window: &Some(session_window),
 					assignment_criteria: &MockAssignmentCriteria,
 					keystore: &LocalKeystore::in_memory(),
+					enable_compact_assignments: false,
 				};
 
 				let info = imported_block_info(&mut ctx, env, hash, &header).await;
@@ -993,6 +1002,7 @@ pub(crate) mod tests {
 					session_window: &session_window,
 					assignment_criteria: &MockAssignmentCriteria,
 					keystore: &LocalKeystore::in_memory(),
+					enable_compact_assignments: false,
 				};
 
 				let info = imported_block_info(&mut ctx, env, hash, &header).await;
@@ -1089,6 +1099,7 @@ pub(crate) mod tests {
 					session_window: &session_window,
 					assignment_criteria: &MockAssignmentCriteria,
 					keystore: &LocalKeystore::in_memory(),
+					enable_compact_assignments: false,
 				};
 
 				let info = imported_block_info(&mut ctx, env, hash, &header).await.unwrap();
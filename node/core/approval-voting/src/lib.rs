@@ -24,7 +24,11 @@
 use polkadot_node_jaeger as jaeger;
 use polkadot_node_primitives::{
 	approval::{
-		BlockApprovalMeta, DelayTranche, IndirectAssignmentCert, IndirectSignedApprovalVote,
+		v2::{
+			AssignmentCertKindV2, CandidateBitfield, CoreBitfield, IndirectAssignmentCertV2,
+			IndirectSignedApprovalVoteV2,
+		},
+		BlockApprovalMeta, DelayTranche,
 	},
 	ValidationResult, APPROVAL_EXECUTION_TIMEOUT,
 };
@@ -32,8 +36,9 @@ use polkadot_node_subsystem::{
 	errors::RecoveryError,
 	messages::{
 		ApprovalCheckError, ApprovalCheckResult, ApprovalDistributionMessage,
-		ApprovalVotingMessage, AssignmentCheckError, AssignmentCheckResult,
-		AvailabilityRecoveryMessage, BlockDescription, CandidateValidationMessage, ChainApiMessage,
+		ApprovalVotingMessage, AssignmentCheckError, AssignmentCheckResult, AssignmentDetails,
+		AvailabilityRecoveryMessage, BlockApprovalDetails, BlockDescription,
+		CandidateApprovalDetails, CandidateValidationMessage, ChainApiMessage,
 		ChainSelectionMessage, DisputeCoordinatorMessage, HighestApprovedAncestorBlock,
 		RuntimeApiMessage, RuntimeApiRequest,
 	},
@@ -49,14 +54,15 @@ use polkadot_node_subsystem_util::{
 	TimeoutExt,
 };
 use polkadot_primitives::{
-	ApprovalVote, BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt, DisputeStatement,
-	GroupIndex, Hash, SessionIndex, SessionInfo, ValidDisputeStatementKind, ValidatorId,
-	ValidatorIndex, ValidatorPair, ValidatorSignature,
+	vstaging::ApprovalVoteMultipleCandidates, BlockNumber, CandidateHash, CandidateIndex,
+	CandidateReceipt, GroupIndex, Hash, SessionIndex, SessionInfo, ValidatorId, ValidatorIndex,
+	ValidatorPair, ValidatorSignature,
 };
 use sc_keystore::LocalKeystore;
 use sp_application_crypto::Pair;
 use sp_consensus::SyncOracle;
 use sp_consensus_slots::Slot;
+use sp_runtime::traits::AppVerify;
 
 use futures::{
 	channel::oneshot,
@@ -109,6 +115,9 @@ const APPROVAL_CACHE_SIZE: NonZeroUsize = match NonZeroUsize::new(1024) {
 
 const TICK_TOO_FAR_IN_FUTURE: Tick = 20; // 10 seconds.
 const APPROVAL_DELAY: Tick = 2;
+/// How long local approvals may wait for further approvals of the same block to be signed
+/// together with.
+const MAX_APPROVAL_COALESCE_WAIT_TICKS: Tick = 2; // 1 second.
 const LOG_TARGET: &str = "parachain::approval-voting";
 
 /// Configuration for the approval voting subsystem
@@ -121,6 +130,12 @@ pub struct Config {
 	/// The slot duration of the consensus algorithm, in milliseconds. Should be evenly
 	/// divisible by 500.
 	pub slot_duration_millis: u64,
+	/// The maximum number of local approvals of candidates in the same block which are
+	/// signed and distributed as a single vote. `1` disables coalescing.
+	pub max_approval_coalesce_count: u32,
+	/// Whether to produce compact assignment certificates, which claim all cores assigned
+	/// through `RelayVRFModulo` at once.
+	pub enable_compact_assignments: bool,
 }
 
 // The mode of the approval voting subsystem. It should start in a `Syncing` mode when it first
@@ -145,6 +160,8 @@ pub struct ApprovalVotingSubsystem {
 	keystore: Arc<LocalKeystore>,
 	db_config: DatabaseConfig,
 	slot_duration_millis: u64,
+	max_approval_coalesce_count: u32,
+	enable_compact_assignments: bool,
 	db: Arc<dyn Database>,
 	mode: Mode,
 	metrics: Metrics,
@@ -356,6 +373,8 @@ impl ApprovalVotingSubsystem {
 		ApprovalVotingSubsystem {
 			keystore,
 			slot_duration_millis: config.slot_duration_millis,
+			max_approval_coalesce_count: config.max_approval_coalesce_count.max(1),
+			enable_compact_assignments: config.enable_compact_assignments,
 			db,
 			db_config: DatabaseConfig {
 				col_approval_data: config.col_approval_data,
@@ -426,6 +445,16 @@ struct ApprovalVoteRequest {
 	block_hash: Hash,
 }
 
+// Local approvals of candidates in a block which wait to be signed together.
+#[derive(Debug, Clone)]
+struct ApprovalsToCoalesce {
+	validator_index: ValidatorIndex,
+	// The candidates, in the order they were approved.
+	candidates: Vec<(CandidateIndex, CandidateHash)>,
+	// The tick at which the approvals are signed, regardless of their number.
+	deadline: Tick,
+}
+
 #[derive(Default)]
 struct Wakeups {
 	// Tick -> [(Relay Block, Candidate Hash)]
@@ -541,6 +570,7 @@ struct ApprovalStatus {
 	required_tranches: RequiredTranches,
 	tranche_now: DelayTranche,
 	block_tick: Tick,
+	no_show_duration: Tick,
 }
 
 #[derive(Copy, Clone)]
@@ -639,6 +669,10 @@ struct State {
 	// Require for `RollingSessionWindow`.
 	db_config: DatabaseConfig,
 	db: Arc<dyn Database>,
+	max_approval_coalesce_count: u32,
+	enable_compact_assignments: bool,
+	// Local approvals waiting to be signed, by relay block.
+	approvals_to_coalesce: HashMap<Hash, ApprovalsToCoalesce>,
 }
 
 #[overseer::contextbounds(ApprovalVoting, prefix = self::overseer)]
@@ -647,6 +681,11 @@ impl State {
 		self.session_window.as_ref().and_then(|w| w.session_info(i))
 	}
 
+	// The earliest tick at which coalesced approvals need to be signed, if any.
+	fn next_approval_coalesce_deadline(&self) -> Option<Tick> {
+		self.approvals_to_coalesce.values().map(|a| a.deadline).min()
+	}
+
 	/// Bring `session_window` up to date.
 	pub async fn cache_session_info_for_head<Context>(
 		&mut self,
@@ -721,7 +760,8 @@ impl State {
 				session_info.needed_approvals as _,
 			);
 
-			let status = ApprovalStatus { required_tranches, block_tick, tranche_now };
+			let status =
+				ApprovalStatus { required_tranches, block_tick, tranche_now, no_show_duration };
 
 			Some((approval_entry, status))
 		} else {
@@ -739,14 +779,13 @@ enum Action {
 		tick: Tick,
 	},
 	LaunchApproval {
-		candidate_hash: CandidateHash,
-		indirect_cert: IndirectAssignmentCert,
+		indirect_cert: IndirectAssignmentCertV2,
+		claimed_candidate_indices: CandidateBitfield,
 		assignment_tranche: DelayTranche,
 		relay_block_hash: Hash,
-		candidate_index: CandidateIndex,
 		session: SessionIndex,
-		candidate: CandidateReceipt,
-		backing_group: GroupIndex,
+		// All candidates claimed by the assignment, with their backing groups.
+		candidates: Vec<(CandidateHash, CandidateReceipt, GroupIndex)>,
 	},
 	NoteApprovedInChainSelection(Hash),
 	IssueApproval(CandidateHash, ApprovalVoteRequest),
@@ -777,6 +816,9 @@ where
 		assignment_criteria,
 		db_config: subsystem.db_config,
 		db: subsystem.db,
+		max_approval_coalesce_count: subsystem.max_approval_coalesce_count,
+		enable_compact_assignments: subsystem.enable_compact_assignments,
+		approvals_to_coalesce: HashMap::new(),
 	};
 
 	let mut wakeups = Wakeups::default();
@@ -797,6 +839,11 @@ where
 
 	loop {
 		let mut overlayed_db = OverlayedBackend::new(&backend);
+		let approval_coalesce_deadline = match state.next_approval_coalesce_deadline() {
+			Some(tick) => state.clock.wait(tick),
+			None => future::pending::<()>().boxed(),
+		};
+
 		let actions = futures::select! {
 			(tick, woken_block, woken_candidate) = wakeups.next(&*state.clock).fuse() => {
 				subsystem.metrics.on_wakeup();
@@ -829,6 +876,28 @@ where
 
 				actions
 			}
+			_ = approval_coalesce_deadline.fuse() => {
+				let tick_now = state.clock.tick_now();
+				let expired: Vec<Hash> = state
+					.approvals_to_coalesce
+					.iter()
+					.filter(|(_, a)| a.deadline <= tick_now)
+					.map(|(block_hash, _)| *block_hash)
+					.collect();
+
+				let mut actions = Vec::new();
+				for block_hash in expired {
+					actions.extend(sign_coalesced_approvals(
+						&mut ctx,
+						&mut state,
+						&mut overlayed_db,
+						&subsystem.metrics,
+						block_hash,
+					).await?);
+				}
+
+				actions
+			}
 			approval_state = currently_checking_set.next(&mut approvals_cache).fuse() => {
 				let mut actions = Vec::new();
 				let (
@@ -860,7 +929,7 @@ where
 			}
 		};
 
-		if handle_actions(
+		let conclude = handle_actions(
 			&mut ctx,
 			&mut state,
 			&mut overlayed_db,
@@ -871,17 +940,19 @@ where
 			&mut subsystem.mode,
 			actions,
 		)
-		.await?
-		{
-			break
-		}
+		.await?;
 
+		// Written before concluding, so approvals signed on shutdown are persisted.
 		if !overlayed_db.is_empty() {
 			let _timer = subsystem.metrics.time_db_transaction();
 
 			let ops = overlayed_db.into_write_ops();
 			backend.write(ops)?;
 		}
+
+		if conclude {
+			break
+		}
 	}
 
 	Ok(())
@@ -954,14 +1025,12 @@ async fn handle_actions<Context>(
 				actions_iter = next_actions.into_iter();
 			},
 			Action::LaunchApproval {
-				candidate_hash,
 				indirect_cert,
+				claimed_candidate_indices,
 				assignment_tranche,
 				relay_block_hash,
-				candidate_index,
 				session,
-				candidate,
-				backing_group,
+				candidates,
 			} => {
 				// Don't launch approval work if the node is syncing.
 				if let Mode::Syncing(_) = *mode {
@@ -974,43 +1043,48 @@ async fn handle_actions<Context>(
 
 				ctx.send_unbounded_message(ApprovalDistributionMessage::DistributeAssignment(
 					indirect_cert,
-					candidate_index,
+					claimed_candidate_indices,
 				));
 
-				match approvals_cache.get(&candidate_hash) {
-					Some(ApprovalOutcome::Approved) => {
-						let new_actions: Vec<Action> = std::iter::once(Action::IssueApproval(
-							candidate_hash,
-							ApprovalVoteRequest { validator_index, block_hash },
-						))
-						.map(|v| v.clone())
-						.chain(actions_iter)
-						.collect();
-						actions_iter = new_actions.into_iter();
-					},
-					None => {
-						let ctx = &mut *ctx;
-						currently_checking_set
-							.insert_relay_block_hash(
+				let mut approvals = Vec::new();
+				for (candidate_hash, candidate, backing_group) in candidates {
+					match approvals_cache.get(&candidate_hash) {
+						Some(ApprovalOutcome::Approved) => {
+							approvals.push(Action::IssueApproval(
 								candidate_hash,
-								validator_index,
-								relay_block_hash,
-								async move {
-									launch_approval(
-										ctx,
-										metrics.clone(),
-										session,
-										candidate,
-										validator_index,
-										block_hash,
-										backing_group,
-									)
-									.await
-								},
-							)
-							.await?;
-					},
-					Some(_) => {},
+								ApprovalVoteRequest { validator_index, block_hash },
+							));
+						},
+						None => {
+							let ctx = &mut *ctx;
+							currently_checking_set
+								.insert_relay_block_hash(
+									candidate_hash,
+									validator_index,
+									relay_block_hash,
+									async move {
+										launch_approval(
+											ctx,
+											metrics.clone(),
+											session,
+											candidate,
+											validator_index,
+											block_hash,
+											backing_group,
+										)
+										.await
+									},
+								)
+								.await?;
+						},
+						Some(_) => {},
+					}
+				}
+
+				if !approvals.is_empty() {
+					let new_actions: Vec<Action> =
+						approvals.into_iter().chain(actions_iter).collect();
+					actions_iter = new_actions.into_iter();
 				}
 			},
 			Action::NoteApprovedInChainSelection(block_hash) => {
@@ -1024,6 +1098,23 @@ async fn handle_actions<Context>(
 				ctx.send_messages(messages.into_iter()).await;
 			},
 			Action::Conclude => {
+				// Approvals waiting to be coalesced are signed now, so the checks they
+				// stand for are not lost with the shutdown.
+				let pending: Vec<Hash> = state.approvals_to_coalesce.keys().copied().collect();
+				let mut next_actions = Vec::new();
+				for block_hash in pending {
+					next_actions.extend(
+						sign_coalesced_approvals(ctx, state, overlayed_db, metrics, block_hash)
+							.await?,
+					);
+				}
+
+				if !next_actions.is_empty() {
+					let next_actions: Vec<Action> =
+						next_actions.into_iter().chain(actions_iter).collect();
+					actions_iter = next_actions.into_iter();
+				}
+
 				conclude = true;
 			},
 		}
@@ -1060,6 +1151,12 @@ fn distribution_messages_for_activation(
 			session: block_entry.session(),
 		});
 
+		// Compact assignments and coalesced approvals are stored for each of the candidates
+		// they cover, so identical certs and signatures are grouped back together.
+		let mut assignments: Vec<(IndirectAssignmentCertV2, Vec<CandidateIndex>)> = Vec::new();
+		let mut approvals: Vec<(ValidatorIndex, ValidatorSignature, Vec<CandidateIndex>)> =
+			Vec::new();
+
 		for (i, (_, candidate_hash)) in block_entry.candidates().iter().enumerate() {
 			let candidate_entry = match db.load_candidate_entry(&candidate_hash)? {
 				Some(c) => c,
@@ -1077,37 +1174,31 @@ fn distribution_messages_for_activation(
 
 			match candidate_entry.approval_entry(&block_hash) {
 				Some(approval_entry) => {
-					match approval_entry.local_statements() {
-						(None, None) | (None, Some(_)) => {}, // second is impossible case.
-						(Some(assignment), None) => {
-							messages.push(ApprovalDistributionMessage::DistributeAssignment(
-								IndirectAssignmentCert {
-									block_hash,
-									validator: assignment.validator_index(),
-									cert: assignment.cert().clone(),
-								},
-								i as _,
-							));
-						},
-						(Some(assignment), Some(approval_sig)) => {
-							messages.push(ApprovalDistributionMessage::DistributeAssignment(
-								IndirectAssignmentCert {
-									block_hash,
-									validator: assignment.validator_index(),
-									cert: assignment.cert().clone(),
-								},
-								i as _,
-							));
+					let (assignment, approval_sig) = match approval_entry.local_statements() {
+						(None, None) | (None, Some(_)) => continue, // second is impossible case.
+						(Some(assignment), approval_sig) => (assignment, approval_sig),
+					};
+
+					let indirect_cert = IndirectAssignmentCertV2 {
+						block_hash,
+						validator: assignment.validator_index(),
+						cert: assignment.cert().clone(),
+					};
+
+					match assignments.iter_mut().find(|(cert, _)| cert == &indirect_cert) {
+						Some((_, indices)) => indices.push(i as _),
+						None => assignments.push((indirect_cert, vec![i as _])),
+					}
 
-							messages.push(ApprovalDistributionMessage::DistributeApproval(
-								IndirectSignedApprovalVote {
-									block_hash,
-									candidate_index: i as _,
-									validator: assignment.validator_index(),
-									signature: approval_sig,
-								},
-							))
-						},
+					if let Some(approval_sig) = approval_sig {
+						match approvals.iter_mut().find(|(_, sig, _)| sig == &approval_sig) {
+							Some((_, _, indices)) => indices.push(i as _),
+							None => approvals.push((
+								assignment.validator_index(),
+								approval_sig,
+								vec![i as _],
+							)),
+						}
 					}
 				},
 				None => {
@@ -1120,6 +1211,29 @@ fn distribution_messages_for_activation(
 				},
 			}
 		}
+
+		// Assignments must be distributed before the approvals relying on them.
+		for (indirect_cert, indices) in assignments {
+			if let Ok(candidate_indices) = CandidateBitfield::try_from(indices) {
+				messages.push(ApprovalDistributionMessage::DistributeAssignment(
+					indirect_cert,
+					candidate_indices,
+				));
+			}
+		}
+
+		for (validator, signature, indices) in approvals {
+			if let Ok(candidate_indices) = CandidateBitfield::try_from(indices) {
+				messages.push(ApprovalDistributionMessage::DistributeApproval(
+					IndirectSignedApprovalVoteV2 {
+						block_hash,
+						candidate_indices,
+						validator,
+						signature,
+					},
+				));
+			}
+		}
 	}
 
 	messages[0] = ApprovalDistributionMessage::NewBlocks(approval_meta);
@@ -1237,12 +1351,117 @@ async fn handle_from_overseer<Context>(
 				get_approval_signatures_for_candidate(ctx, db, candidate_hash, tx).await?;
 				Vec::new()
 			},
+			ApprovalVotingMessage::BlockApprovalDetails(block_hash, tx) => {
+				let _ = tx.send(block_approval_details(state, db, block_hash)?);
+				Vec::new()
+			},
 		},
 	};
 
 	Ok(actions)
 }
 
+// Collect the approval state of all candidates of a block, explaining which approvals are
+// missing for the block to be approved.
+fn block_approval_details(
+	state: &State,
+	db: &OverlayedBackend<'_, impl Backend>,
+	block_hash: Hash,
+) -> SubsystemResult<Option<BlockApprovalDetails>> {
+	let block_entry = match db.load_block_entry(&block_hash)? {
+		Some(b) => b,
+		None => return Ok(None),
+	};
+
+	let tick_now = state.clock.tick_now();
+	let mut candidates = Vec::with_capacity(block_entry.candidates().len());
+	for (core_index, candidate_hash) in block_entry.candidates() {
+		let candidate_entry = match db.load_candidate_entry(candidate_hash)? {
+			Some(c) => c,
+			None => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?candidate_hash,
+					?block_hash,
+					"Missing candidate entry while inspecting block approval",
+				);
+
+				continue
+			},
+		};
+
+		let approval_entry = match candidate_entry.approval_entry(&block_hash) {
+			Some(a) => a,
+			None => continue,
+		};
+
+		let approved = block_entry.is_candidate_approved(candidate_hash);
+		let approvals = candidate_entry.approvals();
+		let status = state.approval_status(&block_entry, &candidate_entry).map(|(_, s)| s);
+
+		let no_shows = status.as_ref().map_or_else(Vec::new, |s| {
+			approval_checking::no_show_validators(
+				approval_entry,
+				approvals,
+				tick_now,
+				s.block_tick,
+				s.no_show_duration,
+			)
+		});
+
+		let missing_approvals = if approved {
+			Vec::new()
+		} else {
+			// The assignments in the tranches which are considered for approval.
+			let up_to = match status.as_ref().map(|s| &s.required_tranches) {
+				Some(RequiredTranches::Exact { needed, .. }) => *needed,
+				Some(RequiredTranches::Pending { considered, .. }) => *considered,
+				Some(RequiredTranches::All) | None => DelayTranche::max_value(),
+			};
+
+			approval_entry
+				.assignments_up_to(up_to)
+				.iter_ones()
+				.filter(|i| !approvals.get(*i).map_or(false, |a| *a))
+				.map(|i| ValidatorIndex(i as _))
+				.collect()
+		};
+
+		candidates.push(CandidateApprovalDetails {
+			candidate_hash: *candidate_hash,
+			core_index: *core_index,
+			backing_group: approval_entry.backing_group(),
+			approved,
+			required_tranches: status.as_ref().map(|s| s.required_tranches.clone().into()),
+			assignments: approval_entry
+				.tranches()
+				.iter()
+				.flat_map(|tranche_entry| {
+					tranche_entry.assignments().iter().map(move |(validator, tick)| {
+						AssignmentDetails {
+							validator: *validator,
+							tranche: tranche_entry.tranche(),
+							tick: *tick,
+						}
+					})
+				})
+				.collect(),
+			approvals: approvals.iter_ones().map(|i| ValidatorIndex(i as _)).collect(),
+			no_shows,
+			missing_approvals,
+		});
+	}
+
+	Ok(Some(BlockApprovalDetails {
+		block_hash,
+		block_number: block_entry.block_number(),
+		session: block_entry.session(),
+		tranche_now: state.clock.tranche_now(state.slot_duration_millis, block_entry.slot()),
+		approved: block_entry.is_fully_approved(),
+		candidates,
+	}))
+}
+
 /// Retrieve approval signatures.
 ///
 /// This involves an unbounded message send to approval-distribution, the caller has to ensure that
@@ -1252,7 +1471,7 @@ async fn get_approval_signatures_for_candidate<Context>(
 	ctx: &mut Context,
 	db: &OverlayedBackend<'_, impl Backend>,
 	candidate_hash: CandidateHash,
-	tx: oneshot::Sender<HashMap<ValidatorIndex, ValidatorSignature>>,
+	tx: oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 ) -> SubsystemResult<()> {
 	let send_votes = |votes| {
 		if let Err(_) = tx.send(votes) {
@@ -1278,6 +1497,9 @@ async fn get_approval_signatures_for_candidate<Context>(
 	let relay_hashes = entry.block_assignments.keys();
 
 	let mut candidate_indices = HashSet::new();
+	// The candidates of each block, to resolve the indices of candidates covered by approvals of
+	// multiple candidates.
+	let mut candidates_by_block = HashMap::new();
 	// Retrieve `CoreIndices`/`CandidateIndices` as required by approval-distribution:
 	for hash in relay_hashes {
 		let entry = match db.load_block_entry(hash)? {
@@ -1298,6 +1520,14 @@ async fn get_approval_signatures_for_candidate<Context>(
 				break
 			}
 		}
+		candidates_by_block.insert(
+			*hash,
+			entry
+				.candidates()
+				.iter()
+				.map(|(_core_index, c_hash)| *c_hash)
+				.collect::<Vec<_>>(),
+		);
 	}
 
 	let mut sender = ctx.sender().clone();
@@ -1321,7 +1551,32 @@ async fn get_approval_signatures_for_candidate<Context>(
 				target: LOG_TARGET,
 				"Request for approval signatures got cancelled by `approval-distribution`."
 			),
-			Some(Ok(votes)) => send_votes(votes),
+			Some(Ok(votes)) => {
+				let votes = votes
+					.into_iter()
+					.filter_map(|(validator_index, (hash, candidate_indices, signature))| {
+						let candidates = candidates_by_block.get(&hash)?;
+						let candidate_hashes = candidate_indices
+							.into_iter()
+							.map(|index| candidates.get(index as usize).copied())
+							.collect::<Option<Vec<_>>>();
+						match candidate_hashes {
+							Some(candidate_hashes) =>
+								Some((validator_index, (candidate_hashes, signature))),
+							None => {
+								gum::debug!(
+									target: LOG_TARGET,
+									?hash,
+									?validator_index,
+									"Approval signature covers unknown candidate indices."
+								);
+								None
+							},
+						}
+					})
+					.collect();
+				send_votes(votes)
+			},
 		}
 	};
 
@@ -1614,7 +1869,12 @@ fn schedule_wakeup_action(
 				last_assignment_tick.map(|l| l + APPROVAL_DELAY).filter(|t| t > &tick_now),
 				next_no_show,
 			)
-			.map(|tick| Action::ScheduleWakeup { block_hash, block_number, candidate_hash, tick })
+			.map(|tick| Action::ScheduleWakeup {
+				block_hash,
+				block_number,
+				candidate_hash,
+				tick,
+			})
 		},
 		RequiredTranches::Pending { considered, next_no_show, clock_drift, .. } => {
 			// select the minimum of `next_no_show`, or the tick of the next non-empty tranche
@@ -1672,8 +1932,8 @@ fn schedule_wakeup_action(
 fn check_and_import_assignment(
 	state: &State,
 	db: &mut OverlayedBackend<'_, impl Backend>,
-	assignment: IndirectAssignmentCert,
-	candidate_index: CandidateIndex,
+	assignment: IndirectAssignmentCertV2,
+	candidate_indices: CandidateBitfield,
 ) -> SubsystemResult<(AssignmentCheckResult, Vec<Action>)> {
 	let tick_now = state.clock.tick_now();
 
@@ -1699,34 +1959,38 @@ fn check_and_import_assignment(
 			)),
 	};
 
-	let (claimed_core_index, assigned_candidate_hash) =
-		match block_entry.candidate(candidate_index as usize) {
-			Some((c, h)) => (*c, *h),
+	// Resolve all claimed candidates along with their cores and entries.
+	let mut claimed_cores = Vec::with_capacity(candidate_indices.count_ones());
+	let mut backing_groups = Vec::with_capacity(candidate_indices.count_ones());
+	let mut candidate_entries = Vec::with_capacity(candidate_indices.count_ones());
+	for candidate_index in candidate_indices.iter_ones() {
+		let candidate_index = candidate_index as CandidateIndex;
+		let (claimed_core_index, assigned_candidate_hash) =
+			match block_entry.candidate(candidate_index as usize) {
+				Some((c, h)) => (*c, *h),
+				None =>
+					return Ok((
+						AssignmentCheckResult::Bad(AssignmentCheckError::InvalidCandidateIndex(
+							candidate_index,
+						)),
+						Vec::new(),
+					)), // no candidate at core.
+			};
+
+		let candidate_entry = match db.load_candidate_entry(&assigned_candidate_hash)? {
+			Some(c) => c,
 			None =>
 				return Ok((
-					AssignmentCheckResult::Bad(AssignmentCheckError::InvalidCandidateIndex(
+					AssignmentCheckResult::Bad(AssignmentCheckError::InvalidCandidate(
 						candidate_index,
+						assigned_candidate_hash,
 					)),
 					Vec::new(),
-				)), // no candidate at core.
-		};
-
-	let mut candidate_entry = match db.load_candidate_entry(&assigned_candidate_hash)? {
-		Some(c) => c,
-		None =>
-			return Ok((
-				AssignmentCheckResult::Bad(AssignmentCheckError::InvalidCandidate(
-					candidate_index,
-					assigned_candidate_hash,
 				)),
-				Vec::new(),
-			)),
-	};
+		};
 
-	let res = {
-		// import the assignment.
-		let approval_entry = match candidate_entry.approval_entry_mut(&assignment.block_hash) {
-			Some(a) => a,
+		let backing_group = match candidate_entry.approval_entry(&assignment.block_hash) {
+			Some(a) => a.backing_group(),
 			None =>
 				return Ok((
 					AssignmentCheckResult::Bad(AssignmentCheckError::Internal(
@@ -1737,44 +2001,72 @@ fn check_and_import_assignment(
 				)),
 		};
 
-		let res = state.assignment_criteria.check_assignment_cert(
-			claimed_core_index,
-			assignment.validator,
-			&criteria::Config::from(session_info),
-			block_entry.relay_vrf_story(),
-			&assignment.cert,
-			approval_entry.backing_group(),
-		);
+		claimed_cores.push(claimed_core_index);
+		backing_groups.push((claimed_core_index, backing_group));
+		candidate_entries.push((assigned_candidate_hash, candidate_entry));
+	}
 
-		let tranche = match res {
-			Err(crate::criteria::InvalidAssignment(reason)) =>
-				return Ok((
-					AssignmentCheckResult::Bad(AssignmentCheckError::InvalidCert(
-						assignment.validator,
-						format!("{:?}", reason),
-					)),
-					Vec::new(),
+	// The criteria expect the backing groups in core order.
+	backing_groups.sort_by_key(|(core, _)| *core);
+
+	let claimed_core_indices = match CoreBitfield::try_from(claimed_cores) {
+		Ok(b) => b,
+		Err(err) =>
+			return Ok((
+				AssignmentCheckResult::Bad(AssignmentCheckError::InvalidCert(
+					assignment.validator,
+					format!("{:?}", err),
 				)),
-			Ok(tranche) => {
-				let current_tranche =
-					state.clock.tranche_now(state.slot_duration_millis, block_entry.slot());
+				Vec::new(),
+			)),
+	};
 
-				let too_far_in_future = current_tranche + TICK_TOO_FAR_IN_FUTURE as DelayTranche;
+	let res = state.assignment_criteria.check_assignment_cert(
+		claimed_core_indices,
+		assignment.validator,
+		&criteria::Config::from(session_info),
+		block_entry.relay_vrf_story(),
+		&assignment.cert,
+		backing_groups.into_iter().map(|(_, group)| group).collect(),
+	);
 
-				if tranche >= too_far_in_future {
-					return Ok((AssignmentCheckResult::TooFarInFuture, Vec::new()))
-				}
+	let tranche = match res {
+		Err(crate::criteria::InvalidAssignment(reason)) =>
+			return Ok((
+				AssignmentCheckResult::Bad(AssignmentCheckError::InvalidCert(
+					assignment.validator,
+					format!("{:?}", reason),
+				)),
+				Vec::new(),
+			)),
+		Ok(tranche) => {
+			let current_tranche =
+				state.clock.tranche_now(state.slot_duration_millis, block_entry.slot());
 
-				tranche
-			},
-		};
+			let too_far_in_future = current_tranche + TICK_TOO_FAR_IN_FUTURE as DelayTranche;
+
+			if tranche >= too_far_in_future {
+				return Ok((AssignmentCheckResult::TooFarInFuture, Vec::new()))
+			}
+
+			tranche
+		},
+	};
 
-		let is_duplicate = approval_entry.is_assigned(assignment.validator);
+	let mut is_duplicate = true;
+	let mut actions = Vec::new();
+
+	for (assigned_candidate_hash, mut candidate_entry) in candidate_entries {
+		// import the assignment.
+		let approval_entry = candidate_entry
+			.approval_entry_mut(&assignment.block_hash)
+			.expect("Approval entry checked above; qed");
+
+		let was_assigned = approval_entry.is_assigned(assignment.validator);
 		approval_entry.import_assignment(tranche, assignment.validator, tick_now);
+		is_duplicate &= was_assigned;
 
-		if is_duplicate {
-			AssignmentCheckResult::AcceptedDuplicate
-		} else {
+		if !was_assigned {
 			gum::trace!(
 				target: LOG_TARGET,
 				validator = assignment.validator.0,
@@ -1782,28 +2074,32 @@ fn check_and_import_assignment(
 				para_id = ?candidate_entry.candidate_receipt().descriptor.para_id,
 				"Imported assignment.",
 			);
-
-			AssignmentCheckResult::Accepted
 		}
-	};
 
-	let mut actions = Vec::new();
+		// We've imported a new assignment, so we need to schedule a wake-up for when that might no-show.
+		if let Some((approval_entry, status)) =
+			state.approval_status(&block_entry, &candidate_entry)
+		{
+			actions.extend(schedule_wakeup_action(
+				approval_entry,
+				block_entry.block_hash(),
+				block_entry.block_number(),
+				assigned_candidate_hash,
+				status.block_tick,
+				tick_now,
+				status.required_tranches,
+			));
+		}
 
-	// We've imported a new approval, so we need to schedule a wake-up for when that might no-show.
-	if let Some((approval_entry, status)) = state.approval_status(&block_entry, &candidate_entry) {
-		actions.extend(schedule_wakeup_action(
-			approval_entry,
-			block_entry.block_hash(),
-			block_entry.block_number(),
-			assigned_candidate_hash,
-			status.block_tick,
-			tick_now,
-			status.required_tranches,
-		));
+		// We also write the candidate entry as it now contains the new candidate.
+		db.write_candidate_entry(candidate_entry.into());
 	}
 
-	// We also write the candidate entry as it now contains the new candidate.
-	db.write_candidate_entry(candidate_entry.into());
+	let res = if is_duplicate {
+		AssignmentCheckResult::AcceptedDuplicate
+	} else {
+		AssignmentCheckResult::Accepted
+	};
 
 	Ok((res, actions))
 }
@@ -1812,7 +2108,7 @@ fn check_and_import_approval<T>(
 	state: &State,
 	db: &mut OverlayedBackend<'_, impl Backend>,
 	metrics: &Metrics,
-	approval: IndirectSignedApprovalVote,
+	approval: IndirectSignedApprovalVoteV2,
 	with_response: impl FnOnce(ApprovalCheckResult) -> T,
 ) -> SubsystemResult<(Vec<Action>, T)> {
 	macro_rules! respond_early {
@@ -1840,12 +2136,21 @@ fn check_and_import_approval<T>(
 		},
 	};
 
-	let approved_candidate_hash = match block_entry.candidate(approval.candidate_index as usize) {
-		Some((_, h)) => *h,
-		None => respond_early!(ApprovalCheckResult::Bad(
-			ApprovalCheckError::InvalidCandidateIndex(approval.candidate_index),
-		)),
-	};
+	if approval.candidate_indices.count_ones() == 0 {
+		respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::NoCandidates))
+	}
+
+	// The candidate hashes, in ascending candidate index order.
+	let mut approved_candidates = Vec::with_capacity(approval.candidate_indices.count_ones());
+	for candidate_index in approval.candidate_indices.iter_ones() {
+		let candidate_index = candidate_index as CandidateIndex;
+		match block_entry.candidate(candidate_index as usize) {
+			Some((_, h)) => approved_candidates.push((candidate_index, *h)),
+			None => respond_early!(ApprovalCheckResult::Bad(
+				ApprovalCheckError::InvalidCandidateIndex(candidate_index),
+			)),
+		}
+	}
 
 	let pubkey = match session_info.validators.get(approval.validator) {
 		Some(k) => k,
@@ -1854,66 +2159,84 @@ fn check_and_import_approval<T>(
 		)),
 	};
 
-	// Signature check:
-	match DisputeStatement::Valid(ValidDisputeStatementKind::ApprovalChecking).check_signature(
-		&pubkey,
-		approved_candidate_hash,
-		block_entry.session(),
-		&approval.signature,
-	) {
-		Err(_) => respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::InvalidSignature(
+	// Signature check. For a single candidate, this is the same payload as a plain
+	// approval vote.
+	let approved_candidate_hashes: Vec<CandidateHash> =
+		approved_candidates.iter().map(|(_, h)| *h).collect();
+	let payload = ApprovalVoteMultipleCandidates(&approved_candidate_hashes)
+		.signing_payload(block_entry.session());
+	if !approval.signature.verify(&payload[..], pubkey) {
+		respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::InvalidSignature(
 			approval.validator
-		),)),
-		Ok(()) => {},
-	};
+		),))
+	}
 
-	let candidate_entry = match db.load_candidate_entry(&approved_candidate_hash)? {
-		Some(c) => c,
-		None => {
-			respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::InvalidCandidate(
-				approval.candidate_index,
-				approved_candidate_hash
-			),))
-		},
-	};
+	let mut candidate_entries = Vec::with_capacity(approved_candidates.len());
+	for (candidate_index, approved_candidate_hash) in approved_candidates {
+		let candidate_entry = match db.load_candidate_entry(&approved_candidate_hash)? {
+			Some(c) => c,
+			None => {
+				respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::InvalidCandidate(
+					candidate_index,
+					approved_candidate_hash
+				),))
+			},
+		};
 
-	// Don't accept approvals until assignment.
-	match candidate_entry.approval_entry(&approval.block_hash) {
-		None => {
-			respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::Internal(
-				approval.block_hash,
-				approved_candidate_hash
-			),))
-		},
-		Some(e) if !e.is_assigned(approval.validator) => {
-			respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::NoAssignment(
-				approval.validator
-			),))
-		},
-		_ => {},
+		// Don't accept approvals until assignment.
+		match candidate_entry.approval_entry(&approval.block_hash) {
+			None => {
+				respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::Internal(
+					approval.block_hash,
+					approved_candidate_hash
+				),))
+			},
+			Some(e) if !e.is_assigned(approval.validator) => {
+				respond_early!(ApprovalCheckResult::Bad(ApprovalCheckError::NoAssignment(
+					approval.validator
+				),))
+			},
+			_ => {},
+		}
+
+		candidate_entries.push((approved_candidate_hash, candidate_entry));
 	}
 
 	// importing the approval can be heavy as it may trigger acceptance for a series of blocks.
 	let t = with_response(ApprovalCheckResult::Accepted);
 
-	gum::trace!(
-		target: LOG_TARGET,
-		validator_index = approval.validator.0,
-		validator = ?pubkey,
-		candidate_hash = ?approved_candidate_hash,
-		para_id = ?candidate_entry.candidate_receipt().descriptor.para_id,
-		"Importing approval vote",
-	);
+	let mut actions = Vec::new();
+	let mut block_entry = Some(block_entry);
+	for (approved_candidate_hash, candidate_entry) in candidate_entries {
+		gum::trace!(
+			target: LOG_TARGET,
+			validator_index = approval.validator.0,
+			validator = ?pubkey,
+			candidate_hash = ?approved_candidate_hash,
+			para_id = ?candidate_entry.candidate_receipt().descriptor.para_id,
+			"Importing approval vote",
+		);
 
-	let actions = advance_approval_state(
-		state,
-		db,
-		&metrics,
-		block_entry,
-		approved_candidate_hash,
-		candidate_entry,
-		ApprovalStateTransition::RemoteApproval(approval.validator),
-	);
+		// Advancing the state may update the block entry, so it is reloaded for every
+		// candidate after the first.
+		let block_entry = match block_entry.take() {
+			Some(b) => b,
+			None => match db.load_block_entry(&approval.block_hash)? {
+				Some(b) => b,
+				None => break,
+			},
+		};
+
+		actions.extend(advance_approval_state(
+			state,
+			db,
+			&metrics,
+			block_entry,
+			approved_candidate_hash,
+			candidate_entry,
+			ApprovalStateTransition::RemoteApproval(approval.validator),
+		));
+	}
 
 	Ok((actions, t))
 }
@@ -2215,9 +2538,6 @@ fn process_wakeup(
 	};
 
 	if let Some((cert, val_index, tranche)) = maybe_cert {
-		let indirect_cert =
-			IndirectAssignmentCert { block_hash: relay_block, validator: val_index, cert };
-
 		let index_in_candidate =
 			block_entry.candidates().iter().position(|(_, h)| &candidate_hash == h);
 
@@ -2230,16 +2550,59 @@ fn process_wakeup(
 				"Launching approval work.",
 			);
 
+			let mut claimed_candidate_indices = vec![i as CandidateIndex];
+			let mut candidates = vec![(candidate_hash, candidate_receipt, backing_group)];
+
+			// A compact cert claims all of its cores at once, so the assignments to the other
+			// candidates it covers are triggered along with this one.
+			if let AssignmentCertKindV2::RelayVRFModuloCompact { ref core_bitfield } = cert.kind {
+				for (other_index, (core, other_hash)) in block_entry.candidates().iter().enumerate()
+				{
+					if other_index == i || !core_bitfield.bit_at(*core) {
+						continue
+					}
+
+					let mut other_entry = match db.load_candidate_entry(other_hash)? {
+						Some(c) => c,
+						None => continue,
+					};
+
+					let (triggered, other_backing_group) =
+						match other_entry.approval_entry_mut(&relay_block) {
+							Some(a) => (
+								a.trigger_our_assignment(state.clock.tick_now()).is_some(),
+								a.backing_group(),
+							),
+							None => continue,
+						};
+
+					if triggered {
+						claimed_candidate_indices.push(other_index as _);
+						candidates.push((
+							*other_hash,
+							other_entry.candidate_receipt().clone(),
+							other_backing_group,
+						));
+						db.write_candidate_entry(other_entry);
+					}
+				}
+			}
+
+			let claimed_candidate_indices = CandidateBitfield::try_from(claimed_candidate_indices)
+				.expect("contains at least the woken candidate; qed");
+
 			// sanity: should always be present.
 			actions.push(Action::LaunchApproval {
-				candidate_hash,
-				indirect_cert,
+				indirect_cert: IndirectAssignmentCertV2 {
+					block_hash: relay_block,
+					validator: val_index,
+					cert,
+				},
+				claimed_candidate_indices,
 				assignment_tranche: tranche,
 				relay_block_hash: relay_block,
-				candidate_index: i as _,
 				session: block_entry.session(),
-				candidate: candidate_receipt,
-				backing_group,
+				candidates,
 			});
 		}
 	}
@@ -2454,6 +2817,9 @@ async fn launch_approval<Context>(
 
 // Issue and import a local approval vote. Should only be invoked after approval checks
 // have been done.
+//
+// With coalescing enabled, the approval is only signed once enough approvals of candidates
+// in the same block are pending, or their deadline has passed.
 #[overseer::contextbounds(ApprovalVoting, prefix = self::overseer)]
 async fn issue_approval<Context>(
 	ctx: &mut Context,
@@ -2518,38 +2884,146 @@ async fn issue_approval<Context>(
 		},
 	};
 
-	let candidate_entry = match db.load_candidate_entry(&candidate_hash)? {
-		Some(c) => c,
-		None => {
-			gum::warn!(
-				target: LOG_TARGET,
-				"Missing entry for candidate index {} included at block {:?}",
-				candidate_index,
+	if db.load_candidate_entry(&candidate_hash)?.is_none() {
+		gum::warn!(
+			target: LOG_TARGET,
+			"Missing entry for candidate index {} included at block {:?}",
+			candidate_index,
+			block_hash,
+		);
+
+		metrics.on_approval_error();
+		return Ok(Vec::new())
+	}
+
+	if session_info.validators.get(validator_index).is_none() {
+		gum::warn!(
+			target: LOG_TARGET,
+			"Validator index {} out of bounds in session {}",
+			validator_index.0,
+			block_entry.session(),
+		);
+
+		metrics.on_approval_error();
+		return Ok(Vec::new())
+	}
+
+	let candidate_index = candidate_index as CandidateIndex;
+
+	if state.max_approval_coalesce_count <= 1 {
+		return sign_and_import_approvals(
+			ctx,
+			state,
+			db,
+			metrics,
+			block_hash,
+			validator_index,
+			vec![(candidate_index, candidate_hash)],
+		)
+		.await
+	}
+
+	let tick_now = state.clock.tick_now();
+	let pending =
+		state
+			.approvals_to_coalesce
+			.entry(block_hash)
+			.or_insert_with(|| ApprovalsToCoalesce {
+				validator_index,
+				candidates: Vec::new(),
+				deadline: tick_now + MAX_APPROVAL_COALESCE_WAIT_TICKS,
+			});
+
+	if !pending.candidates.iter().any(|(_, h)| h == &candidate_hash) {
+		pending.candidates.push((candidate_index, candidate_hash));
+	}
+
+	gum::trace!(
+		target: LOG_TARGET,
+		?candidate_hash,
+		?block_hash,
+		pending = pending.candidates.len(),
+		"Coalescing approval vote",
+	);
+
+	if pending.candidates.len() >= state.max_approval_coalesce_count as usize {
+		sign_coalesced_approvals(ctx, state, db, metrics, block_hash).await
+	} else {
+		Ok(Vec::new())
+	}
+}
+
+// Sign and import the approvals waiting to be coalesced for the given block, if any.
+#[overseer::contextbounds(ApprovalVoting, prefix = self::overseer)]
+async fn sign_coalesced_approvals<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	db: &mut OverlayedBackend<'_, impl Backend>,
+	metrics: &Metrics,
+	block_hash: Hash,
+) -> SubsystemResult<Vec<Action>> {
+	match state.approvals_to_coalesce.remove(&block_hash) {
+		Some(ApprovalsToCoalesce { validator_index, candidates, .. }) =>
+			sign_and_import_approvals(
+				ctx,
+				state,
+				db,
+				metrics,
 				block_hash,
-			);
+				validator_index,
+				candidates,
+			)
+			.await,
+		None => Ok(Vec::new()),
+	}
+}
 
-			metrics.on_approval_error();
-			return Ok(Vec::new())
-		},
-	};
+// Sign a single approval vote covering all given candidates of a block, import it and
+// dispatch it to approval distribution.
+#[overseer::contextbounds(ApprovalVoting, prefix = self::overseer)]
+async fn sign_and_import_approvals<Context>(
+	ctx: &mut Context,
+	state: &mut State,
+	db: &mut OverlayedBackend<'_, impl Backend>,
+	metrics: &Metrics,
+	block_hash: Hash,
+	validator_index: ValidatorIndex,
+	mut candidates: Vec<(CandidateIndex, CandidateHash)>,
+) -> SubsystemResult<Vec<Action>> {
+	let (session, validator_pubkey) = {
+		let block_entry = match db.load_block_entry(&block_hash)? {
+			Some(b) => b,
+			None => {
+				// not a cause for alarm - just lost a race with pruning, most likely.
+				metrics.on_approval_stale();
+				return Ok(Vec::new())
+			},
+		};
 
-	let validator_pubkey = match session_info.validators.get(validator_index) {
-		Some(p) => p,
-		None => {
-			gum::warn!(
-				target: LOG_TARGET,
-				"Validator index {} out of bounds in session {}",
-				validator_index.0,
-				block_entry.session(),
-			);
+		let session = block_entry.session();
+		match state.session_info(session).and_then(|s| s.validators.get(validator_index)) {
+			Some(p) => (session, p.clone()),
+			None => {
+				gum::warn!(
+					target: LOG_TARGET,
+					"Missing session info or validator {} for live block {} in session {}",
+					validator_index.0,
+					block_hash,
+					session,
+				);
 
-			metrics.on_approval_error();
-			return Ok(Vec::new())
-		},
+				metrics.on_approval_error();
+				return Ok(Vec::new())
+			},
+		}
 	};
 
-	let session = block_entry.session();
-	let sig = match sign_approval(&state.keystore, &validator_pubkey, candidate_hash, session) {
+	// The signature is over the candidate hashes in ascending candidate index order.
+	candidates.sort_by_key(|(index, _)| *index);
+	candidates.dedup_by_key(|(index, _)| *index);
+
+	let candidate_hashes: Vec<CandidateHash> = candidates.iter().map(|(_, h)| *h).collect();
+	let sig = match sign_approval(&state.keystore, &validator_pubkey, &candidate_hashes, session) {
 		Some(sig) => sig,
 		None => {
 			gum::warn!(
@@ -2564,31 +3038,51 @@ async fn issue_approval<Context>(
 		},
 	};
 
-	gum::trace!(
-		target: LOG_TARGET,
-		?candidate_hash,
-		?block_hash,
-		validator_index = validator_index.0,
-		"Issuing approval vote",
-	);
+	let mut actions = Vec::new();
+	for (_, candidate_hash) in &candidates {
+		// Advancing the state may update the block entry, so it is loaded for every candidate.
+		let (block_entry, candidate_entry) =
+			match (db.load_block_entry(&block_hash)?, db.load_candidate_entry(candidate_hash)?) {
+				(Some(b), Some(c)) => (b, c),
+				_ => {
+					metrics.on_approval_stale();
+					continue
+				},
+			};
 
-	let actions = advance_approval_state(
-		state,
-		db,
-		metrics,
-		block_entry,
-		candidate_hash,
-		candidate_entry,
-		ApprovalStateTransition::LocalApproval(validator_index as _, sig.clone()),
-	);
+		gum::trace!(
+			target: LOG_TARGET,
+			?candidate_hash,
+			?block_hash,
+			validator_index = validator_index.0,
+			"Issuing approval vote",
+		);
+
+		actions.extend(advance_approval_state(
+			state,
+			db,
+			metrics,
+			block_entry,
+			*candidate_hash,
+			candidate_entry,
+			ApprovalStateTransition::LocalApproval(validator_index as _, sig.clone()),
+		));
 
-	metrics.on_approval_produced();
+		metrics.on_approval_produced();
+	}
+
+	let candidate_indices = match CandidateBitfield::try_from(
+		candidates.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+	) {
+		Ok(b) => b,
+		Err(_) => return Ok(actions),
+	};
 
 	// dispatch to approval distribution.
 	ctx.send_unbounded_message(ApprovalDistributionMessage::DistributeApproval(
-		IndirectSignedApprovalVote {
+		IndirectSignedApprovalVoteV2 {
 			block_hash,
-			candidate_index: candidate_index as _,
+			candidate_indices,
 			validator: validator_index,
 			signature: sig,
 		},
@@ -2597,16 +3091,16 @@ async fn issue_approval<Context>(
 	Ok(actions)
 }
 
-// Sign an approval vote. Fails if the key isn't present in the store.
+// Sign an approval vote for the given candidates. Fails if the key isn't present in the store.
 fn sign_approval(
 	keystore: &LocalKeystore,
 	public: &ValidatorId,
-	candidate_hash: CandidateHash,
+	candidate_hashes: &[CandidateHash],
 	session_index: SessionIndex,
 ) -> Option<ValidatorSignature> {
 	let key = keystore.key_pair::<ValidatorPair>(public).ok().flatten()?;
 
-	let payload = ApprovalVote(candidate_hash).signing_payload(session_index);
+	let payload = ApprovalVoteMultipleCandidates(candidate_hashes).signing_payload(session_index);
 
 	Some(key.sign(&payload[..]))
 }
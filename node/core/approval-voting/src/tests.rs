@@ -19,14 +19,15 @@ use crate::tests::test_constants::TEST_CONFIG;
 use super::*;
 use polkadot_node_primitives::{
 	approval::{
-		AssignmentCert, AssignmentCertKind, DelayTranche, VRFOutput, VRFProof,
-		RELAY_VRF_MODULO_CONTEXT,
+		v2::{AssignmentCertKindV2, AssignmentCertV2},
+		DelayTranche, VRFOutput, VRFProof, RELAY_VRF_MODULO_CONTEXT,
 	},
 	AvailableData, BlockData, PoV,
 };
 use polkadot_node_subsystem::{
 	messages::{
 		AllMessages, ApprovalVotingMessage, AssignmentCheckResult, AvailabilityRecoveryMessage,
+		RequiredTranchesDetails,
 	},
 	ActivatedLeaf, ActiveLeavesUpdate, LeafStatus,
 };
@@ -34,8 +35,8 @@ use polkadot_node_subsystem_test_helpers as test_helpers;
 use polkadot_node_subsystem_util::TimeoutExt;
 use polkadot_overseer::HeadSupportsParachains;
 use polkadot_primitives::{
-	CandidateCommitments, CandidateEvent, CoreIndex, GroupIndex, Header, Id as ParaId, IndexedVec,
-	ValidationCode, ValidatorSignature,
+	ApprovalVote, CandidateCommitments, CandidateEvent, CoreIndex, GroupIndex, Header,
+	Id as ParaId, IndexedVec, ValidationCode, ValidatorSignature,
 };
 use std::time::Duration;
 
@@ -246,12 +247,12 @@ where
 
 	fn check_assignment_cert(
 		&self,
-		_claimed_core_index: polkadot_primitives::CoreIndex,
+		_claimed_core_indices: polkadot_node_primitives::approval::v2::CoreBitfield,
 		validator_index: ValidatorIndex,
 		_config: &criteria::Config,
 		_relay_vrf_story: polkadot_node_primitives::approval::RelayVRFStory,
-		_assignment: &polkadot_node_primitives::approval::AssignmentCert,
-		_backing_group: polkadot_primitives::GroupIndex,
+		_assignment: &polkadot_node_primitives::approval::v2::AssignmentCertV2,
+		_backing_groups: Vec<polkadot_primitives::GroupIndex>,
 	) -> Result<polkadot_node_primitives::approval::DelayTranche, criteria::InvalidAssignment> {
 		self.1(validator_index)
 	}
@@ -382,7 +383,7 @@ impl Backend for TestStore {
 	}
 }
 
-fn garbage_assignment_cert(kind: AssignmentCertKind) -> AssignmentCert {
+fn garbage_assignment_cert(kind: AssignmentCertKindV2) -> AssignmentCertV2 {
 	let ctx = schnorrkel::signing_context(RELAY_VRF_MODULO_CONTEXT);
 	let msg = b"test-garbage";
 	let mut prng = rand_core::OsRng;
@@ -390,7 +391,7 @@ fn garbage_assignment_cert(kind: AssignmentCertKind) -> AssignmentCert {
 	let (inout, proof, _) = keypair.vrf_sign(ctx.bytes(msg));
 	let out = inout.to_output();
 
-	AssignmentCert { kind, vrf: (VRFOutput(out), VRFProof(proof)) }
+	AssignmentCertV2 { kind, vrf: (VRFOutput(out), VRFProof(proof)) }
 }
 
 fn sign_approval(
@@ -492,6 +493,8 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 				col_approval_data: test_constants::TEST_CONFIG.col_approval_data,
 				slot_duration_millis: SLOT_DURATION_MILLIS,
 				col_session_data: TEST_CONFIG.col_session_data,
+				max_approval_coalesce_count: 1,
+				enable_compact_assignments: false,
 			},
 			Arc::new(db),
 			Arc::new(keystore),
@@ -592,7 +595,12 @@ async fn check_and_import_approval(
 		overseer,
 		FromOrchestra::Communication {
 			msg: ApprovalVotingMessage::CheckAndImportApproval(
-				IndirectSignedApprovalVote { block_hash, candidate_index, validator, signature },
+				IndirectSignedApprovalVoteV2 {
+					block_hash,
+					candidate_indices: candidate_index.into(),
+					validator,
+					signature,
+				},
 				tx,
 			),
 		},
@@ -620,12 +628,14 @@ async fn check_and_import_assignment(
 		overseer,
 		FromOrchestra::Communication {
 			msg: ApprovalVotingMessage::CheckAndImportAssignment(
-				IndirectAssignmentCert {
+				IndirectAssignmentCertV2 {
 					block_hash,
 					validator,
-					cert: garbage_assignment_cert(AssignmentCertKind::RelayVRFModulo { sample: 0 }),
+					cert: garbage_assignment_cert(AssignmentCertKindV2::RelayVRFModulo {
+						sample: 0,
+					}),
 				},
-				candidate_index,
+				candidate_index.into(),
 				tx,
 			),
 		},
@@ -634,6 +644,21 @@ async fn check_and_import_assignment(
 	rx
 }
 
+async fn block_approval_details(
+	overseer: &mut VirtualOverseer,
+	block_hash: Hash,
+) -> Option<BlockApprovalDetails> {
+	let (tx, rx) = oneshot::channel();
+	overseer_send(
+		overseer,
+		FromOrchestra::Communication {
+			msg: ApprovalVotingMessage::BlockApprovalDetails(block_hash, tx),
+		},
+	)
+	.await;
+	rx.await.unwrap()
+}
+
 struct BlockConfig {
 	slot: Slot,
 	candidates: Option<Vec<(CandidateReceipt, CoreIndex, GroupIndex)>>,
@@ -1106,14 +1131,14 @@ fn blank_subsystem_act_on_bad_block() {
 			&mut virtual_overseer,
 			FromOrchestra::Communication {
 				msg: ApprovalVotingMessage::CheckAndImportAssignment(
-					IndirectAssignmentCert {
+					IndirectAssignmentCertV2 {
 						block_hash: bad_block_hash.clone(),
 						validator: 0u32.into(),
-						cert: garbage_assignment_cert(AssignmentCertKind::RelayVRFModulo {
+						cert: garbage_assignment_cert(AssignmentCertKindV2::RelayVRFModulo {
 							sample: 0,
 						}),
 					},
-					0u32,
+					0u32.into(),
 					tx,
 				),
 			},
@@ -1519,6 +1544,92 @@ fn subsystem_accepts_and_imports_approval_after_assignment() {
 	});
 }
 
+#[test]
+fn subsystem_block_approval_details_explain_missing_approvals() {
+	test_harness(HarnessConfig::default(), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, sync_oracle_handle: _sync_oracle_handle, .. } =
+			test_harness;
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::ChainApi(ChainApiMessage::FinalizedBlockNumber(rx)) => {
+				rx.send(Ok(0)).unwrap();
+			}
+		);
+
+		let block_hash = Hash::repeat_byte(0x01);
+		let candidate_hash = make_candidate(ParaId::from(0_u32), &block_hash).hash();
+		let candidate_index = 0;
+		let validator = ValidatorIndex(0);
+		let session_index = 1;
+
+		ChainBuilder::new()
+			.add_block(
+				block_hash,
+				ChainBuilder::GENESIS_HASH,
+				1,
+				BlockConfig { slot: Slot::from(1), candidates: None, session_info: None },
+			)
+			.build(&mut virtual_overseer)
+			.await;
+
+		assert_eq!(
+			block_approval_details(&mut virtual_overseer, Hash::repeat_byte(0x02)).await,
+			None,
+		);
+
+		let rx = check_and_import_assignment(
+			&mut virtual_overseer,
+			block_hash,
+			candidate_index,
+			validator,
+		)
+		.await;
+		assert_eq!(rx.await, Ok(AssignmentCheckResult::Accepted));
+
+		let details = block_approval_details(&mut virtual_overseer, block_hash).await.unwrap();
+		assert_eq!(details.block_number, 1);
+		assert_eq!(details.session, session_index);
+		assert!(!details.approved);
+		assert_eq!(details.candidates.len(), 1);
+
+		let candidate = &details.candidates[0];
+		assert_eq!(candidate.candidate_hash, candidate_hash);
+		assert!(!candidate.approved);
+		assert_matches!(
+			candidate.required_tranches,
+			Some(RequiredTranchesDetails::Exact { needed: 0, tolerated_missing: 0, .. })
+		);
+		assert_matches!(
+			&candidate.assignments[..],
+			[AssignmentDetails { validator: ValidatorIndex(0), tranche: 0, .. }]
+		);
+		assert!(candidate.approvals.is_empty());
+		assert!(candidate.no_shows.is_empty());
+		assert_eq!(candidate.missing_approvals, vec![validator]);
+
+		let rx = check_and_import_approval(
+			&mut virtual_overseer,
+			block_hash,
+			candidate_index,
+			validator,
+			candidate_hash,
+			session_index,
+			true,
+			None,
+		)
+		.await;
+		assert_eq!(rx.await, Ok(ApprovalCheckResult::Accepted));
+
+		let details = block_approval_details(&mut virtual_overseer, block_hash).await.unwrap();
+		assert!(details.approved);
+		assert!(details.candidates[0].approved);
+		assert_eq!(details.candidates[0].approvals, vec![validator]);
+		assert!(details.candidates[0].missing_approvals.is_empty());
+
+		virtual_overseer
+	});
+}
+
 #[test]
 fn subsystem_second_approval_import_only_schedules_wakeups() {
 	test_harness(HarnessConfig::default(), |test_harness| async move {
@@ -1774,14 +1885,14 @@ fn linear_import_act_on_leaf() {
 			&mut virtual_overseer,
 			FromOrchestra::Communication {
 				msg: ApprovalVotingMessage::CheckAndImportAssignment(
-					IndirectAssignmentCert {
+					IndirectAssignmentCertV2 {
 						block_hash: head,
 						validator: 0u32.into(),
-						cert: garbage_assignment_cert(AssignmentCertKind::RelayVRFModulo {
+						cert: garbage_assignment_cert(AssignmentCertKindV2::RelayVRFModulo {
 							sample: 0,
 						}),
 					},
-					0u32,
+					0u32.into(),
 					tx,
 				),
 			},
@@ -1844,14 +1955,14 @@ fn forkful_import_at_same_height_act_on_leaf() {
 				&mut virtual_overseer,
 				FromOrchestra::Communication {
 					msg: ApprovalVotingMessage::CheckAndImportAssignment(
-						IndirectAssignmentCert {
+						IndirectAssignmentCertV2 {
 							block_hash: head,
 							validator: 0u32.into(),
-							cert: garbage_assignment_cert(AssignmentCertKind::RelayVRFModulo {
+							cert: garbage_assignment_cert(AssignmentCertKindV2::RelayVRFModulo {
 								sample: 0,
 							}),
 						},
-						0u32,
+						0u32.into(),
 						tx,
 					),
 				},
@@ -2144,6 +2255,175 @@ fn subsystem_import_checked_approval_sets_one_block_bit_at_a_time() {
 	});
 }
 
+#[test]
+fn subsystem_imports_assignments_and_approvals_covering_multiple_candidates() {
+	let config = HarnessConfig::default();
+	let store = config.backend();
+	test_harness(config, |test_harness| async move {
+		let TestHarness { mut virtual_overseer, sync_oracle_handle: _sync_oracle_handle, .. } =
+			test_harness;
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::ChainApi(ChainApiMessage::FinalizedBlockNumber(rx)) => {
+				rx.send(Ok(0)).unwrap();
+			}
+		);
+
+		let block_hash = Hash::repeat_byte(0x01);
+
+		let candidate_receipt1 = {
+			let mut receipt = dummy_candidate_receipt(block_hash);
+			receipt.descriptor.para_id = ParaId::from(1_u32);
+			receipt
+		};
+		let candidate_receipt2 = {
+			let mut receipt = dummy_candidate_receipt(block_hash);
+			receipt.descriptor.para_id = ParaId::from(2_u32);
+			receipt
+		};
+		let candidate_hash1 = candidate_receipt1.hash();
+		let candidate_hash2 = candidate_receipt2.hash();
+		let candidate_indices: CandidateBitfield = vec![0u32, 1].try_into().unwrap();
+
+		let validator1 = ValidatorIndex(0);
+		let validator2 = ValidatorIndex(1);
+		let session_index = 1;
+
+		let validators = vec![
+			Sr25519Keyring::Alice,
+			Sr25519Keyring::Bob,
+			Sr25519Keyring::Charlie,
+			Sr25519Keyring::Dave,
+			Sr25519Keyring::Eve,
+		];
+		let session_info = SessionInfo {
+			validator_groups: IndexedVec::<GroupIndex, Vec<ValidatorIndex>>::from(vec![
+				vec![ValidatorIndex(0), ValidatorIndex(1)],
+				vec![ValidatorIndex(2)],
+				vec![ValidatorIndex(3), ValidatorIndex(4)],
+			]),
+			..session_info(&validators)
+		};
+
+		ChainBuilder::new()
+			.add_block(
+				block_hash,
+				ChainBuilder::GENESIS_HASH,
+				1,
+				BlockConfig {
+					slot: Slot::from(0),
+					candidates: Some(vec![
+						(candidate_receipt1, CoreIndex(0), GroupIndex(1)),
+						(candidate_receipt2, CoreIndex(1), GroupIndex(1)),
+					]),
+					session_info: Some(session_info),
+				},
+			)
+			.build(&mut virtual_overseer)
+			.await;
+
+		for validator in [validator1, validator2] {
+			let (tx, rx) = oneshot::channel();
+			overseer_send(
+				&mut virtual_overseer,
+				FromOrchestra::Communication {
+					msg: ApprovalVotingMessage::CheckAndImportAssignment(
+						IndirectAssignmentCertV2 {
+							block_hash,
+							validator,
+							cert: garbage_assignment_cert(
+								AssignmentCertKindV2::RelayVRFModuloCompact {
+									core_bitfield: vec![CoreIndex(0), CoreIndex(1)]
+										.try_into()
+										.unwrap(),
+								},
+							),
+						},
+						candidate_indices.clone(),
+						tx,
+					),
+				},
+			)
+			.await;
+			assert_eq!(rx.await, Ok(AssignmentCheckResult::Accepted));
+		}
+
+		let sign_multiple = |key: Sr25519Keyring, hashes: &[CandidateHash]| -> ValidatorSignature {
+			key.sign(&ApprovalVoteMultipleCandidates(hashes).signing_payload(session_index))
+				.into()
+		};
+
+		// A signature which doesn't cover all claimed candidates is rejected.
+		let (tx, rx) = oneshot::channel();
+		overseer_send(
+			&mut virtual_overseer,
+			FromOrchestra::Communication {
+				msg: ApprovalVotingMessage::CheckAndImportApproval(
+					IndirectSignedApprovalVoteV2 {
+						block_hash,
+						candidate_indices: candidate_indices.clone(),
+						validator: validator1,
+						signature: sign_multiple(Sr25519Keyring::Alice, &[candidate_hash1]),
+					},
+					tx,
+				),
+			},
+		)
+		.await;
+		assert_eq!(
+			rx.await,
+			Ok(ApprovalCheckResult::Bad(ApprovalCheckError::InvalidSignature(validator1)))
+		);
+
+		let approvals =
+			vec![(validator1, Sr25519Keyring::Alice), (validator2, Sr25519Keyring::Bob)];
+		for (i, (validator, key)) in approvals.into_iter().enumerate() {
+			let expect_block_approved = i == 1;
+
+			let (tx, rx) = oneshot::channel();
+			overseer_send(
+				&mut virtual_overseer,
+				FromOrchestra::Communication {
+					msg: ApprovalVotingMessage::CheckAndImportApproval(
+						IndirectSignedApprovalVoteV2 {
+							block_hash,
+							candidate_indices: candidate_indices.clone(),
+							validator,
+							signature: sign_multiple(key, &[candidate_hash1, candidate_hash2]),
+						},
+						tx,
+					),
+				},
+			)
+			.await;
+
+			if expect_block_approved {
+				assert_matches!(
+					overseer_recv(&mut virtual_overseer).await,
+					AllMessages::ChainSelection(ChainSelectionMessage::Approved(b_hash)) => {
+						assert_eq!(b_hash, block_hash);
+					}
+				);
+			}
+			assert_eq!(rx.await, Ok(ApprovalCheckResult::Accepted));
+
+			// Sleep to get a consistent read on the database.
+			futures_timer::Delay::new(Duration::from_millis(200)).await;
+
+			for candidate_hash in [candidate_hash1, candidate_hash2] {
+				let candidate_entry = store.load_candidate_entry(&candidate_hash).unwrap().unwrap();
+				assert!(candidate_entry.approvals()[validator.0 as usize]);
+			}
+
+			let block_entry = store.load_block_entry(&block_hash).unwrap().unwrap();
+			assert_eq!(block_entry.is_fully_approved(), expect_block_approved);
+		}
+
+		virtual_overseer
+	});
+}
+
 fn approved_ancestor_test(
 	skip_approval: impl Fn(BlockNumber) -> bool,
 	approved_height: BlockNumber,
@@ -2296,7 +2576,9 @@ fn subsystem_validate_approvals_cache() {
 			let _ = assignments.insert(
 				CoreIndex(0),
 				approval_db::v1::OurAssignment {
-					cert: garbage_assignment_cert(AssignmentCertKind::RelayVRFModulo { sample: 0 }),
+					cert: garbage_assignment_cert(AssignmentCertKindV2::RelayVRFModulo {
+						sample: 0,
+					}),
 					tranche: 0,
 					validator_index: ValidatorIndex(0),
 					triggered: false,
@@ -2408,7 +2690,7 @@ pub async fn handle_double_assignment_import(
 			_,
 			c_index,
 		)) => {
-			assert_eq!(candidate_index, c_index);
+			assert_eq!(c_index, candidate_index.into());
 		}
 	);
 
@@ -2423,7 +2705,7 @@ pub async fn handle_double_assignment_import(
 			AllMessages::ApprovalDistribution(
 				ApprovalDistributionMessage::DistributeAssignment(_, c_index),
 			) => {
-				assert_eq!(candidate_index, c_index);
+				assert_eq!(c_index, candidate_index.into());
 			},
 			AllMessages::CandidateValidation(
 				CandidateValidationMessage::ValidateFromExhaustive(_, _, _, _, timeout, tx),
@@ -2510,7 +2792,9 @@ where
 			let _ = assignments.insert(
 				CoreIndex(0),
 				approval_db::v1::OurAssignment {
-					cert: garbage_assignment_cert(AssignmentCertKind::RelayVRFModulo { sample: 0 }),
+					cert: garbage_assignment_cert(AssignmentCertKindV2::RelayVRFModulo {
+						sample: 0,
+					}),
 					tranche: our_assigned_tranche,
 					validator_index: ValidatorIndex(0),
 					triggered: false,
@@ -64,13 +64,18 @@ const PRUNE_BY_TIME_PREFIX: &[u8; 13] = b"prune_by_time";
 // rocksdb doesn't support empty values.
 const TOMBSTONE_VALUE: &[u8] = b" ";
 
-/// Unavailable blocks are kept for 1 hour.
+/// Unavailable blocks are kept for 1 hour by default.
 const KEEP_UNAVAILABLE_FOR: Duration = Duration::from_secs(60 * 60);
 
-/// Finalized data is kept for 25 hours.
+/// Finalized data is kept for 25 hours by default.
 const KEEP_FINALIZED_FOR: Duration = Duration::from_secs(25 * 60 * 60);
 
-/// The pruning interval.
+/// Finalized data is needed to participate in disputes, which can be raised for the 6 sessions of
+/// the dispute window (24 hours on Polkadot) after inclusion. It is therefore kept for at least 25
+/// hours, regardless of the configured `keep_finalized_for` and size limit.
+pub const MIN_KEEP_FINALIZED_FOR: Duration = KEEP_FINALIZED_FOR;
+
+/// The default pruning interval.
 const PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 5);
 
 /// Unix time wrapper with big-endian encoding.
@@ -169,15 +174,19 @@ fn query_inner<D: Decode>(
 	}
 }
 
+// Returns the size of the written entry, key included.
 fn write_available_data(
 	tx: &mut DBTransaction,
 	config: &Config,
 	hash: &CandidateHash,
 	available_data: &AvailableData,
-) {
+) -> u64 {
 	let key = (AVAILABLE_PREFIX, hash).encode();
+	let value = available_data.encode();
+	let size = (key.len() + value.len()) as u64;
 
-	tx.put_vec(config.col_data, &key[..], available_data.encode());
+	tx.put_vec(config.col_data, &key[..], value);
+	size
 }
 
 fn load_available_data(
@@ -207,16 +216,20 @@ fn load_chunk(
 	query_inner(db, config.col_data, &key)
 }
 
+// Returns the size of the written entry, key included.
 fn write_chunk(
 	tx: &mut DBTransaction,
 	config: &Config,
 	candidate_hash: &CandidateHash,
 	chunk_index: ValidatorIndex,
 	erasure_chunk: &ErasureChunk,
-) {
+) -> u64 {
 	let key = (CHUNK_PREFIX, candidate_hash, chunk_index).encode();
+	let value = erasure_chunk.encode();
+	let size = (key.len() + value.len()) as u64;
 
-	tx.put_vec(config.col_data, &key, erasure_chunk.encode());
+	tx.put_vec(config.col_data, &key, value);
+	size
 }
 
 fn delete_chunk(
@@ -240,10 +253,19 @@ fn load_meta(
 	query_inner(db, config.col_meta, &key)
 }
 
-fn write_meta(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash, meta: &CandidateMeta) {
+// Returns the size of the written entry, key included.
+fn write_meta(
+	tx: &mut DBTransaction,
+	config: &Config,
+	hash: &CandidateHash,
+	meta: &CandidateMeta,
+) -> u64 {
 	let key = (META_PREFIX, hash).encode();
+	let value = meta.encode();
+	let size = (key.len() + value.len()) as u64;
 
-	tx.put_vec(config.col_meta, &key, meta.encode());
+	tx.put_vec(config.col_meta, &key, value);
+	size
 }
 
 fn delete_meta(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash) {
@@ -396,28 +418,41 @@ impl Error {
 	}
 }
 
-/// Struct holding pruning timing configuration.
-/// The only purpose of this structure is to use different timing
-/// configurations in production and in testing.
-#[derive(Clone)]
-struct PruningConfig {
+/// The pruning policy of the availability store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruningConfig {
 	/// How long unavailable data should be kept.
-	keep_unavailable_for: Duration,
+	pub keep_unavailable_for: Duration,
 
-	/// How long finalized data should be kept.
-	keep_finalized_for: Duration,
+	/// How long finalized data should be kept. Raised to [`MIN_KEEP_FINALIZED_FOR`] if lower.
+	pub keep_finalized_for: Duration,
 
 	/// How often to perform data pruning.
-	pruning_interval: Duration,
+	pub pruning_interval: Duration,
+
+	/// The maximum size of the store in bytes, enforced on every pruning. Once it is exceeded,
+	/// the data of the candidates finalized the longest time ago is evicted before it expires.
+	/// `None` disables the limit.
+	///
+	/// The data of unfinalized candidates and of candidates finalized less than
+	/// [`MIN_KEEP_FINALIZED_FOR`] ago is never evicted, so the limit only has an effect on nodes
+	/// keeping finalized data for longer than that, e.g. archive nodes.
+	pub max_size: Option<u64>,
+}
+
+impl PruningConfig {
+	/// The default pruning policy, keeping finalized data for 25 hours without a size limit.
+	pub const DEFAULT: Self = Self {
+		keep_unavailable_for: KEEP_UNAVAILABLE_FOR,
+		keep_finalized_for: KEEP_FINALIZED_FOR,
+		pruning_interval: PRUNING_INTERVAL,
+		max_size: None,
+	};
 }
 
 impl Default for PruningConfig {
 	fn default() -> Self {
-		Self {
-			keep_unavailable_for: KEEP_UNAVAILABLE_FOR,
-			keep_finalized_for: KEEP_FINALIZED_FOR,
-			pruning_interval: PRUNING_INTERVAL,
-		}
+		Self::DEFAULT
 	}
 }
 
@@ -428,6 +463,96 @@ pub struct Config {
 	pub col_data: u32,
 	/// The column family for availability store meta information.
 	pub col_meta: u32,
+	/// The pruning policy.
+	pub pruning: PruningConfig,
+}
+
+/// The size in bytes of the entries in the store, keys included, by kind.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StoreSize {
+	pub(crate) available: u64,
+	pub(crate) chunk: u64,
+	pub(crate) meta: u64,
+}
+
+impl StoreSize {
+	fn total(&self) -> u64 {
+		self.available.saturating_add(self.chunk).saturating_add(self.meta)
+	}
+}
+
+impl std::ops::SubAssign for StoreSize {
+	fn sub_assign(&mut self, other: Self) {
+		self.available = self.available.saturating_sub(other.available);
+		self.chunk = self.chunk.saturating_sub(other.chunk);
+		self.meta = self.meta.saturating_sub(other.meta);
+	}
+}
+
+/// Running counters of the size of the store, in total and by candidate.
+///
+/// They are measured from the database once on startup and updated on every write and delete
+/// from then on.
+#[derive(Debug, Default)]
+struct SizeCounters {
+	total: StoreSize,
+	candidates: HashMap<CandidateHash, StoreSize>,
+}
+
+impl SizeCounters {
+	// Measure the size of the store by iterating over all of its data.
+	fn measure(db: &Arc<dyn Database>, config: &Config) -> Result<Self, Error> {
+		let mut candidates = HashMap::new();
+		let total = StoreSize {
+			available: prefix_size(db, config.col_data, AVAILABLE_PREFIX, &mut candidates, |s| {
+				&mut s.available
+			})?,
+			chunk: prefix_size(db, config.col_data, CHUNK_PREFIX, &mut candidates, |s| {
+				&mut s.chunk
+			})?,
+			meta: prefix_size(db, config.col_meta, META_PREFIX, &mut candidates, |s| &mut s.meta)?,
+		};
+
+		Ok(Self { total, candidates })
+	}
+
+	// Note that the available data of the candidate was written.
+	fn note_available_data(&mut self, candidate_hash: CandidateHash, size: u64) {
+		self.note_replaced(candidate_hash, size, |s| &mut s.available)
+	}
+
+	// Note that a chunk of the candidate was written, which wasn't stored before.
+	fn note_chunk(&mut self, candidate_hash: CandidateHash, size: u64) {
+		let candidate_size = self.candidates.entry(candidate_hash).or_default();
+		candidate_size.chunk = candidate_size.chunk.saturating_add(size);
+		self.total.chunk = self.total.chunk.saturating_add(size);
+	}
+
+	// Note that the meta of the candidate was written.
+	fn note_meta(&mut self, candidate_hash: CandidateHash, size: u64) {
+		self.note_replaced(candidate_hash, size, |s| &mut s.meta)
+	}
+
+	// Note that all data of the candidate was deleted.
+	fn note_deleted(&mut self, candidate_hash: &CandidateHash) {
+		if let Some(candidate_size) = self.candidates.remove(candidate_hash) {
+			self.total -= candidate_size;
+		}
+	}
+
+	// Note that an entry of which a candidate has at most one was written, replacing the previous
+	// one.
+	fn note_replaced(
+		&mut self,
+		candidate_hash: CandidateHash,
+		size: u64,
+		field: fn(&mut StoreSize) -> &mut u64,
+	) {
+		let candidate_size = field(self.candidates.entry(candidate_hash).or_default());
+		let total = field(&mut self.total);
+		*total = total.saturating_sub(*candidate_size).saturating_add(size);
+		*candidate_size = size;
+	}
 }
 
 trait Clock: Send + Sync {
@@ -453,6 +578,7 @@ pub struct AvailabilityStoreSubsystem {
 	metrics: Metrics,
 	clock: Box<dyn Clock>,
 	sync_oracle: Box<dyn SyncOracle + Send + Sync>,
+	size: SizeCounters,
 }
 
 impl AvailabilityStoreSubsystem {
@@ -463,10 +589,30 @@ impl AvailabilityStoreSubsystem {
 		sync_oracle: Box<dyn SyncOracle + Send + Sync>,
 		metrics: Metrics,
 	) -> Self {
+		let mut pruning_config = config.pruning;
+		if pruning_config.keep_finalized_for < MIN_KEEP_FINALIZED_FOR {
+			gum::warn!(
+				target: LOG_TARGET,
+				keep_finalized_for = ?pruning_config.keep_finalized_for,
+				min_keep_finalized_for = ?MIN_KEEP_FINALIZED_FOR,
+				"Finalized data must be kept for the whole dispute window, raising `keep_finalized_for`",
+			);
+			pruning_config.keep_finalized_for = MIN_KEEP_FINALIZED_FOR;
+		}
+		if pruning_config.max_size.is_some() &&
+			pruning_config.keep_finalized_for <= MIN_KEEP_FINALIZED_FOR
+		{
+			gum::warn!(
+				target: LOG_TARGET,
+				keep_finalized_for = ?pruning_config.keep_finalized_for,
+				"The size limit has no effect, as finalized data is not kept beyond the dispute window",
+			);
+		}
+
 		Self::with_pruning_config_and_clock(
 			db,
 			config,
-			PruningConfig::default(),
+			pruning_config,
 			Box::new(SystemClock),
 			sync_oracle,
 			metrics,
@@ -491,6 +637,7 @@ impl AvailabilityStoreSubsystem {
 			known_blocks: KnownUnfinalizedBlocks::default(),
 			sync_oracle,
 			finalized_number: None,
+			size: SizeCounters::default(),
 		}
 	}
 }
@@ -541,6 +688,11 @@ impl<Context> AvailabilityStoreSubsystem {
 async fn run<Context>(mut subsystem: AvailabilityStoreSubsystem, mut ctx: Context) {
 	let mut next_pruning = Delay::new(subsystem.pruning_config.pruning_interval).fuse();
 
+	match SizeCounters::measure(&subsystem.db, &subsystem.config) {
+		Ok(size) => subsystem.size = size,
+		Err(e) => e.trace(),
+	}
+
 	loop {
 		let res = run_iteration(&mut ctx, &mut subsystem, &mut next_pruning).await;
 		match res {
@@ -597,7 +749,7 @@ async fn run_iteration<Context>(
 					subsystem.known_blocks.prune_finalized(number);
 					process_block_finalized(
 						ctx,
-						&subsystem,
+						subsystem,
 						hash,
 						number,
 					).await?;
@@ -614,7 +766,14 @@ async fn run_iteration<Context>(
 			*next_pruning = Delay::new(subsystem.pruning_config.pruning_interval).fuse();
 
 			let _timer = subsystem.metrics.time_pruning();
-			prune_all(&subsystem.db, &subsystem.config, &*subsystem.clock)?;
+			prune_all(
+				&subsystem.db,
+				&subsystem.config,
+				&subsystem.pruning_config,
+				&*subsystem.clock,
+				&subsystem.metrics,
+				&mut subsystem.size,
+			)?;
 		}
 	}
 
@@ -661,6 +820,7 @@ async fn process_block_activated<Context>(
 			&mut tx,
 			&subsystem.config,
 			&subsystem.pruning_config,
+			&mut subsystem.size,
 			now,
 			hash,
 			header,
@@ -680,6 +840,7 @@ async fn process_new_head<Context>(
 	db_transaction: &mut DBTransaction,
 	config: &Config,
 	pruning_config: &PruningConfig,
+	size: &mut SizeCounters,
 	now: Duration,
 	hash: Hash,
 	header: Header,
@@ -699,6 +860,7 @@ async fn process_new_head<Context>(
 					db_transaction,
 					config,
 					pruning_config,
+					size,
 					now,
 					n_validators,
 					receipt,
@@ -710,6 +872,7 @@ async fn process_new_head<Context>(
 					db_transaction,
 					config,
 					pruning_config,
+					size,
 					(header.number, hash),
 					receipt,
 				)?;
@@ -726,6 +889,7 @@ fn note_block_backed(
 	db_transaction: &mut DBTransaction,
 	config: &Config,
 	pruning_config: &PruningConfig,
+	size: &mut SizeCounters,
 	now: Duration,
 	n_validators: usize,
 	candidate: CandidateReceipt,
@@ -744,7 +908,7 @@ fn note_block_backed(
 		let prune_at = now + pruning_config.keep_unavailable_for;
 
 		write_pruning_key(db_transaction, config, prune_at, &candidate_hash);
-		write_meta(db_transaction, config, &candidate_hash, &meta);
+		size.note_meta(candidate_hash, write_meta(db_transaction, config, &candidate_hash, &meta));
 	}
 
 	Ok(())
@@ -755,6 +919,7 @@ fn note_block_included(
 	db_transaction: &mut DBTransaction,
 	config: &Config,
 	pruning_config: &PruningConfig,
+	size: &mut SizeCounters,
 	block: (BlockNumber, Hash),
 	candidate: CandidateReceipt,
 ) -> Result<(), Error> {
@@ -805,7 +970,10 @@ fn note_block_included(
 				&block.1,
 				&candidate_hash,
 			);
-			write_meta(db_transaction, config, &candidate_hash, &meta);
+			size.note_meta(
+				candidate_hash,
+				write_meta(db_transaction, config, &candidate_hash, &meta),
+			);
 		},
 	}
 
@@ -825,7 +993,7 @@ macro_rules! peek_num {
 #[overseer::contextbounds(AvailabilityStore, prefix = self::overseer)]
 async fn process_block_finalized<Context>(
 	ctx: &mut Context,
-	subsystem: &AvailabilityStoreSubsystem,
+	subsystem: &mut AvailabilityStoreSubsystem,
 	finalized_hash: Hash,
 	finalized_number: BlockNumber,
 ) -> Result<(), Error> {
@@ -901,7 +1069,7 @@ async fn process_block_finalized<Context>(
 
 		delete_unfinalized_height(&mut db_transaction, &subsystem.config, batch_num);
 
-		update_blocks_at_finalized_height(&subsystem, &mut db_transaction, batch, batch_num, now)?;
+		update_blocks_at_finalized_height(subsystem, &mut db_transaction, batch, batch_num, now)?;
 
 		// We need to write at the end of the loop so the prefix iterator doesn't pick up the same values again
 		// in the next iteration. Another unfortunate effect of having to re-initialize the iterator.
@@ -944,7 +1112,7 @@ fn load_all_at_finalized_height(
 }
 
 fn update_blocks_at_finalized_height(
-	subsystem: &AvailabilityStoreSubsystem,
+	subsystem: &mut AvailabilityStoreSubsystem,
 	db_transaction: &mut DBTransaction,
 	candidates: impl IntoIterator<Item = (CandidateHash, bool)>,
 	block_number: BlockNumber,
@@ -993,7 +1161,8 @@ fn update_blocks_at_finalized_height(
 			meta.state = State::Finalized(now.into());
 
 			// Write the meta and a pruning record.
-			write_meta(db_transaction, &subsystem.config, &candidate_hash, &meta);
+			let meta_size = write_meta(db_transaction, &subsystem.config, &candidate_hash, &meta);
+			subsystem.size.note_meta(candidate_hash, meta_size);
 			write_pruning_key(
 				db_transaction,
 				&subsystem.config,
@@ -1027,7 +1196,8 @@ fn update_blocks_at_finalized_height(
 			};
 
 			// Update the meta entry.
-			write_meta(db_transaction, &subsystem.config, &candidate_hash, &meta)
+			let meta_size = write_meta(db_transaction, &subsystem.config, &candidate_hash, &meta);
+			subsystem.size.note_meta(candidate_hash, meta_size);
 		}
 	}
 
@@ -1094,7 +1264,13 @@ fn process_message(
 			subsystem.metrics.on_chunks_received(1);
 			let _timer = subsystem.metrics.time_store_chunk();
 
-			match store_chunk(&subsystem.db, &subsystem.config, candidate_hash, chunk) {
+			match store_chunk(
+				&subsystem.db,
+				&subsystem.config,
+				&mut subsystem.size,
+				candidate_hash,
+				chunk,
+			) {
 				Ok(true) => {
 					let _ = tx.send(Ok(()));
 				},
@@ -1118,7 +1294,7 @@ fn process_message(
 			let _timer = subsystem.metrics.time_store_available_data();
 
 			let res =
-				store_available_data(&subsystem, candidate_hash, n_validators as _, available_data);
+				store_available_data(subsystem, candidate_hash, n_validators as _, available_data);
 
 			match res {
 				Ok(()) => {
@@ -1139,6 +1315,7 @@ fn process_message(
 fn store_chunk(
 	db: &Arc<dyn Database>,
 	config: &Config,
+	size: &mut SizeCounters,
 	candidate_hash: CandidateHash,
 	chunk: ErasureChunk,
 ) -> Result<bool, Error> {
@@ -1154,8 +1331,11 @@ fn store_chunk(
 		Some(false) => {
 			meta.chunks_stored.set(chunk.index.0 as usize, true);
 
-			write_chunk(&mut tx, config, &candidate_hash, chunk.index, &chunk);
-			write_meta(&mut tx, config, &candidate_hash, &meta);
+			size.note_chunk(
+				candidate_hash,
+				write_chunk(&mut tx, config, &candidate_hash, chunk.index, &chunk),
+			);
+			size.note_meta(candidate_hash, write_meta(&mut tx, config, &candidate_hash, &meta));
 		},
 		None => return Ok(false), // out of bounds.
 	}
@@ -1173,7 +1353,7 @@ fn store_chunk(
 
 // Ok(true) on success, Ok(false) on failure, and Err on internal error.
 fn store_available_data(
-	subsystem: &AvailabilityStoreSubsystem,
+	subsystem: &mut AvailabilityStoreSubsystem,
 	candidate_hash: CandidateHash,
 	n_validators: usize,
 	available_data: AvailableData,
@@ -1215,14 +1395,22 @@ fn store_available_data(
 	);
 
 	for chunk in erasure_chunks {
-		write_chunk(&mut tx, &subsystem.config, &candidate_hash, chunk.index, &chunk);
+		let chunk_size =
+			write_chunk(&mut tx, &subsystem.config, &candidate_hash, chunk.index, &chunk);
+		// Chunks stored before are overwritten.
+		if !meta.chunks_stored.get(chunk.index.0 as usize).map_or(false, |b| *b) {
+			subsystem.size.note_chunk(candidate_hash, chunk_size);
+		}
 	}
 
 	meta.data_available = true;
 	meta.chunks_stored = bitvec::bitvec![u8, BitOrderLsb0; 1; n_validators];
 
-	write_meta(&mut tx, &subsystem.config, &candidate_hash, &meta);
-	write_available_data(&mut tx, &subsystem.config, &candidate_hash, &available_data);
+	let meta_size = write_meta(&mut tx, &subsystem.config, &candidate_hash, &meta);
+	subsystem.size.note_meta(candidate_hash, meta_size);
+	let available_data_size =
+		write_available_data(&mut tx, &subsystem.config, &candidate_hash, &available_data);
+	subsystem.size.note_available_data(candidate_hash, available_data_size);
 
 	subsystem.db.write(tx)?;
 
@@ -1231,7 +1419,14 @@ fn store_available_data(
 	Ok(())
 }
 
-fn prune_all(db: &Arc<dyn Database>, config: &Config, clock: &dyn Clock) -> Result<(), Error> {
+fn prune_all(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	pruning_config: &PruningConfig,
+	clock: &dyn Clock,
+	metrics: &Metrics,
+	size: &mut SizeCounters,
+) -> Result<(), Error> {
 	let now = clock.now()?;
 	let (range_start, range_end) = pruning_range(now);
 
@@ -1253,34 +1448,131 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, clock: &dyn Clock) -> Resu
 
 		// Clean up all attached data of the candidate.
 		if let Some(meta) = load_meta(db, config, &candidate_hash)? {
-			// delete available data.
-			if meta.data_available {
-				delete_available_data(&mut tx, config, &candidate_hash)
-			}
+			delete_candidate_data(&mut tx, config, &candidate_hash, meta);
+		}
+		size.note_deleted(&candidate_hash);
+	}
 
-			// delete chunks.
-			for (i, b) in meta.chunks_stored.iter().enumerate() {
-				if *b {
-					delete_chunk(&mut tx, config, &candidate_hash, ValidatorIndex(i as _));
-				}
-			}
+	db.write(tx)?;
 
-			// delete unfinalized block references. Pruning references don't need to be
-			// manually taken care of as we are deleting them as we go in the outer loop.
-			if let State::Unfinalized(_, blocks) = meta.state {
-				for (block_number, block_hash) in blocks {
-					delete_unfinalized_inclusion(
-						&mut tx,
-						config,
-						block_number.0,
-						&block_hash,
-						&candidate_hash,
-					);
-				}
-			}
+	if let Some(max_size) = pruning_config.max_size {
+		if size.total.total() > max_size {
+			let evicted = evict_finalized(db, config, now, max_size, size)?;
+			metrics.on_candidates_evicted(evicted);
 		}
 	}
+	metrics.on_size_measured(&size.total);
 
-	db.write(tx)?;
 	Ok(())
 }
+
+// Delete the available data, chunks and unfinalized block references of a candidate. Pruning
+// references are left to the caller.
+fn delete_candidate_data(
+	tx: &mut DBTransaction,
+	config: &Config,
+	candidate_hash: &CandidateHash,
+	meta: CandidateMeta,
+) {
+	// delete available data.
+	if meta.data_available {
+		delete_available_data(tx, config, candidate_hash)
+	}
+
+	// delete chunks.
+	for (i, b) in meta.chunks_stored.iter().enumerate() {
+		if *b {
+			delete_chunk(tx, config, candidate_hash, ValidatorIndex(i as _));
+		}
+	}
+
+	// delete unfinalized block references.
+	if let State::Unfinalized(_, blocks) = meta.state {
+		for (block_number, block_hash) in blocks {
+			delete_unfinalized_inclusion(tx, config, block_number.0, &block_hash, candidate_hash);
+		}
+	}
+}
+
+// Sum up the size of all entries with the given prefix, both in total and by the candidate hash
+// following the prefix in the key.
+fn prefix_size(
+	db: &Arc<dyn Database>,
+	column: u32,
+	prefix: &[u8],
+	candidate_sizes: &mut HashMap<CandidateHash, StoreSize>,
+	field: fn(&mut StoreSize) -> &mut u64,
+) -> Result<u64, Error> {
+	let mut size = 0u64;
+	for r in db.iter_with_prefix(column, prefix) {
+		let (k, v) = r?;
+		let entry_size = (k.len() + v.len()) as u64;
+		size = size.saturating_add(entry_size);
+
+		if let Ok(candidate_hash) = CandidateHash::decode(&mut &k[prefix.len()..]) {
+			let candidate_size = field(candidate_sizes.entry(candidate_hash).or_default());
+			*candidate_size = candidate_size.saturating_add(entry_size);
+		}
+	}
+
+	Ok(size)
+}
+
+// Evict finalized candidates in the order they are due to be pruned, i.e. the ones finalized the
+// longest time ago first, until the size of the store is within `max_size`. Candidates finalized
+// less than `MIN_KEEP_FINALIZED_FOR` ago are kept, as they may still be disputed. Returns the
+// number of evicted candidates.
+fn evict_finalized(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	now: Duration,
+	max_size: u64,
+	size: &mut SizeCounters,
+) -> Result<usize, Error> {
+	let mut tx = DBTransaction::new();
+	let mut evicted = 0;
+
+	// Data finalized after the start of the dispute window may still be disputed.
+	let window_start: BETimestamp = match now.checked_sub(MIN_KEEP_FINALIZED_FOR) {
+		Some(window_start) => window_start.into(),
+		None => return Ok(evicted),
+	};
+
+	for r in db.iter_with_prefix(config.col_meta, &PRUNE_BY_TIME_PREFIX[..]) {
+		if size.total.total() <= max_size {
+			break
+		}
+
+		let (k, _v) = r?;
+		let (_, candidate_hash) = match decode_pruning_key(&k[..]) {
+			Ok(m) => m,
+			Err(_) => continue, // sanity
+		};
+
+		let meta = match load_meta(db, config, &candidate_hash)? {
+			Some(meta) if matches!(meta.state, State::Finalized(at) if at <= window_start) => meta,
+			// Unfinalized data might still be needed by the protocol and recently finalized data
+			// by disputes.
+			_ => continue,
+		};
+
+		tx.delete(config.col_meta, &k[..]);
+		delete_meta(&mut tx, config, &candidate_hash);
+		delete_candidate_data(&mut tx, config, &candidate_hash, meta);
+		size.note_deleted(&candidate_hash);
+		evicted += 1;
+	}
+
+	if evicted > 0 {
+		gum::debug!(
+			target: LOG_TARGET,
+			evicted,
+			size = size.total.total(),
+			max_size,
+			"Evicted finalized candidates to stay within the size limit",
+		);
+	}
+
+	db.write(tx)?;
+	Ok(evicted)
+}
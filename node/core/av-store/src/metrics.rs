@@ -26,6 +26,8 @@ pub(crate) struct MetricsInner {
 	store_available_data: prometheus::Histogram,
 	store_chunk: prometheus::Histogram,
 	get_chunk: prometheus::Histogram,
+	size: prometheus::GaugeVec<prometheus::U64>,
+	evicted_candidates_total: prometheus::Counter<prometheus::U64>,
 }
 
 /// Availability metrics.
@@ -85,6 +87,21 @@ impl Metrics {
 	pub(crate) fn time_get_chunk(&self) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
 		self.0.as_ref().map(|metrics| metrics.get_chunk.start_timer())
 	}
+
+	/// Set the size of the stored entries of each kind.
+	pub(crate) fn on_size_measured(&self, size: &crate::StoreSize) {
+		if let Some(metrics) = &self.0 {
+			metrics.size.with_label_values(&["available"]).set(size.available);
+			metrics.size.with_label_values(&["chunk"]).set(size.chunk);
+			metrics.size.with_label_values(&["meta"]).set(size.meta);
+		}
+	}
+
+	pub(crate) fn on_candidates_evicted(&self, count: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.evicted_candidates_total.inc_by(count as u64);
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -152,6 +169,23 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			size: prometheus::register(
+				prometheus::GaugeVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_av_store_size_bytes",
+						"Size of the entries in the availability store, by kind, as of the last pruning",
+					),
+					&["prefix"],
+				)?,
+				registry,
+			)?,
+			evicted_candidates_total: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_av_store_evicted_candidates_total",
+					"Number of finalized candidates evicted early to stay within the size limit.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
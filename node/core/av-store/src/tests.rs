@@ -42,7 +42,8 @@ mod columns {
 	pub const NUM_COLUMNS: u32 = 2;
 }
 
-const TEST_CONFIG: Config = Config { col_data: columns::DATA, col_meta: columns::META };
+const TEST_CONFIG: Config =
+	Config { col_data: columns::DATA, col_meta: columns::META, pruning: PruningConfig::DEFAULT };
 
 type VirtualOverseer = test_helpers::TestSubsystemContextHandle<AvailabilityStoreMessage>;
 
@@ -95,6 +96,7 @@ impl Default for TestState {
 			keep_unavailable_for: Duration::from_secs(1),
 			keep_finalized_for: Duration::from_secs(2),
 			pruning_interval: Duration::from_millis(250),
+			max_size: None,
 		};
 
 		let clock = TestClock { inner: Arc::new(Mutex::new(Duration::from_secs(0))) };
@@ -132,7 +134,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 	let subsystem = AvailabilityStoreSubsystem::with_pruning_config_and_clock(
 		store,
 		TEST_CONFIG,
-		state.pruning_config.clone(),
+		state.pruning_config,
 		Box::new(state.clock),
 		Box::new(NoSyncOracle),
 		Metrics::default(),
@@ -739,6 +741,170 @@ fn stored_data_kept_until_finalized() {
 	});
 }
 
+#[test]
+fn finalized_data_is_evicted_once_the_size_limit_is_exceeded() {
+	let store = test_store();
+	let mut test_state = TestState::default();
+	test_state.pruning_config.keep_finalized_for = MIN_KEEP_FINALIZED_FOR * 2;
+	test_state.pruning_config.max_size = Some(1);
+
+	test_harness(test_state.clone(), store.clone(), |mut virtual_overseer| async move {
+		let n_validators = 10;
+		let validators: Vec<ValidatorId> =
+			(0..n_validators).map(|_| Sr25519Keyring::Alice.public().into()).collect();
+
+		let mut candidates = Vec::new();
+		for i in 0..2u8 {
+			let pov = PoV { block_data: BlockData(vec![i; 16]) };
+			let candidate =
+				TestCandidateBuilder { pov_hash: pov.hash(), ..Default::default() }.build();
+			let available_data = AvailableData {
+				pov: Arc::new(pov),
+				validation_data: test_state.persisted_validation_data.clone(),
+			};
+
+			let (tx, rx) = oneshot::channel();
+			let block_msg = AvailabilityStoreMessage::StoreAvailableData {
+				candidate_hash: candidate.hash(),
+				n_validators,
+				available_data: available_data.clone(),
+				tx,
+			};
+			virtual_overseer.send(FromOrchestra::Communication { msg: block_msg }).await;
+			rx.await.unwrap().unwrap();
+
+			candidates.push((candidate, available_data));
+		}
+
+		let (finalized, finalized_data) = candidates[0].clone();
+		let (unfinalized, unfinalized_data) = candidates[1].clone();
+
+		let finalized_leaf = import_leaf(
+			&mut virtual_overseer,
+			Hash::repeat_byte(2),
+			10,
+			vec![candidate_included(finalized.clone())],
+			validators.clone(),
+		)
+		.await;
+
+		import_leaf(
+			&mut virtual_overseer,
+			finalized_leaf,
+			11,
+			vec![candidate_included(unfinalized.clone())],
+			validators,
+		)
+		.await;
+
+		// Unfinalized data is never evicted.
+		test_state.wait_for_pruning().await;
+		assert_eq!(
+			query_available_data(&mut virtual_overseer, finalized.hash()).await.unwrap(),
+			finalized_data,
+		);
+
+		overseer_signal(&mut virtual_overseer, OverseerSignal::BlockFinalized(finalized_leaf, 10))
+			.await;
+
+		// Data which may still be disputed is never evicted.
+		test_state.wait_for_pruning().await;
+		assert_eq!(
+			query_available_data(&mut virtual_overseer, finalized.hash()).await.unwrap(),
+			finalized_data,
+		);
+
+		// The finalized data is evicted once the dispute window has passed, before
+		// `keep_finalized_for` elapses.
+		test_state.clock.inc(MIN_KEEP_FINALIZED_FOR);
+		test_state.wait_for_pruning().await;
+		assert!(query_available_data(&mut virtual_overseer, finalized.hash()).await.is_none());
+		assert!(has_all_chunks(&mut virtual_overseer, finalized.hash(), n_validators, false).await);
+
+		assert_eq!(
+			query_available_data(&mut virtual_overseer, unfinalized.hash()).await.unwrap(),
+			unfinalized_data,
+		);
+		assert!(
+			has_all_chunks(&mut virtual_overseer, unfinalized.hash(), n_validators, true).await
+		);
+
+		virtual_overseer
+	});
+}
+
+#[test]
+fn size_counters_match_the_measured_size() {
+	let store = test_store();
+	let test_state = TestState::default();
+	let mut subsystem = AvailabilityStoreSubsystem::with_pruning_config_and_clock(
+		store.clone(),
+		TEST_CONFIG,
+		test_state.pruning_config,
+		Box::new(test_state.clock.clone()),
+		Box::new(NoSyncOracle),
+		Metrics::default(),
+	);
+	let assert_size_matches = |subsystem: &AvailabilityStoreSubsystem| {
+		let measured = SizeCounters::measure(&store, &TEST_CONFIG).unwrap();
+		assert_eq!(subsystem.size.total, measured.total);
+		assert_eq!(subsystem.size.candidates, measured.candidates);
+	};
+
+	let n_validators = 10;
+	let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+	let pov = PoV { block_data: BlockData(vec![1; 16]) };
+	let available_data = AvailableData {
+		pov: Arc::new(pov),
+		validation_data: test_state.persisted_validation_data.clone(),
+	};
+	let chunks = erasure::obtain_chunks_v1(n_validators, &available_data).unwrap();
+	let branches = erasure::branches(chunks.as_ref());
+	let chunk = branches
+		.map(|(proof, chunk)| ErasureChunk {
+			chunk: chunk.to_vec(),
+			index: ValidatorIndex(0),
+			proof: Proof::try_from(proof).unwrap(),
+		})
+		.next()
+		.unwrap();
+
+	// Storing a chunk before the available data must not count it twice.
+	with_tx(&store, |tx| {
+		super::write_pruning_key(tx, &TEST_CONFIG, BETimestamp(1), &candidate_hash);
+		let meta_size = super::write_meta(
+			tx,
+			&TEST_CONFIG,
+			&candidate_hash,
+			&CandidateMeta {
+				data_available: false,
+				chunks_stored: bitvec::bitvec![u8, BitOrderLsb0; 0; n_validators],
+				state: State::Unavailable(BETimestamp(0)),
+			},
+		);
+		subsystem.size.note_meta(candidate_hash, meta_size);
+	});
+	assert!(store_chunk(&store, &TEST_CONFIG, &mut subsystem.size, candidate_hash, chunk).unwrap());
+	assert_size_matches(&subsystem);
+
+	store_available_data(&mut subsystem, candidate_hash, n_validators, available_data).unwrap();
+	assert_size_matches(&subsystem);
+	assert!(subsystem.size.total.total() > 0);
+
+	test_state.clock.inc(test_state.pruning_config.keep_unavailable_for * 2);
+	prune_all(
+		&store,
+		&TEST_CONFIG,
+		&test_state.pruning_config,
+		&test_state.clock,
+		&Metrics::default(),
+		&mut subsystem.size,
+	)
+	.unwrap();
+	assert_size_matches(&subsystem);
+	assert_eq!(subsystem.size.total.total(), 0);
+}
+
 #[test]
 fn we_dont_miss_anything_if_import_notifications_are_missed() {
 	let store = test_store();
@@ -76,6 +76,9 @@ pub struct Config {
 	/// The path to the executable which can be used for spawning PVF compilation & validation
 	/// workers.
 	pub program_path: PathBuf,
+	/// Whether to sandbox the PVF compilation & validation workers, as far as supported by the
+	/// system.
+	pub enable_pvf_sandbox: bool,
 }
 
 /// The candidate validation subsystem.
@@ -110,6 +113,7 @@ impl<Context> CandidateValidationSubsystem {
 			self.pvf_metrics,
 			self.config.artifacts_cache_path,
 			self.config.program_path,
+			self.config.enable_pvf_sandbox,
 		)
 		.map_err(|e| SubsystemError::with_origin("candidate-validation", e))
 		.boxed();
@@ -124,11 +128,11 @@ async fn run<Context>(
 	pvf_metrics: polkadot_node_core_pvf::Metrics,
 	cache_path: PathBuf,
 	program_path: PathBuf,
+	enable_pvf_sandbox: bool,
 ) -> SubsystemResult<()> {
-	let (validation_host, task) = polkadot_node_core_pvf::start(
-		polkadot_node_core_pvf::Config::new(cache_path, program_path),
-		pvf_metrics,
-	);
+	let mut config = polkadot_node_core_pvf::Config::new(cache_path, program_path);
+	config.enable_sandbox = enable_pvf_sandbox;
+	let (validation_host, task) = polkadot_node_core_pvf::start(config, pvf_metrics);
 	ctx.spawn_blocking("pvf-validation-host", task.boxed())?;
 
 	loop {
@@ -620,6 +624,8 @@ where
 		Err(ValidationError::InternalError(e)) => Err(ValidationFailed(e)),
 		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::HardTimeout)) =>
 			Ok(ValidationResult::Invalid(InvalidCandidate::Timeout)),
+		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::OutOfFuel)) =>
+			Ok(ValidationResult::Invalid(InvalidCandidate::OutOfFuel)),
 		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::WorkerReportedError(e))) =>
 			Ok(ValidationResult::Invalid(InvalidCandidate::ExecutionError(e))),
 		Err(ValidationError::InvalidCandidate(WasmInvalidCandidate::AmbiguousWorkerDeath)) =>
@@ -23,9 +23,9 @@
 
 use polkadot_primitives::{BlockNumber, Hash};
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use crate::{BlockEntry, Error, LeafEntrySet, Timestamp};
+use crate::{BlockEntry, Error, LeafEntrySet, PrunedBlock, Timestamp};
 
 pub(super) enum BackendWriteOp {
 	WriteBlockEntry(BlockEntry),
@@ -35,6 +35,8 @@ pub(super) enum BackendWriteOp {
 	DeleteBlocksByNumber(BlockNumber),
 	DeleteBlockEntry(Hash),
 	DeleteStagnantAt(Timestamp),
+	WriteBlacklist(BTreeSet<Hash>),
+	WritePrunedBlocks(BTreeMap<Hash, PrunedBlock>),
 }
 
 /// An abstraction over backend storage for the logic of this subsystem.
@@ -56,6 +58,10 @@ pub(super) trait Backend {
 	fn load_first_block_number(&self) -> Result<Option<BlockNumber>, Error>;
 	/// Load blocks by number.
 	fn load_blocks_by_number(&self, number: BlockNumber) -> Result<Vec<Hash>, Error>;
+	/// Load the blocks blacklisted by the operator.
+	fn load_blacklist(&self) -> Result<BTreeSet<Hash>, Error>;
+	/// Load the blocks removed along with stagnant subtrees.
+	fn load_pruned_blocks(&self) -> Result<BTreeMap<Hash, PrunedBlock>, Error>;
 
 	/// Atomically write the list of operations, with later operations taking precedence over prior.
 	fn write<I>(&mut self, ops: I) -> Result<(), Error>
@@ -79,6 +85,10 @@ pub(super) struct OverlayedBackend<'a, B: 'a> {
 	stagnant_at: HashMap<Timestamp, Option<Vec<Hash>>>,
 	// 'None' means query inner.
 	leaves: Option<LeafEntrySet>,
+	// 'None' means query inner.
+	blacklist: Option<BTreeSet<Hash>>,
+	// 'None' means query inner.
+	pruned_blocks: Option<BTreeMap<Hash, PrunedBlock>>,
 }
 
 impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
@@ -89,6 +99,8 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			blocks_by_number: HashMap::new(),
 			stagnant_at: HashMap::new(),
 			leaves: None,
+			blacklist: None,
+			pruned_blocks: None,
 		}
 	}
 
@@ -124,6 +136,22 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.inner.load_stagnant_at(timestamp)
 	}
 
+	pub(super) fn load_blacklist(&self) -> Result<BTreeSet<Hash>, Error> {
+		if let Some(ref blacklist) = self.blacklist {
+			return Ok(blacklist.clone())
+		}
+
+		self.inner.load_blacklist()
+	}
+
+	pub(super) fn load_pruned_blocks(&self) -> Result<BTreeMap<Hash, PrunedBlock>, Error> {
+		if let Some(ref pruned_blocks) = self.pruned_blocks {
+			return Ok(pruned_blocks.clone())
+		}
+
+		self.inner.load_pruned_blocks()
+	}
+
 	pub(super) fn write_block_entry(&mut self, entry: BlockEntry) {
 		self.block_entries.insert(entry.block_hash, Some(entry));
 	}
@@ -156,6 +184,14 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 		self.stagnant_at.insert(timestamp, None);
 	}
 
+	pub(super) fn write_blacklist(&mut self, blacklist: BTreeSet<Hash>) {
+		self.blacklist = Some(blacklist);
+	}
+
+	pub(super) fn write_pruned_blocks(&mut self, pruned_blocks: BTreeMap<Hash, PrunedBlock>) {
+		self.pruned_blocks = Some(pruned_blocks);
+	}
+
 	/// Transform this backend into a set of write-ops to be written to the
 	/// inner backend.
 	pub(super) fn into_write_ops(self) -> impl Iterator<Item = BackendWriteOp> {
@@ -176,10 +212,17 @@ impl<'a, B: 'a + Backend> OverlayedBackend<'a, B> {
 			None => BackendWriteOp::DeleteStagnantAt(n),
 		});
 
+		let blacklist_ops = self.blacklist.into_iter().map(BackendWriteOp::WriteBlacklist);
+
+		let pruned_blocks_ops =
+			self.pruned_blocks.into_iter().map(BackendWriteOp::WritePrunedBlocks);
+
 		block_entry_ops
 			.chain(blocks_by_number_ops)
 			.chain(leaf_ops)
 			.chain(stagnant_at_ops)
+			.chain(blacklist_ops)
+			.chain(pruned_blocks_ops)
 	}
 }
 
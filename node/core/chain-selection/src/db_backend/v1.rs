@@ -23,12 +23,18 @@
 //! ("CS_block_height", BigEndianBlockNumber) -> Vec<Hash>;
 //! ("CS_stagnant_at", BigEndianTimestamp) -> Vec<Hash>;
 //! ("CS_leaves") -> LeafEntrySet;
+//! ("CS_locally_blocked", Hash) -> ();
+//! ("CS_blacklist") -> BTreeSet<Hash>;
+//! ("CS_pruned") -> BTreeMap<Hash, PrunedBlock>;
 //! ```
 //!
 //! The big-endian encoding is used for creating iterators over the key-value DB which are
 //! accessible by prefix, to find the earliest block number stored as well as the all stagnant
 //! blocks.
 //!
+//! Whether a block is non-viable by local policy is kept under a key of its own rather than in
+//! the `BlockEntry`, so that the encoding of the latter stays compatible with older nodes.
+//!
 //! The `Vec`s stored are always non-empty. Empty `Vec`s are not stored on disk so there is no
 //! semantic difference between `None` and an empty `Vec`.
 
@@ -43,12 +49,18 @@ use polkadot_primitives::{BlockNumber, Hash};
 use parity_scale_codec::{Decode, Encode};
 use polkadot_node_subsystem_util::database::{DBTransaction, Database};
 
-use std::sync::Arc;
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	sync::Arc,
+};
 
 const BLOCK_ENTRY_PREFIX: &[u8; 14] = b"CS_block_entry";
 const BLOCK_HEIGHT_PREFIX: &[u8; 15] = b"CS_block_height";
 const STAGNANT_AT_PREFIX: &[u8; 14] = b"CS_stagnant_at";
 const LEAVES_KEY: &[u8; 9] = b"CS_leaves";
+const LOCALLY_BLOCKED_PREFIX: &[u8; 18] = b"CS_locally_blocked";
+const BLACKLIST_KEY: &[u8; 12] = b"CS_blacklist";
+const PRUNED_KEY: &[u8; 9] = b"CS_pruned";
 
 type Timestamp = u64;
 
@@ -103,12 +115,31 @@ impl From<ViabilityCriteria> for crate::ViabilityCriteria {
 	fn from(x: ViabilityCriteria) -> crate::ViabilityCriteria {
 		crate::ViabilityCriteria {
 			explicitly_reverted: x.explicitly_reverted,
+			locally_blocked: false,
 			approval: x.approval.into(),
 			earliest_unviable_ancestor: x.earliest_unviable_ancestor,
 		}
 	}
 }
 
+#[derive(Encode, Decode)]
+struct PrunedBlock {
+	block_number: BlockNumber,
+	approved: bool,
+}
+
+impl From<crate::PrunedBlock> for PrunedBlock {
+	fn from(x: crate::PrunedBlock) -> Self {
+		PrunedBlock { block_number: x.block_number, approved: x.approved }
+	}
+}
+
+impl From<PrunedBlock> for crate::PrunedBlock {
+	fn from(x: PrunedBlock) -> crate::PrunedBlock {
+		crate::PrunedBlock { block_number: x.block_number, approved: x.approved }
+	}
+}
+
 #[derive(Encode, Decode)]
 struct LeafEntry {
 	weight: BlockWeight,
@@ -208,8 +239,18 @@ impl DbBackend {
 
 impl Backend for DbBackend {
 	fn load_block_entry(&self, hash: &Hash) -> Result<Option<crate::BlockEntry>, Error> {
-		load_decode::<BlockEntry>(&*self.inner, self.config.col_data, &block_entry_key(hash))
-			.map(|o| o.map(Into::into))
+		let block_entry =
+			load_decode::<BlockEntry>(&*self.inner, self.config.col_data, &block_entry_key(hash))?;
+
+		match block_entry {
+			None => Ok(None),
+			Some(block_entry) => {
+				let mut block_entry: crate::BlockEntry = block_entry.into();
+				block_entry.viability.locally_blocked =
+					self.inner.get(self.config.col_data, &locally_blocked_key(hash))?.is_some();
+				Ok(Some(block_entry))
+			},
+		}
 	}
 
 	fn load_leaves(&self) -> Result<crate::LeafEntrySet, Error> {
@@ -273,6 +314,21 @@ impl Backend for DbBackend {
 			.map(|o| o.unwrap_or_default())
 	}
 
+	fn load_blacklist(&self) -> Result<BTreeSet<Hash>, Error> {
+		load_decode::<BTreeSet<Hash>>(&*self.inner, self.config.col_data, BLACKLIST_KEY)
+			.map(|o| o.unwrap_or_default())
+	}
+
+	fn load_pruned_blocks(&self) -> Result<BTreeMap<Hash, crate::PrunedBlock>, Error> {
+		load_decode::<BTreeMap<Hash, PrunedBlock>>(&*self.inner, self.config.col_data, PRUNED_KEY)
+			.map(|o| {
+				o.unwrap_or_default()
+					.into_iter()
+					.map(|(hash, pruned)| (hash, pruned.into()))
+					.collect()
+			})
+	}
+
 	/// Atomically write the list of operations, with later operations taking precedence over prior.
 	fn write<I>(&mut self, ops: I) -> Result<(), Error>
 	where
@@ -282,6 +338,13 @@ impl Backend for DbBackend {
 		for op in ops {
 			match op {
 				BackendWriteOp::WriteBlockEntry(block_entry) => {
+					let locally_blocked_key = locally_blocked_key(&block_entry.block_hash);
+					if block_entry.viability.locally_blocked {
+						tx.put_vec(self.config.col_data, &locally_blocked_key, Vec::new());
+					} else {
+						tx.delete(self.config.col_data, &locally_blocked_key);
+					}
+
 					let block_entry: BlockEntry = block_entry.into();
 					tx.put_vec(
 						self.config.col_data,
@@ -324,11 +387,28 @@ impl Backend for DbBackend {
 				},
 				BackendWriteOp::DeleteBlockEntry(hash) => {
 					tx.delete(self.config.col_data, &block_entry_key(&hash));
+					tx.delete(self.config.col_data, &locally_blocked_key(&hash));
 				},
 				BackendWriteOp::DeleteStagnantAt(timestamp) => {
 					let timestamp: Timestamp = timestamp.into();
 					tx.delete(self.config.col_data, &stagnant_at_key(timestamp));
 				},
+				BackendWriteOp::WriteBlacklist(blacklist) =>
+					if blacklist.is_empty() {
+						tx.delete(self.config.col_data, &BLACKLIST_KEY[..]);
+					} else {
+						tx.put_vec(self.config.col_data, &BLACKLIST_KEY[..], blacklist.encode());
+					},
+				BackendWriteOp::WritePrunedBlocks(pruned_blocks) =>
+					if pruned_blocks.is_empty() {
+						tx.delete(self.config.col_data, &PRUNED_KEY[..]);
+					} else {
+						let pruned_blocks: BTreeMap<Hash, PrunedBlock> = pruned_blocks
+							.into_iter()
+							.map(|(hash, pruned)| (hash, pruned.into()))
+							.collect();
+						tx.put_vec(self.config.col_data, &PRUNED_KEY[..], pruned_blocks.encode());
+					},
 			}
 		}
 
@@ -354,6 +434,13 @@ fn block_entry_key(hash: &Hash) -> [u8; 14 + 32] {
 	key
 }
 
+fn locally_blocked_key(hash: &Hash) -> [u8; 18 + 32] {
+	let mut key = [0; 18 + 32];
+	key[..18].copy_from_slice(LOCALLY_BLOCKED_PREFIX);
+	hash.using_encoded(|s| key[18..].copy_from_slice(s));
+	key
+}
+
 fn block_height_key(number: BlockNumber) -> [u8; 15 + 4] {
 	let mut key = [0; 15 + 4];
 	key[..15].copy_from_slice(BLOCK_HEIGHT_PREFIX);
@@ -627,4 +714,97 @@ mod tests {
 
 		assert_eq!(backend.load_blocks_by_number(10).unwrap(), vec![Hash::repeat_byte(3)]);
 	}
+
+	#[test]
+	fn write_read_locally_blocked_block_entry() {
+		let db = test_db();
+		let config = Config { col_data: 0 };
+
+		let mut backend = DbBackend::new(db, config);
+
+		let mut block_entry: crate::BlockEntry = BlockEntry {
+			block_hash: Hash::repeat_byte(1),
+			block_number: 1,
+			parent_hash: Hash::repeat_byte(0),
+			children: vec![],
+			viability: ViabilityCriteria {
+				earliest_unviable_ancestor: None,
+				explicitly_reverted: false,
+				approval: Approval::Unapproved,
+			},
+			weight: 100,
+		}
+		.into();
+		block_entry.viability.locally_blocked = true;
+
+		backend
+			.write(vec![BackendWriteOp::WriteBlockEntry(block_entry.clone())])
+			.unwrap();
+
+		assert!(
+			backend
+				.load_block_entry(&block_entry.block_hash)
+				.unwrap()
+				.unwrap()
+				.viability
+				.locally_blocked
+		);
+
+		block_entry.viability.locally_blocked = false;
+		backend
+			.write(vec![BackendWriteOp::WriteBlockEntry(block_entry.clone())])
+			.unwrap();
+
+		assert!(
+			!backend
+				.load_block_entry(&block_entry.block_hash)
+				.unwrap()
+				.unwrap()
+				.viability
+				.locally_blocked
+		);
+	}
+
+	#[test]
+	fn write_read_blacklist() {
+		let db = test_db();
+		let config = Config { col_data: 0 };
+
+		let mut backend = DbBackend::new(db, config);
+
+		assert!(backend.load_blacklist().unwrap().is_empty());
+
+		let blacklist = BTreeSet::from([Hash::repeat_byte(1), Hash::repeat_byte(2)]);
+		backend.write(vec![BackendWriteOp::WriteBlacklist(blacklist.clone())]).unwrap();
+
+		assert_eq!(backend.load_blacklist().unwrap(), blacklist);
+
+		backend.write(vec![BackendWriteOp::WriteBlacklist(BTreeSet::new())]).unwrap();
+
+		assert!(backend.load_blacklist().unwrap().is_empty());
+	}
+
+	#[test]
+	fn write_read_pruned_blocks() {
+		let db = test_db();
+		let config = Config { col_data: 0 };
+
+		let mut backend = DbBackend::new(db, config);
+
+		assert!(backend.load_pruned_blocks().unwrap().is_empty());
+
+		let pruned_blocks = BTreeMap::from([
+			(Hash::repeat_byte(1), crate::PrunedBlock { block_number: 1, approved: false }),
+			(Hash::repeat_byte(2), crate::PrunedBlock { block_number: 2, approved: true }),
+		]);
+		backend
+			.write(vec![BackendWriteOp::WritePrunedBlocks(pruned_blocks.clone())])
+			.unwrap();
+
+		assert_eq!(backend.load_pruned_blocks().unwrap(), pruned_blocks);
+
+		backend.write(vec![BackendWriteOp::WritePrunedBlocks(BTreeMap::new())]).unwrap();
+
+		assert!(backend.load_pruned_blocks().unwrap().is_empty());
+	}
 }
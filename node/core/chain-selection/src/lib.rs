@@ -19,7 +19,7 @@
 use polkadot_node_primitives::BlockWeight;
 use polkadot_node_subsystem::{
 	errors::ChainApiError,
-	messages::{ChainApiMessage, ChainSelectionMessage},
+	messages::{BlockApprovalStatus, ChainApiMessage, ChainSelectionMessage, LeafDetails},
 	overseer::{self, SubsystemSender},
 	FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError,
 };
@@ -30,6 +30,7 @@ use futures::{channel::oneshot, future::Either, prelude::*};
 use parity_scale_codec::Error as CodecError;
 
 use std::{
+	collections::BTreeSet,
 	sync::Arc,
 	time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -38,8 +39,11 @@ use crate::backend::{Backend, BackendWriteOp, OverlayedBackend};
 
 mod backend;
 mod db_backend;
+mod metrics;
 mod tree;
 
+pub use metrics::Metrics;
+
 #[cfg(test)]
 mod tests;
 
@@ -50,6 +54,8 @@ type Timestamp = u64;
 // If a block isn't approved in 120 seconds, nodes will abandon it
 // and begin building on another chain.
 const STAGNANT_TIMEOUT: Timestamp = 120;
+/// The default time after which unapproved blocks are considered stagnant.
+pub const DEFAULT_STAGNANT_TIMEOUT: Duration = Duration::from_secs(STAGNANT_TIMEOUT);
 // Delay prunning of the stagnant keys in prune only mode by 25 hours to avoid interception with the finality
 const STAGNANT_PRUNE_DELAY: Timestamp = 25 * 60 * 60;
 // Maximum number of stagnant entries cleaned during one `STAGNANT_TIMEOUT` iteration
@@ -71,14 +77,27 @@ impl Approval {
 	}
 }
 
+impl From<&Approval> for BlockApprovalStatus {
+	fn from(approval: &Approval) -> Self {
+		match approval {
+			Approval::Approved => BlockApprovalStatus::Approved,
+			Approval::Unapproved => BlockApprovalStatus::Unapproved,
+			Approval::Stagnant => BlockApprovalStatus::Stagnant,
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 struct ViabilityCriteria {
 	// Whether this block has been explicitly reverted by one of its descendants.
 	explicitly_reverted: bool,
+	// Whether this block has been marked as non-viable by local policy, i.e. the
+	// operator blacklist or one of the configured `ViabilityRule`s.
+	locally_blocked: bool,
 	// The approval state of this block specifically.
 	approval: Approval,
 	// The earliest unviable ancestor - the hash of the earliest unfinalized
-	// block in the ancestry which is explicitly reverted or stagnant.
+	// block in the ancestry which is explicitly reverted, locally blocked or stagnant.
 	earliest_unviable_ancestor: Option<Hash>,
 }
 
@@ -88,9 +107,9 @@ impl ViabilityCriteria {
 	}
 
 	// Whether the current block is explicitly viable.
-	// That is, whether the current block is neither reverted nor stagnant.
+	// That is, whether the current block is neither reverted, locally blocked nor stagnant.
 	fn is_explicitly_viable(&self) -> bool {
-		!self.explicitly_reverted && !self.approval.is_stagnant()
+		!self.explicitly_reverted && !self.locally_blocked && !self.approval.is_stagnant()
 	}
 
 	// Whether the parent is viable. This assumes that the parent
@@ -189,6 +208,16 @@ impl BlockEntry {
 	}
 }
 
+// A block which was removed from the tree along with a stagnant subtree. Its descendants are
+// rejected upon import, until it is finalized over.
+#[derive(Debug, Clone, PartialEq)]
+struct PrunedBlock {
+	block_number: BlockNumber,
+	// Whether the block got approved after being pruned. Such a block and its descendants
+	// are imported again.
+	approved: bool,
+}
+
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
 pub enum Error {
@@ -299,11 +328,36 @@ impl StagnantCheckInterval {
 	}
 }
 
+/// A local policy which marks blocks as non-viable, in addition to approval checking,
+/// stagnancy and reversions.
+///
+/// Rules are consulted for every block upon import, and again for all unfinalized blocks
+/// whenever a block is finalized or a `ChainSelectionMessage::ReevaluateViability` is received.
+/// A block which any rule considers non-viable is treated like a reverted one: neither it nor
+/// any of its descendants is built upon or finalized.
+///
+/// Rules which need to query other subsystems can do so through an overseer handle. They must
+/// not wait on the chain selection subsystem itself, as it is blocked while evaluating them.
+#[async_trait::async_trait]
+pub trait ViabilityRule: Send + Sync {
+	/// A short name of the rule, used for logging.
+	fn name(&self) -> &'static str;
+
+	/// Whether the block with the given hash and header is non-viable.
+	async fn is_non_viable(&self, block_hash: Hash, header: &Header) -> bool;
+}
+
 /// Mode of the stagnant check operations: check and prune or prune only
 #[derive(Debug, Clone)]
 pub enum StagnantCheckMode {
 	CheckAndPrune,
 	PruneOnly,
+	/// Like `CheckAndPrune`, and additionally remove blocks which are still stagnant
+	/// after the grace period from the database, along with all their descendants.
+	CheckAndPruneSubtrees {
+		/// How long blocks are kept after becoming stagnant.
+		grace_period: Duration,
+	},
 }
 
 impl Default for StagnantCheckMode {
@@ -321,19 +375,30 @@ pub struct Config {
 	pub stagnant_check_interval: StagnantCheckInterval,
 	/// Mode of stagnant checks
 	pub stagnant_check_mode: StagnantCheckMode,
+	/// How long a block may stay unapproved before it is considered stagnant.
+	pub stagnant_timeout: Duration,
 }
 
 /// The chain selection subsystem.
 pub struct ChainSelectionSubsystem {
 	config: Config,
 	db: Arc<dyn Database>,
+	rules: Vec<Box<dyn ViabilityRule>>,
+	metrics: Metrics,
 }
 
 impl ChainSelectionSubsystem {
-	/// Create a new instance of the subsystem with the given config
-	/// and key-value store.
-	pub fn new(config: Config, db: Arc<dyn Database>) -> Self {
-		ChainSelectionSubsystem { config, db }
+	/// Create a new instance of the subsystem with the given config,
+	/// key-value store and metrics.
+	pub fn new(config: Config, db: Arc<dyn Database>, metrics: Metrics) -> Self {
+		ChainSelectionSubsystem { config, db, rules: Vec::new(), metrics }
+	}
+
+	/// Use the given rules to mark blocks as non-viable, in addition to the
+	/// operator blacklist.
+	pub fn with_viability_rules(mut self, rules: Vec<Box<dyn ViabilityRule>>) -> Self {
+		self.rules = rules;
+		self
 	}
 
 	/// Revert to the block corresponding to the specified `hash`.
@@ -362,7 +427,10 @@ impl<Context> ChainSelectionSubsystem {
 				backend,
 				self.config.stagnant_check_interval,
 				self.config.stagnant_check_mode,
+				self.config.stagnant_timeout.as_secs(),
 				Box::new(SystemClock),
+				self.rules,
+				self.metrics,
 			)
 			.map(Ok)
 			.boxed(),
@@ -377,7 +445,10 @@ async fn run<Context, B>(
 	mut backend: B,
 	stagnant_check_interval: StagnantCheckInterval,
 	stagnant_check_mode: StagnantCheckMode,
+	stagnant_timeout: Timestamp,
 	clock: Box<dyn Clock + Send + Sync>,
+	rules: Vec<Box<dyn ViabilityRule>>,
+	metrics: Metrics,
 ) where
 	B: Backend,
 {
@@ -388,7 +459,10 @@ async fn run<Context, B>(
 			&mut backend,
 			&stagnant_check_interval,
 			&stagnant_check_mode,
+			stagnant_timeout,
 			&*clock,
+			&rules,
+			&metrics,
 		)
 		.await;
 		match res {
@@ -416,7 +490,10 @@ async fn run_until_error<Context, B>(
 	backend: &mut B,
 	stagnant_check_interval: &StagnantCheckInterval,
 	stagnant_check_mode: &StagnantCheckMode,
+	stagnant_timeout: Timestamp,
 	clock: &(dyn Clock + Sync),
+	rules: &[Box<dyn ViabilityRule>],
+	metrics: &Metrics,
 ) -> Result<(), Error>
 where
 	B: Backend,
@@ -435,15 +512,24 @@ where
 							let write_ops = handle_active_leaf(
 								ctx.sender(),
 								&*backend,
-								clock.timestamp_now() + STAGNANT_TIMEOUT,
+								clock.timestamp_now() + stagnant_timeout,
 								leaf.hash,
+								rules,
 							).await?;
 
 							backend.write(write_ops)?;
+							note_viable_leaves(&*backend, metrics)?;
 						}
 					}
 					FromOrchestra::Signal(OverseerSignal::BlockFinalized(h, n)) => {
-						handle_finalized_block(backend, h, n)?
+						handle_finalized_block(backend, h, n)?;
+						let write_ops = handle_reevaluate_viability(
+							ctx.sender(),
+							&*backend,
+							rules,
+						).await?;
+						backend.write(write_ops)?;
+						note_viable_leaves(&*backend, metrics)?;
 					}
 					FromOrchestra::Communication { msg } => match msg {
 						ChainSelectionMessage::Approved(hash) => {
@@ -470,12 +556,47 @@ where
 							let write_ops = handle_revert_blocks(backend, blocks_to_revert)?;
 							backend.write(write_ops)?;
 						}
+						ChainSelectionMessage::BlacklistBlock(hash) => {
+							let write_ops = handle_blacklist_block(backend, hash)?;
+							backend.write(write_ops)?;
+						}
+						ChainSelectionMessage::UnblacklistBlock(hash) => {
+							let write_ops = handle_unblacklist_block(
+								ctx.sender(),
+								&*backend,
+								rules,
+								hash,
+							).await?;
+							backend.write(write_ops)?;
+						}
+						ChainSelectionMessage::Blacklist(tx) => {
+							let _ = tx.send(backend.load_blacklist()?.into_iter().collect());
+						}
+						ChainSelectionMessage::ReevaluateViability => {
+							let write_ops = handle_reevaluate_viability(
+								ctx.sender(),
+								&*backend,
+								rules,
+							).await?;
+							backend.write(write_ops)?;
+							note_viable_leaves(&*backend, metrics)?;
+						}
+						ChainSelectionMessage::LeafDetails(tx) => {
+							let _ = tx.send(load_leaf_details(&*backend)?);
+						}
 					}
 				}
 			}
 			_ = stagnant_check_stream.next().fuse() => {
 				match stagnant_check_mode {
-					StagnantCheckMode::CheckAndPrune => detect_stagnant(backend, clock.timestamp_now(), MAX_STAGNANT_ENTRIES),
+					StagnantCheckMode::CheckAndPrune => detect_stagnant(backend, clock.timestamp_now(), MAX_STAGNANT_ENTRIES, None, metrics),
+					StagnantCheckMode::CheckAndPruneSubtrees { grace_period } => detect_stagnant(
+						backend,
+						clock.timestamp_now(),
+						MAX_STAGNANT_ENTRIES,
+						Some(grace_period.as_secs()),
+						metrics,
+					),
 					StagnantCheckMode::PruneOnly => {
 						let now_timestamp = clock.timestamp_now();
 						prune_only_stagnant(backend, now_timestamp - STAGNANT_PRUNE_DELAY, MAX_STAGNANT_ENTRIES)
@@ -552,6 +673,7 @@ async fn handle_active_leaf(
 	backend: &impl Backend,
 	stagnant_at: Timestamp,
 	hash: Hash,
+	rules: &[Box<dyn ViabilityRule>],
 ) -> Result<Vec<BackendWriteOp>, Error> {
 	let lower_bound = match backend.load_first_block_number()? {
 		Some(l) => {
@@ -580,11 +702,36 @@ async fn handle_active_leaf(
 	)
 	.await?;
 
+	let blacklist = backend.load_blacklist()?;
 	let mut overlay = OverlayedBackend::new(backend);
+	let mut pruned_blocks = overlay.load_pruned_blocks()?;
+	let mut pruned_blocks_changed = false;
 
 	// determine_new_blocks gives blocks in descending order.
 	// for this, we want ascending order.
 	for (hash, header) in new_blocks.into_iter().rev() {
+		// Blocks pruned along with a stagnant subtree are not imported again, and neither are
+		// blocks building on them, unless they got approved in the meantime.
+		let parent_pruned = pruned_blocks.contains_key(&header.parent_hash);
+		let approved = match pruned_blocks.get(&hash) {
+			Some(pruned) if pruned.approved && !parent_pruned => true,
+			Some(_) => continue,
+			None if parent_pruned => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?hash,
+					parent_hash = ?header.parent_hash,
+					"Rejecting block building on a pruned block",
+				);
+
+				pruned_blocks
+					.insert(hash, PrunedBlock { block_number: header.number, approved: false });
+				pruned_blocks_changed = true;
+				continue
+			},
+			None => false,
+		};
+
 		let weight = match fetch_block_weight(sender, hash).await? {
 			None => {
 				gum::warn!(
@@ -601,6 +748,7 @@ async fn handle_active_leaf(
 		};
 
 		let reversion_logs = extract_reversion_logs(&header);
+		let locally_blocked = is_locally_blocked(&blacklist, rules, hash, &header).await;
 		tree::import_block(
 			&mut overlay,
 			hash,
@@ -609,12 +757,50 @@ async fn handle_active_leaf(
 			reversion_logs,
 			weight,
 			stagnant_at,
+			locally_blocked,
 		)?;
+		if approved {
+			tree::approve_block(&mut overlay, hash)?;
+			pruned_blocks.remove(&hash);
+			pruned_blocks_changed = true;
+		}
+	}
+
+	if pruned_blocks_changed {
+		overlay.write_pruned_blocks(pruned_blocks);
 	}
 
 	Ok(overlay.into_write_ops().collect())
 }
 
+// Whether the block is non-viable according to the operator blacklist or any of the
+// viability rules.
+async fn is_locally_blocked(
+	blacklist: &BTreeSet<Hash>,
+	rules: &[Box<dyn ViabilityRule>],
+	block_hash: Hash,
+	header: &Header,
+) -> bool {
+	if blacklist.contains(&block_hash) {
+		gum::debug!(target: LOG_TARGET, ?block_hash, "Block is blacklisted by the operator");
+		return true
+	}
+
+	for rule in rules {
+		if rule.is_non_viable(block_hash, header).await {
+			gum::debug!(
+				target: LOG_TARGET,
+				?block_hash,
+				rule = rule.name(),
+				"Block is marked as non-viable by a local rule",
+			);
+			return true
+		}
+	}
+
+	false
+}
+
 // Extract all reversion logs from a header in ascending order.
 //
 // Ignores logs with number >= the block header number.
@@ -664,7 +850,26 @@ fn handle_finalized_block(
 	finalized_hash: Hash,
 	finalized_number: BlockNumber,
 ) -> Result<(), Error> {
-	let ops = tree::finalize_block(&*backend, finalized_hash, finalized_number)?.into_write_ops();
+	let blacklist = backend.load_blacklist()?;
+	let ops = {
+		let mut overlay = tree::finalize_block(&*backend, finalized_hash, finalized_number)?;
+
+		// Blacklisted blocks which got finalized or pruned along with their fork are gone for
+		// good. Blocks which were never imported are kept, as they may still be.
+		let mut retained = BTreeSet::new();
+		for hash in &blacklist {
+			if backend.load_block_entry(hash)?.is_none() ||
+				overlay.load_block_entry(hash)?.is_some()
+			{
+				retained.insert(*hash);
+			}
+		}
+		if retained.len() != blacklist.len() {
+			overlay.write_blacklist(retained);
+		}
+
+		overlay.into_write_ops()
+	};
 
 	backend.write(ops)
 }
@@ -697,18 +902,170 @@ fn handle_revert_blocks(
 	Ok(overlay.into_write_ops().collect())
 }
 
+// Add a block to the operator blacklist. If the block is already imported, it and all its
+// descendants become non-viable right away, otherwise this happens once it is imported.
+fn handle_blacklist_block(
+	backend: &impl Backend,
+	block_hash: Hash,
+) -> Result<Vec<BackendWriteOp>, Error> {
+	let mut overlay = OverlayedBackend::new(backend);
+
+	let mut blacklist = overlay.load_blacklist()?;
+	if blacklist.insert(block_hash) {
+		overlay.write_blacklist(blacklist);
+	}
+
+	tree::set_locally_blocked(&mut overlay, block_hash, true)?;
+
+	Ok(overlay.into_write_ops().collect())
+}
+
+// Remove a block from the operator blacklist. The block stays non-viable if one of the
+// viability rules considers it as such.
+async fn handle_unblacklist_block(
+	sender: &mut impl SubsystemSender<ChainApiMessage>,
+	backend: &impl Backend,
+	rules: &[Box<dyn ViabilityRule>],
+	block_hash: Hash,
+) -> Result<Vec<BackendWriteOp>, Error> {
+	let mut blacklist = backend.load_blacklist()?;
+	if !blacklist.remove(&block_hash) {
+		return Ok(Vec::new())
+	}
+
+	// If the header is unavailable, the operator's decision takes precedence.
+	let blocked_by_rule = if rules.is_empty() || backend.load_block_entry(&block_hash)?.is_none() {
+		false
+	} else {
+		match fetch_header(sender, block_hash).await? {
+			Some(header) => is_locally_blocked(&BTreeSet::new(), rules, block_hash, &header).await,
+			None => false,
+		}
+	};
+
+	let mut overlay = OverlayedBackend::new(backend);
+	overlay.write_blacklist(blacklist);
+	tree::set_locally_blocked(&mut overlay, block_hash, blocked_by_rule)?;
+
+	Ok(overlay.into_write_ops().collect())
+}
+
+// Consult the blacklist and the viability rules again for all unfinalized blocks, as the state
+// the rules are based on may have changed since the blocks were imported.
+async fn handle_reevaluate_viability(
+	sender: &mut impl overseer::SubsystemSender<ChainApiMessage>,
+	backend: &impl Backend,
+	rules: &[Box<dyn ViabilityRule>],
+) -> Result<Vec<BackendWriteOp>, Error> {
+	if rules.is_empty() {
+		return Ok(Vec::new())
+	}
+
+	let blacklist = backend.load_blacklist()?;
+	let mut overlay = OverlayedBackend::new(backend);
+
+	// All unfinalized blocks are stored by number, starting above the finalized block.
+	let mut block_number = match backend.load_first_block_number()? {
+		Some(n) => n,
+		None => return Ok(Vec::new()),
+	};
+	loop {
+		let block_hashes = backend.load_blocks_by_number(block_number)?;
+		if block_hashes.is_empty() {
+			break
+		}
+
+		for block_hash in block_hashes {
+			let header = match fetch_header(sender, block_hash).await? {
+				Some(header) => header,
+				None => continue,
+			};
+			let locally_blocked = is_locally_blocked(&blacklist, rules, block_hash, &header).await;
+			tree::set_locally_blocked(&mut overlay, block_hash, locally_blocked)?;
+		}
+
+		block_number += 1;
+	}
+
+	Ok(overlay.into_write_ops().collect())
+}
+
 fn detect_stagnant(
 	backend: &mut impl Backend,
 	now: Timestamp,
 	max_elements: usize,
+	prune_after: Option<Timestamp>,
+	metrics: &Metrics,
 ) -> Result<(), Error> {
-	let ops = {
-		let overlay = tree::detect_stagnant(&*backend, now, max_elements)?;
+	let (ops, outcome) = {
+		let (overlay, outcome) = tree::detect_stagnant(&*backend, now, max_elements, prune_after)?;
 
-		overlay.into_write_ops()
+		(overlay.into_write_ops(), outcome)
 	};
 
-	backend.write(ops)
+	backend.write(ops)?;
+
+	metrics.on_stagnant_blocks(outcome.stagnant);
+	metrics.on_pruned_blocks(outcome.pruned);
+	note_viable_leaves(&*backend, metrics)
+}
+
+fn note_viable_leaves(backend: &impl Backend, metrics: &Metrics) -> Result<(), Error> {
+	metrics.on_viable_leaves(backend.load_leaves()?.inner.len());
+	Ok(())
+}
+
+// Load all leaves of the tree of unfinalized blocks, including the non-viable ones.
+//
+// Viable blocks whose children are all non-viable are leaves as well.
+fn load_leaf_details(backend: &impl Backend) -> Result<Vec<LeafDetails>, Error> {
+	let viable_leaves = backend.load_leaves()?;
+	let mut leaves = Vec::new();
+
+	let mut number = match backend.load_first_block_number()? {
+		None => return Ok(leaves),
+		Some(number) => number,
+	};
+
+	// The tree is contiguous from the first stored block number onwards.
+	loop {
+		let blocks_at = backend.load_blocks_by_number(number)?;
+		if blocks_at.is_empty() {
+			break
+		}
+
+		for block_hash in blocks_at {
+			let entry = match backend.load_block_entry(&block_hash)? {
+				Some(entry)
+					if entry.children.is_empty() ||
+						viable_leaves.inner.iter().any(|leaf| leaf.block_hash == block_hash) =>
+					entry,
+				_ => continue,
+			};
+
+			leaves.push(LeafDetails {
+				block_hash,
+				block_number: entry.block_number,
+				weight: entry.weight,
+				viable: entry.viability.is_viable(),
+				approval: (&entry.viability.approval).into(),
+				explicitly_reverted: entry.viability.explicitly_reverted,
+				locally_blocked: entry.viability.locally_blocked,
+				earliest_unviable_ancestor: entry.viability.earliest_unviable_ancestor,
+			});
+		}
+
+		number += 1;
+	}
+
+	leaves.sort_by(|a, b| {
+		b.viable
+			.cmp(&a.viable)
+			.then(b.weight.cmp(&a.weight))
+			.then(b.block_number.cmp(&a.block_number))
+	});
+
+	Ok(leaves)
 }
 
 fn prune_only_stagnant(
@@ -0,0 +1,82 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics definitions for the chain selection subsystem.
+
+use polkadot_node_subsystem_util::metrics::{self, prometheus};
+
+#[derive(Clone)]
+struct MetricsInner {
+	viable_leaves: prometheus::Gauge<prometheus::U64>,
+	stagnant_blocks: prometheus::Counter<prometheus::U64>,
+	pruned_blocks: prometheus::Counter<prometheus::U64>,
+}
+
+/// Chain selection metrics.
+#[derive(Default, Clone)]
+pub struct Metrics(Option<MetricsInner>);
+
+impl Metrics {
+	/// Set the number of viable leaves.
+	pub(crate) fn on_viable_leaves(&self, count: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.viable_leaves.set(count as u64);
+		}
+	}
+
+	/// Called when blocks are found to be stagnant.
+	pub(crate) fn on_stagnant_blocks(&self, count: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.stagnant_blocks.inc_by(count as u64);
+		}
+	}
+
+	/// Called when blocks are pruned along with stagnant subtrees.
+	pub(crate) fn on_pruned_blocks(&self, count: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.pruned_blocks.inc_by(count as u64);
+		}
+	}
+}
+
+impl metrics::Metrics for Metrics {
+	fn try_register(registry: &prometheus::Registry) -> Result<Self, prometheus::PrometheusError> {
+		let metrics = MetricsInner {
+			viable_leaves: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_parachain_chain_selection_viable_leaves",
+					"Number of viable leaves of the tree of unfinalized blocks.",
+				)?,
+				registry,
+			)?,
+			stagnant_blocks: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_chain_selection_stagnant_blocks_total",
+					"Number of blocks found to be stagnant.",
+				)?,
+				registry,
+			)?,
+			pruned_blocks: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_chain_selection_pruned_blocks_total",
+					"Number of unfinalized blocks pruned along with stagnant subtrees.",
+				)?,
+				registry,
+			)?,
+		};
+		Ok(Self(Some(metrics)))
+	}
+}
@@ -22,9 +22,9 @@
 
 use super::*;
 use std::{
-	collections::{BTreeMap, HashMap, HashSet},
+	collections::{BTreeMap, BTreeSet, HashMap, HashSet},
 	sync::{
-		atomic::{AtomicU64, Ordering as AtomicOrdering},
+		atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering},
 		Arc,
 	},
 };
@@ -47,6 +47,8 @@ struct TestBackendInner {
 	block_entries: HashMap<Hash, BlockEntry>,
 	blocks_by_number: BTreeMap<BlockNumber, Vec<Hash>>,
 	stagnant_at: BTreeMap<Timestamp, Vec<Hash>>,
+	blacklist: BTreeSet<Hash>,
+	pruned_blocks: BTreeMap<Hash, PrunedBlock>,
 	// earlier wakers at the back.
 	write_wakers: Vec<oneshot::Sender<()>>,
 }
@@ -162,6 +164,13 @@ impl Backend for TestBackend {
 			.get(&number)
 			.map_or(Vec::new(), |v| v.clone()))
 	}
+	fn load_blacklist(&self) -> Result<BTreeSet<Hash>, Error> {
+		Ok(self.inner.lock().blacklist.clone())
+	}
+
+	fn load_pruned_blocks(&self) -> Result<BTreeMap<Hash, PrunedBlock>, Error> {
+		Ok(self.inner.lock().pruned_blocks.clone())
+	}
 
 	fn write<I>(&mut self, ops: I) -> Result<(), Error>
 	where
@@ -199,6 +208,12 @@ impl Backend for TestBackend {
 				BackendWriteOp::DeleteStagnantAt(time) => {
 					inner.stagnant_at.remove(&time);
 				},
+				BackendWriteOp::WriteBlacklist(blacklist) => {
+					inner.blacklist = blacklist;
+				},
+				BackendWriteOp::WritePrunedBlocks(pruned_blocks) => {
+					inner.pruned_blocks = pruned_blocks;
+				},
 			}
 		}
 
@@ -234,6 +249,14 @@ type VirtualOverseer = test_helpers::TestSubsystemContextHandle<ChainSelectionMe
 
 fn test_harness<T: Future<Output = VirtualOverseer>>(
 	test: impl FnOnce(TestBackend, TestClock, VirtualOverseer) -> T,
+) {
+	test_harness_with(StagnantCheckMode::CheckAndPrune, Vec::new(), test)
+}
+
+fn test_harness_with<T: Future<Output = VirtualOverseer>>(
+	stagnant_check_mode: StagnantCheckMode,
+	rules: Vec<Box<dyn ViabilityRule>>,
+	test: impl FnOnce(TestBackend, TestClock, VirtualOverseer) -> T,
 ) {
 	let pool = TaskExecutor::new();
 	let (context, virtual_overseer) = test_helpers::make_subsystem_context(pool);
@@ -244,8 +267,11 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 		context,
 		backend.clone(),
 		StagnantCheckInterval::new(TEST_STAGNANT_INTERVAL),
-		StagnantCheckMode::CheckAndPrune,
+		stagnant_check_mode,
+		STAGNANT_TIMEOUT,
 		Box::new(clock.clone()),
+		rules,
+		Metrics::default(),
 	);
 
 	let test_fut = test(backend, clock, virtual_overseer);
@@ -2117,3 +2143,643 @@ fn revert_blocks_against_finalized_is_ignored() {
 		virtual_overseer
 	})
 }
+
+async fn assert_blacklist_query(virtual_overseer: &mut VirtualOverseer, blacklist: Vec<Hash>) {
+	let (tx, rx) = oneshot::channel();
+	virtual_overseer
+		.send(FromOrchestra::Communication { msg: ChainSelectionMessage::Blacklist(tx) })
+		.await;
+
+	assert_eq!(rx.await.unwrap(), blacklist);
+}
+
+#[test]
+fn blacklist_block_message_marks_chain_non_viable() {
+	test_harness(|backend, _, mut virtual_overseer| async move {
+		let finalized_number = 0;
+		let finalized_hash = Hash::repeat_byte(0);
+
+		let (head_hash, built_chain) =
+			construct_chain_on_base(vec![1, 2, 3], finalized_number, finalized_hash, |_| {});
+
+		import_blocks_into(
+			&mut virtual_overseer,
+			&backend,
+			Some((finalized_number, finalized_hash)),
+			built_chain.clone(),
+		)
+		.await;
+
+		let block_1_hash = built_chain[0].0.hash();
+		let block_2_hash = built_chain[1].0.hash();
+
+		let (_, write_rx) = backend.await_next_write();
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: ChainSelectionMessage::BlacklistBlock(block_2_hash),
+			})
+			.await;
+
+		write_rx.await.unwrap();
+
+		assert!(
+			backend
+				.load_block_entry(&block_2_hash)
+				.unwrap()
+				.unwrap()
+				.viability
+				.locally_blocked
+		);
+		assert_eq!(
+			backend
+				.load_block_entry(&head_hash)
+				.unwrap()
+				.unwrap()
+				.viability
+				.earliest_unviable_ancestor,
+			Some(block_2_hash)
+		);
+		assert_leaves(&backend, vec![block_1_hash]);
+		assert_blacklist_query(&mut virtual_overseer, vec![block_2_hash]).await;
+
+		let (_, write_rx) = backend.await_next_write();
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: ChainSelectionMessage::UnblacklistBlock(block_2_hash),
+			})
+			.await;
+
+		write_rx.await.unwrap();
+
+		assert!(
+			!backend
+				.load_block_entry(&block_2_hash)
+				.unwrap()
+				.unwrap()
+				.viability
+				.locally_blocked
+		);
+		assert_leaves(&backend, vec![head_hash]);
+		assert_blacklist_query(&mut virtual_overseer, vec![]).await;
+
+		virtual_overseer
+	})
+}
+
+#[test]
+fn blacklisted_block_is_non_viable_upon_import() {
+	test_harness(|backend, _, mut virtual_overseer| async move {
+		let finalized_number = 0;
+		let finalized_hash = Hash::repeat_byte(0);
+
+		let (_, built_chain) =
+			construct_chain_on_base(vec![1, 2, 3], finalized_number, finalized_hash, |_| {});
+
+		let block_1_hash = built_chain[0].0.hash();
+		let block_2_hash = built_chain[1].0.hash();
+
+		let (_, write_rx) = backend.await_next_write();
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: ChainSelectionMessage::BlacklistBlock(block_2_hash),
+			})
+			.await;
+
+		write_rx.await.unwrap();
+
+		import_blocks_into(
+			&mut virtual_overseer,
+			&backend,
+			Some((finalized_number, finalized_hash)),
+			built_chain.clone(),
+		)
+		.await;
+
+		assert_backend_contains(&backend, built_chain.iter().map(|&(ref h, _)| h));
+		assert!(
+			backend
+				.load_block_entry(&block_2_hash)
+				.unwrap()
+				.unwrap()
+				.viability
+				.locally_blocked
+		);
+		assert_leaves(&backend, vec![block_1_hash]);
+		assert_leaves_query(&mut virtual_overseer, vec![block_1_hash]).await;
+
+		virtual_overseer
+	})
+}
+
+struct BlockNumberRule(BlockNumber);
+
+#[async_trait::async_trait]
+impl ViabilityRule for BlockNumberRule {
+	fn name(&self) -> &'static str {
+		"block-number"
+	}
+
+	async fn is_non_viable(&self, _block_hash: Hash, header: &Header) -> bool {
+		header.number == self.0
+	}
+}
+
+#[derive(Clone, Default)]
+struct ToggledRule(Arc<AtomicBool>);
+
+#[async_trait::async_trait]
+impl ViabilityRule for ToggledRule {
+	fn name(&self) -> &'static str {
+		"toggled"
+	}
+
+	async fn is_non_viable(&self, _block_hash: Hash, header: &Header) -> bool {
+		header.number == 2 && self.0.load(AtomicOrdering::Relaxed)
+	}
+}
+
+#[test]
+fn viability_rules_are_reevaluated() {
+	let rule = ToggledRule::default();
+	rule.0.store(true, AtomicOrdering::Relaxed);
+	test_harness_with(
+		StagnantCheckMode::CheckAndPrune,
+		vec![Box::new(rule.clone()) as Box<dyn ViabilityRule>],
+		|backend, _, mut virtual_overseer| async move {
+			let finalized_number = 0;
+			let finalized_hash = Hash::repeat_byte(0);
+
+			let (head_hash, built_chain) =
+				construct_chain_on_base(vec![1, 2, 3], finalized_number, finalized_hash, |_| {});
+
+			import_blocks_into(
+				&mut virtual_overseer,
+				&backend,
+				Some((finalized_number, finalized_hash)),
+				built_chain.clone(),
+			)
+			.await;
+
+			let block_1_hash = built_chain[0].0.hash();
+			assert_leaves(&backend, vec![block_1_hash]);
+
+			rule.0.store(false, AtomicOrdering::Relaxed);
+
+			let (_, write_rx) = backend.await_next_write();
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: ChainSelectionMessage::ReevaluateViability,
+				})
+				.await;
+
+			for (header, _) in &built_chain {
+				answer_header_request(&mut virtual_overseer, header.clone()).await;
+			}
+			write_rx.await.unwrap();
+
+			assert!(
+				!backend
+					.load_block_entry(&built_chain[1].0.hash())
+					.unwrap()
+					.unwrap()
+					.viability
+					.locally_blocked
+			);
+			assert_leaves(&backend, vec![head_hash]);
+
+			// Finalizing a block re-evaluates the rules for the remaining unfinalized blocks.
+			rule.0.store(true, AtomicOrdering::Relaxed);
+			finalize_block(&mut virtual_overseer, &backend, 1, block_1_hash).await;
+
+			let (_, write_rx) = backend.await_next_write();
+			for (header, _) in &built_chain[1..] {
+				answer_header_request(&mut virtual_overseer, header.clone()).await;
+			}
+			write_rx.await.unwrap();
+
+			assert_leaves(&backend, vec![]);
+
+			virtual_overseer
+		},
+	)
+}
+
+#[test]
+fn finalization_prunes_blacklist() {
+	test_harness(|backend, _, mut virtual_overseer| async move {
+		let finalized_number = 0;
+		let finalized_hash = Hash::repeat_byte(0);
+
+		let (_, chain_a) =
+			construct_chain_on_base(vec![1], finalized_number, finalized_hash, |_| {});
+		let (b_hash, chain_b) =
+			construct_chain_on_base(vec![2], finalized_number, finalized_hash, |h| {
+				salt_header(h, b"b")
+			});
+
+		import_blocks_into(
+			&mut virtual_overseer,
+			&backend,
+			Some((finalized_number, finalized_hash)),
+			chain_a.clone(),
+		)
+		.await;
+		import_blocks_into(&mut virtual_overseer, &backend, None, chain_b.clone()).await;
+
+		// A block which is not imported yet.
+		let unknown_hash = Hash::repeat_byte(42);
+		for hash in [b_hash, unknown_hash] {
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: ChainSelectionMessage::BlacklistBlock(hash),
+				})
+				.await;
+		}
+		let mut blacklist = vec![b_hash, unknown_hash];
+		blacklist.sort();
+		assert_blacklist_query(&mut virtual_overseer, blacklist).await;
+
+		// Finalizing the competing fork prunes the blacklisted block.
+		finalize_block(&mut virtual_overseer, &backend, 1, chain_a[0].0.hash()).await;
+
+		assert!(backend.load_block_entry(&b_hash).unwrap().is_none());
+		assert_blacklist_query(&mut virtual_overseer, vec![unknown_hash]).await;
+
+		virtual_overseer
+	})
+}
+
+#[test]
+fn viability_rule_marks_block_non_viable_upon_import() {
+	test_harness_with(
+		StagnantCheckMode::CheckAndPrune,
+		vec![Box::new(BlockNumberRule(2)) as Box<dyn ViabilityRule>],
+		|backend, _, mut virtual_overseer| async move {
+			let finalized_number = 0;
+			let finalized_hash = Hash::repeat_byte(0);
+
+			let (head_hash, built_chain) =
+				construct_chain_on_base(vec![1, 2, 3], finalized_number, finalized_hash, |_| {});
+
+			import_blocks_into(
+				&mut virtual_overseer,
+				&backend,
+				Some((finalized_number, finalized_hash)),
+				built_chain.clone(),
+			)
+			.await;
+
+			let block_1_hash = built_chain[0].0.hash();
+			let block_2_hash = built_chain[1].0.hash();
+
+			assert!(
+				backend
+					.load_block_entry(&block_2_hash)
+					.unwrap()
+					.unwrap()
+					.viability
+					.locally_blocked
+			);
+			assert_eq!(
+				backend
+					.load_block_entry(&head_hash)
+					.unwrap()
+					.unwrap()
+					.viability
+					.earliest_unviable_ancestor,
+				Some(block_2_hash)
+			);
+			assert_leaves(&backend, vec![block_1_hash]);
+
+			// Removing a block from the blacklist doesn't override the rules.
+			let (_, write_rx) = backend.await_next_write();
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: ChainSelectionMessage::BlacklistBlock(block_2_hash),
+				})
+				.await;
+
+			write_rx.await.unwrap();
+
+			let (_, write_rx) = backend.await_next_write();
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: ChainSelectionMessage::UnblacklistBlock(block_2_hash),
+				})
+				.await;
+
+			answer_header_request(&mut virtual_overseer, built_chain[1].0.clone()).await;
+			write_rx.await.unwrap();
+
+			assert!(
+				backend
+					.load_block_entry(&block_2_hash)
+					.unwrap()
+					.unwrap()
+					.viability
+					.locally_blocked
+			);
+			assert_leaves(&backend, vec![block_1_hash]);
+
+			virtual_overseer
+		},
+	)
+}
+
+#[test]
+fn prunes_stagnant_subtree_after_grace_period() {
+	let grace_period = 10;
+	test_harness_with(
+		StagnantCheckMode::CheckAndPruneSubtrees {
+			grace_period: Duration::from_secs(grace_period),
+		},
+		Vec::new(),
+		|backend, clock, mut virtual_overseer| async move {
+			let finalized_number = 0;
+			let finalized_hash = Hash::repeat_byte(0);
+
+			// F <- A1 <- A2
+			// F <- B1
+
+			let (a1_hash, chain_a) =
+				construct_chain_on_base(vec![1], finalized_number, finalized_hash, |h| {
+					salt_header(h, b"a");
+				});
+
+			let (a2_hash, chain_a_ext) = construct_chain_on_base(vec![1], 1, a1_hash, |h| {
+				salt_header(h, b"a");
+			});
+
+			let (b1_hash, chain_b) =
+				construct_chain_on_base(vec![2], finalized_number, finalized_hash, |h| {
+					salt_header(h, b"b");
+				});
+
+			import_chains_into_empty(
+				&mut virtual_overseer,
+				&backend,
+				finalized_number,
+				finalized_hash,
+				vec![chain_a.clone(), chain_b.clone()],
+			)
+			.await;
+
+			approve_block(&mut virtual_overseer, &backend, b1_hash).await;
+
+			clock.inc_by(1);
+
+			import_blocks_into(&mut virtual_overseer, &backend, None, chain_a_ext.clone()).await;
+
+			{
+				let (_, write_rx) = backend.await_next_write();
+				clock.inc_by(STAGNANT_TIMEOUT - 1);
+
+				write_rx.await.unwrap();
+			}
+
+			// A1 is stagnant and scheduled for pruning.
+			assert_matches!(
+				backend.load_block_entry(&a1_hash).unwrap().unwrap().viability.approval,
+				Approval::Stagnant
+			);
+			backend.assert_stagnant_at_state(vec![
+				(STAGNANT_TIMEOUT + 1, vec![a2_hash]),
+				(STAGNANT_TIMEOUT + grace_period, vec![a1_hash]),
+			]);
+			assert_leaves(&backend, vec![b1_hash]);
+
+			{
+				let (_, write_rx) = backend.await_next_write();
+				clock.inc_by(grace_period);
+
+				write_rx.await.unwrap();
+			}
+
+			backend.assert_contains_only(vec![(1, b1_hash)]);
+			assert_leaves(&backend, vec![b1_hash]);
+
+			virtual_overseer
+		},
+	)
+}
+
+// Import a leaf whose parent and grandparent are unknown to the subsystem, answering the
+// requests for the weights of the given blocks.
+async fn import_leaf_with_unknown_ancestry(
+	virtual_overseer: &mut VirtualOverseer,
+	backend: &TestBackend,
+	leaf: (Header, BlockWeight),
+	parent: Header,
+	grandparent: Header,
+	weights: Vec<(Hash, BlockWeight)>,
+) {
+	let (header, weight) = leaf;
+	let hash = header.hash();
+	let (_, write_rx) = backend.await_next_write();
+	virtual_overseer
+		.send(
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(ActivatedLeaf {
+				hash,
+				number: header.number,
+				status: LeafStatus::Fresh,
+				span: Arc::new(jaeger::Span::Disabled),
+			}))
+			.into(),
+		)
+		.await;
+
+	answer_header_request(virtual_overseer, header.clone()).await;
+	assert_matches!(
+		virtual_overseer.recv().await,
+		AllMessages::ChainApi(ChainApiMessage::Ancestors { hash: h, k: 2, response_channel: tx }) => {
+			assert_eq!(h, hash);
+			let _ = tx.send(Ok(vec![parent.hash(), grandparent.hash()]));
+		}
+	);
+	answer_header_request(virtual_overseer, parent).await;
+	answer_header_request(virtual_overseer, grandparent).await;
+	for (hash, weight) in weights.into_iter().chain(std::iter::once((hash, weight))) {
+		answer_weight_request(virtual_overseer, hash, weight).await;
+	}
+
+	write_rx.await.unwrap();
+}
+
+#[test]
+fn children_of_pruned_blocks_are_not_imported() {
+	let grace_period = 10;
+	test_harness_with(
+		StagnantCheckMode::CheckAndPruneSubtrees {
+			grace_period: Duration::from_secs(grace_period),
+		},
+		Vec::new(),
+		|backend, clock, mut virtual_overseer| async move {
+			let finalized_number = 0;
+			let finalized_hash = Hash::repeat_byte(0);
+
+			// F <- A1 <- A2 <- A3
+			//             ^--- A3'
+			// F <- B1
+
+			let (a2_hash, chain_a) =
+				construct_chain_on_base(vec![1, 1], finalized_number, finalized_hash, |h| {
+					salt_header(h, b"a");
+				});
+			let a1_hash = chain_a[0].0.hash();
+			let (a3_hash, chain_a3) = construct_chain_on_base(vec![1], 2, a2_hash, |h| {
+				salt_header(h, b"a");
+			});
+			let (a3_prime_hash, chain_a3_prime) =
+				construct_chain_on_base(vec![1], 2, a2_hash, |h| {
+					salt_header(h, b"a'");
+				});
+			let (b1_hash, chain_b) =
+				construct_chain_on_base(vec![2], finalized_number, finalized_hash, |h| {
+					salt_header(h, b"b");
+				});
+
+			import_chains_into_empty(
+				&mut virtual_overseer,
+				&backend,
+				finalized_number,
+				finalized_hash,
+				vec![chain_a.clone(), chain_b.clone()],
+			)
+			.await;
+			approve_block(&mut virtual_overseer, &backend, b1_hash).await;
+
+			// A1 and A2 become stagnant and are pruned after the grace period.
+			for duration in [STAGNANT_TIMEOUT, grace_period] {
+				let (_, write_rx) = backend.await_next_write();
+				clock.inc_by(duration);
+
+				write_rx.await.unwrap();
+			}
+			backend.assert_contains_only(vec![(1, b1_hash)]);
+
+			// A child of a pruned block is rejected.
+			import_leaf_with_unknown_ancestry(
+				&mut virtual_overseer,
+				&backend,
+				chain_a3[0].clone(),
+				chain_a[1].0.clone(),
+				chain_a[0].0.clone(),
+				Vec::new(),
+			)
+			.await;
+			backend.assert_contains_only(vec![(1, b1_hash)]);
+			assert_leaves(&backend, vec![b1_hash]);
+			assert!(backend.load_pruned_blocks().unwrap().contains_key(&a3_hash));
+
+			// Once the pruned blocks get approved, they are imported again along with new
+			// children, while the rejected child stays pruned.
+			approve_block(&mut virtual_overseer, &backend, a1_hash).await;
+			approve_block(&mut virtual_overseer, &backend, a2_hash).await;
+
+			import_leaf_with_unknown_ancestry(
+				&mut virtual_overseer,
+				&backend,
+				chain_a3_prime[0].clone(),
+				chain_a[1].0.clone(),
+				chain_a[0].0.clone(),
+				vec![(a1_hash, 1), (a2_hash, 1)],
+			)
+			.await;
+			backend.assert_contains_only(vec![
+				(1, b1_hash),
+				(1, a1_hash),
+				(2, a2_hash),
+				(3, a3_prime_hash),
+			]);
+			assert_matches!(
+				backend.load_block_entry(&a2_hash).unwrap().unwrap().viability.approval,
+				Approval::Approved
+			);
+			assert_leaves(&backend, vec![b1_hash, a3_prime_hash]);
+			assert_eq!(
+				backend.load_pruned_blocks().unwrap().keys().collect::<Vec<_>>(),
+				vec![&a3_hash]
+			);
+
+			// Finalizing over the pruned blocks forgets them.
+			finalize_block(&mut virtual_overseer, &backend, 3, a3_prime_hash).await;
+			assert!(backend.load_pruned_blocks().unwrap().is_empty());
+
+			virtual_overseer
+		},
+	)
+}
+
+#[test]
+fn leaf_details_include_non_viable_leaves() {
+	test_harness(|backend, _, mut virtual_overseer| async move {
+		let finalized_number = 0;
+		let finalized_hash = Hash::repeat_byte(0);
+
+		// F <- A1 <- A2
+		// F <- B1
+
+		let (a2_hash, chain_a) =
+			construct_chain_on_base(vec![1, 1], finalized_number, finalized_hash, |h| {
+				salt_header(h, b"a");
+			});
+
+		let (b1_hash, chain_b) =
+			construct_chain_on_base(vec![3], finalized_number, finalized_hash, |h| {
+				salt_header(h, b"b");
+			});
+
+		import_chains_into_empty(
+			&mut virtual_overseer,
+			&backend,
+			finalized_number,
+			finalized_hash,
+			vec![chain_a.clone(), chain_b.clone()],
+		)
+		.await;
+
+		approve_block(&mut virtual_overseer, &backend, a2_hash).await;
+
+		let (_, write_rx) = backend.await_next_write();
+		virtual_overseer
+			.send(FromOrchestra::Communication {
+				msg: ChainSelectionMessage::BlacklistBlock(b1_hash),
+			})
+			.await;
+
+		write_rx.await.unwrap();
+
+		let (tx, rx) = oneshot::channel();
+		virtual_overseer
+			.send(FromOrchestra::Communication { msg: ChainSelectionMessage::LeafDetails(tx) })
+			.await;
+
+		assert_eq!(
+			rx.await.unwrap(),
+			vec![
+				LeafDetails {
+					block_hash: a2_hash,
+					block_number: 2,
+					weight: 1,
+					viable: true,
+					approval: BlockApprovalStatus::Approved,
+					explicitly_reverted: false,
+					locally_blocked: false,
+					earliest_unviable_ancestor: None,
+				},
+				LeafDetails {
+					block_hash: b1_hash,
+					block_number: 1,
+					weight: 3,
+					viable: false,
+					approval: BlockApprovalStatus::Unapproved,
+					explicitly_reverted: false,
+					locally_blocked: true,
+					earliest_unviable_ancestor: None,
+				},
+			]
+		);
+
+		virtual_overseer
+	})
+}
@@ -29,7 +29,9 @@ use polkadot_primitives::{BlockNumber, Hash};
 
 use std::collections::HashMap;
 
-use super::{Approval, BlockEntry, Error, LeafEntry, Timestamp, ViabilityCriteria, LOG_TARGET};
+use super::{
+	Approval, BlockEntry, Error, LeafEntry, PrunedBlock, Timestamp, ViabilityCriteria, LOG_TARGET,
+};
 use crate::backend::{Backend, OverlayedBackend};
 
 // A viability update to be applied to a block.
@@ -236,7 +238,8 @@ fn propagate_viability_update(
 	Ok(())
 }
 
-/// Imports a new block and applies any reversions to ancestors.
+/// Imports a new block, marks it as non-viable if it is blocked by local policy and applies
+/// any reversions to ancestors.
 pub(crate) fn import_block(
 	backend: &mut OverlayedBackend<impl Backend>,
 	block_hash: Hash,
@@ -245,8 +248,12 @@ pub(crate) fn import_block(
 	reversion_logs: Vec<BlockNumber>,
 	weight: BlockWeight,
 	stagnant_at: Timestamp,
+	locally_blocked: bool,
 ) -> Result<(), Error> {
 	add_block(backend, block_hash, block_number, parent_hash, weight, stagnant_at)?;
+	if locally_blocked {
+		set_locally_blocked(backend, block_hash, true)?;
+	}
 	apply_ancestor_reversions(backend, block_hash, block_number, reversion_logs)?;
 
 	Ok(())
@@ -316,6 +323,7 @@ fn add_block(
 		viability: ViabilityCriteria {
 			earliest_unviable_ancestor: inherited_viability,
 			explicitly_reverted: false,
+			locally_blocked: false,
 			approval: Approval::Unapproved,
 		},
 		weight,
@@ -448,6 +456,14 @@ pub(super) fn finalize_block<'a, B: Backend + 'a>(
 	let earliest_stored_number = backend.load_first_block_number()?;
 	let mut backend = OverlayedBackend::new(backend);
 
+	// Blocks pruned at or below the finalized height can't be imported anymore.
+	let mut pruned_blocks = backend.load_pruned_blocks()?;
+	let pruned_count = pruned_blocks.len();
+	pruned_blocks.retain(|_, pruned| pruned.block_number > finalized_number);
+	if pruned_blocks.len() != pruned_count {
+		backend.write_pruned_blocks(pruned_blocks);
+	}
+
 	let earliest_stored_number = match earliest_stored_number {
 		None => {
 			// This implies that there are no unfinalized blocks and hence nothing
@@ -553,28 +569,104 @@ pub(super) fn approve_block(
 			backend.write_block_entry(entry);
 		}
 	} else {
-		gum::debug!(
-			target: LOG_TARGET,
-			block_hash = ?approved_hash,
-			"Missing entry for freshly-approved block. Ignoring"
-		);
+		let mut pruned_blocks = backend.load_pruned_blocks()?;
+		match pruned_blocks.get_mut(&approved_hash) {
+			Some(pruned) => {
+				// The block got approved after all, so it is imported again along with the
+				// next leaf building on it.
+				gum::info!(
+					target: LOG_TARGET,
+					block_hash = ?approved_hash,
+					block_number = pruned.block_number,
+					"Block pruned as stagnant got approved",
+				);
+
+				pruned.approved = true;
+				backend.write_pruned_blocks(pruned_blocks);
+			},
+			None => gum::debug!(
+				target: LOG_TARGET,
+				block_hash = ?approved_hash,
+				"Missing entry for freshly-approved block. Ignoring"
+			),
+		}
+	}
+
+	Ok(())
+}
+
+/// Mark a block as blocked by local policy, or lift such a mark, and update the
+/// viability of itself and its descendants accordingly.
+pub(super) fn set_locally_blocked(
+	backend: &mut OverlayedBackend<impl Backend>,
+	block_hash: Hash,
+	locally_blocked: bool,
+) -> Result<(), Error> {
+	let mut entry = match backend.load_block_entry(&block_hash)? {
+		Some(entry) => entry,
+		None => {
+			gum::debug!(
+				target: LOG_TARGET,
+				?block_hash,
+				locally_blocked,
+				"Missing entry for locally (un)blocked block. Ignoring"
+			);
+
+			return Ok(())
+		},
+	};
+
+	if entry.viability.locally_blocked == locally_blocked {
+		return Ok(())
+	}
+
+	gum::info!(
+		target: LOG_TARGET,
+		?block_hash,
+		locally_blocked,
+		"Block is (un)marked as non-viable by local policy",
+	);
+
+	let was_explicitly_viable = entry.viability.is_explicitly_viable();
+	entry.viability.locally_blocked = locally_blocked;
+
+	// Unlike approval, this can change the viability in both directions.
+	if was_explicitly_viable != entry.viability.is_explicitly_viable() {
+		propagate_viability_update(backend, entry)?;
+	} else {
+		backend.write_block_entry(entry);
 	}
 
 	Ok(())
 }
 
+/// The number of blocks affected by a check for stagnant blocks.
+#[derive(Debug, Default)]
+pub(super) struct StagnantCheckOutcome {
+	/// The number of blocks which became stagnant.
+	pub(super) stagnant: usize,
+	/// The number of blocks which were pruned along with stagnant subtrees.
+	pub(super) pruned: usize,
+}
+
 /// Check whether any blocks up to the given timestamp are stagnant and update
 /// accordingly.
 ///
+/// If `prune_after` is supplied, blocks which are still stagnant after that many
+/// seconds are removed along with all their descendants.
+///
 /// This accepts a fresh backend and returns an overlay on top of it representing
 /// all changes made.
 pub(super) fn detect_stagnant<'a, B: 'a + Backend>(
 	backend: &'a B,
 	up_to: Timestamp,
 	max_elements: usize,
-) -> Result<OverlayedBackend<'a, B>, Error> {
+	prune_after: Option<Timestamp>,
+) -> Result<(OverlayedBackend<'a, B>, StagnantCheckOutcome), Error> {
 	let stagnant_up_to = backend.load_stagnant_at_up_to(up_to, max_elements)?;
 	let mut backend = OverlayedBackend::new(backend);
+	let mut outcome = StagnantCheckOutcome::default();
+	let mut to_prune_later = Vec::new();
 
 	let (min_ts, max_ts) = match stagnant_up_to.len() {
 		0 => (0 as Timestamp, 0 as Timestamp),
@@ -598,9 +690,27 @@ pub(super) fn detect_stagnant<'a, B: 'a + Backend>(
 
 		for block_hash in maybe_stagnant {
 			if let Some(mut entry) = backend.load_block_entry(&block_hash)? {
+				// Stagnant blocks are only revisited if they are to be pruned,
+				// once their grace period is over.
+				if prune_after.is_some() && entry.viability.approval.is_stagnant() {
+					gum::debug!(
+						target: LOG_TARGET,
+						?block_hash,
+						?timestamp,
+						"Pruning stagnant subtree",
+					);
+
+					outcome.pruned += prune_subtree(&mut backend, entry)?;
+					continue
+				}
+
 				let was_viable = entry.viability.is_viable();
 				if let Approval::Unapproved = entry.viability.approval {
 					entry.viability.approval = Approval::Stagnant;
+					outcome.stagnant += 1;
+					if prune_after.is_some() {
+						to_prune_later.push(block_hash);
+					}
 				}
 				let is_viable = entry.viability.is_viable();
 				gum::trace!(
@@ -628,7 +738,69 @@ pub(super) fn detect_stagnant<'a, B: 'a + Backend>(
 		}
 	}
 
-	Ok(backend)
+	if let Some(prune_after) = prune_after.filter(|_| !to_prune_later.is_empty()) {
+		// Scheduling strictly after `up_to` ensures that the entries aren't
+		// affected by the deletions of this check.
+		let prune_at = up_to + prune_after.max(1);
+		let mut stagnant_at = backend.load_stagnant_at(prune_at)?;
+		stagnant_at.extend(to_prune_later);
+		backend.write_stagnant_at(prune_at, stagnant_at);
+	}
+
+	Ok((backend, outcome))
+}
+
+// Remove a block along with all its descendants, returning the number of removed blocks.
+//
+// The block is expected to be non-viable, so the parent keeps its place in the set of
+// viable leaves. The stagnant-at entries of the removed blocks are pruned lazily.
+//
+// The removed blocks are remembered until they are finalized over, so that they and their
+// descendants aren't imported again by later leaves.
+fn prune_subtree(
+	backend: &mut OverlayedBackend<impl Backend>,
+	root: BlockEntry,
+) -> Result<usize, Error> {
+	if let Some(mut parent) = backend.load_block_entry(&root.parent_hash)? {
+		parent.children.retain(|h| h != &root.block_hash);
+		backend.write_block_entry(parent);
+	}
+
+	let mut viable_leaves = backend.load_leaves()?;
+	let mut leaves_changed = false;
+	let mut pruned_blocks = backend.load_pruned_blocks()?;
+	let mut pruned = 0;
+
+	let mut frontier = vec![root];
+	while let Some(entry) = frontier.pop() {
+		backend.delete_block_entry(&entry.block_hash);
+		leaves_changed |= viable_leaves.remove(&entry.block_hash);
+		pruned_blocks.insert(
+			entry.block_hash,
+			PrunedBlock {
+				block_number: entry.block_number,
+				approved: matches!(entry.viability.approval, Approval::Approved),
+			},
+		);
+
+		let mut blocks_at_height = backend.load_blocks_by_number(entry.block_number)?;
+		blocks_at_height.retain(|h| h != &entry.block_hash);
+		backend.write_blocks_by_number(entry.block_number, blocks_at_height);
+		pruned += 1;
+
+		for child in entry.children {
+			if let Some(child) = backend.load_block_entry(&child)? {
+				frontier.push(child);
+			}
+		}
+	}
+
+	if leaves_changed {
+		backend.write_leaves(viable_leaves);
+	}
+	backend.write_pruned_blocks(pruned_blocks);
+
+	Ok(pruned)
 }
 
 /// Prune stagnant entries at some timestamp without other checks
@@ -712,6 +884,7 @@ pub(super) fn revert_to<'a, B: Backend + 'a>(
 			let block_number = first_number.saturating_sub(1);
 			let viability = ViabilityCriteria {
 				explicitly_reverted: false,
+				locally_blocked: false,
 				approval: Approval::Approved,
 				earliest_unviable_ancestor: None,
 			};
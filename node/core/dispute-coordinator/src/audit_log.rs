@@ -0,0 +1,646 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Audit log of concluded disputes.
+//!
+//! If enabled, every dispute concluding on this node is appended as one JSON object per line to the
+//! audit log, together with all the votes and their signatures, the validators which backed the
+//! candidate on chain and the validators the runtime is going to slash for it. Records can be
+//! checked offline against the `SessionInfo` of their session with [`ConcludedDispute::verify`],
+//! e.g. via the `dispute-audit-verifier` utility.
+//!
+//! Records are written by a blocking task, the log file is rotated once it exceeds a configured
+//! size. Writing the log requires the `audit-log` feature, without it the audit log can't be
+//! enabled.
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	path::PathBuf,
+};
+
+use futures::{channel::mpsc, future::BoxFuture};
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+use polkadot_node_primitives::{CandidateVotes, DisputeStatus, Timestamp};
+use polkadot_primitives::{
+	vstaging::{DisputeStatement, ValidDisputeStatementKind},
+	CandidateHash, Hash, IndexedVec, InvalidDisputeStatementKind, SessionIndex, ValidatorId,
+	ValidatorIndex, ValidatorSignature,
+};
+
+use crate::LOG_TARGET;
+
+#[cfg(feature = "audit-log")]
+mod writer;
+
+/// The outcome of a concluded dispute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Outcome {
+	/// The dispute concluded in favor of the candidate.
+	Valid,
+	/// The dispute concluded against the candidate.
+	Invalid,
+}
+
+/// The kind of statement a vote comes from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum VoteKind {
+	/// An explicit statement issued as part of a dispute.
+	Explicit,
+	/// A seconded statement from the backing phase.
+	#[serde(rename_all = "camelCase")]
+	BackingSeconded {
+		/// The relay parent of the backing signing context.
+		parent_hash: Hash,
+	},
+	/// A valid statement from the backing phase.
+	#[serde(rename_all = "camelCase")]
+	BackingValid {
+		/// The relay parent of the backing signing context.
+		parent_hash: Hash,
+	},
+	/// An approval vote from the approval checking phase.
+	ApprovalChecking,
+	/// An approval vote from the approval checking phase covering multiple candidates.
+	#[serde(rename_all = "camelCase")]
+	ApprovalCheckingMultipleCandidates {
+		/// The candidates the vote was signed for, including the disputed one.
+		candidate_hashes: Vec<Hash>,
+	},
+}
+
+impl VoteKind {
+	/// Whether the vote comes from the backing phase.
+	pub fn is_backing(&self) -> bool {
+		matches!(self, VoteKind::BackingSeconded { .. } | VoteKind::BackingValid { .. })
+	}
+
+	fn valid(kind: &ValidDisputeStatementKind) -> Self {
+		match kind {
+			ValidDisputeStatementKind::Explicit => VoteKind::Explicit,
+			ValidDisputeStatementKind::BackingSeconded(parent_hash) =>
+				VoteKind::BackingSeconded { parent_hash: *parent_hash },
+			ValidDisputeStatementKind::BackingValid(parent_hash) =>
+				VoteKind::BackingValid { parent_hash: *parent_hash },
+			ValidDisputeStatementKind::ApprovalChecking => VoteKind::ApprovalChecking,
+			ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(candidate_hashes) =>
+				VoteKind::ApprovalCheckingMultipleCandidates {
+					candidate_hashes: candidate_hashes.iter().map(|hash| hash.0).collect(),
+				},
+		}
+	}
+
+	fn invalid(kind: &InvalidDisputeStatementKind) -> Self {
+		match *kind {
+			InvalidDisputeStatementKind::Explicit => VoteKind::Explicit,
+		}
+	}
+
+	/// The dispute statement the vote was signed for, `None` if the kind can't be signed with the
+	/// given validity.
+	fn statement(&self, valid: bool) -> Option<DisputeStatement> {
+		let statement = match (self, valid) {
+			(VoteKind::Explicit, true) =>
+				DisputeStatement::Valid(ValidDisputeStatementKind::Explicit),
+			(VoteKind::BackingSeconded { parent_hash }, true) =>
+				DisputeStatement::Valid(ValidDisputeStatementKind::BackingSeconded(*parent_hash)),
+			(VoteKind::BackingValid { parent_hash }, true) =>
+				DisputeStatement::Valid(ValidDisputeStatementKind::BackingValid(*parent_hash)),
+			(VoteKind::ApprovalChecking, true) =>
+				DisputeStatement::Valid(ValidDisputeStatementKind::ApprovalChecking),
+			(VoteKind::ApprovalCheckingMultipleCandidates { candidate_hashes }, true) =>
+				DisputeStatement::Valid(
+					ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(
+						candidate_hashes.iter().copied().map(CandidateHash).collect(),
+					),
+				),
+			(VoteKind::Explicit, false) =>
+				DisputeStatement::Invalid(InvalidDisputeStatementKind::Explicit),
+			(_, false) => return None,
+		};
+		Some(statement)
+	}
+}
+
+/// A single vote on the disputed candidate.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Vote {
+	/// The index of the validator in the session.
+	pub validator_index: u32,
+	/// The SCALE encoded `ValidatorId` of the validator.
+	pub validator_id: Bytes,
+	/// Whether the validator considers the candidate valid.
+	pub valid: bool,
+	/// The kind of statement the vote comes from.
+	pub kind: VoteKind,
+	/// The SCALE encoded `ValidatorSignature` of the statement.
+	pub signature: Bytes,
+	/// Whether the vote was cast by this node.
+	pub local: bool,
+}
+
+/// The kind of offence the runtime slashes validators for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OffenceKind {
+	/// Backing a candidate that turned out to be invalid.
+	ForInvalid,
+}
+
+/// The validators the runtime slashes for a concluded dispute.
+///
+/// Mirrors the `PendingSlashes` recorded by the runtime, as long as it can't report the offence
+/// right away.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSlashes {
+	/// The offence the validators are slashed for.
+	pub kind: OffenceKind,
+	/// The indices of the slashed validators, ascending.
+	pub validators: Vec<u32>,
+	/// Whether this node voted against the candidate, thus against the slashed validators.
+	pub voted_invalid_by_us: bool,
+}
+
+impl PendingSlashes {
+	/// Derive the slashes from the votes of a dispute, the way the runtime does: only the on-chain
+	/// backers of a candidate that concluded invalid, which voted for the candidate, get slashed.
+	pub fn from_votes(outcome: Outcome, votes: &[Vote], backers: &[u32]) -> Option<Self> {
+		if outcome == Outcome::Valid {
+			return None
+		}
+		let backers: BTreeSet<u32> = backers.iter().copied().collect();
+		let losers: BTreeSet<u32> = votes
+			.iter()
+			.filter(|vote| vote.valid)
+			.map(|vote| vote.validator_index)
+			.collect();
+		let validators: Vec<u32> = losers.intersection(&backers).copied().collect();
+		if validators.is_empty() {
+			return None
+		}
+		Some(Self {
+			kind: OffenceKind::ForInvalid,
+			validators,
+			voted_invalid_by_us: votes.iter().any(|vote| vote.local && !vote.valid),
+		})
+	}
+}
+
+/// A record of the audit log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConcludedDispute {
+	/// The session the candidate appeared in.
+	pub session: SessionIndex,
+	/// The hash of the disputed candidate.
+	pub candidate_hash: Hash,
+	/// The para the candidate belongs to.
+	pub para_id: u32,
+	/// The relay parent of the candidate.
+	pub relay_parent: Hash,
+	/// The outcome of the dispute.
+	pub outcome: Outcome,
+	/// Unix timestamp in seconds of the conclusion.
+	pub concluded_at: Timestamp,
+	/// All votes known at the time of the conclusion, sorted by validator index.
+	pub votes: Vec<Vote>,
+	/// The indices of the validators which backed the candidate on chain, ascending.
+	pub backers: Vec<u32>,
+	/// The validators slashed for the dispute, if any.
+	pub pending_slashes: Option<PendingSlashes>,
+}
+
+/// Errors of [`ConcludedDispute::verify`].
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum VerificationError {
+	#[error("Validator {0} is not part of the session")]
+	UnknownValidator(u32),
+
+	#[error("Validator {0} is logged with a key not matching the session")]
+	KeyMismatch(u32),
+
+	#[error("The signature of validator {0} can't be decoded")]
+	MalformedSignature(u32),
+
+	#[error("The vote of validator {0} has a kind which can't be signed with its validity")]
+	MalformedStatement(u32),
+
+	#[error("The signature of validator {0} is invalid")]
+	InvalidSignature(u32),
+
+	#[error("The pending slashes don't match the votes")]
+	SlashesMismatch,
+}
+
+impl ConcludedDispute {
+	/// Build the record of a dispute, `None` if the dispute has not concluded.
+	pub fn new(
+		session: SessionIndex,
+		votes: &CandidateVotes,
+		status: DisputeStatus,
+		validators: &IndexedVec<ValidatorIndex, ValidatorId>,
+		local_validators: &impl Fn(ValidatorIndex) -> bool,
+		backers: &BTreeSet<ValidatorIndex>,
+	) -> Option<Self> {
+		// Conclusion against takes precedence, as in `DisputeStatus`.
+		let (outcome, concluded_at) = match status {
+			DisputeStatus::ConcludedAgainst(at) => (Outcome::Invalid, at),
+			DisputeStatus::ConcludedFor(at) => (Outcome::Valid, at),
+			DisputeStatus::Active | DisputeStatus::Confirmed => return None,
+		};
+
+		let vote = |index: &ValidatorIndex, valid, kind, signature: &ValidatorSignature| Vote {
+			validator_index: index.0,
+			validator_id: validators.get(*index).map(|id| Bytes(id.encode())).unwrap_or_default(),
+			valid,
+			kind,
+			signature: Bytes(signature.encode()),
+			local: local_validators(*index),
+		};
+		let mut logged: Vec<Vote> = votes
+			.valid
+			.raw()
+			.iter()
+			.map(|(index, (kind, signature))| vote(index, true, VoteKind::valid(kind), signature))
+			.chain(votes.invalid.iter().map(|(index, (kind, signature))| {
+				vote(index, false, VoteKind::invalid(kind), signature)
+			}))
+			.collect();
+		logged.sort_by_key(|vote| (vote.validator_index, !vote.valid));
+
+		let backers: Vec<u32> = backers.iter().map(|index| index.0).collect();
+		let descriptor = &votes.candidate_receipt.descriptor;
+		Some(Self {
+			session,
+			candidate_hash: votes.candidate_receipt.hash().0,
+			para_id: descriptor.para_id.into(),
+			relay_parent: descriptor.relay_parent,
+			outcome,
+			concluded_at,
+			pending_slashes: PendingSlashes::from_votes(outcome, &logged, &backers),
+			votes: logged,
+			backers,
+		})
+	}
+
+	/// Re-check the record against the validators of the `SessionInfo` of its session: all keys
+	/// must match the session, all signatures must be valid and the slashes must follow from the
+	/// votes.
+	pub fn verify(&self, validators: &[ValidatorId]) -> Result<(), VerificationError> {
+		let candidate_hash = CandidateHash(self.candidate_hash);
+		for vote in &self.votes {
+			let index = vote.validator_index;
+			let validator_id = validators
+				.get(index as usize)
+				.ok_or(VerificationError::UnknownValidator(index))?;
+			if vote.validator_id.0 != validator_id.encode() {
+				return Err(VerificationError::KeyMismatch(index))
+			}
+			let signature = ValidatorSignature::decode(&mut &vote.signature[..])
+				.map_err(|_| VerificationError::MalformedSignature(index))?;
+			vote.kind
+				.statement(vote.valid)
+				.ok_or(VerificationError::MalformedStatement(index))?
+				.check_signature(validator_id, candidate_hash, self.session, &signature)
+				.map_err(|_| VerificationError::InvalidSignature(index))?;
+		}
+
+		if self.pending_slashes !=
+			PendingSlashes::from_votes(self.outcome, &self.votes, &self.backers)
+		{
+			return Err(VerificationError::SlashesMismatch)
+		}
+		Ok(())
+	}
+}
+
+/// Configuration of the audit log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLogConfig {
+	/// The file to append records of concluded disputes to.
+	pub path: PathBuf,
+	/// The size in bytes the file is rotated at.
+	pub max_file_size: u64,
+	/// How many rotated files to keep, the most recent one as `<path>.1`.
+	pub max_rotated_files: u32,
+}
+
+impl AuditLogConfig {
+	/// The default size in bytes the file is rotated at.
+	pub const DEFAULT_MAX_FILE_SIZE: u64 = 100 * 1024 * 1024;
+	/// The default number of rotated files to keep.
+	pub const DEFAULT_MAX_ROTATED_FILES: u32 = 5;
+
+	/// Create a configuration appending to the file at the given path, rotating it at the default
+	/// size.
+	pub fn new(path: PathBuf) -> Self {
+		Self {
+			path,
+			max_file_size: Self::DEFAULT_MAX_FILE_SIZE,
+			max_rotated_files: Self::DEFAULT_MAX_ROTATED_FILES,
+		}
+	}
+}
+
+/// Hands records of concluded disputes over to the blocking task writing them and keeps track of
+/// the on-chain backers of candidates, for determining the pending slashes.
+#[cfg_attr(not(feature = "audit-log"), allow(dead_code))]
+pub(crate) struct AuditLog {
+	records: mpsc::UnboundedSender<ConcludedDispute>,
+	/// The task writing the records, until it gets spawned.
+	writer: Option<BoxFuture<'static, ()>>,
+	/// The validators which backed candidates on chain, by session.
+	on_chain_backers: BTreeMap<(SessionIndex, CandidateHash), BTreeSet<ValidatorIndex>>,
+}
+
+impl AuditLog {
+	/// Create an audit log with the given configuration.
+	///
+	/// The task returned by `take_writer` needs to be spawned for records to be written.
+	#[cfg(feature = "audit-log")]
+	pub fn new(config: AuditLogConfig) -> Option<Self> {
+		use futures::FutureExt;
+
+		let (records, receiver) = mpsc::unbounded();
+		let writer = writer::AuditLogWriter::new(config).run(receiver).boxed();
+		Some(Self { records, writer: Some(writer), on_chain_backers: BTreeMap::new() })
+	}
+
+	/// The audit log can't be written without the `audit-log` feature, so this always returns
+	/// `None`.
+	#[cfg(not(feature = "audit-log"))]
+	pub fn new(config: AuditLogConfig) -> Option<Self> {
+		gum::warn!(
+			target: LOG_TARGET,
+			path = ?config.path,
+			"The dispute audit log is not available, the node was built without the `audit-log` \
+			feature of the dispute coordinator.",
+		);
+		None
+	}
+
+	/// The task writing the records, to be spawned as a blocking task. Returns `None` on all but
+	/// the first call.
+	pub fn take_writer(&mut self) -> Option<BoxFuture<'static, ()>> {
+		self.writer.take()
+	}
+
+	/// Note validators which backed a candidate on chain.
+	pub fn note_on_chain_backers(
+		&mut self,
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+		backers: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		self.on_chain_backers
+			.entry((session, candidate_hash))
+			.or_default()
+			.extend(backers);
+	}
+
+	/// The validators known to have backed a candidate on chain.
+	pub fn on_chain_backers(
+		&self,
+		session: SessionIndex,
+		candidate_hash: CandidateHash,
+	) -> BTreeSet<ValidatorIndex> {
+		self.on_chain_backers
+			.get(&(session, candidate_hash))
+			.cloned()
+			.unwrap_or_default()
+	}
+
+	/// Forget the on-chain backers of sessions older than the given one.
+	pub fn prune_old(&mut self, oldest_index: SessionIndex) {
+		self.on_chain_backers.retain(|(session, _), _| *session >= oldest_index);
+	}
+
+	/// Queue a record for being appended to the log.
+	pub fn write(&self, record: ConcludedDispute) {
+		if self.records.unbounded_send(record).is_err() {
+			gum::warn!(target: LOG_TARGET, "Audit log writer is gone, dropping record.");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use polkadot_node_primitives::disputes::ValidCandidateVotes;
+	use polkadot_primitives::CandidateReceipt;
+	use sp_keyring::Sr25519Keyring;
+	use std::collections::BTreeMap;
+	use test_helpers::{dummy_candidate_receipt_bad_sig, dummy_hash};
+
+	const SESSION: SessionIndex = 3;
+
+	fn validators() -> Vec<ValidatorId> {
+		[Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie]
+			.iter()
+			.map(|k| k.public().into())
+			.collect()
+	}
+
+	fn sign(
+		keyring: Sr25519Keyring,
+		statement: &DisputeStatement,
+		receipt: &CandidateReceipt,
+	) -> ValidatorSignature {
+		keyring.sign(&statement.payload_data(receipt.hash(), SESSION).unwrap()).into()
+	}
+
+	pub(super) fn concluded_against() -> ConcludedDispute {
+		let receipt = dummy_candidate_receipt_bad_sig(dummy_hash(), Some(Default::default()));
+		let backing = ValidDisputeStatementKind::BackingSeconded(dummy_hash());
+		let explicit_valid = ValidDisputeStatementKind::Explicit;
+		let explicit_invalid = InvalidDisputeStatementKind::Explicit;
+
+		let valid: ValidCandidateVotes = [
+			(
+				ValidatorIndex(0),
+				(
+					backing.clone(),
+					sign(Sr25519Keyring::Alice, &DisputeStatement::Valid(backing), &receipt),
+				),
+			),
+			(
+				ValidatorIndex(1),
+				(
+					explicit_valid.clone(),
+					sign(Sr25519Keyring::Bob, &DisputeStatement::Valid(explicit_valid), &receipt),
+				),
+			),
+		]
+		.into_iter()
+		.collect();
+		let invalid = BTreeMap::from([(
+			ValidatorIndex(2),
+			(
+				explicit_invalid,
+				sign(
+					Sr25519Keyring::Charlie,
+					&DisputeStatement::Invalid(explicit_invalid),
+					&receipt,
+				),
+			),
+		)]);
+		let votes = CandidateVotes { candidate_receipt: receipt, valid, invalid };
+
+		ConcludedDispute::new(
+			SESSION,
+			&votes,
+			DisputeStatus::ConcludedAgainst(42),
+			&validators().into(),
+			&|index| index == ValidatorIndex(2),
+			&BTreeSet::from([ValidatorIndex(0), ValidatorIndex(2)]),
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn only_concluded_disputes_are_recorded() {
+		let receipt = dummy_candidate_receipt_bad_sig(dummy_hash(), Some(Default::default()));
+		let votes = CandidateVotes {
+			candidate_receipt: receipt,
+			valid: ValidCandidateVotes::new(),
+			invalid: BTreeMap::new(),
+		};
+		for status in [DisputeStatus::Active, DisputeStatus::Confirmed] {
+			assert!(ConcludedDispute::new(
+				SESSION,
+				&votes,
+				status,
+				&validators().into(),
+				&|_| false,
+				&BTreeSet::new(),
+			)
+			.is_none());
+		}
+	}
+
+	#[test]
+	fn records_votes_and_slashes() {
+		let record = concluded_against();
+
+		assert_eq!(record.outcome, Outcome::Invalid);
+		assert_eq!(record.concluded_at, 42);
+		assert_eq!(
+			record
+				.votes
+				.iter()
+				.map(|v| (v.validator_index, v.valid, v.local))
+				.collect::<Vec<_>>(),
+			vec![(0, true, false), (1, true, false), (2, false, true)],
+		);
+		assert_eq!(record.backers, vec![0, 2]);
+		// Only the on-chain backer which voted for the invalid candidate gets slashed.
+		assert_eq!(
+			record.pending_slashes,
+			Some(PendingSlashes {
+				kind: OffenceKind::ForInvalid,
+				validators: vec![0],
+				voted_invalid_by_us: true,
+			}),
+		);
+	}
+
+	#[test]
+	fn records_survive_json_roundtrip_and_verify() {
+		let record = concluded_against();
+		let line = serde_json::to_string(&record).unwrap();
+		let decoded: ConcludedDispute = serde_json::from_str(&line).unwrap();
+
+		assert_eq!(decoded, record);
+		assert_eq!(decoded.verify(&validators()), Ok(()));
+	}
+
+	#[test]
+	fn verification_detects_tampering() {
+		let validators = validators();
+
+		let mut record = concluded_against();
+		record.votes[1].valid = false;
+		record.votes[1].kind = VoteKind::Explicit;
+		assert_eq!(record.verify(&validators), Err(VerificationError::InvalidSignature(1)));
+
+		let mut record = concluded_against();
+		record.votes[0].validator_id = record.votes[1].validator_id.clone();
+		assert_eq!(record.verify(&validators), Err(VerificationError::KeyMismatch(0)));
+
+		let mut record = concluded_against();
+		record.pending_slashes = None;
+		assert_eq!(record.verify(&validators), Err(VerificationError::SlashesMismatch));
+
+		let mut record = concluded_against();
+		record.backers = vec![0, 1];
+		assert_eq!(record.verify(&validators), Err(VerificationError::SlashesMismatch));
+
+		assert_eq!(
+			concluded_against().verify(&validators[..2]),
+			Err(VerificationError::UnknownValidator(2))
+		);
+	}
+
+	#[cfg(feature = "audit-log")]
+	#[test]
+	fn records_are_written_by_the_writer_task() {
+		let dir = tempfile::tempdir().unwrap();
+		let config = AuditLogConfig::new(dir.path().join("dispute-audit.jsonl"));
+		let mut audit_log = AuditLog::new(config.clone()).unwrap();
+		let writer = audit_log.take_writer().unwrap();
+		assert!(audit_log.take_writer().is_none());
+
+		audit_log.write(concluded_against());
+		audit_log.write(concluded_against());
+		drop(audit_log);
+		futures::executor::block_on(writer);
+
+		let log = std::fs::read_to_string(&config.path).unwrap();
+		let records = log
+			.lines()
+			.map(|line| serde_json::from_str::<ConcludedDispute>(line).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(records, vec![concluded_against(), concluded_against()]);
+	}
+
+	#[cfg(feature = "audit-log")]
+	#[test]
+	fn on_chain_backers_are_pruned_with_their_session() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut audit_log =
+			AuditLog::new(AuditLogConfig::new(dir.path().join("audit.jsonl"))).unwrap();
+		let candidate_hash = CandidateHash(dummy_hash());
+
+		audit_log.note_on_chain_backers(1, candidate_hash, [ValidatorIndex(1)]);
+		audit_log.note_on_chain_backers(2, candidate_hash, [ValidatorIndex(0)]);
+		audit_log.note_on_chain_backers(2, candidate_hash, [ValidatorIndex(3)]);
+		assert_eq!(
+			audit_log.on_chain_backers(2, candidate_hash),
+			BTreeSet::from([ValidatorIndex(0), ValidatorIndex(3)]),
+		);
+
+		audit_log.prune_old(2);
+		assert!(audit_log.on_chain_backers(1, candidate_hash).is_empty());
+		assert_eq!(audit_log.on_chain_backers(2, candidate_hash).len(), 2);
+	}
+}
@@ -0,0 +1,159 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Writing the audit log to disk.
+
+use std::{
+	ffi::OsString,
+	fs::{self, File, OpenOptions},
+	io::{self, BufWriter, Write},
+	path::PathBuf,
+};
+
+use futures::{channel::mpsc, StreamExt};
+
+use super::{AuditLogConfig, ConcludedDispute};
+use crate::LOG_TARGET;
+
+impl AuditLogConfig {
+	/// The path of the rotated file with the given number.
+	fn rotated_path(&self, n: u32) -> PathBuf {
+		let mut path = OsString::from(self.path.clone());
+		path.push(format!(".{}", n));
+		path.into()
+	}
+}
+
+/// Appends records of concluded disputes to the file, rotating it once it gets too large.
+pub(super) struct AuditLogWriter {
+	config: AuditLogConfig,
+	file: Option<BufWriter<File>>,
+	/// The size of the file in bytes.
+	size: u64,
+}
+
+impl AuditLogWriter {
+	pub(super) fn new(config: AuditLogConfig) -> Self {
+		Self { config, file: None, size: 0 }
+	}
+
+	/// Write all received records, flushing the file whenever no more records are pending.
+	pub(super) async fn run(mut self, mut records: mpsc::UnboundedReceiver<ConcludedDispute>) {
+		while let Some(record) = records.next().await {
+			let mut result = self.write(&record);
+			while let Ok(Some(record)) = records.try_next() {
+				result = result.and_then(|()| self.write(&record));
+			}
+			if let Err(err) = result.and_then(|()| self.flush()) {
+				gum::warn!(
+					target: LOG_TARGET,
+					?err,
+					path = ?self.config.path,
+					"Could not write concluded disputes to the audit log."
+				);
+				// Reopen the file on the next write.
+				self.file = None;
+			}
+		}
+	}
+
+	/// Append a record to the file, rotating it first if it would exceed the maximum size.
+	fn write(&mut self, record: &ConcludedDispute) -> io::Result<()> {
+		let mut line = serde_json::to_vec(record)?;
+		line.push(b'\n');
+
+		if self.file.is_none() {
+			self.open()?;
+		}
+		if self.size > 0 && self.size + line.len() as u64 > self.config.max_file_size {
+			self.rotate()?;
+			self.open()?;
+		}
+		let file = self.file.as_mut().expect("The file got opened above; qed");
+		file.write_all(&line)?;
+		self.size += line.len() as u64;
+		Ok(())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		match self.file.as_mut() {
+			Some(file) => file.flush(),
+			None => Ok(()),
+		}
+	}
+
+	fn open(&mut self) -> io::Result<()> {
+		let file = OpenOptions::new().create(true).append(true).open(&self.config.path)?;
+		self.size = file.metadata()?.len();
+		self.file = Some(BufWriter::new(file));
+		Ok(())
+	}
+
+	/// Close the file and shift it and the rotated files by one, dropping the oldest one.
+	fn rotate(&mut self) -> io::Result<()> {
+		if let Some(mut file) = self.file.take() {
+			file.flush()?;
+		}
+		self.size = 0;
+		if self.config.max_rotated_files == 0 {
+			return fs::remove_file(&self.config.path)
+		}
+		for n in (1..self.config.max_rotated_files).rev() {
+			let rotated = self.config.rotated_path(n);
+			if rotated.exists() {
+				fs::rename(rotated, self.config.rotated_path(n + 1))?;
+			}
+		}
+		fs::rename(&self.config.path, self.config.rotated_path(1))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::audit_log::tests::concluded_against;
+
+	#[test]
+	fn log_is_rotated() {
+		let dir = tempfile::tempdir().unwrap();
+		let record = concluded_against();
+		let line_len = serde_json::to_vec(&record).unwrap().len() as u64 + 1;
+		let config = AuditLogConfig {
+			path: dir.path().join("dispute-audit.jsonl"),
+			max_file_size: line_len * 2,
+			max_rotated_files: 2,
+		};
+		let lines = |path: PathBuf| fs::read_to_string(path).unwrap().lines().count();
+
+		let mut writer = AuditLogWriter::new(config.clone());
+		for _ in 0..7 {
+			writer.write(&record).unwrap();
+		}
+		writer.flush().unwrap();
+
+		assert_eq!(lines(config.path.clone()), 1);
+		assert_eq!(lines(config.rotated_path(1)), 2);
+		assert_eq!(lines(config.rotated_path(2)), 2);
+		// The oldest records got dropped.
+		assert!(!config.rotated_path(3).exists());
+
+		// Appending continues with the existing file.
+		let mut writer = AuditLogWriter::new(config.clone());
+		writer.write(&record).unwrap();
+		writer.flush().unwrap();
+		assert_eq!(lines(config.path.clone()), 2);
+	}
+}
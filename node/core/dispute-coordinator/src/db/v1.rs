@@ -19,8 +19,8 @@
 use polkadot_node_primitives::DisputeStatus;
 use polkadot_node_subsystem_util::database::{DBTransaction, Database};
 use polkadot_primitives::{
-	CandidateHash, CandidateReceipt, Hash, InvalidDisputeStatementKind, SessionIndex,
-	ValidDisputeStatementKind, ValidatorIndex, ValidatorSignature,
+	vstaging::ValidDisputeStatementKind, CandidateHash, CandidateReceipt, Hash,
+	InvalidDisputeStatementKind, SessionIndex, ValidatorIndex, ValidatorSignature,
 };
 
 use std::sync::Arc;
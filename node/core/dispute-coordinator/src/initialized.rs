@@ -31,7 +31,8 @@ use polkadot_node_primitives::{
 use polkadot_node_subsystem::{
 	messages::{
 		ApprovalVotingMessage, BlockDescription, ChainSelectionMessage, DisputeCoordinatorMessage,
-		DisputeDistributionMessage, ImportStatementsResult,
+		DisputeCoordinatorStatus, DisputeDistributionMessage, ImportStatementsResult,
+		RuntimeApiMessage, RuntimeApiRequest,
 	},
 	overseer, ActivatedLeaf, ActiveLeavesUpdate, FromOrchestra, OverseerSignal,
 };
@@ -39,12 +40,13 @@ use polkadot_node_subsystem_util::rolling_session_window::{
 	RollingSessionWindow, SessionWindowUpdate, SessionsUnavailable,
 };
 use polkadot_primitives::{
-	BlockNumber, CandidateHash, CandidateReceipt, CompactStatement, DisputeStatement,
-	DisputeStatementSet, Hash, ScrapedOnChainVotes, SessionIndex, SessionInfo,
-	ValidDisputeStatementKind, ValidatorId, ValidatorIndex,
+	vstaging::{DisputeStatement, ValidDisputeStatementKind},
+	BlockNumber, CandidateHash, CandidateReceipt, CompactStatement, DisputeStatementSet, Hash,
+	ScrapedOnChainVotes, SessionIndex, SessionInfo, ValidatorId, ValidatorIndex,
 };
 
 use crate::{
+	audit_log::{AuditLog, ConcludedDispute},
 	error::{log_error, Error, FatalError, FatalResult, JfyiError, JfyiResult, Result},
 	import::{CandidateEnvironment, CandidateVoteState},
 	is_potential_spam,
@@ -79,6 +81,9 @@ pub struct Initialized {
 	scraper: ChainScraper,
 	participation_receiver: WorkerMessageReceiver,
 	metrics: Metrics,
+	audit_log: Option<AuditLog>,
+	// The most recently activated leaf, for querying the runtime version.
+	latest_leaf: Option<Hash>,
 	// This tracks only rolling session window failures.
 	// It can be a `Vec` if the need to track more arises.
 	error: Option<SessionsUnavailable>,
@@ -93,7 +98,7 @@ impl Initialized {
 		spam_slots: SpamSlots,
 		scraper: ChainScraper,
 	) -> Self {
-		let DisputeCoordinatorSubsystem { config: _, store: _, keystore, metrics } = subsystem;
+		let DisputeCoordinatorSubsystem { config, store: _, keystore, metrics } = subsystem;
 
 		let (participation_sender, participation_receiver) = mpsc::channel(1);
 		let participation = Participation::new(participation_sender);
@@ -108,6 +113,8 @@ impl Initialized {
 			participation,
 			participation_receiver,
 			metrics,
+			audit_log: config.audit_log.and_then(AuditLog::new),
+			latest_leaf: None,
 			error: None,
 		}
 	}
@@ -127,6 +134,10 @@ impl Initialized {
 	where
 		B: Backend,
 	{
+		if let Some(writer) = self.audit_log.as_mut().and_then(AuditLog::take_writer) {
+			ctx.spawn_blocking("dispute-audit-log", writer)
+				.map_err(FatalError::SpawnFailed)?;
+		}
 		loop {
 			let res = self
 				.run_until_error(
@@ -188,6 +199,7 @@ impl Initialized {
 		}
 
 		if let Some(first_leaf) = first_leaf.take() {
+			self.latest_leaf = Some(first_leaf.hash);
 			// Also provide first leaf to participation for good measure.
 			self.participation
 				.process_active_leaves_update(ctx, &ActiveLeavesUpdate::start_work(first_leaf))
@@ -275,13 +287,12 @@ impl Initialized {
 		let scraped_updates =
 			self.scraper.process_active_leaves_update(ctx.sender(), &update).await?;
 		log_error(
-			self.participation
-				.bump_to_priority_for_candidates(ctx, &scraped_updates.included_receipts)
-				.await,
+			self.participation.bump_to_priority_for_candidates(&scraped_updates.included_receipts),
 		)?;
 		self.participation.process_active_leaves_update(ctx, &update).await?;
 
 		if let Some(new_leaf) = update.activated {
+			self.latest_leaf = Some(new_leaf.hash);
 			match self
 				.rolling_session_window
 				.cache_session_info_for_head(ctx.sender(), new_leaf.hash)
@@ -309,6 +320,9 @@ impl Initialized {
 
 						db::v1::note_earliest_session(overlay_db, new_window_start)?;
 						self.spam_slots.prune_old(new_window_start);
+						if let Some(audit_log) = self.audit_log.as_mut() {
+							audit_log.prune_old(new_window_start);
+						}
 					}
 				},
 				Ok(SessionWindowUpdate::Unchanged) => {},
@@ -375,6 +389,13 @@ impl Initialized {
 				?relay_parent,
 				"Importing backing votes from chain for candidate"
 			);
+			if let Some(audit_log) = self.audit_log.as_mut() {
+				audit_log.note_on_chain_backers(
+					session,
+					candidate_hash,
+					backers.iter().map(|(validator_index, _)| *validator_index),
+				);
+			}
 			let statements = backers
 				.into_iter()
 				.filter_map(|(validator_index, attestation)| {
@@ -401,7 +422,7 @@ impl Initialized {
 						};
 					debug_assert!(
 						SignedDisputeStatement::new_checked(
-							DisputeStatement::Valid(valid_statement_kind),
+							DisputeStatement::Valid(valid_statement_kind.clone()),
 							candidate_hash,
 							session,
 							validator_public.clone(),
@@ -474,6 +495,16 @@ impl Initialized {
 					continue
 				};
 
+			if let Some(audit_log) = self.audit_log.as_mut() {
+				audit_log.note_on_chain_backers(
+					session,
+					candidate_hash,
+					statements.iter().filter_map(|(statement, validator_index, _)| {
+						statement.is_backing().then(|| *validator_index)
+					}),
+				);
+			}
+
 			let statements = statements
 				.into_iter()
 				.filter_map(|(dispute_statement, validator_index, validator_signature)| {
@@ -494,7 +525,7 @@ impl Initialized {
 
 					Some((
 						SignedDisputeStatement::new_unchecked_from_trusted_source(
-							dispute_statement,
+							dispute_statement.into(),
 							candidate_hash,
 							session,
 							validator_public,
@@ -641,6 +672,22 @@ impl Initialized {
 				}
 				let _ = tx.send(query_output);
 			},
+			DisputeCoordinatorMessage::Status(tx) => {
+				gum::trace!(target: LOG_TARGET, "DisputeCoordinatorMessage::Status");
+
+				let mut spam_slots = self.spam_slots.occupied().collect::<Vec<_>>();
+				spam_slots.sort();
+				let (priority_queue_len, best_effort_queue_len) =
+					self.participation.queue_lengths();
+
+				let _ = tx.send(DisputeCoordinatorStatus {
+					spam_slots,
+					max_spam_votes: SpamSlots::max_spam_votes(),
+					priority_queue_len,
+					best_effort_queue_len,
+					running_participations: self.participation.running_participations(),
+				});
+			},
 			DisputeCoordinatorMessage::IssueLocalStatement(
 				session,
 				candidate_hash,
@@ -694,6 +741,34 @@ impl Initialized {
 		Ok(())
 	}
 
+	// Whether the runtime at the latest leaf supports approval votes of multiple candidates as
+	// dispute statements.
+	async fn supports_multiple_candidate_approvals<Context>(&self, ctx: &mut Context) -> bool {
+		let leaf = match self.latest_leaf {
+			Some(leaf) => leaf,
+			None => return false,
+		};
+
+		let (tx, rx) = oneshot::channel();
+		ctx.send_message(RuntimeApiMessage::Request(leaf, RuntimeApiRequest::Version(tx)))
+			.await;
+
+		match rx.await {
+			Ok(Ok(version)) =>
+				version >= RuntimeApiRequest::MULTIPLE_CANDIDATE_APPROVALS_RUNTIME_REQUIREMENT,
+			Ok(Err(error)) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?leaf,
+					?error,
+					"Failed to fetch ParachainHost runtime api version"
+				);
+				false
+			},
+			Err(_) => false,
+		}
+	}
+
 	// We use fatal result rather than result here. Reason being, We for example increase
 	// spam slots in this function. If then the import fails for some non fatal and
 	// unrelated reason, we should likely actually decrement previously incremented spam
@@ -713,6 +788,26 @@ impl Initialized {
 			return Ok(ImportStatementsResult::InvalidImport)
 		}
 
+		// Votes we import end up in dispute messages and in the paras inherent, so don't import
+		// any kind of vote the runtime doesn't understand.
+		let statements = if statements
+			.iter()
+			.any(|(statement, _)| is_multiple_candidate_approval(statement.statement())) &&
+			!self.supports_multiple_candidate_approvals(ctx).await
+		{
+			gum::debug!(
+				target: LOG_TARGET,
+				?session,
+				"Ignoring approval votes of multiple candidates, not supported by the runtime",
+			);
+			statements
+				.into_iter()
+				.filter(|(statement, _)| !is_multiple_candidate_approval(statement.statement()))
+				.collect()
+		} else {
+			statements
+		};
+
 		let env = match CandidateEnvironment::new(
 			&self.keystore,
 			&self.rolling_session_window,
@@ -807,12 +902,17 @@ impl Initialized {
 						);
 						intermediate_result
 					},
-					Ok(votes) => {
+					Ok(mut votes) => {
 						gum::trace!(
 							target: LOG_TARGET,
 							count = votes.len(),
 							"Successfully received approval votes."
 						);
+						if votes.values().any(|(candidate_hashes, _)| candidate_hashes.len() > 1) &&
+							!self.supports_multiple_candidate_approvals(ctx).await
+						{
+							votes.retain(|_, (candidate_hashes, _)| candidate_hashes.len() == 1);
+						}
 						intermediate_result.import_approval_votes(&env, votes, now)
 					},
 				}
@@ -912,7 +1012,8 @@ impl Initialized {
 				.queue_participation(
 					ctx,
 					priority,
-					ParticipationRequest::new(new_state.candidate_receipt().clone(), session),
+					ParticipationRequest::new(new_state.candidate_receipt().clone(), session)
+						.with_votes(new_state.votes().voted_indices().len() as u32),
 				)
 				.await;
 			log_error(r)?;
@@ -937,7 +1038,7 @@ impl Initialized {
 		// Also send any already existing approval vote on new disputes:
 		if import_result.is_freshly_disputed() {
 			let our_approval_votes = new_state.own_approval_votes().into_iter().flatten();
-			for (validator_index, sig) in our_approval_votes {
+			for (validator_index, statement, sig) in our_approval_votes {
 				let pub_key = match env.validators().get(validator_index) {
 					None => {
 						gum::error!(
@@ -951,7 +1052,7 @@ impl Initialized {
 					Some(k) => k,
 				};
 				let statement = SignedDisputeStatement::new_unchecked_from_trusted_source(
-					DisputeStatement::Valid(ValidDisputeStatementKind::ApprovalChecking),
+					statement.clone(),
 					candidate_hash,
 					session,
 					pub_key.clone(),
@@ -1070,6 +1171,9 @@ impl Initialized {
 			);
 			self.metrics.on_concluded_invalid();
 		}
+		if import_result.is_freshly_concluded() {
+			self.write_audit_log(session, &env, new_state);
+		}
 
 		// Only write when votes have changed.
 		if let Some(votes) = import_result.into_updated_votes() {
@@ -1079,6 +1183,32 @@ impl Initialized {
 		Ok(ImportStatementsResult::ValidImport)
 	}
 
+	/// Append the record of a freshly concluded dispute to the audit log, if any.
+	fn write_audit_log(
+		&self,
+		session: SessionIndex,
+		env: &CandidateEnvironment,
+		state: &CandidateVoteState<CandidateVotes>,
+	) {
+		let (audit_log, status) = match (&self.audit_log, state.dispute_status()) {
+			(Some(audit_log), Some(status)) => (audit_log, *status),
+			_ => return,
+		};
+		let is_local = |index| env.controlled_indices().contains(&index);
+		let record = match ConcludedDispute::new(
+			session,
+			state.votes(),
+			status,
+			env.validators(),
+			&is_local,
+			&audit_log.on_chain_backers(session, state.votes().candidate_receipt.hash()),
+		) {
+			Some(record) => record,
+			None => return,
+		};
+		audit_log.write(record);
+	}
+
 	async fn issue_local_statement<Context>(
 		&mut self,
 		ctx: &mut Context,
@@ -1256,6 +1386,13 @@ impl MaybeCandidateReceipt {
 	}
 }
 
+fn is_multiple_candidate_approval(statement: &DisputeStatement) -> bool {
+	matches!(
+		statement,
+		DisputeStatement::Valid(ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(_))
+	)
+}
+
 /// Determine the best block and its block number.
 /// Assumes `block_descriptions` are sorted from the one
 /// with the lowest `BlockNumber` to the highest.
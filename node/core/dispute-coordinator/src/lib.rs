@@ -42,7 +42,9 @@ use polkadot_node_subsystem_util::{
 	database::Database,
 	rolling_session_window::{DatabaseParams, RollingSessionWindow},
 };
-use polkadot_primitives::{DisputeStatement, ScrapedOnChainVotes, SessionInfo, ValidatorIndex};
+use polkadot_primitives::{
+	vstaging::DisputeStatement, ScrapedOnChainVotes, SessionInfo, ValidatorIndex,
+};
 
 use crate::{
 	error::{FatalResult, JfyiError, Result},
@@ -104,6 +106,10 @@ mod metrics;
 /// Status tracking of disputes (`DisputeStatus`).
 mod status;
 
+/// Audit log of concluded disputes, with its records being verifiable offline.
+pub mod audit_log;
+pub use audit_log::AuditLogConfig;
+
 use crate::status::Clock;
 
 #[cfg(test)]
@@ -120,12 +126,14 @@ pub struct DisputeCoordinatorSubsystem {
 }
 
 /// Configuration for the dispute coordinator subsystem.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Config {
 	/// The data column in the store to use for dispute data.
 	pub col_dispute_data: u32,
 	/// The data column in the store to use for session data.
 	pub col_session_data: u32,
+	/// Where to append records of concluded disputes to, if enabled.
+	pub audit_log: Option<AuditLogConfig>,
 }
 
 impl Config {
@@ -352,7 +360,8 @@ impl DisputeCoordinatorSubsystem {
 						ParticipationRequest::new(
 							vote_state.votes().candidate_receipt.clone(),
 							session,
-						),
+						)
+						.with_votes(vote_state.votes().voted_indices().len() as u32),
 					));
 				}
 				// Else make sure our own vote is distributed:
@@ -539,7 +548,7 @@ pub fn make_dispute_message(
 				.next()
 				.ok_or(DisputeMessageCreationError::NoOppositeVote)?;
 			let other_vote = SignedDisputeStatement::new_checked(
-				DisputeStatement::Valid(*statement_kind),
+				DisputeStatement::Valid(statement_kind.clone()),
 				*our_vote.candidate_hash(),
 				our_vote.session_index(),
 				validators
@@ -14,9 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashSet;
-#[cfg(test)]
-use std::time::Duration;
+use std::{
+	collections::HashSet,
+	num::NonZeroUsize,
+	time::{Duration, Instant},
+};
 
 use futures::{
 	channel::{mpsc, oneshot},
@@ -24,6 +26,7 @@ use futures::{
 };
 #[cfg(test)]
 use futures_timer::Delay;
+use lru::LruCache;
 
 use polkadot_node_primitives::{ValidationResult, APPROVAL_EXECUTION_TIMEOUT};
 use polkadot_node_subsystem::{
@@ -31,7 +34,9 @@ use polkadot_node_subsystem::{
 	overseer, ActiveLeavesUpdate, RecoveryError,
 };
 use polkadot_node_subsystem_util::runtime::get_validation_code_by_hash;
-use polkadot_primitives::{BlockNumber, CandidateHash, CandidateReceipt, Hash, SessionIndex};
+use polkadot_primitives::{
+	BlockNumber, CandidateHash, CandidateReceipt, Hash, SessionIndex, ValidationCodeHash,
+};
 
 use crate::LOG_TARGET;
 
@@ -46,16 +51,47 @@ mod queues;
 use queues::Queues;
 pub use queues::{ParticipationPriority, ParticipationRequest, QueueError};
 
-/// How many participation processes do we want to run in parallel the most.
+/// How many participation processes do we want to run in parallel, as long as we have no
+/// measurements of the PVF execution time.
 ///
 /// This should be a relatively low value, while we might have a speedup once we fetched the data,
 /// due to multi-core architectures, but the fetching itself can not be improved by parallel
 /// requests. This means that higher numbers make it harder for a single dispute to resolve fast.
 #[cfg(not(test))]
-const MAX_PARALLEL_PARTICIPATIONS: usize = 3;
+const DEFAULT_PARALLEL_PARTICIPATIONS: usize = 3;
+#[cfg(test)]
+const DEFAULT_PARALLEL_PARTICIPATIONS: usize = 1;
+
+/// How many participation processes do we want to run in parallel the least, no matter how slow
+/// PVF execution is.
+const MIN_PARALLEL_PARTICIPATIONS: usize = 1;
+
+/// How many participation processes do we want to run in parallel the most, no matter how fast
+/// PVF execution is.
+#[cfg(not(test))]
+const MAX_PARALLEL_PARTICIPATIONS: usize = 6;
 #[cfg(test)]
 pub(crate) const MAX_PARALLEL_PARTICIPATIONS: usize = 1;
 
+/// The PVF execution time, i.e. the round-trip of a `ValidateFromExhaustive` request, for which
+/// `DEFAULT_PARALLEL_PARTICIPATIONS` are run in parallel.
+///
+/// Faster executions allow for proportionally more, slower executions for proportionally less
+/// parallel participations.
+const EXPECTED_EXECUTION_TIME: Duration = Duration::from_secs(2);
+
+/// The weight of a new measurement in the moving average of the PVF execution time, in percent.
+const EXECUTION_TIME_SMOOTHING_PERCENT: u32 = 20;
+
+/// How many validation codes to remember as already executed.
+///
+/// The execution time is only measured for validation codes that were executed before, see
+/// `ParticipationCapacity::note_execution_time`.
+const EXECUTED_CODE_CACHE_SIZE: NonZeroUsize = match NonZeroUsize::new(64) {
+	Some(cap) => cap,
+	None => panic!("Executed code cache size must be non-zero"),
+};
+
 /// Keep track of disputes we need to participate in.
 ///
 /// - Prioritize and queue participations
@@ -69,11 +105,17 @@ pub struct Participation {
 	worker_sender: WorkerMessageSender,
 	/// Some recent block for retrieving validation code from chain.
 	recent_block: Option<(BlockNumber, Hash)>,
+	/// How many participations may run in parallel.
+	capacity: ParticipationCapacity,
 }
 
 /// Message from worker tasks.
 #[derive(Debug)]
-pub struct WorkerMessage(ParticipationStatement);
+pub struct WorkerMessage {
+	statement: ParticipationStatement,
+	/// How long the PVF execution took, if the candidate got validated.
+	execution_time: Option<Duration>,
+}
 
 /// Sender use by worker tasks.
 pub type WorkerMessageSender = mpsc::Sender<WorkerMessage>;
@@ -119,10 +161,81 @@ impl ParticipationOutcome {
 }
 
 impl WorkerMessage {
-	fn from_request(req: ParticipationRequest, outcome: ParticipationOutcome) -> Self {
+	fn from_request(
+		req: ParticipationRequest,
+		outcome: ParticipationOutcome,
+		execution_time: Option<Duration>,
+	) -> Self {
 		let session = req.session();
 		let (candidate_hash, candidate_receipt) = req.into_candidate_info();
-		Self(ParticipationStatement { session, candidate_hash, candidate_receipt, outcome })
+		Self {
+			statement: ParticipationStatement {
+				session,
+				candidate_hash,
+				candidate_receipt,
+				outcome,
+			},
+			execution_time,
+		}
+	}
+}
+
+/// Derives the number of participations to run in parallel from the measured PVF execution time.
+///
+/// The faster PVFs are executed, the more participations we can afford to run in parallel,
+/// which helps working through the queues during dispute storms.
+struct ParticipationCapacity {
+	/// How many participations to run in parallel without any measurements.
+	default: usize,
+	/// How many participations to run in parallel the most.
+	max: usize,
+	/// Moving average of the PVF execution time of participations.
+	average_execution_time: Option<Duration>,
+	/// Validation codes which were already executed, thus have their PVF artifact prepared.
+	executed_code: LruCache<ValidationCodeHash, ()>,
+}
+
+impl ParticipationCapacity {
+	fn new(default: usize, max: usize) -> Self {
+		Self {
+			default,
+			max,
+			average_execution_time: None,
+			executed_code: LruCache::new(EXECUTED_CODE_CACHE_SIZE),
+		}
+	}
+
+	/// Take the execution time of a finished participation into account.
+	///
+	/// The first execution of a validation code also waits for the PVF to be queued for
+	/// preparation and to be compiled, which says nothing about the time further executions take,
+	/// so it is not taken into account.
+	fn note_execution_time(
+		&mut self,
+		validation_code_hash: ValidationCodeHash,
+		execution_time: Duration,
+	) {
+		if self.executed_code.put(validation_code_hash, ()).is_none() {
+			return
+		}
+		self.average_execution_time = Some(match self.average_execution_time {
+			None => execution_time,
+			Some(average) =>
+				(average * (100 - EXECUTION_TIME_SMOOTHING_PERCENT) +
+					execution_time * EXECUTION_TIME_SMOOTHING_PERCENT) /
+					100,
+		});
+	}
+
+	/// The number of participations that may run in parallel.
+	fn get(&self) -> usize {
+		let average = match self.average_execution_time {
+			None => return self.default,
+			Some(average) => average.max(Duration::from_millis(1)),
+		};
+		let scaled =
+			EXPECTED_EXECUTION_TIME.as_millis() * self.default as u128 / average.as_millis();
+		(scaled as usize).clamp(MIN_PARALLEL_PARTICIPATIONS, self.max)
 	}
 }
 
@@ -139,6 +252,10 @@ impl Participation {
 			queue: Queues::new(),
 			worker_sender: sender,
 			recent_block: None,
+			capacity: ParticipationCapacity::new(
+				DEFAULT_PARALLEL_PARTICIPATIONS,
+				MAX_PARALLEL_PARTICIPATIONS,
+			),
 		}
 	}
 
@@ -160,7 +277,7 @@ impl Participation {
 		}
 		// Available capacity - participate right away (if we already have a recent block):
 		if let Some((_, h)) = self.recent_block {
-			if self.running_participations.len() < MAX_PARALLEL_PARTICIPATIONS {
+			if self.running_participations.len() < self.capacity.get() {
 				self.fork_participation(ctx, req, h)?;
 				return Ok(())
 			}
@@ -183,8 +300,14 @@ impl Participation {
 		ctx: &mut Context,
 		msg: WorkerMessage,
 	) -> FatalResult<ParticipationStatement> {
-		let WorkerMessage(statement) = msg;
+		let WorkerMessage { statement, execution_time } = msg;
 		self.running_participations.remove(&statement.candidate_hash);
+		if let Some(execution_time) = execution_time {
+			self.capacity.note_execution_time(
+				statement.candidate_receipt.descriptor.validation_code_hash,
+				execution_time,
+			);
+		}
 		let recent_block = self.recent_block.expect("We never ever reset recent_block to `None` and we already received a result, so it must have been set before. qed.");
 		self.dequeue_until_capacity(ctx, recent_block.1).await?;
 		Ok(statement)
@@ -215,26 +338,35 @@ impl Participation {
 		Ok(())
 	}
 
+	/// Number of queued participation requests as `(priority, best_effort)`.
+	pub fn queue_lengths(&self) -> (usize, usize) {
+		self.queue.lengths()
+	}
+
+	/// Number of participations currently running.
+	pub fn running_participations(&self) -> usize {
+		self.running_participations.len()
+	}
+
 	/// Moving any request concerning the given candidates from best-effort to
 	/// priority, ignoring any candidates that don't have any queued participation requests.
-	pub async fn bump_to_priority_for_candidates<Context>(
+	pub fn bump_to_priority_for_candidates(
 		&mut self,
-		ctx: &mut Context,
 		included_receipts: &Vec<CandidateReceipt>,
 	) -> Result<()> {
 		for receipt in included_receipts {
-			self.queue.prioritize_if_present(ctx.sender(), receipt).await?;
+			self.queue.prioritize_if_present(receipt)?;
 		}
 		Ok(())
 	}
 
-	/// Dequeue until `MAX_PARALLEL_PARTICIPATIONS` is reached.
+	/// Dequeue until the participation capacity is reached.
 	async fn dequeue_until_capacity<Context>(
 		&mut self,
 		ctx: &mut Context,
 		recent_head: Hash,
 	) -> FatalResult<()> {
-		while self.running_participations.len() < MAX_PARALLEL_PARTICIPATIONS {
+		while self.running_participations.len() < self.capacity.get() {
 			if let Some(req) = self.queue.dequeue() {
 				self.fork_participation(ctx, req, recent_head)?;
 			} else {
@@ -342,6 +474,7 @@ async fn participate(
 	// be run outside of backing and therefore should be subject to the
 	// same level of leeway.
 	let (validation_tx, validation_rx) = oneshot::channel();
+	let validation_started = Instant::now();
 	sender
 		.send_message(CandidateValidationMessage::ValidateFromExhaustive(
 			available_data.validation_data,
@@ -384,10 +517,24 @@ async fn participate(
 				invalid,
 			);
 
-			send_result(&mut result_sender, req, ParticipationOutcome::Invalid).await;
+			let execution_time = validation_started.elapsed();
+			send_validation_result(
+				&mut result_sender,
+				req,
+				ParticipationOutcome::Invalid,
+				execution_time,
+			)
+			.await;
 		},
 		Ok(Ok(ValidationResult::Valid(_, _))) => {
-			send_result(&mut result_sender, req, ParticipationOutcome::Valid).await;
+			let execution_time = validation_started.elapsed();
+			send_validation_result(
+				&mut result_sender,
+				req,
+				ParticipationOutcome::Valid,
+				execution_time,
+			)
+			.await;
 		},
 	}
 }
@@ -398,7 +545,23 @@ async fn send_result(
 	req: ParticipationRequest,
 	outcome: ParticipationOutcome,
 ) {
-	if let Err(err) = sender.feed(WorkerMessage::from_request(req, outcome)).await {
+	feed_worker_message(sender, WorkerMessage::from_request(req, outcome, None)).await
+}
+
+/// Helper function for sending the result of a finished PVF execution back, together with the
+/// time the execution took.
+async fn send_validation_result(
+	sender: &mut WorkerMessageSender,
+	req: ParticipationRequest,
+	outcome: ParticipationOutcome,
+	execution_time: Duration,
+) {
+	feed_worker_message(sender, WorkerMessage::from_request(req, outcome, Some(execution_time)))
+		.await
+}
+
+async fn feed_worker_message(sender: &mut WorkerMessageSender, msg: WorkerMessage) {
+	if let Err(err) = sender.feed(msg).await {
 		gum::error!(
 			target: LOG_TARGET,
 			?err,
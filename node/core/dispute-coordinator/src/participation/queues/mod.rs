@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::{cmp::Ordering, collections::BTreeMap};
+use std::{
+	cmp::Ordering,
+	collections::{BTreeMap, HashMap},
+};
 
 use futures::channel::oneshot;
 use polkadot_node_subsystem::{messages::ChainApiMessage, overseer};
@@ -56,6 +59,12 @@ pub struct Queues {
 
 	/// Priority queue.
 	priority: BTreeMap<CandidateComparator, ParticipationRequest>,
+
+	/// The comparators of all queued candidates in either queue.
+	///
+	/// The comparator of a candidate changes with the number of known votes, this allows us to
+	/// find the entry of a candidate that got queued again with more votes.
+	comparators: HashMap<CandidateHash, CandidateComparator>,
 }
 
 /// A dispute participation request that can be queued.
@@ -64,6 +73,7 @@ pub struct ParticipationRequest {
 	candidate_hash: CandidateHash,
 	candidate_receipt: CandidateReceipt,
 	session: SessionIndex,
+	votes: u32,
 }
 
 /// Whether a `ParticipationRequest` should be put on best-effort or the priority queue.
@@ -108,7 +118,16 @@ pub enum QueueError {
 impl ParticipationRequest {
 	/// Create a new `ParticipationRequest` to be queued.
 	pub fn new(candidate_receipt: CandidateReceipt, session: SessionIndex) -> Self {
-		Self { candidate_hash: candidate_receipt.hash(), candidate_receipt, session }
+		Self { candidate_hash: candidate_receipt.hash(), candidate_receipt, session, votes: 0 }
+	}
+
+	/// Set the number of votes already known for the candidate.
+	///
+	/// Candidates with more votes are closer to a conclusion and are thus participated in first,
+	/// among candidates with the same relay parent block number.
+	pub fn with_votes(mut self, votes: u32) -> Self {
+		self.votes = votes;
+		self
 	}
 
 	pub fn candidate_receipt(&'_ self) -> &'_ CandidateReceipt {
@@ -120,6 +139,9 @@ impl ParticipationRequest {
 	pub fn session(&self) -> SessionIndex {
 		self.session
 	}
+	pub fn votes(&self) -> u32 {
+		self.votes
+	}
 	pub fn into_candidate_info(self) -> (CandidateHash, CandidateReceipt) {
 		let Self { candidate_hash, candidate_receipt, .. } = self;
 		(candidate_hash, candidate_receipt)
@@ -129,7 +151,11 @@ impl ParticipationRequest {
 impl Queues {
 	/// Create new `Queues`.
 	pub fn new() -> Self {
-		Self { best_effort: BTreeMap::new(), priority: BTreeMap::new() }
+		Self {
+			best_effort: BTreeMap::new(),
+			priority: BTreeMap::new(),
+			comparators: HashMap::new(),
+		}
 	}
 
 	/// Will put message in queue, either priority or best effort depending on priority.
@@ -144,7 +170,8 @@ impl Queues {
 		priority: ParticipationPriority,
 		req: ParticipationRequest,
 	) -> Result<()> {
-		let comparator = CandidateComparator::new(sender, &req.candidate_receipt).await?;
+		let comparator =
+			CandidateComparator::new(sender, &req.candidate_receipt, req.votes).await?;
 
 		self.queue_with_comparator(comparator, priority, req)?;
 		Ok(())
@@ -159,27 +186,29 @@ impl Queues {
 		self.pop_best_effort().map(|d| d.1)
 	}
 
+	/// Number of queued requests as `(priority, best_effort)`.
+	pub fn lengths(&self) -> (usize, usize) {
+		(self.priority.len(), self.best_effort.len())
+	}
+
 	/// Reprioritizes any participation requests pertaining to the
 	/// passed candidates from best effort to priority.
-	pub async fn prioritize_if_present(
-		&mut self,
-		sender: &mut impl overseer::DisputeCoordinatorSenderTrait,
-		receipt: &CandidateReceipt,
-	) -> Result<()> {
-		let comparator = CandidateComparator::new(sender, receipt).await?;
-		self.prioritize_with_comparator(comparator)?;
+	pub fn prioritize_if_present(&mut self, receipt: &CandidateReceipt) -> Result<()> {
+		self.prioritize_candidate(&receipt.hash())?;
 		Ok(())
 	}
 
-	fn prioritize_with_comparator(
+	fn prioritize_candidate(
 		&mut self,
-		comparator: CandidateComparator,
+		candidate_hash: &CandidateHash,
 	) -> std::result::Result<(), QueueError> {
 		if self.priority.len() >= PRIORITY_QUEUE_SIZE {
 			return Err(QueueError::PriorityFull)
 		}
-		if let Some(request) = self.best_effort.remove(&comparator) {
-			self.priority.insert(comparator, request);
+		if let Some(comparator) = self.comparators.get(candidate_hash) {
+			if let Some(request) = self.best_effort.remove(comparator) {
+				self.priority.insert(*comparator, request);
+			}
 		}
 		Ok(())
 	}
@@ -190,35 +219,61 @@ impl Queues {
 		priority: ParticipationPriority,
 		req: ParticipationRequest,
 	) -> std::result::Result<(), QueueError> {
+		let previous = self.comparators.get(&comparator.candidate_hash).copied();
 		if priority.is_priority() {
-			if self.priority.len() >= PRIORITY_QUEUE_SIZE {
+			// Replacing an entry does not grow the queue:
+			if self.priority.len() >= PRIORITY_QUEUE_SIZE &&
+				!previous.map_or(false, |previous| self.priority.contains_key(&previous))
+			{
 				return Err(QueueError::PriorityFull)
 			}
-			// Remove any best effort entry:
-			self.best_effort.remove(&comparator);
+			// Remove any previous entry, it might have been queued with less votes:
+			if let Some(previous) = previous {
+				self.best_effort.remove(&previous);
+				self.priority.remove(&previous);
+			}
 			self.priority.insert(comparator, req);
 		} else {
-			if self.priority.contains_key(&comparator) {
+			if previous.map_or(false, |previous| self.priority.contains_key(&previous)) {
 				// The candidate is already in priority queue - don't
 				// add in in best effort too.
 				return Ok(())
 			}
-			if self.best_effort.len() >= BEST_EFFORT_QUEUE_SIZE {
+			if self.best_effort.len() >= BEST_EFFORT_QUEUE_SIZE &&
+				!previous.map_or(false, |previous| self.best_effort.contains_key(&previous))
+			{
 				return Err(QueueError::BestEffortFull)
 			}
+			if let Some(previous) = previous {
+				self.best_effort.remove(&previous);
+			}
 			self.best_effort.insert(comparator, req);
 		}
+		self.comparators.insert(comparator.candidate_hash, comparator);
 		Ok(())
 	}
 
 	/// Get best from the best effort queue.
 	fn pop_best_effort(&mut self) -> Option<(CandidateComparator, ParticipationRequest)> {
-		return Self::pop_impl(&mut self.best_effort)
+		let popped = Self::pop_impl(&mut self.best_effort);
+		self.forget_comparator(popped)
 	}
 
 	/// Get best priority queue entry.
 	fn pop_priority(&mut self) -> Option<(CandidateComparator, ParticipationRequest)> {
-		return Self::pop_impl(&mut self.priority)
+		let popped = Self::pop_impl(&mut self.priority);
+		self.forget_comparator(popped)
+	}
+
+	/// Remove the comparator of a dequeued entry from the index.
+	fn forget_comparator(
+		&mut self,
+		popped: Option<(CandidateComparator, ParticipationRequest)>,
+	) -> Option<(CandidateComparator, ParticipationRequest)> {
+		if let Some((comparator, _)) = &popped {
+			self.comparators.remove(&comparator.candidate_hash);
+		}
+		popped
 	}
 
 	// `pop_best_effort` and `pop_priority` do the same but on different `BTreeMap`s. This function has
@@ -242,7 +297,8 @@ impl Queues {
 /// `Comparator` for ordering of disputes for candidates.
 ///
 /// This `comparator` makes it possible to order disputes based on age and to ensure some fairness
-/// between chains in case of equally old disputes.
+/// between chains in case of equally old disputes. Among equally old disputes, the ones with more
+/// votes are ordered first, as they need fewer additional votes to conclude.
 ///
 /// Objective ordering between nodes is important in case of lots disputes, so nodes will pull in
 /// the same direction and work on resolving the same disputes first. This ensures that we will
@@ -273,6 +329,12 @@ struct CandidateComparator {
 	/// that is not stable. If a new fork appears after the fact, we would start ordering the same
 	/// candidate differently, which would result in the same candidate getting queued twice.
 	relay_parent_block_number: Option<BlockNumber>,
+	/// The number of votes known for the candidate at the time it got queued.
+	///
+	/// Note: Nodes might have seen a slightly different number of votes, so this is only used to
+	/// order candidates with the same relay parent block number, which keeps the ordering mostly
+	/// objective.
+	votes: u32,
 	/// By adding the `CandidateHash`, we can guarantee a unique ordering across candidates with the
 	/// same relay parent block number. Candidates without `relay_parent_block_number` are ordered by
 	/// the `candidate_hash` (and treated with the lowest priority, as already mentioned).
@@ -285,20 +347,21 @@ impl CandidateComparator {
 	/// Useful for testing.
 	#[cfg(test)]
 	pub fn new_dummy(block_number: Option<BlockNumber>, candidate_hash: CandidateHash) -> Self {
-		Self { relay_parent_block_number: block_number, candidate_hash }
+		Self { relay_parent_block_number: block_number, votes: 0, candidate_hash }
 	}
 
 	/// Create a candidate comparator for a given candidate.
 	///
 	/// Returns:
-	///	- `Ok(CandidateComparator{Some(relay_parent_block_number), candidate_hash})` when the
-	/// 	relay parent can be obtained. This is the happy case.
-	/// - `Ok(CandidateComparator{None, candidate_hash})` in case the candidate's relay parent
-	/// 	can't be obtained.
+	///	- `Ok(CandidateComparator{Some(relay_parent_block_number), votes, candidate_hash})` when
+	/// 	the relay parent can be obtained. This is the happy case.
+	/// - `Ok(CandidateComparator{None, votes, candidate_hash})` in case the candidate's relay
+	/// 	parent can't be obtained.
 	///	- `FatalError` in case the chain API call fails with an unexpected error.
 	pub async fn new(
 		sender: &mut impl overseer::DisputeCoordinatorSenderTrait,
 		candidate: &CandidateReceipt,
+		votes: u32,
 	) -> FatalResult<Self> {
 		let candidate_hash = candidate.hash();
 		let n = get_block_number(sender, candidate.descriptor().relay_parent).await?;
@@ -312,7 +375,7 @@ impl CandidateComparator {
 			);
 		}
 
-		Ok(CandidateComparator { relay_parent_block_number: n, candidate_hash })
+		Ok(CandidateComparator { relay_parent_block_number: n, votes, candidate_hash })
 	}
 }
 
@@ -339,8 +402,12 @@ impl Ord for CandidateComparator {
 			},
 			(Some(self_relay_parent_block_num), Some(other_relay_parent_block_num)) => {
 				match self_relay_parent_block_num.cmp(&other_relay_parent_block_num) {
-					// if the relay parent is the same for both -> compare hashes
-					Ordering::Equal => self.candidate_hash.cmp(&other.candidate_hash),
+					// if the relay parent is the same for both -> more votes first, then compare
+					// hashes
+					Ordering::Equal => other
+						.votes
+						.cmp(&self.votes)
+						.then_with(|| self.candidate_hash.cmp(&other.candidate_hash)),
 					// if not - return the result from comparing the relay parent block numbers
 					o => return o,
 				}
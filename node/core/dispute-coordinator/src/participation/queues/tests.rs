@@ -197,3 +197,101 @@ fn candidate_is_only_dequeued_once() {
 	assert_eq!(queue.dequeue(), Some(req1));
 	assert_eq!(queue.dequeue(), None);
 }
+
+/// Candidates with the same relay parent block number are ordered by the number of known votes.
+#[test]
+fn more_votes_are_dequeued_first() {
+	let mut queue = Queues::new();
+	let req_few_votes = make_participation_request(Hash::repeat_byte(0x01)).with_votes(2);
+	let req_many_votes = make_participation_request(Hash::repeat_byte(0x02)).with_votes(5);
+	let req_older = make_participation_request(Hash::repeat_byte(0x03)).with_votes(1);
+
+	for (req, relay_parent) in
+		[(&req_few_votes, Some(2)), (&req_many_votes, Some(2)), (&req_older, Some(1))]
+	{
+		let mut comparator = make_dummy_comparator(req, relay_parent);
+		comparator.votes = req.votes();
+		queue
+			.queue_with_comparator(comparator, ParticipationPriority::BestEffort, req.clone())
+			.unwrap();
+	}
+
+	assert_eq!(queue.dequeue(), Some(req_older));
+	assert_eq!(queue.dequeue(), Some(req_many_votes));
+	assert_eq!(queue.dequeue(), Some(req_few_votes));
+	assert_matches!(queue.dequeue(), None);
+}
+
+/// Queuing a candidate again with more votes replaces the previous entry.
+#[test]
+fn requeue_with_more_votes_replaces_entry() {
+	let mut queue = Queues::new();
+	let req = make_participation_request(Hash::repeat_byte(0x01));
+	let other = make_participation_request(Hash::repeat_byte(0x02)).with_votes(3);
+
+	let mut other_comparator = make_dummy_comparator(&other, Some(1));
+	other_comparator.votes = 3;
+	queue
+		.queue_with_comparator(other_comparator, ParticipationPriority::BestEffort, other.clone())
+		.unwrap();
+	queue
+		.queue_with_comparator(
+			make_dummy_comparator(&req, Some(1)),
+			ParticipationPriority::BestEffort,
+			req.clone(),
+		)
+		.unwrap();
+
+	// Queue the same candidate again, now with more votes than `other`:
+	let req = req.with_votes(4);
+	let mut comparator = make_dummy_comparator(&req, Some(1));
+	comparator.votes = 4;
+	queue
+		.queue_with_comparator(comparator, ParticipationPriority::BestEffort, req.clone())
+		.unwrap();
+
+	// It can still be moved to the priority queue by candidate hash:
+	queue.prioritize_candidate(req.candidate_hash()).unwrap();
+
+	assert_eq!(queue.dequeue(), Some(req));
+	assert_eq!(queue.dequeue(), Some(other));
+	assert_matches!(queue.dequeue(), None);
+}
+
+/// Queuing a candidate again must succeed even if its queue is full, as the entry is replaced.
+#[test]
+fn requeue_succeeds_on_full_queue() {
+	let mut queue = Queues::new();
+	let reqs = (1..=5u8)
+		.map(|i| make_participation_request(Hash::repeat_byte(i)))
+		.collect::<Vec<_>>();
+	let queue_req = |queue: &mut Queues, n: usize, priority| {
+		queue.queue_with_comparator(
+			make_dummy_comparator(&reqs[n], Some(n as BlockNumber)),
+			priority,
+			reqs[n].clone(),
+		)
+	};
+
+	queue_req(&mut queue, 0, ParticipationPriority::Priority).unwrap();
+	queue_req(&mut queue, 1, ParticipationPriority::Priority).unwrap();
+	queue_req(&mut queue, 2, ParticipationPriority::BestEffort).unwrap();
+	queue_req(&mut queue, 3, ParticipationPriority::BestEffort).unwrap();
+	queue_req(&mut queue, 4, ParticipationPriority::BestEffort).unwrap();
+	assert_eq!(queue.lengths(), (2, 3));
+
+	// Both queues are full, still the same candidates can be queued again:
+	queue_req(&mut queue, 1, ParticipationPriority::Priority).unwrap();
+	queue_req(&mut queue, 3, ParticipationPriority::BestEffort).unwrap();
+	assert_eq!(queue.lengths(), (2, 3));
+	// But no new candidates:
+	assert_matches!(
+		queue_req(&mut queue, 2, ParticipationPriority::Priority),
+		Err(QueueError::PriorityFull)
+	);
+
+	for req in reqs {
+		assert_eq!(queue.dequeue(), Some(req));
+	}
+	assert_eq!(queue.dequeue(), None);
+}
@@ -544,3 +544,43 @@ fn cast_valid_vote_if_validation_passes() {
 		);
 	})
 }
+
+#[test]
+fn capacity_scales_with_execution_time() {
+	let code = ValidationCodeHash::from(Hash::repeat_byte(1));
+	let mut capacity = ParticipationCapacity::new(3, 6);
+	assert_eq!(capacity.get(), 3);
+
+	// The first execution of a validation code includes compilation and is not taken into
+	// account:
+	capacity.note_execution_time(code, EXPECTED_EXECUTION_TIME * 100);
+	assert_eq!(capacity.get(), 3);
+
+	// Fast executions allow for more parallel ones, up to the maximum:
+	capacity.note_execution_time(code, EXPECTED_EXECUTION_TIME / 2);
+	assert_eq!(capacity.get(), 6);
+	capacity.note_execution_time(code, Duration::ZERO);
+	assert_eq!(capacity.get(), 6);
+
+	// Slow executions allow for less parallel ones, down to the minimum:
+	let mut capacity = ParticipationCapacity::new(3, 6);
+	capacity.note_execution_time(code, Duration::ZERO);
+	capacity.note_execution_time(code, EXPECTED_EXECUTION_TIME * 3);
+	assert_eq!(capacity.get(), 1);
+	capacity.note_execution_time(code, EXPECTED_EXECUTION_TIME * 100);
+	assert_eq!(capacity.get(), MIN_PARALLEL_PARTICIPATIONS);
+}
+
+#[test]
+fn capacity_uses_moving_average_of_execution_time() {
+	let code = ValidationCodeHash::from(Hash::repeat_byte(1));
+	let mut capacity = ParticipationCapacity::new(3, 6);
+	capacity.note_execution_time(code, Duration::ZERO);
+	capacity.note_execution_time(code, EXPECTED_EXECUTION_TIME);
+	assert_eq!(capacity.get(), 3);
+
+	// A single outlier only moves the average by `EXECUTION_TIME_SMOOTHING_PERCENT`:
+	capacity.note_execution_time(code, EXPECTED_EXECUTION_TIME * 6);
+	assert_eq!(capacity.average_execution_time, Some(EXPECTED_EXECUTION_TIME * 2),);
+	assert_eq!(capacity.get(), 1);
+}
@@ -127,6 +127,19 @@ impl SpamSlots {
 			}
 		}
 	}
+
+	/// All occupied spam slots as `(session, validator, count)`.
+	pub fn occupied(&self) -> impl Iterator<Item = (SessionIndex, ValidatorIndex, SpamCount)> + '_ {
+		self.slots
+			.iter()
+			.map(|((session, validator), count)| (*session, *validator, *count))
+	}
+
+	/// How many unconfirmed disputes a validator is allowed to import per session.
+	pub fn max_spam_votes() -> SpamCount {
+		MAX_SPAM_VOTES
+	}
+
 	/// Prune all spam slots for sessions older than the given index.
 	pub fn prune_old(&mut self, oldest_index: SessionIndex) {
 		self.unconfirmed.retain(|(session, _), _| *session >= oldest_index);
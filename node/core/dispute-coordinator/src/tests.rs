@@ -61,10 +61,11 @@ use polkadot_node_subsystem_test_helpers::{
 	make_buffered_subsystem_context, TestSubsystemContextHandle,
 };
 use polkadot_primitives::{
+	vstaging::{ApprovalVoteMultipleCandidates, DisputeStatement, ValidDisputeStatementKind},
 	ApprovalVote, BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash,
-	CandidateReceipt, CoreIndex, DisputeStatement, GroupIndex, Hash, HeadData, Header, IndexedVec,
+	CandidateReceipt, CoreIndex, GroupIndex, Hash, HeadData, Header, IndexedVec,
 	MultiDisputeStatementSet, ScrapedOnChainVotes, SessionIndex, SessionInfo, SigningContext,
-	ValidDisputeStatementKind, ValidatorId, ValidatorIndex, ValidatorSignature,
+	ValidatorId, ValidatorIndex, ValidatorSignature,
 };
 
 use crate::{
@@ -213,7 +214,7 @@ impl Default for TestState {
 		let db = kvdb_memorydb::create(1);
 		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[]);
 		let db = Arc::new(db);
-		let config = Config { col_dispute_data: 0, col_session_data: 1 };
+		let config = Config { col_dispute_data: 0, col_session_data: 1, audit_log: None };
 
 		let genesis_header = Header {
 			parent_hash: Hash::zero(),
@@ -583,6 +584,24 @@ impl TestState {
 		)
 	}
 
+	fn sign_approval_of_multiple_candidates(
+		&self,
+		index: ValidatorIndex,
+		candidate_hashes: &[CandidateHash],
+		session: SessionIndex,
+	) -> ValidatorSignature {
+		let keystore = self.master_keystore.clone() as SyncCryptoStorePtr;
+		let validator_id = self.validators[index.0 as usize].public();
+
+		let payload = ApprovalVoteMultipleCandidates(candidate_hashes).signing_payload(session);
+		SyncCryptoStore::sign_with(&*keystore, ValidatorId::ID, &validator_id.into(), &payload[..])
+			.ok()
+			.flatten()
+			.unwrap()
+			.try_into()
+			.unwrap()
+	}
+
 	fn resume<F>(mut self, test: F) -> Self
 	where
 		F: FnOnce(TestState, VirtualOverseer) -> BoxFuture<'static, TestState>,
@@ -690,7 +709,7 @@ fn make_candidate_included_event(candidate_receipt: CandidateReceipt) -> Candida
 pub async fn handle_approval_vote_request(
 	ctx_handle: &mut VirtualOverseer,
 	expected_hash: &CandidateHash,
-	votes_to_send: HashMap<ValidatorIndex, ValidatorSignature>,
+	votes_to_send: HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>,
 ) {
 	assert_matches!(
 		ctx_handle.recv().await,
@@ -704,6 +723,17 @@ pub async fn handle_approval_vote_request(
 	);
 }
 
+/// Handle a request for the `ParachainHost` runtime api version.
+async fn handle_runtime_version_request(ctx_handle: &mut VirtualOverseer, version: u32) {
+	assert_matches!(
+		ctx_handle.recv().await,
+		AllMessages::RuntimeApi(RuntimeApiMessage::Request(_, RuntimeApiRequest::Version(tx))) => {
+			tx.send(Ok(version)).unwrap();
+		},
+		"overseer did not receive `RuntimeApiRequest::Version` message.",
+	);
+}
+
 /// Handle block number request. In the context of these tests this message is required for
 /// handling comparator creation for enqueuing participations.
 async fn handle_get_block_number(ctx_handle: &mut VirtualOverseer, test_state: &TestState) {
@@ -839,6 +869,23 @@ fn too_many_unconfirmed_statements_are_considered_spam() {
 			// Result should be invalid, because it should be considered spam.
 			assert_matches!(confirmation_rx.await, Ok(ImportStatementsResult::InvalidImport));
 
+			// The spam slot of the invalid voter is reported as fully occupied:
+			{
+				let (tx, rx) = oneshot::channel();
+				virtual_overseer
+					.send(FromOrchestra::Communication {
+						msg: DisputeCoordinatorMessage::Status(tx),
+					})
+					.await;
+
+				let status = rx.await.unwrap();
+				assert_eq!(status.spam_slots, vec![(session, ValidatorIndex(1), 1)]);
+				assert_eq!(status.max_spam_votes, 1);
+				assert_eq!(status.priority_queue_len, 0);
+				assert_eq!(status.best_effort_queue_len, 0);
+				assert_eq!(status.running_participations, 0);
+			}
+
 			virtual_overseer.send(FromOrchestra::Signal(OverseerSignal::Conclude)).await;
 
 			// No more messages expected:
@@ -896,9 +943,12 @@ fn approval_vote_import_works() {
 				.await;
 			gum::trace!("After sending `ImportStatements`");
 
-			let approval_votes = [(ValidatorIndex(4), approval_vote.into_validator_signature())]
-				.into_iter()
-				.collect();
+			let approval_votes = [(
+				ValidatorIndex(4),
+				(vec![candidate_hash1], approval_vote.into_validator_signature()),
+			)]
+			.into_iter()
+			.collect();
 
 			handle_approval_vote_request(&mut virtual_overseer, &candidate_hash1, approval_votes)
 				.await;
@@ -948,6 +998,107 @@ fn approval_vote_import_works() {
 	});
 }
 
+fn approval_vote_of_multiple_candidates_import(runtime_version: u32, imported: bool) {
+	test_harness(move |mut test_state, mut virtual_overseer| {
+		Box::pin(async move {
+			let session = 1;
+
+			test_state.handle_resume_sync(&mut virtual_overseer, session).await;
+
+			let candidate_receipt1 = make_valid_candidate_receipt();
+			let candidate_hash1 = candidate_receipt1.hash();
+			let candidate_hashes = vec![CandidateHash(Hash::repeat_byte(0x42)), candidate_hash1];
+
+			test_state
+				.activate_leaf_at_session(&mut virtual_overseer, session, 1, Vec::new())
+				.await;
+
+			let (valid_vote1, invalid_vote1) = generate_opposing_votes_pair(
+				&test_state,
+				ValidatorIndex(3),
+				ValidatorIndex(1),
+				candidate_hash1,
+				session,
+				VoteType::Backing,
+			)
+			.await;
+
+			let signature = test_state.sign_approval_of_multiple_candidates(
+				ValidatorIndex(4),
+				&candidate_hashes,
+				session,
+			);
+
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_receipt: candidate_receipt1.clone(),
+						session,
+						statements: vec![
+							(valid_vote1, ValidatorIndex(3)),
+							(invalid_vote1, ValidatorIndex(1)),
+						],
+						pending_confirmation: None,
+					},
+				})
+				.await;
+
+			let approval_votes = [(ValidatorIndex(4), (candidate_hashes.clone(), signature))]
+				.into_iter()
+				.collect();
+
+			handle_approval_vote_request(&mut virtual_overseer, &candidate_hash1, approval_votes)
+				.await;
+			handle_runtime_version_request(&mut virtual_overseer, runtime_version).await;
+
+			{
+				let (tx, rx) = oneshot::channel();
+				virtual_overseer
+					.send(FromOrchestra::Communication {
+						msg: DisputeCoordinatorMessage::QueryCandidateVotes(
+							vec![(session, candidate_hash1)],
+							tx,
+						),
+					})
+					.await;
+
+				let (_, _, votes) = rx.await.unwrap().get(0).unwrap().clone();
+				assert_eq!(
+					votes.valid.raw().get(&ValidatorIndex(4)).map(|(kind, _)| kind.clone()),
+					imported.then(|| {
+						ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(
+							candidate_hashes,
+						)
+					}),
+				);
+			}
+
+			virtual_overseer.send(FromOrchestra::Signal(OverseerSignal::Conclude)).await;
+
+			// No more messages expected:
+			assert!(virtual_overseer.try_recv().await.is_none());
+
+			test_state
+		})
+	});
+}
+
+#[test]
+fn approval_vote_of_multiple_candidates_import_works() {
+	approval_vote_of_multiple_candidates_import(
+		RuntimeApiRequest::MULTIPLE_CANDIDATE_APPROVALS_RUNTIME_REQUIREMENT,
+		true,
+	);
+}
+
+#[test]
+fn approval_vote_of_multiple_candidates_is_ignored_without_runtime_support() {
+	approval_vote_of_multiple_candidates_import(
+		RuntimeApiRequest::MULTIPLE_CANDIDATE_APPROVALS_RUNTIME_REQUIREMENT - 1,
+		false,
+	);
+}
+
 #[test]
 fn dispute_gets_confirmed_via_participation() {
 	test_harness(|mut test_state, mut virtual_overseer| {
@@ -27,8 +27,8 @@ use polkadot_node_subsystem::{
 	overseer, ActivatedLeaf,
 };
 use polkadot_primitives::{
-	supermajority_threshold, CandidateHash, DisputeState, DisputeStatement, DisputeStatementSet,
-	Hash, MultiDisputeStatementSet, SessionIndex, ValidDisputeStatementKind, ValidatorIndex,
+	supermajority_threshold, vstaging, CandidateHash, DisputeState, DisputeStatement,
+	DisputeStatementSet, Hash, MultiDisputeStatementSet, SessionIndex, ValidatorIndex,
 };
 use std::{
 	collections::{BTreeMap, HashMap},
@@ -199,14 +199,14 @@ where
 				votes.valid.retain(|validator_idx, (statement_kind, _)| {
 					is_vote_worth_to_keep(
 						validator_idx,
-						DisputeStatement::Valid(*statement_kind),
+						vstaging::DisputeStatement::Valid(statement_kind.clone()),
 						&onchain_state,
 					)
 				});
 				votes.invalid.retain(|validator_idx, (statement_kind, _)| {
 					is_vote_worth_to_keep(
 						validator_idx,
-						DisputeStatement::Invalid(*statement_kind),
+						vstaging::DisputeStatement::Invalid(*statement_kind),
 						&onchain_state,
 					)
 				});
@@ -361,19 +361,19 @@ fn partition_recent_disputes(
 /// Determines if a vote is worth to be kept, based on the onchain disputes
 fn is_vote_worth_to_keep(
 	validator_index: &ValidatorIndex,
-	dispute_statement: DisputeStatement,
+	dispute_statement: vstaging::DisputeStatement,
 	onchain_state: &DisputeState,
 ) -> bool {
 	let (offchain_vote, valid_kind) = match dispute_statement {
-		DisputeStatement::Valid(kind) => (true, Some(kind)),
-		DisputeStatement::Invalid(_) => (false, None),
+		vstaging::DisputeStatement::Valid(kind) => (true, Some(kind)),
+		vstaging::DisputeStatement::Invalid(_) => (false, None),
 	};
 	// We want to keep all backing votes. This maximizes the number of backers
 	// punished when misbehaving.
 	if let Some(kind) = valid_kind {
 		match kind {
-			ValidDisputeStatementKind::BackingValid(_) |
-			ValidDisputeStatementKind::BackingSeconded(_) => return true,
+			vstaging::ValidDisputeStatementKind::BackingValid(_) |
+			vstaging::ValidDisputeStatementKind::BackingSeconded(_) => return true,
 			_ => (),
 		}
 	}
@@ -432,10 +432,12 @@ fn make_multi_dispute_statement_set(
 	dispute_candidate_votes
 		.into_iter()
 		.map(|((session_index, candidate_hash), votes)| {
+			// Approvals of multiple candidates are not understood by the runtime, drop them.
 			let valid_statements = votes
 				.valid
 				.into_iter()
-				.map(|(i, (s, sig))| (DisputeStatement::Valid(s), i, sig));
+				.filter_map(|(i, (s, sig))| Some((DisputeStatement::Valid(s.into_v2()?), i, sig)))
+				.collect::<Vec<_>>();
 
 			let invalid_statements = votes
 				.invalid
@@ -449,7 +451,7 @@ fn make_multi_dispute_statement_set(
 			DisputeStatementSet {
 				candidate_hash,
 				session: session_index,
-				statements: valid_statements.chain(invalid_statements).collect(),
+				statements: valid_statements.into_iter().chain(invalid_statements).collect(),
 			}
 		})
 		.collect()
@@ -26,8 +26,8 @@ use polkadot_node_subsystem::messages::{
 };
 use polkadot_node_subsystem_test_helpers::TestSubsystemSender;
 use polkadot_primitives::{
-	CandidateHash, DisputeState, InvalidDisputeStatementKind, SessionIndex,
-	ValidDisputeStatementKind, ValidatorSignature,
+	vstaging::{DisputeStatement, ValidDisputeStatementKind},
+	CandidateHash, DisputeState, InvalidDisputeStatementKind, SessionIndex, ValidatorSignature,
 };
 use std::sync::Arc;
 use test_helpers;
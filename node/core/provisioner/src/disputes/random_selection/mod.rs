@@ -154,10 +154,12 @@ where
 	dispute_candidate_votes
 		.into_iter()
 		.map(|(session_index, candidate_hash, votes)| {
+			// Approvals of multiple candidates are not understood by the runtime, drop them.
 			let valid_statements = votes
 				.valid
 				.into_iter()
-				.map(|(i, (s, sig))| (DisputeStatement::Valid(s), i, sig));
+				.filter_map(|(i, (s, sig))| Some((DisputeStatement::Valid(s.into_v2()?), i, sig)))
+				.collect::<Vec<_>>();
 
 			let invalid_statements = votes
 				.invalid
@@ -171,7 +173,7 @@ where
 			DisputeStatementSet {
 				candidate_hash,
 				session: session_index,
-				statements: valid_statements.chain(invalid_statements).collect(),
+				statements: valid_statements.into_iter().chain(invalid_statements).collect(),
 			}
 		})
 		.collect()
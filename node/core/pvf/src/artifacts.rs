@@ -14,7 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{error::PrepareError, host::PrepareResultSender, prepare::PrepareStats};
+//! PVF artifacts (final compiled code blobs).
+//!
+//! # Lifecycle of an artifact
+//!
+//! 1. During node start-up, the artifacts cache is scanned. Artifacts compiled by the same wasmtime
+//!    and node versions whose checksums match are registered as prepared, everything else is
+//!    removed. The checksum of every artifact is recomputed, so that artifacts corrupted on disk
+//!    are never handed to the executor.
+//!
+//! 2. An artifact is compiled by a prepare worker into a temporary file. The host records the
+//!    checksum of the artifact next to it and then moves the artifact into its final location.
+//!
+//! 3. Artifacts which were not needed for a while are pruned, together with their checksums.
+
+use crate::{error::PrepareError, host::PrepareResultSender, prepare::PrepareStats, LOG_TARGET};
 use always_assert::always;
 use polkadot_parachain::primitives::ValidationCodeHash;
 use polkadot_primitives::vstaging::ExecutorParamsHash;
@@ -38,6 +52,31 @@ impl AsRef<[u8]> for CompiledArtifact {
 	}
 }
 
+/// The version of wasmtime that compiles the artifacts.
+///
+/// `wasmtime-environ` must be kept at the version of wasmtime used by `sc-executor-wasmtime`.
+const WASMTIME_VERSION: &str = wasmtime_environ::VERSION;
+
+/// The version of the node, which pins the version of the executor. Includes the commit the node
+/// is built from, so that development builds of the same version don't share artifacts.
+const NODE_VERSION: &str = env!("SUBSTRATE_CLI_IMPL_VERSION");
+
+/// The suffix of the file name of the checksum stored next to each artifact.
+pub const CHECKSUM_SUFFIX: &str = ".checksum";
+
+/// Computes the checksum of the given artifact contents.
+pub fn checksum(artifact: &[u8]) -> String {
+	format!("{:#x}", sp_core::H256(sp_core::blake2_256(artifact)))
+}
+
+/// Returns the path to the checksum of the artifact at the given path.
+pub fn checksum_path(artifact_path: &Path) -> PathBuf {
+	// Not using `with_extension`, as the versions in the file name contain dots.
+	let mut path = artifact_path.as_os_str().to_owned();
+	path.push(CHECKSUM_SUFFIX);
+	path.into()
+}
+
 /// Identifier of an artifact. Encodes a code hash of the PVF and a hash of executor parameter set.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ArtifactId {
@@ -46,7 +85,11 @@ pub struct ArtifactId {
 }
 
 impl ArtifactId {
-	const PREFIX: &'static str = "wasmtime_";
+	/// The prefix of the artifact file names. Artifacts compiled by other versions of wasmtime or of
+	/// the node are not recognized.
+	fn prefix() -> String {
+		format!("wasmtime_v{}_polkadot_v{}_", WASMTIME_VERSION, NODE_VERSION)
+	}
 
 	/// Creates a new artifact ID with the given hash.
 	pub fn new(code_hash: ValidationCodeHash, executor_params_hash: ExecutorParamsHash) -> Self {
@@ -54,12 +97,11 @@ impl ArtifactId {
 	}
 
 	/// Tries to recover the artifact id from the given file name.
-	#[cfg(test)]
 	pub fn from_file_name(file_name: &str) -> Option<Self> {
 		use polkadot_core_primitives::Hash;
 		use std::str::FromStr as _;
 
-		let file_name = file_name.strip_prefix(Self::PREFIX)?;
+		let file_name = file_name.strip_prefix(&Self::prefix())?;
 		let (code_hash_str, executor_params_hash_str) = file_name.split_once('_')?;
 		let code_hash = Hash::from_str(code_hash_str).ok()?.into();
 		let executor_params_hash =
@@ -71,9 +113,14 @@ impl ArtifactId {
 	/// Returns the expected path to this artifact given the root of the cache.
 	pub fn path(&self, cache_path: &Path) -> PathBuf {
 		let file_name =
-			format!("{}{:#x}_{:#x}", Self::PREFIX, self.code_hash, self.executor_params_hash);
+			format!("{}{:#x}_{:#x}", Self::prefix(), self.code_hash, self.executor_params_hash);
 		cache_path.join(file_name)
 	}
+
+	/// Returns the path to the checksum of this artifact given the root of the cache.
+	pub fn checksum_path(&self, cache_path: &Path) -> PathBuf {
+		checksum_path(&self.path(cache_path))
+	}
 }
 
 /// A bundle of the artifact ID and the path.
@@ -128,23 +175,139 @@ pub enum ArtifactState {
 	},
 }
 
+/// Checks whether the file at `path` is an artifact compiled by this version of wasmtime and the
+/// node, whose checksum matches. Returns the id of the artifact if so.
+async fn validate_artifact(cache_path: &Path, path: &Path) -> Option<ArtifactId> {
+	let artifact_id = ArtifactId::from_file_name(path.file_name()?.to_str()?)?;
+	// Make sure the file name is canonical, so that the artifact is found under its path.
+	if artifact_id.path(cache_path) != path {
+		return None
+	}
+
+	let expected = tokio::fs::read_to_string(artifact_id.checksum_path(cache_path)).await.ok()?;
+	let artifact = tokio::fs::read(path).await.ok()?;
+	if checksum(&artifact) != expected {
+		gum::warn!(
+			target: LOG_TARGET,
+			validation_code_hash = ?artifact_id.code_hash,
+			"checksum mismatch of artifact {}",
+			path.display(),
+		);
+		return None
+	}
+
+	Some(artifact_id)
+}
+
 /// A container of all known artifact ids and their states.
 pub struct Artifacts {
 	artifacts: HashMap<ArtifactId, ArtifactState>,
 }
 
 impl Artifacts {
-	/// Initialize a blank cache at the given path. This will clear everything present at the
-	/// given path, to be populated over time.
+	/// Initialize the cache at the given path, reusing the artifacts present there.
 	///
-	/// The recognized artifacts will be filled in the table and unrecognized will be removed.
+	/// The recognized artifacts whose checksums match will be filled in the table as prepared,
+	/// everything else will be removed.
 	pub async fn new(cache_path: &Path) -> Self {
 		// Make sure that the cache path directory and all its parents are created.
-		// First delete the entire cache. Nodes are long-running so this should populate shortly.
-		let _ = tokio::fs::remove_dir_all(cache_path).await;
 		let _ = tokio::fs::create_dir_all(cache_path).await;
 
-		Self { artifacts: HashMap::new() }
+		let mut artifacts = HashMap::new();
+		let now = SystemTime::now();
+		let mut to_remove = vec![];
+		let mut checksums = vec![];
+
+		let mut dir = match tokio::fs::read_dir(cache_path).await {
+			Ok(dir) => dir,
+			Err(err) => {
+				gum::warn!(
+					target: LOG_TARGET,
+					"failed to read the artifacts cache {}: {:?}",
+					cache_path.display(),
+					err,
+				);
+				return Self { artifacts }
+			},
+		};
+		loop {
+			let entry = match dir.next_entry().await {
+				Ok(Some(entry)) => entry,
+				Ok(None) => break,
+				Err(err) => {
+					gum::warn!(
+						target: LOG_TARGET,
+						"failed to read the artifacts cache {}: {:?}",
+						cache_path.display(),
+						err,
+					);
+					break
+				},
+			};
+			let path = entry.path();
+
+			let checksum_of = path
+				.to_str()
+				.and_then(|path| path.strip_suffix(CHECKSUM_SUFFIX))
+				.map(PathBuf::from);
+			if let Some(artifact_path) = checksum_of {
+				// Checksums are validated together with their artifacts.
+				checksums.push((path, artifact_path));
+				continue
+			}
+
+			match validate_artifact(cache_path, &path).await {
+				Some(artifact_id) => {
+					gum::debug!(
+						target: LOG_TARGET,
+						validation_code_hash = ?artifact_id.code_hash,
+						"reusing artifact {}",
+						path.display(),
+					);
+					artifacts.insert(
+						artifact_id,
+						ArtifactState::Prepared {
+							last_time_needed: now,
+							prepare_stats: PrepareStats::default(),
+						},
+					);
+				},
+				None => to_remove.push(path),
+			}
+		}
+
+		// Remove the checksums of removed or missing artifacts.
+		for (checksum_path, artifact_path) in checksums {
+			if to_remove.contains(&artifact_path) || !artifact_path.exists() {
+				to_remove.push(checksum_path);
+			}
+		}
+
+		for path in to_remove {
+			gum::debug!(target: LOG_TARGET, "removing stale artifact {}", path.display());
+			let result = if path.is_dir() {
+				tokio::fs::remove_dir_all(&path).await
+			} else {
+				tokio::fs::remove_file(&path).await
+			};
+			if let Err(err) = result {
+				gum::warn!(
+					target: LOG_TARGET,
+					"failed to remove stale artifact {}: {:?}",
+					path.display(),
+					err,
+				);
+			}
+		}
+
+		gum::info!(
+			target: LOG_TARGET,
+			"reusing {} artifacts from {}",
+			artifacts.len(),
+			cache_path.display(),
+		);
+
+		Self { artifacts }
 	}
 
 	#[cfg(test)]
@@ -218,7 +381,7 @@ impl Artifacts {
 
 #[cfg(test)]
 mod tests {
-	use super::{ArtifactId, Artifacts};
+	use super::{checksum, ArtifactId, ArtifactState, Artifacts};
 	use polkadot_primitives::vstaging::ExecutorParamsHash;
 	use sp_core::H256;
 	use std::{path::Path, str::FromStr};
@@ -227,11 +390,17 @@ mod tests {
 	fn from_file_name() {
 		assert!(ArtifactId::from_file_name("").is_none());
 		assert!(ArtifactId::from_file_name("junk").is_none());
+		// Artifacts of other versions are not recognized.
+		assert!(ArtifactId::from_file_name(
+			"wasmtime_0x0022800000000000000000000000000000000000000000000000000000000000_0x0033900000000000000000000000000000000000000000000000000000000000"
+		)
+		.is_none());
 
 		assert_eq!(
-			ArtifactId::from_file_name(
-				"wasmtime_0x0022800000000000000000000000000000000000000000000000000000000000_0x0033900000000000000000000000000000000000000000000000000000000000"
-			),
+			ArtifactId::from_file_name(&format!(
+				"{}0x0022800000000000000000000000000000000000000000000000000000000000_0x0033900000000000000000000000000000000000000000000000000000000000",
+				ArtifactId::prefix(),
+			)),
 			Some(ArtifactId::new(
 				hex_literal::hex![
 					"0022800000000000000000000000000000000000000000000000000000000000"
@@ -251,12 +420,22 @@ mod tests {
 			H256::from_str("1234567890123456789012345678901234567890123456789012345678901234")
 				.unwrap();
 
+		let artifact_id = ArtifactId::new(hash.into(), ExecutorParamsHash::from_hash(hash));
+
 		assert_eq!(
-			ArtifactId::new(hash.into(), ExecutorParamsHash::from_hash(hash)).path(path).to_str(),
+			artifact_id.path(path).to_str(),
 			Some(
-				"/test/wasmtime_0x1234567890123456789012345678901234567890123456789012345678901234_0x1234567890123456789012345678901234567890123456789012345678901234"
+				format!(
+					"/test/{}0x1234567890123456789012345678901234567890123456789012345678901234_0x1234567890123456789012345678901234567890123456789012345678901234",
+					ArtifactId::prefix(),
+				)
+				.as_str()
 			),
 		);
+		assert_eq!(
+			artifact_id.checksum_path(path).to_str(),
+			Some(format!("{}.checksum", artifact_id.path(path).display()).as_str()),
+		);
 	}
 
 	#[tokio::test]
@@ -282,4 +461,65 @@ mod tests {
 
 		std::fs::remove_dir_all(fake_cache_path).unwrap();
 	}
+
+	#[tokio::test]
+	async fn artifacts_reuses_valid_artifacts_on_startup() {
+		let fake_cache_path = crate::worker_common::tmpfile("test-cache").await.unwrap();
+		std::fs::create_dir_all(&fake_cache_path).unwrap();
+
+		let artifact_id = |i: u8| {
+			let hash = H256::repeat_byte(i);
+			ArtifactId::new(hash.into(), ExecutorParamsHash::from_hash(hash))
+		};
+
+		let write_checksum = |artifact_id: &ArtifactId, contents: &[u8]| {
+			std::fs::write(artifact_id.checksum_path(&fake_cache_path), checksum(contents)).unwrap()
+		};
+
+		// A valid artifact.
+		let valid = artifact_id(1);
+		std::fs::write(valid.path(&fake_cache_path), b"valid").unwrap();
+		write_checksum(&valid, b"valid");
+
+		// An artifact corrupted in place, keeping its size.
+		let rotten = artifact_id(5);
+		std::fs::write(rotten.path(&fake_cache_path), b"rotten").unwrap();
+		write_checksum(&rotten, b"rotten");
+		std::fs::write(rotten.path(&fake_cache_path), b"Rotten").unwrap();
+
+		// An artifact which doesn't match its checksum.
+		let corrupted = artifact_id(2);
+		std::fs::write(corrupted.path(&fake_cache_path), b"corrupted").unwrap();
+		write_checksum(&corrupted, b"valid");
+
+		// An artifact without a checksum.
+		let unchecked = artifact_id(3);
+		std::fs::write(unchecked.path(&fake_cache_path), b"unchecked").unwrap();
+
+		// A checksum without an artifact.
+		let missing = artifact_id(4);
+		write_checksum(&missing, b"missing");
+
+		let mut artifacts = Artifacts::new(&fake_cache_path).await;
+
+		assert!(matches!(
+			artifacts.artifact_state_mut(&valid),
+			Some(ArtifactState::Prepared { .. })
+		));
+		for artifact_id in [&rotten, &corrupted, &unchecked, &missing] {
+			assert!(artifacts.artifact_state_mut(artifact_id).is_none());
+		}
+
+		let mut remaining = std::fs::read_dir(&fake_cache_path)
+			.unwrap()
+			.map(|entry| entry.unwrap().path())
+			.collect::<Vec<_>>();
+		remaining.sort();
+		assert_eq!(
+			remaining,
+			vec![valid.path(&fake_cache_path), valid.checksum_path(&fake_cache_path)]
+		);
+
+		std::fs::remove_dir_all(fake_cache_path).unwrap();
+	}
 }
@@ -114,6 +114,9 @@ pub enum InvalidCandidate {
 	AmbiguousWorkerDeath,
 	/// PVF execution (compilation is not included) took more time than was allotted.
 	HardTimeout,
+	/// PVF execution consumed more fuel than allowed by the executor parameters. Unlike
+	/// [`InvalidCandidate::HardTimeout`], this does not depend on the performance of the machine.
+	OutOfFuel,
 }
 
 impl From<PrepareError> for ValidationError {
@@ -21,6 +21,7 @@ use crate::{
 	artifacts::{ArtifactId, ArtifactPathId},
 	host::ResultSender,
 	metrics::Metrics,
+	security::SecurityStatus,
 	worker_common::{IdleWorker, WorkerHandle},
 	InvalidCandidate, ValidationError, LOG_TARGET,
 };
@@ -137,6 +138,7 @@ struct Queue {
 
 	program_path: PathBuf,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 
 	/// The queue of jobs that are waiting for a worker to pick up.
 	queue: VecDeque<ExecuteJob>,
@@ -150,12 +152,14 @@ impl Queue {
 		program_path: PathBuf,
 		worker_capacity: usize,
 		spawn_timeout: Duration,
+		security_status: SecurityStatus,
 		to_queue_rx: mpsc::Receiver<ToQueue>,
 	) -> Self {
 		Self {
 			metrics,
 			program_path,
 			spawn_timeout,
+			security_status,
 			to_queue_rx,
 			queue: VecDeque::new(),
 			mux: Mux::new(),
@@ -335,6 +339,11 @@ fn handle_job_finish(
 			(Some(idle_worker), Err(ValidationError::InternalError(err)), None),
 		Outcome::HardTimeout =>
 			(None, Err(ValidationError::InvalidCandidate(InvalidCandidate::HardTimeout)), None),
+		Outcome::OutOfFuel { idle_worker } => (
+			Some(idle_worker),
+			Err(ValidationError::InvalidCandidate(InvalidCandidate::OutOfFuel)),
+			None,
+		),
 		Outcome::IoErr => (
 			None,
 			Err(ValidationError::InvalidCandidate(InvalidCandidate::AmbiguousWorkerDeath)),
@@ -393,9 +402,15 @@ fn spawn_extra_worker(queue: &mut Queue, job: ExecuteJob) {
 	queue.metrics.execute_worker().on_begin_spawn();
 	gum::debug!(target: LOG_TARGET, "spawning an extra worker");
 
-	queue
-		.mux
-		.push(spawn_worker_task(queue.program_path.clone(), job, queue.spawn_timeout).boxed());
+	queue.mux.push(
+		spawn_worker_task(
+			queue.program_path.clone(),
+			job,
+			queue.spawn_timeout,
+			queue.security_status,
+		)
+		.boxed(),
+	);
 	queue.workers.spawn_inflight += 1;
 }
 
@@ -409,11 +424,18 @@ async fn spawn_worker_task(
 	program_path: PathBuf,
 	job: ExecuteJob,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 ) -> QueueEvent {
 	use futures_timer::Delay;
 
 	loop {
-		match super::worker::spawn(&program_path, job.executor_params.clone(), spawn_timeout).await
+		match super::worker::spawn(
+			&program_path,
+			job.executor_params.clone(),
+			spawn_timeout,
+			security_status,
+		)
+		.await
 		{
 			Ok((idle, handle)) => break QueueEvent::Spawn(idle, handle, job),
 			Err(err) => {
@@ -475,8 +497,17 @@ pub fn start(
 	program_path: PathBuf,
 	worker_capacity: usize,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 ) -> (mpsc::Sender<ToQueue>, impl Future<Output = ()>) {
 	let (to_queue_tx, to_queue_rx) = mpsc::channel(20);
-	let run = Queue::new(metrics, program_path, worker_capacity, spawn_timeout, to_queue_rx).run();
+	let run = Queue::new(
+		metrics,
+		program_path,
+		worker_capacity,
+		spawn_timeout,
+		security_status,
+		to_queue_rx,
+	)
+	.run();
 	(to_queue_tx, run)
 }
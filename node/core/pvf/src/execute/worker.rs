@@ -16,7 +16,8 @@
 
 use crate::{
 	artifacts::ArtifactPathId,
-	executor_intf::Executor,
+	executor_intf::{ExecuteError, Executor},
+	security::{self, SecurityStatus},
 	worker_common::{
 		bytes_to_path, cpu_time_monitor_loop, framed_recv, framed_send, path_to_bytes,
 		spawn_with_program_path, worker_event_loop, IdleWorker, SpawnErr, WorkerHandle,
@@ -46,21 +47,26 @@ pub async fn spawn(
 	program_path: &Path,
 	executor_params: ExecutorParams,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
-	let (mut idle_worker, worker_handle) =
-		spawn_with_program_path("execute", program_path, &["execute-worker"], spawn_timeout)
-			.await?;
-	send_handshake(&mut idle_worker.stream, Handshake { executor_params })
-		.await
-		.map_err(|error| {
-			gum::warn!(
-				target: LOG_TARGET,
-				worker_pid = %idle_worker.pid,
-				?error,
-				"failed to send a handshake to the spawned worker",
-			);
-			SpawnErr::Handshake
-		})?;
+	let (mut idle_worker, worker_handle) = spawn_with_program_path(
+		"execute",
+		program_path,
+		&["execute-worker"],
+		spawn_timeout,
+		security_status,
+	)
+	.await?;
+	let handshake = Handshake { executor_params, security_status };
+	send_handshake(&mut idle_worker.stream, handshake).await.map_err(|error| {
+		gum::warn!(
+			target: LOG_TARGET,
+			worker_pid = %idle_worker.pid,
+			?error,
+			"failed to send a handshake to the spawned worker",
+		);
+		SpawnErr::Handshake
+	})?;
 	Ok((idle_worker, worker_handle))
 }
 
@@ -77,6 +83,9 @@ pub enum Outcome {
 	InternalError { err: String, idle_worker: IdleWorker },
 	/// The execution time exceeded the hard limit. The worker is terminated.
 	HardTimeout,
+	/// The execution consumed more fuel than allowed by the executor params. The worker is ready
+	/// for another job.
+	OutOfFuel { idle_worker: IdleWorker },
 	/// An I/O error happened during communication with the worker. This may mean that the worker
 	/// process already died. The token is not returned in any case.
 	IoErr,
@@ -172,6 +181,7 @@ pub async fn start_work(
 		Response::InvalidCandidate(err) =>
 			Outcome::InvalidCandidate { err, idle_worker: IdleWorker { stream, pid } },
 		Response::TimedOut => Outcome::HardTimeout,
+		Response::OutOfFuel => Outcome::OutOfFuel { idle_worker: IdleWorker { stream, pid } },
 		Response::InternalError(err) =>
 			Outcome::InternalError { err, idle_worker: IdleWorker { stream, pid } },
 	}
@@ -239,6 +249,8 @@ async fn recv_response(stream: &mut UnixStream) -> io::Result<Response> {
 #[derive(Encode, Decode)]
 struct Handshake {
 	executor_params: ExecutorParams,
+	/// The sandboxing measures the worker should enforce on itself.
+	security_status: SecurityStatus,
 }
 
 #[derive(Encode, Decode)]
@@ -246,6 +258,7 @@ pub enum Response {
 	Ok { result_descriptor: ValidationResult, duration: Duration },
 	InvalidCandidate(String),
 	TimedOut,
+	OutOfFuel,
 	InternalError(String),
 }
 
@@ -269,6 +282,18 @@ pub fn worker_entrypoint(socket_path: &str) {
 			io::Error::new(io::ErrorKind::Other, format!("cannot create executor: {}", e))
 		})?);
 
+		let security_status = handshake.security_status;
+		// The host has checked that the measures are supported, so failing to enforce them is not
+		// expected. We don't fail the worker over it though, as that would fail the jobs.
+		if let Err(err) = security::apply_worker_restrictions(&security_status) {
+			gum::warn!(
+				target: LOG_TARGET,
+				worker_pid = %std::process::id(),
+				"failed to sandbox the execute worker: {}",
+				err,
+			);
+		}
+
 		loop {
 			let (artifact_path, params, execution_timeout) = recv_request(&mut stream).await?;
 			gum::debug!(
@@ -291,7 +316,13 @@ pub fn worker_entrypoint(socket_path: &str) {
 			let executor_2 = executor.clone();
 			let execute_fut = rt_handle
 				.spawn_blocking(move || {
-					validate_using_artifact(&artifact_path, &params, executor_2, cpu_time_start)
+					validate_using_artifact(
+						&artifact_path,
+						&params,
+						executor_2,
+						security_status,
+						cpu_time_start,
+					)
 				})
 				.fuse();
 
@@ -333,15 +364,34 @@ fn validate_using_artifact(
 	artifact_path: &Path,
 	params: &[u8],
 	executor: Arc<Executor>,
+	security_status: SecurityStatus,
 	cpu_time_start: ProcessTime,
 ) -> Response {
-	let descriptor_bytes = match unsafe {
-		// SAFETY: this should be safe since the compiled artifact passed here comes from the
-		//         file created by the prepare workers. These files are obtained by calling
-		//         [`executor_intf::prepare`].
-		executor.execute(artifact_path.as_ref(), params)
-	} {
-		Err(err) => return Response::format_invalid("execute", &err),
+	// SAFETY: this should be safe since the compiled artifact passed here comes from the
+	//         file created by the prepare workers. These files are obtained by calling
+	//         [`executor_intf::prepare`].
+	let result = if security_status.can_enable_landlock {
+		// Landlock restrictions can't be lifted, so each job gets a fresh thread which may only
+		// read the artifact of this job.
+		let restrict = || {
+			if let Err(err) =
+				security::restrict_thread_filesystem(&security_status, &[artifact_path])
+			{
+				gum::warn!(
+					target: LOG_TARGET,
+					worker_pid = %std::process::id(),
+					"failed to restrict the filesystem access of the execute worker: {}",
+					err,
+				);
+			}
+		};
+		unsafe { executor.execute_on_new_thread(artifact_path.as_ref(), params, restrict) }
+	} else {
+		unsafe { executor.execute(artifact_path.as_ref(), params) }
+	};
+	let descriptor_bytes = match result {
+		Err(ExecuteError::OutOfFuel) => return Response::OutOfFuel,
+		Err(ExecuteError::Other(err)) => return Response::format_invalid("execute", &err),
 		Ok(d) => d,
 	};
 
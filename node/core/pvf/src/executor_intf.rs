@@ -24,8 +24,13 @@ use sc_executor_common::{
 use sc_executor_wasmtime::{Config, DeterministicStackLimit, Semantics};
 use sp_core::storage::{ChildInfo, TrackedStorageKey};
 use sp_externalities::MultiRemovalResults;
+use sp_wasm_interface::{
+	Function, FunctionContext, HostFunctionRegistry, Signature, Value, ValueType,
+};
 use std::{
 	any::{Any, TypeId},
+	cell::Cell,
+	fmt,
 	path::Path,
 };
 
@@ -47,6 +52,12 @@ const EXTRA_HEAP_PAGES: u32 = 2048;
 /// The number of bytes devoted for the stack during wasm execution of a PVF.
 const NATIVE_STACK_MAX: u32 = 256 * 1024 * 1024;
 
+/// The name of the host function that PVFs instrumented with fuel metering import to consume fuel.
+///
+/// It's deliberately not `gas`, which is the name `wasm-instrument` uses, to rule out clashing with
+/// an import of the PVF itself.
+const CONSUME_FUEL: &str = "ext_pvf_consume_fuel_version_1";
+
 // VALUES OF THE DEFAULT CONFIGURATION SHOULD NEVER BE CHANGED
 // They are used as base values for the execution environment parametrization.
 // To overwrite them, add new ones to `EXECUTOR_PARAMS` in the `session_info` pallet and perform
@@ -107,9 +118,59 @@ pub fn prepare(
 ) -> Result<Vec<u8>, sc_executor_common::error::WasmError> {
 	let semantics = params_to_wasmtime_semantics(executor_params)
 		.map_err(|e| sc_executor_common::error::WasmError::Other(e))?;
+	let blob = match params_to_max_fuel(executor_params) {
+		Some(_) => inject_fuel_metering(blob)?,
+		None => blob,
+	};
 	sc_executor_wasmtime::prepare_runtime_artifact(blob, &semantics)
 }
 
+/// Instruments the code to consume one unit of fuel for each executed wasm instruction.
+///
+/// The fuel is consumed by calling the [`CONSUME_FUEL`] host function at the start of every metered
+/// block, i.e. every sequence of instructions which are either all executed or none is.
+fn inject_fuel_metering(
+	blob: RuntimeBlob,
+) -> Result<RuntimeBlob, sc_executor_common::error::WasmError> {
+	use sc_executor_common::error::WasmError;
+	use wasm_instrument::{
+		gas_metering::{self, ConstantCostRules},
+		parity_wasm::elements::{self, External, Module},
+	};
+
+	let module = elements::deserialize_buffer::<Module>(&blob.serialize())
+		.map_err(|e| WasmError::Other(format!("cannot deserialize module: {}", e)))?;
+	let mut module = gas_metering::inject(module, &ConstantCostRules::default(), "env")
+		.map_err(|_| WasmError::Other("cannot inject fuel metering".to_owned()))?;
+
+	// The import of the metering function is appended to the imported functions, rename it.
+	let consume_fuel_import = module.import_section_mut().and_then(|section| {
+		section
+			.entries_mut()
+			.iter_mut()
+			.rev()
+			.find(|entry| matches!(entry.external(), External::Function(_)))
+	});
+	match consume_fuel_import {
+		Some(import) => *import.field_mut() = CONSUME_FUEL.to_owned(),
+		None => return Err(WasmError::Other("fuel metering import not found".to_owned())),
+	}
+
+	let code = elements::serialize(module)
+		.map_err(|e| WasmError::Other(format!("cannot serialize module: {}", e)))?;
+	RuntimeBlob::new(&code)
+}
+
+/// Returns the fuel budget of PVF execution, if any is set by the executor params.
+fn params_to_max_fuel(par: &ExecutorParams) -> Option<u64> {
+	par.iter()
+		.filter_map(|p| match p {
+			ExecutorParam::ExecutionMaxFuel(max_fuel) => Some(*max_fuel),
+			_ => None,
+		})
+		.last()
+}
+
 fn params_to_wasmtime_semantics(par: &ExecutorParams) -> Result<Semantics, String> {
 	let mut sem = DEFAULT_CONFIG.semantics.clone();
 	let mut stack_limit = if let Some(stack_limit) = sem.deterministic_stack_limit.clone() {
@@ -126,16 +187,39 @@ fn params_to_wasmtime_semantics(par: &ExecutorParams) -> Result<Semantics, Strin
 			ExecutorParam::StackLogicalMax(slm) => stack_limit.logical_max = *slm,
 			ExecutorParam::StackNativeMax(snm) => stack_limit.native_stack_max = *snm,
 			ExecutorParam::PrecheckingMaxMemory(_) => (), // TODO: Not implemented yet
+			ExecutorParam::ExecutionMaxFuel(_) => (),     // Enforced by the instrumentation in `prepare`
 		}
 	}
 	sem.deterministic_stack_limit = Some(stack_limit);
 	Ok(sem)
 }
 
+/// An error that occurred while executing a PVF.
+#[derive(Debug)]
+pub enum ExecuteError {
+	/// The PVF consumed all of the fuel allowed by [`ExecutorParam::ExecutionMaxFuel`].
+	OutOfFuel,
+	/// Any other error, e.g. a trap.
+	Other(String),
+}
+
+impl fmt::Display for ExecuteError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ExecuteError::OutOfFuel => write!(f, "execute: out of fuel"),
+			ExecuteError::Other(err) => write!(f, "{}", err),
+		}
+	}
+}
+
+impl std::error::Error for ExecuteError {}
+
 pub struct Executor {
 	thread_pool: rayon::ThreadPool,
+	thread_stack_size: usize,
 	spawner: TaskSpawner,
 	config: Config,
+	max_fuel: Option<u64>,
 }
 
 impl Executor {
@@ -188,8 +272,9 @@ impl Executor {
 
 		let mut config = DEFAULT_CONFIG.clone();
 		config.semantics = params_to_wasmtime_semantics(&params)?;
+		let max_fuel = params_to_max_fuel(&params);
 
-		Ok(Self { thread_pool, spawner, config })
+		Ok(Self { thread_pool, thread_stack_size, spawner, config, max_fuel })
 	}
 
 	/// Executes the given PVF in the form of a compiled artifact and returns the result of execution
@@ -209,7 +294,7 @@ impl Executor {
 		&self,
 		compiled_artifact_path: &Path,
 		params: &[u8],
-	) -> Result<Vec<u8>, String> {
+	) -> Result<Vec<u8>, ExecuteError> {
 		let spawner = self.spawner.clone();
 		let mut result = None;
 		self.thread_pool.scope({
@@ -217,14 +302,59 @@ impl Executor {
 			move |s| {
 				s.spawn(move |_| {
 					// spawn does not return a value, so we need to use a variable to pass the result.
-					*result = Some(
-						do_execute(compiled_artifact_path, self.config.clone(), params, spawner)
-							.map_err(|err| format!("execute error: {:?}", err)),
-					);
+					*result = Some(self.execute_on_current_thread(
+						compiled_artifact_path,
+						params,
+						spawner,
+					));
 				});
 			}
 		});
-		result.unwrap_or_else(|| Err("rayon thread pool spawn failed".to_string()))
+		result.unwrap_or_else(|| {
+			Err(ExecuteError::Other("rayon thread pool spawn failed".to_string()))
+		})
+	}
+
+	/// Like [`Executor::execute`], but on a thread spawned for this execution only, which first
+	/// calls `prepare_thread`. This allows restricting the thread to this execution, e.g. to the
+	/// artifact it runs, at the cost of spawning a thread.
+	///
+	/// # Safety
+	///
+	/// The same as for [`Executor::execute`].
+	pub unsafe fn execute_on_new_thread(
+		&self,
+		compiled_artifact_path: &Path,
+		params: &[u8],
+		prepare_thread: impl FnOnce() + Send,
+	) -> Result<Vec<u8>, ExecuteError> {
+		let spawner = self.spawner.clone();
+		std::thread::scope(|s| {
+			std::thread::Builder::new()
+				.stack_size(self.thread_stack_size)
+				.spawn_scoped(s, move || {
+					prepare_thread();
+					self.execute_on_current_thread(compiled_artifact_path, params, spawner)
+				})
+				.map_err(|e| ExecuteError::Other(format!("cannot spawn execution thread: {}", e)))?
+				.join()
+				.map_err(|_| ExecuteError::Other("execution thread panicked".to_string()))?
+		})
+	}
+
+	unsafe fn execute_on_current_thread(
+		&self,
+		compiled_artifact_path: &Path,
+		params: &[u8],
+		spawner: TaskSpawner,
+	) -> Result<Vec<u8>, ExecuteError> {
+		// The fuel is consumed by the host function on this very thread.
+		refuel(self.max_fuel);
+		match do_execute(compiled_artifact_path, self.config.clone(), params, spawner) {
+			// Running out of fuel traps, so the execution error itself is not telling.
+			Err(_) if is_out_of_fuel() => Err(ExecuteError::OutOfFuel),
+			res => res.map_err(|err| ExecuteError::Other(format!("execute error: {:?}", err))),
+		}
 	}
 }
 
@@ -257,8 +387,78 @@ type HostFunctions = (
 	sp_io::allocator::HostFunctions,
 	sp_io::logging::HostFunctions,
 	sp_io::trie::HostFunctions,
+	ConsumeFuel,
 );
 
+thread_local! {
+	/// The fuel left for the PVF executed on this thread, or `None` if it ran out of fuel.
+	static FUEL_LEFT: Cell<Option<u64>> = Cell::new(Some(u64::MAX));
+}
+
+/// Sets the fuel for the next PVF executed on this thread. Without a budget the fuel is unlimited,
+/// since the PVF is not instrumented anyway.
+fn refuel(max_fuel: Option<u64>) {
+	FUEL_LEFT.with(|fuel| fuel.set(Some(max_fuel.unwrap_or(u64::MAX))));
+}
+
+/// Returns whether the PVF executed on this thread ran out of fuel.
+fn is_out_of_fuel() -> bool {
+	FUEL_LEFT.with(|fuel| fuel.get().is_none())
+}
+
+fn consume_fuel(amount: u64) -> Result<(), String> {
+	FUEL_LEFT.with(|fuel| {
+		let left = fuel.get().and_then(|left| left.checked_sub(amount));
+		fuel.set(left);
+		left.map(|_| ()).ok_or_else(|| "out of fuel".to_owned())
+	})
+}
+
+/// The host function called by PVFs instrumented with fuel metering. Traps once the PVF has run out
+/// of fuel.
+struct ConsumeFuel;
+
+impl Function for ConsumeFuel {
+	fn name(&self) -> &str {
+		CONSUME_FUEL
+	}
+
+	fn signature(&self) -> Signature {
+		Signature::new_with_args(&[ValueType::I64][..])
+	}
+
+	fn execute(
+		&self,
+		_context: &mut dyn FunctionContext,
+		args: &mut dyn Iterator<Item = Value>,
+	) -> sp_wasm_interface::Result<Option<Value>> {
+		match args.next() {
+			Some(Value::I64(amount)) => consume_fuel(amount as u64).map(|()| None),
+			_ => Err(format!("{}: invalid arguments", CONSUME_FUEL)),
+		}
+	}
+}
+
+impl sp_wasm_interface::HostFunctions for ConsumeFuel {
+	fn host_functions() -> Vec<&'static dyn Function> {
+		vec![&ConsumeFuel]
+	}
+
+	fn register_static<T>(registry: &mut T) -> Result<(), T::Error>
+	where
+		T: HostFunctionRegistry,
+	{
+		registry.register_static(
+			CONSUME_FUEL,
+			|_caller: sp_wasm_interface::wasmtime::Caller<T::State>,
+			 amount: i64|
+			 -> Result<(), sp_wasm_interface::anyhow::Error> {
+				consume_fuel(amount as u64).map_err(sp_wasm_interface::anyhow::Error::msg)
+			},
+		)
+	}
+}
+
 /// The validation externalities that will panic on any storage related access.
 struct ValidationExternalities(sp_externalities::Extensions);
 
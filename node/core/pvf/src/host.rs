@@ -25,7 +25,9 @@ use crate::{
 	error::PrepareError,
 	execute,
 	metrics::Metrics,
-	prepare, PrepareResult, Priority, PvfWithExecutorParams, ValidationError, LOG_TARGET,
+	prepare,
+	security::SecurityStatus,
+	PrepareResult, Priority, PvfWithExecutorParams, ValidationError, LOG_TARGET,
 };
 use always_assert::never;
 use futures::{
@@ -170,6 +172,8 @@ pub struct Config {
 	pub execute_worker_spawn_timeout: Duration,
 	/// The maximum number of execute workers that can run at the same time.
 	pub execute_workers_max_num: usize,
+	/// Whether to sandbox the workers, as far as supported by the system.
+	pub enable_sandbox: bool,
 }
 
 impl Config {
@@ -188,6 +192,7 @@ impl Config {
 			execute_worker_program_path: program_path,
 			execute_worker_spawn_timeout: Duration::from_secs(3),
 			execute_workers_max_num: 2,
+			enable_sandbox: false,
 		}
 	}
 }
@@ -205,11 +210,21 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 
 	let validation_host = ValidationHost { to_host_tx };
 
+	let security_status = if config.enable_sandbox {
+		let security_status = SecurityStatus::check(&config.prepare_worker_program_path);
+		security_status.log();
+		security_status
+	} else {
+		SecurityStatus::default()
+	};
+	metrics.observe_security_status(&security_status);
+
 	let (to_prepare_pool, from_prepare_pool, run_prepare_pool) = prepare::start_pool(
 		metrics.clone(),
 		config.prepare_worker_program_path.clone(),
 		config.cache_path.clone(),
 		config.prepare_worker_spawn_timeout,
+		security_status,
 	);
 
 	let (to_prepare_queue_tx, from_prepare_queue_rx, run_prepare_queue) = prepare::start_queue(
@@ -226,6 +241,7 @@ pub fn start(config: Config, metrics: Metrics) -> (ValidationHost, impl Future<O
 		config.execute_worker_program_path.to_owned(),
 		config.execute_workers_max_num,
 		config.execute_worker_spawn_timeout,
+		security_status,
 	);
 
 	let (to_sweeper_tx, to_sweeper_rx) = mpsc::channel(100);
@@ -806,6 +822,8 @@ async fn handle_cleanup_pulse(
 		);
 		let artifact_path = artifact_id.path(cache_path);
 		sweeper_tx.send(artifact_path).await.map_err(|_| Fatal)?;
+		let checksum_path = artifact_id.checksum_path(cache_path);
+		sweeper_tx.send(checksum_path).await.map_err(|_| Fatal)?;
 	}
 
 	Ok(())
@@ -1098,6 +1116,10 @@ mod tests {
 			&mut test.run,
 			async {
 				assert_eq!(to_sweeper_rx.next().await.unwrap(), artifact_path(2));
+				assert_eq!(
+					to_sweeper_rx.next().await.unwrap(),
+					artifact_id(2).checksum_path(&PathBuf::from(std::env::temp_dir())),
+				);
 			}
 			.boxed(),
 		)
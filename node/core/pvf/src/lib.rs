@@ -81,6 +81,10 @@
 //! The artifact is saved on disk and is also tracked by an in memory table. This in memory table
 //! doesn't contain the artifact contents though, only a flag that the given artifact is compiled.
 //!
+//! The artifacts on disk survive restarts of the node. On startup, the artifacts compiled by the
+//! same versions of wasmtime and the node are registered as compiled, if they match the checksums
+//! recorded next to them. Any other files in the cache are removed.
+//!
 //! A pruning task will run at a fixed interval of time. This task will remove all artifacts that
 //! weren't used or received a heads up signal for a while.
 //!
@@ -99,6 +103,7 @@ mod metrics;
 mod prepare;
 mod priority;
 mod pvf;
+mod security;
 mod worker_common;
 
 #[doc(hidden)]
@@ -107,8 +112,9 @@ pub mod testing;
 #[doc(hidden)]
 pub use sp_tracing;
 
+pub use artifacts::CHECKSUM_SUFFIX;
 pub use error::{InvalidCandidate, PrepareError, PrepareResult, ValidationError};
-pub use prepare::PrepareStats;
+pub use prepare::{MemoryAllocationStats, MemoryStats, PrepareStats};
 pub use priority::Priority;
 pub use pvf::PvfWithExecutorParams;
 
@@ -16,7 +16,7 @@
 
 //! Prometheus metrics related to the validation host.
 
-use crate::prepare::MemoryStats;
+use crate::{prepare::MemoryStats, security::SecurityStatus};
 use polkadot_node_metrics::metrics::{self, prometheus};
 
 /// Validation host metrics.
@@ -94,6 +94,21 @@ impl Metrics {
 			}
 		}
 	}
+
+	/// Observe the sandboxing measures enforced on the workers.
+	pub(crate) fn observe_security_status(&self, security_status: &SecurityStatus) {
+		if let Some(metrics) = &self.0 {
+			let SecurityStatus { can_enable_landlock, can_enable_seccomp, can_unshare_network } =
+				*security_status;
+			for (measure, enabled) in [
+				("landlock", can_enable_landlock),
+				("seccomp", can_enable_seccomp),
+				("unshare_network", can_unshare_network),
+			] {
+				metrics.security_status.with_label_values(&[measure]).set(enabled as u64);
+			}
+		}
+	}
 }
 
 #[derive(Clone)]
@@ -113,6 +128,7 @@ struct MetricsInner {
 	preparation_max_allocated: prometheus::Histogram,
 	#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
 	preparation_max_resident: prometheus::Histogram,
+	security_status: prometheus::GaugeVec<prometheus::U64>,
 }
 
 impl metrics::Metrics for Metrics {
@@ -269,6 +285,16 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			security_status: prometheus::register(
+				prometheus::GaugeVec::new(
+					prometheus::Opts::new(
+						"polkadot_pvf_security_status",
+						"Whether the sandboxing measure is enforced on the workers",
+					),
+					&["measure"],
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(inner)))
 	}
@@ -41,6 +41,26 @@ pub struct MemoryStats {
 	pub max_rss: Option<i64>,
 }
 
+impl MemoryStats {
+	/// The peak resident memory of the preparation thread in kilobytes (`ru_maxrss`), if measured.
+	pub fn max_rss_kb(&self) -> Option<i64> {
+		#[cfg(target_os = "linux")]
+		return self.max_rss;
+
+		#[cfg(not(target_os = "linux"))]
+		None
+	}
+
+	/// The peak memory stats observed by the memory tracker, if measured.
+	pub fn max_allocation_stats(&self) -> Option<&MemoryAllocationStats> {
+		#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
+		return self.memory_tracker_stats.as_ref();
+
+		#[cfg(not(any(target_os = "linux", feature = "jemalloc-allocator")))]
+		None
+	}
+}
+
 /// Statistics of collected memory metrics.
 #[non_exhaustive]
 #[derive(Clone, Debug, Default, Encode, Decode)]
@@ -27,7 +27,7 @@ mod pool;
 mod queue;
 mod worker;
 
-pub use memory_stats::MemoryStats;
+pub use memory_stats::{MemoryAllocationStats, MemoryStats};
 pub use pool::start as start_pool;
 pub use queue::{start as start_queue, FromQueue, ToQueue};
 pub use worker::worker_entrypoint;
@@ -40,3 +40,15 @@ pub struct PrepareStats {
 	cpu_time_elapsed: std::time::Duration,
 	memory_stats: MemoryStats,
 }
+
+impl PrepareStats {
+	/// The CPU time the preparation took.
+	pub fn cpu_time_elapsed(&self) -> std::time::Duration {
+		self.cpu_time_elapsed
+	}
+
+	/// The memory used by the preparation.
+	pub fn memory_stats(&self) -> &MemoryStats {
+		&self.memory_stats
+	}
+}
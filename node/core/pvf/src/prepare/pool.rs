@@ -19,6 +19,7 @@ use crate::{
 	error::{PrepareError, PrepareResult},
 	metrics::Metrics,
 	pvf::PvfWithExecutorParams,
+	security::SecurityStatus,
 	worker_common::{IdleWorker, WorkerHandle},
 	LOG_TARGET,
 };
@@ -116,6 +117,7 @@ struct Pool {
 	program_path: PathBuf,
 	cache_path: PathBuf,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 	to_pool: mpsc::Receiver<ToPool>,
 	from_pool: mpsc::UnboundedSender<FromPool>,
 	spawned: HopSlotMap<Worker, WorkerData>,
@@ -131,6 +133,7 @@ async fn run(
 		program_path,
 		cache_path,
 		spawn_timeout,
+		security_status,
 		to_pool,
 		mut from_pool,
 		mut spawned,
@@ -158,6 +161,7 @@ async fn run(
 					&program_path,
 					&cache_path,
 					spawn_timeout,
+					security_status,
 					&mut spawned,
 					&mut mux,
 					to_pool,
@@ -204,6 +208,7 @@ fn handle_to_pool(
 	program_path: &Path,
 	cache_path: &Path,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 	spawned: &mut HopSlotMap<Worker, WorkerData>,
 	mux: &mut Mux,
 	to_pool: ToPool,
@@ -212,7 +217,9 @@ fn handle_to_pool(
 		ToPool::Spawn => {
 			gum::debug!(target: LOG_TARGET, "spawning a new prepare worker");
 			metrics.prepare_worker().on_begin_spawn();
-			mux.push(spawn_worker_task(program_path.to_owned(), spawn_timeout).boxed());
+			mux.push(
+				spawn_worker_task(program_path.to_owned(), spawn_timeout, security_status).boxed(),
+			);
 		},
 		ToPool::StartWork { worker, pvf_with_params, artifact_path, preparation_timeout } => {
 			if let Some(data) = spawned.get_mut(worker) {
@@ -252,11 +259,15 @@ fn handle_to_pool(
 	}
 }
 
-async fn spawn_worker_task(program_path: PathBuf, spawn_timeout: Duration) -> PoolEvent {
+async fn spawn_worker_task(
+	program_path: PathBuf,
+	spawn_timeout: Duration,
+	security_status: SecurityStatus,
+) -> PoolEvent {
 	use futures_timer::Delay;
 
 	loop {
-		match worker::spawn(&program_path, spawn_timeout).await {
+		match worker::spawn(&program_path, spawn_timeout, security_status).await {
 			Ok((idle, handle)) => break PoolEvent::Spawn(idle, handle),
 			Err(err) => {
 				gum::warn!(target: LOG_TARGET, "failed to spawn a prepare worker: {:?}", err);
@@ -432,6 +443,7 @@ pub fn start(
 	program_path: PathBuf,
 	cache_path: PathBuf,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 ) -> (mpsc::Sender<ToPool>, mpsc::UnboundedReceiver<FromPool>, impl Future<Output = ()>) {
 	let (to_pool_tx, to_pool_rx) = mpsc::channel(10);
 	let (from_pool_tx, from_pool_rx) = mpsc::unbounded();
@@ -441,6 +453,7 @@ pub fn start(
 		program_path,
 		cache_path,
 		spawn_timeout,
+		security_status,
 		to_pool: to_pool_rx,
 		from_pool: from_pool_tx,
 		spawned: HopSlotMap::with_capacity_and_key(20),
@@ -20,11 +20,12 @@ use super::memory_stats::max_rss_stat::{extract_max_rss_stat, get_max_rss_thread
 use super::memory_stats::memory_tracker::{get_memory_tracker_loop_stats, memory_tracker_loop};
 use super::memory_stats::MemoryStats;
 use crate::{
-	artifacts::CompiledArtifact,
+	artifacts::{checksum, checksum_path, CompiledArtifact},
 	error::{PrepareError, PrepareResult},
 	metrics::Metrics,
 	prepare::PrepareStats,
 	pvf::PvfWithExecutorParams,
+	security::{self, SecurityStatus},
 	worker_common::{
 		bytes_to_path, cpu_time_monitor_loop, framed_recv, framed_send, path_to_bytes,
 		spawn_with_program_path, tmpfile_in, worker_event_loop, IdleWorker, SpawnErr, WorkerHandle,
@@ -46,13 +47,34 @@ use std::{
 use tokio::{io, net::UnixStream};
 
 /// Spawns a new worker with the given program path that acts as the worker and the spawn timeout.
+/// Sends a handshake message to the worker as soon as it is spawned.
 ///
 /// The program should be able to handle `<program-path> prepare-worker <socket-path>` invocation.
 pub async fn spawn(
 	program_path: &Path,
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
-	spawn_with_program_path("prepare", program_path, &["prepare-worker"], spawn_timeout).await
+	let (mut idle_worker, worker_handle) = spawn_with_program_path(
+		"prepare",
+		program_path,
+		&["prepare-worker"],
+		spawn_timeout,
+		security_status,
+	)
+	.await?;
+	send_handshake(&mut idle_worker.stream, Handshake { security_status })
+		.await
+		.map_err(|error| {
+			gum::warn!(
+				target: LOG_TARGET,
+				worker_pid = %idle_worker.pid,
+				?error,
+				"failed to send a handshake to the spawned worker",
+			);
+			SpawnErr::Handshake
+		})?;
+	Ok((idle_worker, worker_handle))
 }
 
 pub enum Outcome {
@@ -64,7 +86,7 @@ pub enum Outcome {
 	/// The temporary file for the artifact could not be created at the given cache path.
 	CreateTmpFileErr { worker: IdleWorker, err: String },
 	/// The response from the worker is received, but the file cannot be renamed (moved) to the
-	/// final destination location.
+	/// final destination location, or its checksum cannot be recorded.
 	RenameTmpFileErr { worker: IdleWorker, result: PrepareResult, err: String },
 	/// The worker failed to finish the job until the given deadline.
 	///
@@ -201,13 +223,20 @@ async fn handle_response(
 		artifact_path.display(),
 	);
 
-	let outcome = match tokio::fs::rename(&tmp_file, &artifact_path).await {
+	// Record the checksum before moving the artifact, so that it can be validated when the artifact
+	// is reused after a restart.
+	let promote = async {
+		let artifact = tokio::fs::read(&tmp_file).await?;
+		tokio::fs::write(checksum_path(&artifact_path), checksum(&artifact)).await?;
+		tokio::fs::rename(&tmp_file, &artifact_path).await
+	};
+	let outcome = match promote.await {
 		Ok(()) => Outcome::Concluded { worker, result },
 		Err(err) => {
 			gum::warn!(
 				target: LOG_TARGET,
 				%worker_pid,
-				"failed to rename the artifact from {} to {}: {:?}",
+				"failed to promote the artifact from {} to {}: {:?}",
 				tmp_file.display(),
 				artifact_path.display(),
 				err,
@@ -271,6 +300,21 @@ where
 	outcome
 }
 
+async fn send_handshake(stream: &mut UnixStream, handshake: Handshake) -> io::Result<()> {
+	framed_send(stream, &handshake.encode()).await
+}
+
+async fn recv_handshake(stream: &mut UnixStream) -> io::Result<Handshake> {
+	let handshake_enc = framed_recv(stream).await?;
+	let handshake = Handshake::decode(&mut &handshake_enc[..]).map_err(|_| {
+		io::Error::new(
+			io::ErrorKind::Other,
+			"prepare pvf recv_handshake: failed to decode Handshake".to_owned(),
+		)
+	})?;
+	Ok(handshake)
+}
+
 async fn send_request(
 	stream: &mut UnixStream,
 	pvf_with_params: PvfWithExecutorParams,
@@ -334,6 +378,12 @@ async fn recv_response(stream: &mut UnixStream, pid: u32) -> io::Result<PrepareR
 	Ok(result)
 }
 
+#[derive(Encode, Decode)]
+struct Handshake {
+	/// The sandboxing measures the worker should enforce on itself.
+	security_status: SecurityStatus,
+}
+
 /// The entrypoint that the spawned prepare worker should start with. The `socket_path` specifies
 /// the path to the socket used to communicate with the host.
 ///
@@ -358,6 +408,18 @@ async fn recv_response(stream: &mut UnixStream, pid: u32) -> io::Result<PrepareR
 ///	   send that in the `PrepareResult`.
 pub fn worker_entrypoint(socket_path: &str) {
 	worker_event_loop("prepare", socket_path, |rt_handle, mut stream| async move {
+		let Handshake { security_status } = recv_handshake(&mut stream).await?;
+		// The host has checked that the measures are supported, so failing to enforce them is not
+		// expected. We don't fail the worker over it though, as that would fail the jobs.
+		if let Err(err) = security::apply_worker_restrictions(&security_status) {
+			gum::warn!(
+				target: LOG_TARGET,
+				worker_pid = %std::process::id(),
+				"failed to sandbox the prepare worker: {}",
+				err,
+			);
+		}
+
 		loop {
 			let worker_pid = std::process::id();
 			let (pvf_with_params, dest, preparation_timeout) = recv_request(&mut stream).await?;
@@ -373,7 +435,8 @@ pub fn worker_entrypoint(socket_path: &str) {
 			#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
 			let (memory_tracker_tx, memory_tracker_rx) = channel::<()>();
 			#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
-			let memory_tracker_fut = rt_handle.spawn_blocking(move || memory_tracker_loop(memory_tracker_rx));
+			let memory_tracker_fut =
+				rt_handle.spawn_blocking(move || memory_tracker_loop(memory_tracker_rx));
 
 			// Spawn a new thread that runs the CPU time monitor.
 			let (cpu_time_monitor_tx, cpu_time_monitor_rx) = channel::<()>();
@@ -385,13 +448,41 @@ pub fn worker_entrypoint(socket_path: &str) {
 			// Spawn another thread for preparation.
 			let prepare_fut = rt_handle
 				.spawn_blocking(move || {
-					let result = prepare_artifact(pvf_with_params);
-
-					// Get the `ru_maxrss` stat. If supported, call getrusage for the thread.
-					#[cfg(target_os = "linux")]
-					let result = result.map(|artifact| (artifact, get_max_rss_thread()));
-
-					result
+					let prepare = move || {
+						// The artifact is written by the event loop, so preparation doesn't need
+						// any filesystem access.
+						if let Err(err) =
+							security::restrict_thread_filesystem(&security_status, &[])
+						{
+							gum::warn!(
+								target: LOG_TARGET,
+								%worker_pid,
+								"failed to restrict the filesystem access of the prepare worker: {}",
+								err,
+							);
+						}
+
+						let result = prepare_artifact(pvf_with_params);
+
+						// Get the `ru_maxrss` stat. If supported, call getrusage for the thread.
+						#[cfg(target_os = "linux")]
+						let result = result.map(|artifact| (artifact, get_max_rss_thread()));
+
+						result
+					};
+
+					if security_status.can_enable_landlock {
+						// The restriction cannot be lifted from a thread, while the blocking threads
+						// of the runtime are reused for writing the artifact. Hence prepare on a
+						// dedicated thread.
+						std::thread::spawn(prepare).join().unwrap_or_else(|panic_payload| {
+							Err(PrepareError::Panic(crate::error::stringify_panic_payload(
+								panic_payload,
+							)))
+						})
+					} else {
+						prepare()
+					}
 				})
 				.fuse();
 
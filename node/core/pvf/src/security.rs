@@ -0,0 +1,312 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Sandboxing of the worker processes.
+//!
+//! The workers run untrusted PVF code, so as a defence in depth against an escape from wasmtime the
+//! host can opt into confining them on Linux:
+//!
+//! - The workers are spawned in their own user and network namespace, so they don't have any
+//!   network interfaces to talk to.
+//!
+//! - A seccomp filter kills the worker as soon as it attempts any networking syscall.
+//!
+//! - Landlock restricts the filesystem access of the threads that process the jobs. The thread
+//!   preparing an artifact cannot access the filesystem at all, since the artifact is written by
+//!   the worker's event loop. The thread executing an artifact can only read from the artifact
+//!   cache directory.
+//!
+//! The host checks which of these measures are supported on the system at startup and only
+//! enforces those.
+
+use crate::LOG_TARGET;
+use parity_scale_codec::{Decode, Encode};
+use std::path::Path;
+
+/// The sandboxing measures that are enforced on the workers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Encode, Decode)]
+pub struct SecurityStatus {
+	/// Whether the filesystem access of the workers is restricted with landlock.
+	pub can_enable_landlock: bool,
+	/// Whether networking syscalls of the workers are blocked with seccomp.
+	pub can_enable_seccomp: bool,
+	/// Whether the workers are spawned in their own network namespace.
+	pub can_unshare_network: bool,
+}
+
+impl SecurityStatus {
+	/// Check which of the sandboxing measures are supported on this system.
+	///
+	/// The `program_path` is the worker binary, which is used to check whether new processes can
+	/// be spawned in their own network namespace.
+	#[cfg(target_os = "linux")]
+	pub fn check(program_path: &Path) -> Self {
+		Self {
+			can_enable_landlock: landlock::check_is_fully_enabled(),
+			can_enable_seccomp: seccomp::check_is_fully_enabled(),
+			can_unshare_network: unshare::check_can_unshare_network(program_path),
+		}
+	}
+
+	/// Check which of the sandboxing measures are supported on this system.
+	///
+	/// Sandboxing is only supported on Linux, so none of them are.
+	#[cfg(not(target_os = "linux"))]
+	pub fn check(_program_path: &Path) -> Self {
+		Self::default()
+	}
+
+	/// Log the enforced measures, warning about those which are not supported.
+	pub fn log(&self) {
+		if cfg!(not(target_os = "linux")) {
+			gum::warn!(
+				target: LOG_TARGET,
+				"Sandboxing of PVF workers is only supported on Linux. The workers will not be sandboxed.",
+			);
+			return
+		}
+
+		gum::info!(
+			target: LOG_TARGET,
+			landlock = self.can_enable_landlock,
+			seccomp = self.can_enable_seccomp,
+			unshare_network = self.can_unshare_network,
+			"Sandboxing of PVF workers enabled",
+		);
+
+		if !self.can_enable_landlock {
+			gum::warn!(
+				target: LOG_TARGET,
+				"Cannot enable landlock, the filesystem access of PVF workers will not be restricted. \
+				Landlock requires Linux 5.13 or newer.",
+			);
+		}
+		if !self.can_enable_seccomp {
+			gum::warn!(
+				target: LOG_TARGET,
+				"Cannot enable seccomp, networking syscalls of PVF workers will not be blocked.",
+			);
+		}
+		if !self.can_unshare_network {
+			gum::warn!(
+				target: LOG_TARGET,
+				"Cannot unshare the network namespace, PVF workers will share the network of the host. \
+				This requires unprivileged user namespaces to be enabled.",
+			);
+		}
+	}
+}
+
+/// Restrictions which are applied to the whole worker process.
+///
+/// Should be called by the worker once it connected to the host.
+#[cfg(target_os = "linux")]
+pub fn apply_worker_restrictions(status: &SecurityStatus) -> Result<(), String> {
+	if status.can_enable_seccomp {
+		seccomp::try_restrict_all_threads()?;
+	}
+	Ok(())
+}
+
+/// Restrictions which are applied to the whole worker process.
+///
+/// Sandboxing is only supported on Linux, so this does nothing.
+#[cfg(not(target_os = "linux"))]
+pub fn apply_worker_restrictions(_status: &SecurityStatus) -> Result<(), String> {
+	Ok(())
+}
+
+/// Restrict the filesystem access of the calling thread to reading beneath `read_paths`.
+///
+/// Passing no paths denies any filesystem access. The restriction is applied only once per thread,
+/// so this may be called for each job processed by a thread.
+#[cfg(target_os = "linux")]
+pub fn restrict_thread_filesystem(
+	status: &SecurityStatus,
+	read_paths: &[&Path],
+) -> Result<(), String> {
+	if status.can_enable_landlock {
+		landlock::try_restrict_thread_once(read_paths)?;
+	}
+	Ok(())
+}
+
+/// Restrict the filesystem access of the calling thread to reading beneath `read_paths`.
+///
+/// Sandboxing is only supported on Linux, so this does nothing.
+#[cfg(not(target_os = "linux"))]
+pub fn restrict_thread_filesystem(
+	_status: &SecurityStatus,
+	_read_paths: &[&Path],
+) -> Result<(), String> {
+	Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub mod landlock {
+	//! Restricting the filesystem access of a thread with landlock.
+
+	use landlock::{
+		path_beneath_rules, Access, AccessFs, Ruleset, RulesetAttr, RulesetCreatedAttr,
+		RulesetStatus, ABI,
+	};
+	use std::{cell::Cell, path::Path};
+
+	/// The landlock ABI we rely on. The first version is available since Linux 5.13.
+	const LANDLOCK_ABI: ABI = ABI::V1;
+
+	thread_local! {
+		/// Whether the current thread is already restricted.
+		static RESTRICTED: Cell<bool> = Cell::new(false);
+	}
+
+	/// Restrict the calling thread to reading beneath `read_paths`, denying any other filesystem
+	/// access. Threads spawned afterwards by this thread inherit the restriction.
+	pub fn try_restrict_thread(read_paths: &[&Path]) -> Result<RulesetStatus, String> {
+		let status = Ruleset::default()
+			.handle_access(AccessFs::from_all(LANDLOCK_ABI))
+			.and_then(|ruleset| ruleset.create())
+			.and_then(|ruleset| {
+				ruleset.add_rules(path_beneath_rules(read_paths, AccessFs::from_read(LANDLOCK_ABI)))
+			})
+			.and_then(|ruleset| ruleset.restrict_self())
+			.map_err(|e| format!("landlock: {}", e))?;
+
+		Ok(status.ruleset)
+	}
+
+	/// Like [`try_restrict_thread`], but does nothing if the calling thread is already restricted.
+	///
+	/// Only a limited number of landlock rulesets can be stacked, so threads processing many jobs
+	/// must not be restricted repeatedly.
+	pub fn try_restrict_thread_once(read_paths: &[&Path]) -> Result<(), String> {
+		if RESTRICTED.with(|restricted| restricted.get()) {
+			return Ok(())
+		}
+
+		match try_restrict_thread(read_paths)? {
+			RulesetStatus::FullyEnforced => {
+				RESTRICTED.with(|restricted| restricted.set(true));
+				Ok(())
+			},
+			status => Err(format!("landlock: ruleset not fully enforced: {:?}", status)),
+		}
+	}
+
+	/// Check whether landlock is fully supported, by restricting a throwaway thread.
+	pub fn check_is_fully_enabled() -> bool {
+		std::thread::spawn(|| matches!(try_restrict_thread(&[]), Ok(RulesetStatus::FullyEnforced)))
+			.join()
+			.unwrap_or(false)
+	}
+}
+
+#[cfg(target_os = "linux")]
+pub mod seccomp {
+	//! Blocking networking syscalls with seccomp.
+
+	use seccompiler::{
+		apply_filter, apply_filter_all_threads, BpfProgram, SeccompAction, SeccompFilter,
+		TargetArch,
+	};
+
+	/// The networking syscalls the workers never need. They are connected to the host before the
+	/// filter is applied and only use the already open socket afterwards.
+	const BLOCKED_SYSCALLS: &[libc::c_long] = &[
+		libc::SYS_socket,
+		libc::SYS_socketpair,
+		libc::SYS_connect,
+		libc::SYS_bind,
+		libc::SYS_listen,
+		libc::SYS_accept,
+		libc::SYS_accept4,
+	];
+
+	fn filter() -> Result<BpfProgram, String> {
+		let arch = TargetArch::try_from(std::env::consts::ARCH)
+			.map_err(|e| format!("seccomp: unsupported architecture: {}", e))?;
+		let rules = BLOCKED_SYSCALLS.iter().map(|syscall| (*syscall as i64, vec![])).collect();
+
+		SeccompFilter::new(
+			rules,
+			// Allow any syscall that is not blocked.
+			SeccompAction::Allow,
+			// Kill the worker as soon as it attempts any networking.
+			SeccompAction::KillProcess,
+			arch,
+		)
+		.and_then(BpfProgram::try_from)
+		.map_err(|e| format!("seccomp: {}", e))
+	}
+
+	/// Apply the filter to all threads of the process.
+	pub fn try_restrict_all_threads() -> Result<(), String> {
+		apply_filter_all_threads(&filter()?).map_err(|e| format!("seccomp: {}", e))
+	}
+
+	/// Check whether seccomp is supported, by applying the filter to a throwaway thread.
+	pub fn check_is_fully_enabled() -> bool {
+		std::thread::spawn(|| filter().and_then(|f| apply_filter(&f).map_err(|e| e.to_string())))
+			.join()
+			.map_or(false, |result| result.is_ok())
+	}
+}
+
+#[cfg(target_os = "linux")]
+pub mod unshare {
+	//! Spawning the workers in their own network namespace.
+
+	use std::{io, os::unix::process::CommandExt, path::Path, process::Stdio};
+
+	/// Move the calling process into a new user and network namespace.
+	///
+	/// A new user namespace is required to create a network namespace without privileges. This
+	/// must be called from a single-threaded process, i.e. between forking and executing the
+	/// worker.
+	pub fn unshare_network() -> io::Result<()> {
+		// SAFETY: `unshare` does not touch any memory of the process.
+		if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } == 0 {
+			Ok(())
+		} else {
+			Err(io::Error::last_os_error())
+		}
+	}
+
+	/// Check whether a process can be spawned in its own network namespace.
+	///
+	/// Spawning fails if unsharing fails, so the spawned process is killed right away.
+	pub fn check_can_unshare_network(program_path: &Path) -> bool {
+		let mut command = std::process::Command::new(program_path);
+		command
+			.arg("--version")
+			.stdin(Stdio::null())
+			.stdout(Stdio::null())
+			.stderr(Stdio::null());
+		// SAFETY: `unshare_network` is async-signal-safe, as it only performs a syscall.
+		unsafe {
+			command.pre_exec(unshare_network);
+		}
+
+		match command.spawn() {
+			Ok(mut child) => {
+				let _ = child.kill();
+				let _ = child.wait();
+				true
+			},
+			Err(_) => false,
+		}
+	}
+}
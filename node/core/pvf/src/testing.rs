@@ -22,7 +22,10 @@
 use polkadot_primitives::vstaging::ExecutorParams;
 
 pub mod worker_common {
-	pub use crate::worker_common::{spawn_with_program_path, SpawnErr};
+	pub use crate::{
+		security::SecurityStatus,
+		worker_common::{spawn_with_program_path, SpawnErr},
+	};
 }
 
 /// A function that emulates the stitches together behaviors of the preparation and the execution
@@ -16,7 +16,7 @@
 
 //! Common logic for implementation of worker processes.
 
-use crate::LOG_TARGET;
+use crate::{security::SecurityStatus, LOG_TARGET};
 use cpu_time::ProcessTime;
 use futures::{never::Never, FutureExt as _};
 use futures_timer::Delay;
@@ -52,6 +52,7 @@ pub async fn spawn_with_program_path(
 	program_path: impl Into<PathBuf>,
 	extra_args: &'static [&'static str],
 	spawn_timeout: Duration,
+	security_status: SecurityStatus,
 ) -> Result<(IdleWorker, WorkerHandle), SpawnErr> {
 	let program_path = program_path.into();
 	with_transient_socket_path(debug_id, |socket_path| {
@@ -68,15 +69,16 @@ pub async fn spawn_with_program_path(
 			})?;
 
 			let handle =
-				WorkerHandle::spawn(program_path, extra_args, socket_path).map_err(|err| {
-					gum::warn!(
-						target: LOG_TARGET,
-						%debug_id,
-						"cannot spawn a worker: {:?}",
-						err,
-					);
-					SpawnErr::ProcessSpawn
-				})?;
+				WorkerHandle::spawn(program_path, extra_args, socket_path, security_status)
+					.map_err(|err| {
+						gum::warn!(
+							target: LOG_TARGET,
+							%debug_id,
+							"cannot spawn a worker: {:?}",
+							err,
+						);
+						SpawnErr::ProcessSpawn
+					})?;
 
 			futures::select! {
 				accept_result = listener.accept().fuse() => {
@@ -277,13 +279,26 @@ impl WorkerHandle {
 		program: impl AsRef<Path>,
 		extra_args: &[&str],
 		socket_path: impl AsRef<Path>,
+		security_status: SecurityStatus,
 	) -> io::Result<Self> {
-		let mut child = process::Command::new(program.as_ref())
+		let mut command = process::Command::new(program.as_ref());
+		command
 			.args(extra_args)
 			.arg(socket_path.as_ref().as_os_str())
 			.stdout(std::process::Stdio::piped())
-			.kill_on_drop(true)
-			.spawn()?;
+			.kill_on_drop(true);
+
+		#[cfg(target_os = "linux")]
+		if security_status.can_unshare_network {
+			// SAFETY: `unshare_network` is async-signal-safe, as it only performs a syscall.
+			unsafe {
+				command.pre_exec(crate::security::unshare::unshare_network);
+			}
+		}
+		#[cfg(not(target_os = "linux"))]
+		let _ = security_status;
+
+		let mut child = command.spawn()?;
 
 		let child_id = child
 			.id()
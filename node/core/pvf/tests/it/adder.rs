@@ -51,6 +51,37 @@ async fn execute_good_block_on_parent() {
 	assert_eq!(new_head.post_state, hash_state(512));
 }
 
+#[tokio::test]
+async fn execute_good_block_on_parent_with_sandbox() {
+	let parent_head = HeadData { number: 0, parent_hash: [0; 32], post_state: hash_state(0) };
+
+	let block_data = BlockData { state: 0, add: 512 };
+
+	let host = TestHost::new_with_config(|cfg| {
+		cfg.enable_sandbox = true;
+	});
+
+	let ret = host
+		.validate_candidate(
+			adder::wasm_binary_unwrap(),
+			ValidationParams {
+				parent_head: GenericHeadData(parent_head.encode()),
+				block_data: GenericBlockData(block_data.encode()),
+				relay_parent_number: 1,
+				relay_parent_storage_root: Default::default(),
+			},
+			Default::default(),
+		)
+		.await
+		.unwrap();
+
+	let new_head = HeadData::decode(&mut &ret.head_data.0[..]).unwrap();
+
+	assert_eq!(new_head.number, 1);
+	assert_eq!(new_head.parent_hash, parent_head.hash());
+	assert_eq!(new_head.post_state, hash_state(512));
+}
+
 #[tokio::test]
 async fn execute_good_chain_on_parent() {
 	let mut number = 0;
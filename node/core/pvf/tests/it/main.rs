@@ -109,6 +109,34 @@ async fn terminates_on_timeout() {
 	assert!(duration < TEST_EXECUTION_TIMEOUT * JOB_TIMEOUT_WALL_CLOCK_FACTOR);
 }
 
+#[tokio::test]
+async fn terminates_on_running_out_of_fuel() {
+	let host = TestHost::new();
+
+	let start = std::time::Instant::now();
+	let result = host
+		.validate_candidate(
+			halt::wasm_binary_unwrap(),
+			ValidationParams {
+				block_data: BlockData(Vec::new()),
+				parent_head: Default::default(),
+				relay_parent_number: 1,
+				relay_parent_storage_root: Default::default(),
+			},
+			ExecutorParams::from(&[ExecutorParam::ExecutionMaxFuel(1_000_000)][..]),
+		)
+		.await;
+
+	match result {
+		Err(ValidationError::InvalidCandidate(InvalidCandidate::OutOfFuel)) => {},
+		r => panic!("{:?}", r),
+	}
+
+	// The budget is exhausted long before the timeout.
+	let duration = std::time::Instant::now().duration_since(start);
+	assert!(duration < TEST_EXECUTION_TIMEOUT);
+}
+
 #[tokio::test]
 async fn ensure_parallel_execution() {
 	// Run some jobs that do not complete, thus timing out.
@@ -15,14 +15,21 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::PUPPET_EXE;
-use polkadot_node_core_pvf::testing::worker_common::{spawn_with_program_path, SpawnErr};
+use polkadot_node_core_pvf::testing::worker_common::{
+	spawn_with_program_path, SecurityStatus, SpawnErr,
+};
 use std::time::Duration;
 
 #[tokio::test]
 async fn spawn_timeout() {
-	let result =
-		spawn_with_program_path("integration-test", PUPPET_EXE, &["sleep"], Duration::from_secs(2))
-			.await;
+	let result = spawn_with_program_path(
+		"integration-test",
+		PUPPET_EXE,
+		&["sleep"],
+		Duration::from_secs(2),
+		SecurityStatus::default(),
+	)
+	.await;
 	assert!(matches!(result, Err(SpawnErr::AcceptTimeout)));
 }
 
@@ -33,6 +40,7 @@ async fn should_connect() {
 		PUPPET_EXE,
 		&["prepare-worker"],
 		Duration::from_secs(2),
+		SecurityStatus::default(),
 	)
 	.await
 	.unwrap();
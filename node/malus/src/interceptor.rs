@@ -22,7 +22,7 @@
 
 use polkadot_node_subsystem::*;
 pub use polkadot_node_subsystem::{messages, messages::*, overseer, FromOrchestra};
-use std::{future::Future, pin::Pin};
+use std::{future::Future, pin::Pin, time::Duration};
 
 /// Filter incoming and outgoing messages.
 pub trait MessageInterceptor<Sender>: Send + Sync + Clone + 'static
@@ -47,6 +47,14 @@ where
 		Some(msg)
 	}
 
+	/// The time to hold back an incoming message, that passed `intercept_incoming`, before
+	/// it is handed to the subsystem.
+	///
+	/// Note that the subsystem does not receive any other message in the meantime.
+	fn incoming_delay(&self, _msg: &FromOrchestra<Self::Message>) -> Option<Duration> {
+		None
+	}
+
 	/// Modify outgoing messages.
 	fn intercept_outgoing(
 		&self,
@@ -54,6 +62,27 @@ where
 	) -> Option<<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages> {
 		Some(msg)
 	}
+
+	/// The time to hold back an outgoing message, that passed `intercept_outgoing`, before
+	/// it is sent.
+	///
+	/// Note that the subsystem cannot send any other message in the meantime. Messages sent
+	/// with `send_unbounded_message` are never delayed.
+	fn outgoing_delay(
+		&self,
+		_msg: &<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages,
+	) -> Option<Duration> {
+		None
+	}
+
+	/// Additional messages to send right after an outgoing message, that passed
+	/// `intercept_outgoing`.
+	fn additional_outgoing(
+		&self,
+		_msg: &<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages,
+	) -> Vec<<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages> {
+		Vec::new()
+	}
 }
 
 /// A sender with the outgoing messages filtered.
@@ -85,7 +114,14 @@ where
 					<<Fil as MessageInterceptor<Sender>>::Message as overseer::AssociateOutgoing
 				>::OutgoingMessages as From<OutgoingMessage>>::from(msg);
 		if let Some(msg) = self.message_filter.intercept_outgoing(msg) {
+			if let Some(delay) = self.message_filter.outgoing_delay(&msg) {
+				futures_timer::Delay::new(delay).await;
+			}
+			let additional = self.message_filter.additional_outgoing(&msg);
 			self.inner.send_message(msg).await;
+			for msg in additional {
+				self.inner.send_message(msg).await;
+			}
 		}
 	}
 
@@ -104,7 +140,11 @@ where
 					<<Fil as MessageInterceptor<Sender>>::Message as overseer::AssociateOutgoing
 				>::OutgoingMessages as From<OutgoingMessage>>::from(msg);
 		if let Some(msg) = self.message_filter.intercept_outgoing(msg) {
+			let additional = self.message_filter.additional_outgoing(&msg);
 			self.inner.send_unbounded_message(msg);
+			for msg in additional {
+				self.inner.send_unbounded_message(msg);
+			}
 		}
 	}
 }
@@ -182,6 +222,9 @@ where
 					if let Some(msg) =
 						self.message_filter.intercept_incoming(self.inner.sender(), msg)
 					{
+						if let Some(delay) = self.message_filter.incoming_delay(&msg) {
+							futures_timer::Delay::new(delay).await;
+						}
 						return Ok(Some(msg))
 					},
 			}
@@ -192,6 +235,9 @@ where
 		loop {
 			let msg = self.inner.recv().await?;
 			if let Some(msg) = self.message_filter.intercept_incoming(self.inner.sender(), msg) {
+				if let Some(delay) = self.message_filter.incoming_delay(&msg) {
+					futures_timer::Delay::new(delay).await;
+				}
 				return Ok(msg)
 			}
 		}
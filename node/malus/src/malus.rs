@@ -36,6 +36,12 @@ enum NemesisVariant {
 	BackGarbageCandidate(BackGarbageCandidateOptions),
 	/// Delayed disputing of ancestors that are perfectly fine.
 	DisputeAncestor(DisputeAncestorOptions),
+	/// Drop or delay messages according to a behaviour spec loaded from a file.
+	Scripted(ScriptedOptions),
+	/// Serve missing or broken erasure chunks.
+	WithholdAvailability(WithholdAvailabilityOptions),
+	/// Trigger approval assignments, but never distribute the approvals.
+	ApprovalNoShow(ApprovalNoShowOptions),
 
 	#[allow(missing_docs)]
 	#[command(name = "prepare-worker", hide = true)]
@@ -88,6 +94,26 @@ impl MalusCli {
 					finality_delay,
 				)?
 			},
+			NemesisVariant::Scripted(opts) => {
+				let ScriptedOptions { spec, cli } = opts;
+				let spec = BehaviourSpec::load(&spec)?;
+
+				polkadot_cli::run_node(cli, ScriptedBehaviour { spec }, finality_delay)?
+			},
+			NemesisVariant::WithholdAvailability(opts) => {
+				let WithholdAvailabilityOptions { misbehavior, percentage, cli } = opts;
+
+				polkadot_cli::run_node(
+					cli,
+					WithholdAvailability { misbehavior, percentage },
+					finality_delay,
+				)?
+			},
+			NemesisVariant::ApprovalNoShow(opts) => {
+				let ApprovalNoShowOptions { percentage, cli } = opts;
+
+				polkadot_cli::run_node(cli, ApprovalNoShow { percentage }, finality_delay)?
+			},
 			NemesisVariant::PvfPrepareWorker(cmd) => {
 				#[cfg(target_os = "android")]
 				{
@@ -197,6 +223,69 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn spec_works_scripted() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"scripted",
+			"--spec",
+			"behaviour.toml",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::Scripted(run),
+			..
+		} => {
+			assert_eq!(run.spec, std::path::PathBuf::from("behaviour.toml"));
+			assert!(run.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn misbehavior_works_withhold_availability() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"withhold-availability",
+			"--misbehavior",
+			"wrong-proof",
+			"--percentage",
+			"50",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::WithholdAvailability(run),
+			..
+		} => {
+			assert_eq!(
+				clap::ValueEnum::to_possible_value(&run.misbehavior).unwrap().get_name(),
+				"wrong-proof",
+			);
+			assert_eq!(run.percentage, 50);
+			assert!(run.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn percentage_works_approval_no_show() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"approval-no-show",
+			"--percentage",
+			"70",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::ApprovalNoShow(run),
+			..
+		} => {
+			assert_eq!(run.percentage, 70);
+			assert!(run.cli.run.base.bob);
+		});
+	}
+
 	#[test]
 	#[should_panic]
 	fn validate_range_for_percentage() {
@@ -0,0 +1,119 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node that triggers its approval assignments but never distributes the
+//! corresponding approvals, so that it is seen as a no-show by the rest of the network.
+//! This exercises the escalation to later tranches in approval voting.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	prepared_overseer_builder,
+	service::{
+		AuthorityDiscoveryApi, AuxStore, BabeApi, Block, Error, HeaderBackend, Overseer,
+		OverseerConnector, OverseerGen, OverseerGenArgs, OverseerHandle, ParachainHost,
+		ProvideRuntimeApi,
+	},
+	Cli,
+};
+use polkadot_node_subsystem::{overseer, SpawnGlue};
+use sp_core::traits::SpawnNamed;
+
+use crate::{interceptor::*, shared::MALUS};
+
+use rand::distributions::{Bernoulli, Distribution};
+use std::sync::Arc;
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct ApprovalNoShowOptions {
+	/// Determines the percentage of approvals that are withheld after the assignment was
+	/// distributed. Defaults to 100% of approvals.
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// An interceptor of the approval voting subsystem that lets the assignments through, but drops
+/// the approvals.
+#[derive(Clone)]
+pub struct WithholdApprovals {
+	distribution: Bernoulli,
+}
+
+impl<Sender> MessageInterceptor<Sender> for WithholdApprovals
+where
+	Sender: overseer::ApprovalVotingSenderTrait + Clone + Send + 'static,
+{
+	type Message = ApprovalVotingMessage;
+
+	fn intercept_outgoing(
+		&self,
+		msg: overseer::ApprovalVotingOutgoingMessages,
+	) -> Option<overseer::ApprovalVotingOutgoingMessages> {
+		match msg {
+			overseer::ApprovalVotingOutgoingMessages::ApprovalDistributionMessage(
+				ApprovalDistributionMessage::DistributeApproval(vote),
+			) if self.distribution.sample(&mut rand::thread_rng()) => {
+				gum::info!(
+					target: MALUS,
+					candidate_indices = ?vote.candidate_indices,
+					block_hash = ?vote.block_hash,
+					validator_index = ?vote.validator,
+					"😈 Not distributing the approval, becoming a no-show.",
+				);
+				None
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+/// Generates an overseer that replaces the approval voting subsystem with our malicious variant.
+pub(crate) struct ApprovalNoShow {
+	/// The probability of behaving maliciously.
+	pub percentage: u8,
+}
+
+impl OverseerGen for ApprovalNoShow {
+	fn generate<'a, Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'a, Spawner, RuntimeClient>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		let approval_filter = WithholdApprovals {
+			distribution: Bernoulli::new(f64::from(self.percentage) / 100.0)
+				.expect("Invalid probability! Percentage must be in range [0..=100]."),
+		};
+
+		prepared_overseer_builder(args)?
+			.replace_approval_voting(move |approval_voting_subsystem| {
+				InterceptedSubsystem::new(approval_voting_subsystem, approval_filter)
+			})
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
+	}
+}
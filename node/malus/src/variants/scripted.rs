@@ -0,0 +1,1016 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This variant of Malus applies a declarative behaviour spec, loaded from a TOML or JSON file,
+//! to the messages received and sent by a set of subsystems.
+//!
+//! Each rule of the spec targets a subsystem, the messages it receives or sends and, optionally,
+//! a message variant, e.g. `DistributeAssignment` for the messages `approval-distribution`
+//! receives or `QueryChunk` for those `availability-store` receives. Matching messages are
+//! dropped or delayed, with the configured probability and only within the configured session
+//! window. The statements `candidate-backing` shares can also be equivocated, i.e. followed by a
+//! conflicting statement signed with the same key:
+//!
+//! ```toml
+//! [[rules]]
+//! subsystem = "approval-distribution"
+//! message = "DistributeApproval"
+//! action = { kind = "drop" }
+//! percentage = 50
+//! sessions = { start = 2, end = 5 }
+//!
+//! [[rules]]
+//! subsystem = "availability-store"
+//! message = "QueryChunk"
+//! action = { kind = "delay", millis = 2000 }
+//!
+//! [[rules]]
+//! subsystem = "candidate-backing"
+//! direction = "outgoing"
+//! message = "Share"
+//! action = { kind = "equivocate" }
+//! ```
+
+use polkadot_cli::{
+	prepared_overseer_builder,
+	service::{
+		AuthorityDiscoveryApi, AuxStore, BabeApi, Block, Error, HeaderBackend, Overseer,
+		OverseerConnector, OverseerGen, OverseerGenArgs, OverseerHandle, ParachainHost,
+		ProvideRuntimeApi,
+	},
+	Cli,
+};
+use polkadot_node_primitives::{SignedFullStatement, Statement};
+use polkadot_node_subsystem::{
+	messages::{
+		ApprovalDistributionMessage, ApprovalVotingMessage, AvailabilityDistributionMessage,
+		AvailabilityRecoveryMessage, AvailabilityStoreMessage, BitfieldDistributionMessage,
+		CandidateBackingMessage, CandidateValidationMessage, ChainApiMessage,
+		ChainSelectionMessage, CollatorProtocolMessage, DisputeCoordinatorMessage,
+		DisputeDistributionMessage, NetworkBridgeTxMessage, ProvisionerMessage, RuntimeApiMessage,
+		RuntimeApiRequest, StatementDistributionMessage,
+	},
+	overseer, SpawnGlue,
+};
+use polkadot_primitives::{Hash, SessionIndex, SigningContext, ValidatorId};
+use sp_application_crypto::AppKey;
+use sp_core::traits::SpawnNamed;
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+
+use crate::{interceptor::*, shared::MALUS};
+
+use color_eyre::eyre;
+use futures::channel::oneshot;
+use rand::distributions::{Bernoulli, Distribution};
+use serde::Deserialize;
+use std::{
+	marker::PhantomData,
+	path::{Path, PathBuf},
+	sync::{Arc, RwLock},
+	time::Duration,
+};
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct ScriptedOptions {
+	/// Path to the behaviour spec. Files with a `.json` extension are parsed as JSON, everything
+	/// else as TOML.
+	#[clap(long)]
+	pub spec: PathBuf,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// A subsystem whose messages can be intercepted by a behaviour spec.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TargetSubsystem {
+	ApprovalDistribution,
+	ApprovalVoting,
+	AvailabilityDistribution,
+	AvailabilityRecovery,
+	AvailabilityStore,
+	BitfieldDistribution,
+	CandidateBacking,
+	DisputeCoordinator,
+	DisputeDistribution,
+	StatementDistribution,
+}
+
+/// The messages of a subsystem a rule applies to.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+	/// The messages received by the subsystem.
+	Incoming,
+	/// The messages sent by the subsystem.
+	Outgoing,
+}
+
+impl Default for Direction {
+	fn default() -> Self {
+		Self::Incoming
+	}
+}
+
+/// What to do with a message matched by a rule.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Action {
+	/// Never deliver the message.
+	Drop,
+	/// Hold the message back for the given number of milliseconds.
+	///
+	/// Note that the messages received or sent after the delayed one are held back as well.
+	/// Outgoing messages sent without a bound on the channel are never delayed.
+	Delay { millis: u64 },
+	/// Follow a statement shared by `candidate-backing` with a conflicting statement signed with
+	/// the same key. Only `Seconded` statements are equivocated, by seconding a candidate with
+	/// different head data.
+	Equivocate,
+}
+
+/// The range of sessions in which a rule is active. Both bounds are optional, `start` is
+/// inclusive and `end` is exclusive.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SessionWindow {
+	#[serde(default)]
+	pub start: Option<SessionIndex>,
+	#[serde(default)]
+	pub end: Option<SessionIndex>,
+}
+
+impl SessionWindow {
+	fn contains(&self, session: SessionIndex) -> bool {
+		self.start.map_or(true, |start| session >= start) &&
+			self.end.map_or(true, |end| session < end)
+	}
+}
+
+fn default_percentage() -> u8 {
+	100
+}
+
+/// A single rule of a behaviour spec.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+	/// The subsystem receiving or sending the messages.
+	pub subsystem: TargetSubsystem,
+	/// Whether the messages received or sent by the subsystem are matched. Defaults to the
+	/// received ones.
+	#[serde(default)]
+	pub direction: Direction,
+	/// The name of the message variant to match, e.g. `Share` for the
+	/// `StatementDistributionMessage::Share` messages `candidate-backing` sends. All messages are
+	/// matched if not set.
+	#[serde(default)]
+	pub message: Option<String>,
+	/// What to do with the matched messages.
+	pub action: Action,
+	/// The percentage of matched messages the action is applied to. Defaults to 100%.
+	#[serde(default = "default_percentage")]
+	pub percentage: u8,
+	/// The sessions in which the rule is active. The rule is always active if not set.
+	#[serde(default)]
+	pub sessions: Option<SessionWindow>,
+}
+
+/// A declarative description of the misbehaviour of a malus node.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BehaviourSpec {
+	#[serde(default)]
+	pub rules: Vec<Rule>,
+}
+
+impl BehaviourSpec {
+	/// Load and validate the spec from the given file.
+	pub fn load(path: &Path) -> eyre::Result<Self> {
+		let raw = std::fs::read_to_string(path)?;
+		if path.extension().map_or(false, |ext| ext == "json") {
+			Self::from_json(&raw)
+		} else {
+			Self::from_toml(&raw)
+		}
+	}
+
+	/// Parse and validate a spec in the TOML format.
+	pub fn from_toml(raw: &str) -> eyre::Result<Self> {
+		let spec: Self = toml::from_str(raw)?;
+		spec.validate()?;
+		Ok(spec)
+	}
+
+	/// Parse and validate a spec in the JSON format.
+	pub fn from_json(raw: &str) -> eyre::Result<Self> {
+		let spec: Self = serde_json::from_str(raw)?;
+		spec.validate()?;
+		Ok(spec)
+	}
+
+	fn validate(&self) -> eyre::Result<()> {
+		for rule in &self.rules {
+			if rule.percentage > 100 {
+				return Err(eyre::eyre!(
+					"Invalid percentage {} for {:?}! Percentage must be in range [0..=100].",
+					rule.percentage,
+					rule.subsystem,
+				))
+			}
+			if rule.action == Action::Equivocate &&
+				(rule.subsystem != TargetSubsystem::CandidateBacking ||
+					rule.direction != Direction::Outgoing ||
+					rule.message.as_ref().map_or(false, |message| message != "Share"))
+			{
+				return Err(eyre::eyre!(
+					"Invalid rule for {:?}! Only the outgoing `Share` messages of `candidate-backing` can be equivocated.",
+					rule.subsystem,
+				))
+			}
+		}
+		Ok(())
+	}
+
+	/// Create an interceptor applying the rules targeting `subsystem`.
+	fn interceptor<M>(
+		&self,
+		subsystem: TargetSubsystem,
+		equivocator: Equivocator,
+	) -> ScriptedInterceptor<M> {
+		let rules = self
+			.rules
+			.iter()
+			.filter(|rule| rule.subsystem == subsystem)
+			.map(|rule| ActiveRule {
+				direction: rule.direction,
+				message: rule.message.clone(),
+				action: rule.action,
+				distribution: Bernoulli::new(f64::from(rule.percentage) / 100.0)
+					.expect("percentage is validated when the spec is loaded; qed"),
+				sessions: rule.sessions,
+			})
+			.collect();
+		ScriptedInterceptor { rules: Arc::new(rules), equivocator, _phantom: PhantomData }
+	}
+}
+
+/// Keeps track of the latest session observed by the runtime API subsystem.
+#[derive(Clone, Debug, Default)]
+pub struct SessionTracker(Arc<RwLock<Option<SessionIndex>>>);
+
+impl SessionTracker {
+	fn current(&self) -> Option<SessionIndex> {
+		*self.0.read().expect("the lock is never poisoned; qed")
+	}
+
+	fn note(&self, session: SessionIndex) {
+		let mut current = self.0.write().expect("the lock is never poisoned; qed");
+		if current.map_or(true, |current| current < session) {
+			*current = Some(session);
+		}
+	}
+}
+
+/// An interceptor of the runtime API subsystem that records the sessions of the requested
+/// blocks, as requested by the other subsystems on every new leaf.
+#[derive(Clone)]
+pub struct TrackSessions<Spawner> {
+	sessions: SessionTracker,
+	spawner: Spawner,
+}
+
+impl<Sender, Spawner> MessageInterceptor<Sender> for TrackSessions<Spawner>
+where
+	Sender: overseer::RuntimeApiSenderTrait + Clone + Send + 'static,
+	Spawner: overseer::gen::Spawner + Clone + 'static,
+{
+	type Message = RuntimeApiMessage;
+
+	fn intercept_incoming(
+		&self,
+		_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg:
+					RuntimeApiMessage::Request(
+						relay_parent,
+						RuntimeApiRequest::SessionIndexForChild(response_sender),
+					),
+			} => {
+				let (tx, rx) = oneshot::channel();
+				let sessions = self.sessions.clone();
+				self.spawner.spawn(
+					"malus-track-sessions",
+					Some("malus"),
+					Box::pin(async move {
+						if let Ok(response) = rx.await {
+							if let Ok(session) = &response {
+								sessions.note(*session);
+							}
+							let _ = response_sender.send(response);
+						}
+					}),
+				);
+				Some(FromOrchestra::Communication {
+					msg: RuntimeApiMessage::Request(
+						relay_parent,
+						RuntimeApiRequest::SessionIndexForChild(tx),
+					),
+				})
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+struct ActiveRule {
+	direction: Direction,
+	message: Option<String>,
+	action: Action,
+	distribution: Bernoulli,
+	sessions: Option<SessionWindow>,
+}
+
+impl ActiveRule {
+	/// Whether the rule should be applied to a message of the given kind in the given session.
+	fn fires(&self, kind: &str, session: Option<SessionIndex>) -> bool {
+		if self.message.as_ref().map_or(false, |message| message != kind) {
+			return false
+		}
+		let in_window = match (self.sessions, session) {
+			(None, _) => true,
+			(Some(window), Some(session)) => window.contains(session),
+			// Windowed rules stay inactive until the first session is known.
+			(Some(_), None) => false,
+		};
+		in_window && self.distribution.sample(&mut rand::thread_rng())
+	}
+}
+
+/// A message whose variant can be matched by the rules of a behaviour spec.
+pub trait MessageKind {
+	/// The name of the variant of the message. For the messages sent by a subsystem, this is the
+	/// variant of the message the receiving subsystem gets.
+	fn kind(&self) -> &'static str;
+}
+
+macro_rules! impl_message_kind {
+	($($message:ty { $($variant:ident),* $(,)? })*) => {$(
+		impl MessageKind for $message {
+			fn kind(&self) -> &'static str {
+				match self {
+					$(Self::$variant { .. } => stringify!($variant),)*
+				}
+			}
+		}
+	)*};
+}
+
+impl_message_kind! {
+	ApprovalDistributionMessage {
+		NewBlocks,
+		DistributeAssignment,
+		DistributeApproval,
+		NetworkBridgeUpdate,
+		GetApprovalSignatures,
+	}
+	ApprovalVotingMessage {
+		CheckAndImportAssignment,
+		CheckAndImportApproval,
+		ApprovedAncestor,
+		GetApprovalSignaturesForCandidate,
+		BlockApprovalDetails,
+	}
+	AvailabilityDistributionMessage { FetchPoV }
+	AvailabilityRecoveryMessage { RecoverAvailableData }
+	AvailabilityStoreMessage {
+		QueryAvailableData,
+		QueryDataAvailability,
+		QueryChunk,
+		QueryAllChunks,
+		QueryChunkAvailability,
+		StoreChunk,
+		StoreAvailableData,
+	}
+	BitfieldDistributionMessage { DistributeBitfield, NetworkBridgeUpdate }
+	CandidateBackingMessage { GetBackedCandidates, Second, Statement }
+	CandidateValidationMessage { ValidateFromChainState, ValidateFromExhaustive, PreCheck }
+	ChainApiMessage {
+		BlockNumber,
+		BlockHeader,
+		BlockWeight,
+		FinalizedBlockHash,
+		FinalizedBlockNumber,
+		Ancestors,
+	}
+	ChainSelectionMessage {
+		Approved,
+		Leaves,
+		BestLeafContaining,
+		RevertBlocks,
+		BlacklistBlock,
+		UnblacklistBlock,
+		Blacklist,
+		ReevaluateViability,
+		LeafDetails,
+	}
+	CollatorProtocolMessage {
+		CollateOn,
+		DistributeCollation,
+		ReportCollator,
+		NetworkBridgeUpdate,
+		Invalid,
+		Seconded,
+	}
+	DisputeCoordinatorMessage {
+		ImportStatements,
+		RecentDisputes,
+		ActiveDisputes,
+		QueryCandidateVotes,
+		IssueLocalStatement,
+		DetermineUndisputedChain,
+		Status,
+	}
+	DisputeDistributionMessage { SendDispute }
+	NetworkBridgeTxMessage {
+		ReportPeer,
+		DisconnectPeer,
+		SendValidationMessage,
+		SendCollationMessage,
+		SendValidationMessages,
+		SendCollationMessages,
+		SendRequests,
+		ConnectToValidators,
+		ConnectToResolvedValidators,
+	}
+	ProvisionerMessage { RequestInherentData, ProvisionableData }
+	RuntimeApiMessage { Request }
+	StatementDistributionMessage { Share, NetworkBridgeUpdate }
+}
+
+/// The messages sent by a subsystem, which may be equivocated.
+pub trait OutgoingMessage: MessageKind + Sized {
+	/// A message conflicting with this one, signed by `equivocator`, if it can be equivocated.
+	fn equivocation(&self, _equivocator: &Equivocator) -> Option<Self> {
+		None
+	}
+}
+
+macro_rules! impl_outgoing_message {
+	($($outgoing:ty { $($message:ident),* $(,)? })*) => {$(
+		impl MessageKind for $outgoing {
+			fn kind(&self) -> &'static str {
+				match self {
+					$(Self::$message(msg) => msg.kind(),)*
+				}
+			}
+		}
+	)*};
+}
+
+impl_outgoing_message! {
+	overseer::ApprovalDistributionOutgoingMessages { NetworkBridgeTxMessage, ApprovalVotingMessage }
+	overseer::ApprovalVotingOutgoingMessages {
+		ApprovalDistributionMessage,
+		AvailabilityRecoveryMessage,
+		CandidateValidationMessage,
+		ChainApiMessage,
+		ChainSelectionMessage,
+		DisputeCoordinatorMessage,
+		RuntimeApiMessage,
+	}
+	overseer::AvailabilityDistributionOutgoingMessages {
+		AvailabilityStoreMessage,
+		AvailabilityRecoveryMessage,
+		ChainApiMessage,
+		RuntimeApiMessage,
+		NetworkBridgeTxMessage,
+	}
+	overseer::AvailabilityRecoveryOutgoingMessages {
+		NetworkBridgeTxMessage,
+		RuntimeApiMessage,
+		AvailabilityStoreMessage,
+	}
+	overseer::AvailabilityStoreOutgoingMessages { ChainApiMessage, RuntimeApiMessage }
+	overseer::BitfieldDistributionOutgoingMessages {
+		RuntimeApiMessage,
+		NetworkBridgeTxMessage,
+		ProvisionerMessage,
+	}
+	overseer::CandidateBackingOutgoingMessages {
+		CandidateValidationMessage,
+		CollatorProtocolMessage,
+		AvailabilityDistributionMessage,
+		AvailabilityStoreMessage,
+		StatementDistributionMessage,
+		ProvisionerMessage,
+		RuntimeApiMessage,
+	}
+	overseer::DisputeCoordinatorOutgoingMessages {
+		RuntimeApiMessage,
+		ChainApiMessage,
+		DisputeDistributionMessage,
+		CandidateValidationMessage,
+		ApprovalVotingMessage,
+		AvailabilityStoreMessage,
+		AvailabilityRecoveryMessage,
+		ChainSelectionMessage,
+	}
+	overseer::DisputeDistributionOutgoingMessages {
+		RuntimeApiMessage,
+		DisputeCoordinatorMessage,
+		NetworkBridgeTxMessage,
+	}
+	overseer::StatementDistributionOutgoingMessages {
+		NetworkBridgeTxMessage,
+		CandidateBackingMessage,
+		RuntimeApiMessage,
+	}
+}
+
+impl OutgoingMessage for overseer::ApprovalDistributionOutgoingMessages {}
+impl OutgoingMessage for overseer::ApprovalVotingOutgoingMessages {}
+impl OutgoingMessage for overseer::AvailabilityDistributionOutgoingMessages {}
+impl OutgoingMessage for overseer::AvailabilityRecoveryOutgoingMessages {}
+impl OutgoingMessage for overseer::AvailabilityStoreOutgoingMessages {}
+impl OutgoingMessage for overseer::BitfieldDistributionOutgoingMessages {}
+impl OutgoingMessage for overseer::DisputeCoordinatorOutgoingMessages {}
+impl OutgoingMessage for overseer::DisputeDistributionOutgoingMessages {}
+impl OutgoingMessage for overseer::StatementDistributionOutgoingMessages {}
+
+impl OutgoingMessage for overseer::CandidateBackingOutgoingMessages {
+	fn equivocation(&self, equivocator: &Equivocator) -> Option<Self> {
+		match self {
+			Self::StatementDistributionMessage(StatementDistributionMessage::Share(
+				relay_parent,
+				statement,
+			)) => equivocator.equivocate(*relay_parent, statement).map(|statement| {
+				StatementDistributionMessage::Share(*relay_parent, statement).into()
+			}),
+			_ => None,
+		}
+	}
+}
+
+/// Signs statements conflicting with the ones shared by this node.
+#[derive(Clone)]
+pub struct Equivocator {
+	keystore: SyncCryptoStorePtr,
+	sessions: SessionTracker,
+}
+
+impl Equivocator {
+	/// Sign a `Seconded` statement for a candidate conflicting with the one seconded by
+	/// `statement`, with the same key and in the same signing context.
+	fn equivocate(
+		&self,
+		relay_parent: Hash,
+		statement: &SignedFullStatement,
+	) -> Option<SignedFullStatement> {
+		let mut candidate = match statement.payload() {
+			Statement::Seconded(candidate) => candidate.clone(),
+			Statement::Valid(_) => return None,
+		};
+		candidate.commitments.head_data.0.push(0);
+		let conflicting = Statement::Seconded(candidate);
+
+		// The statement is signed in the session of the child of `relay_parent`, which is either
+		// the latest session or, right after a session change, the one before.
+		let latest = self.sessions.current()?;
+		let keys = SyncCryptoStore::sr25519_public_keys(&*self.keystore, ValidatorId::ID);
+		for session_index in [latest, latest.saturating_sub(1)] {
+			let context = SigningContext { session_index, parent_hash: relay_parent };
+			for key in keys.iter().map(|key| ValidatorId::from(*key)) {
+				if statement.as_unchecked().check_signature(&context, &key).is_err() {
+					continue
+				}
+				let payload = conflicting.to_compact().signing_payload(&context);
+				let signature = SyncCryptoStore::sign_with(
+					&*self.keystore,
+					ValidatorId::ID,
+					&(&key).into(),
+					&payload,
+				)
+				.ok()??;
+				return SignedFullStatement::new(
+					conflicting,
+					statement.validator_index(),
+					signature.try_into().ok()?,
+					&context,
+					&key,
+				)
+			}
+		}
+		None
+	}
+}
+
+/// An interceptor applying the rules of a behaviour spec to the messages of a subsystem.
+pub struct ScriptedInterceptor<M> {
+	rules: Arc<Vec<ActiveRule>>,
+	equivocator: Equivocator,
+	_phantom: PhantomData<fn() -> M>,
+}
+
+impl<M> Clone for ScriptedInterceptor<M> {
+	fn clone(&self) -> Self {
+		Self {
+			rules: self.rules.clone(),
+			equivocator: self.equivocator.clone(),
+			_phantom: PhantomData,
+		}
+	}
+}
+
+impl<M> ScriptedInterceptor<M> {
+	/// Find the first rule for the given direction with the given action that fires for the
+	/// message.
+	fn firing_action(
+		&self,
+		direction: Direction,
+		msg: &impl MessageKind,
+		matches: impl Fn(&Action) -> bool,
+	) -> Option<Action> {
+		let mut rules = self
+			.rules
+			.iter()
+			.filter(|rule| rule.direction == direction && matches(&rule.action));
+		let kind = msg.kind();
+		let session = self.equivocator.sessions.current();
+		let action = rules.find(|rule| rule.fires(kind, session)).map(|rule| rule.action)?;
+
+		gum::info!(
+			target: MALUS,
+			?session,
+			?direction,
+			?action,
+			"😈 Applying scripted behaviour to {}.",
+			kind,
+		);
+		Some(action)
+	}
+
+	fn delay(&self, direction: Direction, msg: &impl MessageKind) -> Option<Duration> {
+		match self.firing_action(direction, msg, |action| matches!(action, Action::Delay { .. })) {
+			Some(Action::Delay { millis }) => Some(Duration::from_millis(millis)),
+			_ => None,
+		}
+	}
+
+	/// The conflicting messages to send after the given outgoing message.
+	fn equivocations<O: OutgoingMessage>(&self, msg: &O) -> Vec<O> {
+		self.firing_action(Direction::Outgoing, msg, |action| *action == Action::Equivocate)
+			.and_then(|_| msg.equivocation(&self.equivocator))
+			.into_iter()
+			.collect()
+	}
+}
+
+impl<Sender, M> MessageInterceptor<Sender> for ScriptedInterceptor<M>
+where
+	M: overseer::AssociateOutgoing + MessageKind + Send + 'static,
+	<M as overseer::AssociateOutgoing>::OutgoingMessages: OutgoingMessage,
+	Sender: overseer::SubsystemSender<<M as overseer::AssociateOutgoing>::OutgoingMessages>
+		+ Clone
+		+ 'static,
+{
+	type Message = M;
+
+	fn intercept_incoming(
+		&self,
+		_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication { msg } =>
+				match self
+					.firing_action(Direction::Incoming, &msg, |action| *action == Action::Drop)
+				{
+					Some(_) => None,
+					None => Some(FromOrchestra::Communication { msg }),
+				},
+			// Signals are never intercepted.
+			sig => Some(sig),
+		}
+	}
+
+	fn incoming_delay(&self, msg: &FromOrchestra<Self::Message>) -> Option<Duration> {
+		match msg {
+			FromOrchestra::Communication { msg } => self.delay(Direction::Incoming, msg),
+			FromOrchestra::Signal(_) => None,
+		}
+	}
+
+	fn intercept_outgoing(
+		&self,
+		msg: <Self::Message as overseer::AssociateOutgoing>::OutgoingMessages,
+	) -> Option<<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages> {
+		match self.firing_action(Direction::Outgoing, &msg, |action| *action == Action::Drop) {
+			Some(_) => None,
+			None => Some(msg),
+		}
+	}
+
+	fn outgoing_delay(
+		&self,
+		msg: &<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages,
+	) -> Option<Duration> {
+		self.delay(Direction::Outgoing, msg)
+	}
+
+	fn additional_outgoing(
+		&self,
+		msg: &<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages,
+	) -> Vec<<Self::Message as overseer::AssociateOutgoing>::OutgoingMessages> {
+		self.equivocations(msg)
+	}
+}
+
+/// Generates an overseer that applies a behaviour spec to the messages of all the subsystems that
+/// can be targeted by it.
+pub(crate) struct ScriptedBehaviour {
+	pub spec: BehaviourSpec,
+}
+
+impl OverseerGen for ScriptedBehaviour {
+	fn generate<'a, Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'a, Spawner, RuntimeClient>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		let sessions = SessionTracker::default();
+		let track_sessions =
+			TrackSessions { sessions: sessions.clone(), spawner: SpawnGlue(args.spawner.clone()) };
+		let keystore: SyncCryptoStorePtr = args.keystore.clone();
+		let equivocator = Equivocator { keystore, sessions };
+
+		let spec = &self.spec;
+		let approval_distribution = spec.interceptor::<ApprovalDistributionMessage>(
+			TargetSubsystem::ApprovalDistribution,
+			equivocator.clone(),
+		);
+		let approval_voting = spec.interceptor::<ApprovalVotingMessage>(
+			TargetSubsystem::ApprovalVoting,
+			equivocator.clone(),
+		);
+		let availability_distribution = spec.interceptor::<AvailabilityDistributionMessage>(
+			TargetSubsystem::AvailabilityDistribution,
+			equivocator.clone(),
+		);
+		let availability_recovery = spec.interceptor::<AvailabilityRecoveryMessage>(
+			TargetSubsystem::AvailabilityRecovery,
+			equivocator.clone(),
+		);
+		let availability_store = spec.interceptor::<AvailabilityStoreMessage>(
+			TargetSubsystem::AvailabilityStore,
+			equivocator.clone(),
+		);
+		let bitfield_distribution = spec.interceptor::<BitfieldDistributionMessage>(
+			TargetSubsystem::BitfieldDistribution,
+			equivocator.clone(),
+		);
+		let candidate_backing = spec.interceptor::<CandidateBackingMessage>(
+			TargetSubsystem::CandidateBacking,
+			equivocator.clone(),
+		);
+		let dispute_coordinator = spec.interceptor::<DisputeCoordinatorMessage>(
+			TargetSubsystem::DisputeCoordinator,
+			equivocator.clone(),
+		);
+		let dispute_distribution = spec.interceptor::<DisputeDistributionMessage>(
+			TargetSubsystem::DisputeDistribution,
+			equivocator.clone(),
+		);
+		let statement_distribution = spec.interceptor::<StatementDistributionMessage>(
+			TargetSubsystem::StatementDistribution,
+			equivocator,
+		);
+
+		prepared_overseer_builder(args)?
+			.replace_runtime_api(move |s| InterceptedSubsystem::new(s, track_sessions))
+			.replace_approval_distribution(move |s| {
+				InterceptedSubsystem::new(s, approval_distribution)
+			})
+			.replace_approval_voting(move |s| InterceptedSubsystem::new(s, approval_voting))
+			.replace_availability_distribution(move |s| {
+				InterceptedSubsystem::new(s, availability_distribution)
+			})
+			.replace_availability_recovery(move |s| {
+				InterceptedSubsystem::new(s, availability_recovery)
+			})
+			.replace_availability_store(move |s| InterceptedSubsystem::new(s, availability_store))
+			.replace_bitfield_distribution(move |s| {
+				InterceptedSubsystem::new(s, bitfield_distribution)
+			})
+			.replace_candidate_backing(move |s| InterceptedSubsystem::new(s, candidate_backing))
+			.replace_dispute_coordinator(move |s| InterceptedSubsystem::new(s, dispute_coordinator))
+			.replace_dispute_distribution(move |s| {
+				InterceptedSubsystem::new(s, dispute_distribution)
+			})
+			.replace_statement_distribution(move |s| {
+				InterceptedSubsystem::new(s, statement_distribution)
+			})
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_primitives::ValidatorIndex;
+	use polkadot_primitives_test_helpers::dummy_committed_candidate_receipt;
+	use sp_keystore::testing::KeyStore;
+
+	fn equivocator() -> Equivocator {
+		Equivocator { keystore: Arc::new(KeyStore::new()), sessions: SessionTracker::default() }
+	}
+
+	#[test]
+	fn toml_and_json_specs_are_equivalent() {
+		let from_toml = BehaviourSpec::from_toml(
+			r#"
+			[[rules]]
+			subsystem = "approval-distribution"
+			message = "DistributeApproval"
+			action = { kind = "drop" }
+			percentage = 50
+			sessions = { start = 2, end = 5 }
+
+			[[rules]]
+			subsystem = "availability-store"
+			action = { kind = "delay", millis = 2000 }
+			"#,
+		)
+		.unwrap();
+		let from_json = BehaviourSpec::from_json(
+			r#"{ "rules": [
+				{
+					"subsystem": "approval-distribution",
+					"message": "DistributeApproval",
+					"action": { "kind": "drop" },
+					"percentage": 50,
+					"sessions": { "start": 2, "end": 5 }
+				},
+				{
+					"subsystem": "availability-store",
+					"action": { "kind": "delay", "millis": 2000 }
+				}
+			] }"#,
+		)
+		.unwrap();
+
+		assert_eq!(from_toml, from_json);
+		assert_eq!(from_toml.rules[1].percentage, 100);
+		assert_eq!(from_toml.rules[1].sessions, None);
+		assert_eq!(from_toml.rules[1].action, Action::Delay { millis: 2000 });
+	}
+
+	#[test]
+	fn invalid_percentage_is_rejected() {
+		assert!(BehaviourSpec::from_toml(
+			r#"
+			[[rules]]
+			subsystem = "approval-voting"
+			action = { kind = "drop" }
+			percentage = 101
+			"#,
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn rules_fire_within_session_window_only() {
+		let spec = BehaviourSpec::from_toml(
+			r#"
+			[[rules]]
+			subsystem = "availability-store"
+			message = "QueryChunk"
+			action = { kind = "drop" }
+			sessions = { start = 2, end = 5 }
+			"#,
+		)
+		.unwrap();
+		let interceptor: ScriptedInterceptor<()> =
+			spec.interceptor(TargetSubsystem::AvailabilityStore, equivocator());
+		let rule = &interceptor.rules[0];
+
+		assert!(!rule.fires("QueryChunk", None));
+		assert!(!rule.fires("QueryChunk", Some(1)));
+		assert!(rule.fires("QueryChunk", Some(2)));
+		assert!(rule.fires("QueryChunk", Some(4)));
+		assert!(!rule.fires("QueryChunk", Some(5)));
+		assert!(!rule.fires("QueryAvailableData", Some(3)));
+	}
+
+	#[test]
+	fn message_kind_is_the_variant_name() {
+		let (tx, _) = futures::channel::oneshot::channel();
+		let msg = ChainSelectionMessage::Leaves(tx);
+		assert_eq!(msg.kind(), "Leaves");
+
+		let msg = ChainSelectionMessage::Approved(Hash::zero());
+		assert_eq!(msg.kind(), "Approved");
+		let outgoing: overseer::ApprovalVotingOutgoingMessages = msg.into();
+		assert_eq!(outgoing.kind(), "Approved");
+	}
+
+	#[test]
+	fn equivocation_rules_are_restricted_to_shared_statements() {
+		let spec = |subsystem: &str, direction: &str, message: &str| {
+			BehaviourSpec::from_toml(&format!(
+				r#"
+				[[rules]]
+				subsystem = "{}"
+				direction = "{}"
+				message = "{}"
+				action = {{ kind = "equivocate" }}
+				"#,
+				subsystem, direction, message,
+			))
+		};
+
+		assert!(spec("candidate-backing", "outgoing", "Share").is_ok());
+		assert!(spec("candidate-backing", "incoming", "Share").is_err());
+		assert!(spec("candidate-backing", "outgoing", "Second").is_err());
+		assert!(spec("approval-voting", "outgoing", "Share").is_err());
+	}
+
+	#[test]
+	fn seconded_statements_are_equivocated() {
+		let equivocator = equivocator();
+		let key: ValidatorId =
+			SyncCryptoStore::sr25519_generate_new(&*equivocator.keystore, ValidatorId::ID, None)
+				.unwrap()
+				.into();
+		equivocator.sessions.note(2);
+
+		let relay_parent = Hash::repeat_byte(1);
+		let context = SigningContext { session_index: 1, parent_hash: relay_parent };
+		let candidate = dummy_committed_candidate_receipt(relay_parent);
+		let statement = futures::executor::block_on(SignedFullStatement::sign(
+			&equivocator.keystore,
+			Statement::Seconded(candidate.clone()),
+			&context,
+			ValidatorIndex(3),
+			&key,
+		))
+		.unwrap()
+		.unwrap();
+
+		let conflicting = equivocator.equivocate(relay_parent, &statement).unwrap();
+		assert_eq!(conflicting.validator_index(), ValidatorIndex(3));
+		assert!(conflicting.as_unchecked().check_signature(&context, &key).is_ok());
+		match conflicting.payload() {
+			Statement::Seconded(conflicting) => {
+				assert_ne!(conflicting.hash(), candidate.hash());
+				assert_eq!(conflicting.descriptor, candidate.descriptor);
+			},
+			Statement::Valid(_) => panic!("the seconded candidate must be equivocated"),
+		}
+
+		let valid = futures::executor::block_on(SignedFullStatement::sign(
+			&equivocator.keystore,
+			Statement::Valid(candidate.hash()),
+			&context,
+			ValidatorIndex(3),
+			&key,
+		))
+		.unwrap()
+		.unwrap();
+		assert!(equivocator.equivocate(relay_parent, &valid).is_none());
+	}
+
+	#[test]
+	fn session_tracker_keeps_the_latest_session() {
+		let tracker = SessionTracker::default();
+		assert_eq!(tracker.current(), None);
+		tracker.note(3);
+		tracker.note(2);
+		assert_eq!(tracker.current(), Some(3));
+	}
+}
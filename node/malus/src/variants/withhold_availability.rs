@@ -0,0 +1,202 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node that serves missing or broken erasure chunks from its availability store.
+//!
+//! Chunks are queried from the availability store when answering `ChunkFetchingRequest`s, so
+//! other validators fetching chunks from this node either get no chunk at all, a chunk with
+//! corrupted data or a chunk with a corrupted Merkle proof. This exercises the fallbacks of
+//! availability distribution and recovery.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	prepared_overseer_builder,
+	service::{
+		AuthorityDiscoveryApi, AuxStore, BabeApi, Block, Error, HeaderBackend, Overseer,
+		OverseerConnector, OverseerGen, OverseerGenArgs, OverseerHandle, ParachainHost,
+		ProvideRuntimeApi,
+	},
+	Cli,
+};
+use polkadot_node_primitives::{ErasureChunk, Proof};
+use polkadot_node_subsystem::{overseer, SpawnGlue};
+use sp_core::traits::SpawnNamed;
+
+use crate::{interceptor::*, shared::MALUS};
+
+use futures::channel::oneshot;
+use rand::distributions::{Bernoulli, Distribution};
+use std::sync::Arc;
+
+/// How to misbehave when serving a chunk.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum ChunkMisbehavior {
+	/// Pretend not to have the chunk.
+	Missing,
+	/// Serve the chunk with corrupted data.
+	WrongChunk,
+	/// Serve the chunk with a corrupted Merkle proof.
+	WrongProof,
+}
+
+impl ChunkMisbehavior {
+	/// Break the given chunk.
+	fn apply(self, chunk: ErasureChunk) -> Option<ErasureChunk> {
+		match self {
+			ChunkMisbehavior::Missing => None,
+			ChunkMisbehavior::WrongChunk => {
+				let mut chunk = chunk;
+				chunk.chunk.iter_mut().for_each(|byte| *byte = !*byte);
+				Some(chunk)
+			},
+			ChunkMisbehavior::WrongProof => {
+				let mut chunk = chunk;
+				let mut nodes: Vec<Vec<u8>> =
+					chunk.proof().iter().map(|node| node.to_vec()).collect();
+				if let Some(byte) = nodes.first_mut().and_then(|node| node.last_mut()) {
+					*byte = !*byte;
+				}
+				chunk.proof = Proof::try_from(nodes)
+					.expect("the shape of the proof is unchanged and thus still valid; qed");
+				Some(chunk)
+			},
+		}
+	}
+}
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct WithholdAvailabilityOptions {
+	/// How to misbehave when serving chunks.
+	#[arg(long, value_enum, ignore_case = true, default_value_t = ChunkMisbehavior::Missing)]
+	pub misbehavior: ChunkMisbehavior,
+
+	/// Determines the percentage of chunk requests that are answered maliciously.
+	/// Defaults to 100% of chunk requests.
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// An interceptor of the availability store subsystem that breaks the chunks it serves.
+#[derive(Clone)]
+pub struct BreakChunks<Spawner> {
+	misbehavior: ChunkMisbehavior,
+	distribution: Bernoulli,
+	spawner: Spawner,
+}
+
+impl<Sender, Spawner> MessageInterceptor<Sender> for BreakChunks<Spawner>
+where
+	Sender: overseer::AvailabilityStoreSenderTrait + Clone + Send + 'static,
+	Spawner: overseer::gen::Spawner + Clone + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	fn intercept_incoming(
+		&self,
+		_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, sender),
+			} => {
+				if !self.distribution.sample(&mut rand::thread_rng()) {
+					return Some(FromOrchestra::Communication {
+						msg: AvailabilityStoreMessage::QueryChunk(
+							candidate_hash,
+							validator_index,
+							sender,
+						),
+					})
+				}
+
+				gum::info!(
+					target: MALUS,
+					?candidate_hash,
+					?validator_index,
+					misbehavior = ?self.misbehavior,
+					"😈 Serving a broken chunk.",
+				);
+
+				if self.misbehavior == ChunkMisbehavior::Missing {
+					let _ = sender.send(None);
+					return None
+				}
+
+				let (tx, rx) = oneshot::channel();
+				let misbehavior = self.misbehavior;
+				self.spawner.spawn(
+					"malus-break-chunk",
+					Some("malus"),
+					Box::pin(async move {
+						if let Ok(chunk) = rx.await {
+							let _ = sender.send(chunk.and_then(|chunk| misbehavior.apply(chunk)));
+						}
+					}),
+				);
+				Some(FromOrchestra::Communication {
+					msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, tx),
+				})
+			},
+			msg => Some(msg),
+		}
+	}
+}
+
+/// Generates an overseer that replaces the availability store subsystem with our malicious
+/// variant.
+pub(crate) struct WithholdAvailability {
+	/// How to misbehave when serving chunks.
+	pub misbehavior: ChunkMisbehavior,
+	/// The probability of behaving maliciously.
+	pub percentage: u8,
+}
+
+impl OverseerGen for WithholdAvailability {
+	fn generate<'a, Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'a, Spawner, RuntimeClient>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
+		RuntimeClient::Api: ParachainHost<Block> + BabeApi<Block> + AuthorityDiscoveryApi<Block>,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		let chunk_filter = BreakChunks {
+			misbehavior: self.misbehavior,
+			distribution: Bernoulli::new(f64::from(self.percentage) / 100.0)
+				.expect("Invalid probability! Percentage must be in range [0..=100]."),
+			spawner: SpawnGlue(args.spawner.clone()),
+		};
+
+		prepared_overseer_builder(args)?
+			.replace_availability_store(move |av_store_subsystem| {
+				InterceptedSubsystem::new(av_store_subsystem, chunk_filter)
+			})
+			.build_with_connector(connector)
+			.map_err(|e| e.into())
+	}
+}
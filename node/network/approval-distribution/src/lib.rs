@@ -21,14 +21,18 @@
 #![warn(missing_docs)]
 
 use futures::{channel::oneshot, FutureExt as _};
+use parity_scale_codec::Encode;
 use polkadot_node_network_protocol::{
 	self as net_protocol,
 	grid_topology::{RandomRouting, RequiredRouting, SessionGridTopologies, SessionGridTopology},
-	peer_set::MAX_NOTIFICATION_SIZE,
-	v1 as protocol_v1, PeerId, UnifiedReputationChange as Rep, Versioned, View,
+	peer_set::{ProtocolVersion, ValidationVersion, MAX_NOTIFICATION_SIZE},
+	v1 as protocol_v1, v2 as protocol_v2, PeerId, UnifiedReputationChange as Rep, Versioned, View,
 };
 use polkadot_node_primitives::approval::{
-	AssignmentCert, BlockApprovalMeta, IndirectAssignmentCert, IndirectSignedApprovalVote,
+	v2::{
+		AssignmentCertV2, CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2,
+	},
+	BlockApprovalMeta, IndirectAssignmentCert, IndirectSignedApprovalVote,
 };
 use polkadot_node_subsystem::{
 	messages::{
@@ -41,7 +45,10 @@ use polkadot_primitives::{
 	BlockNumber, CandidateIndex, Hash, SessionIndex, ValidatorIndex, ValidatorSignature,
 };
 use rand::{CryptoRng, Rng, SeedableRng};
-use std::collections::{hash_map, BTreeMap, HashMap, HashSet, VecDeque};
+use std::{
+	collections::{hash_map, BTreeMap, HashMap, HashSet, VecDeque},
+	iter::Peekable,
+};
 
 use self::metrics::Metrics;
 
@@ -170,7 +177,7 @@ struct State {
 	pending_known: HashMap<Hash, Vec<(PeerId, PendingMessage)>>,
 
 	/// Peer data is partially stored here, and partially inline within the [`BlockEntry`]s
-	peer_views: HashMap<PeerId, View>,
+	peer_views: HashMap<PeerId, PeerEntry>,
 
 	/// Keeps a topology for various different sessions.
 	topologies: SessionGridTopologies,
@@ -182,6 +189,13 @@ struct State {
 	aggression_config: AggressionConfig,
 }
 
+/// The view of a connected peer and the protocol version it is connected with.
+#[derive(Debug, Clone, PartialEq)]
+struct PeerEntry {
+	view: View,
+	version: ProtocolVersion,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum MessageKind {
 	Assignment,
@@ -191,6 +205,23 @@ enum MessageKind {
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct MessageSubject(Hash, CandidateIndex, ValidatorIndex);
 
+/// The subjects of a message by `validator` covering all `candidates` of a block.
+///
+/// Knowledge is tracked per candidate, a message covering several candidates is known
+/// once all of its subjects are.
+fn message_subjects(
+	block_hash: Hash,
+	candidates: &CandidateBitfield,
+	validator: ValidatorIndex,
+) -> Vec<MessageSubject> {
+	candidates
+		.iter_ones()
+		.map(|candidate_index| {
+			MessageSubject(block_hash, candidate_index as CandidateIndex, validator)
+		})
+		.collect()
+}
+
 #[derive(Debug, Clone, Default)]
 struct Knowledge {
 	// When there is no entry, this means the message is unknown
@@ -209,6 +240,17 @@ impl Knowledge {
 		}
 	}
 
+	fn contains_all(&self, messages: &[MessageSubject], kind: MessageKind) -> bool {
+		messages.iter().all(|message| self.contains(message, kind))
+	}
+
+	// Returns `true` if any of the messages wasn't known before.
+	fn insert_all(&mut self, messages: &[MessageSubject], kind: MessageKind) -> bool {
+		messages
+			.iter()
+			.fold(false, |inserted, message| self.insert(message.clone(), kind) || inserted)
+	}
+
 	fn insert(&mut self, message: MessageSubject, kind: MessageKind) -> bool {
 		match self.known_messages.entry(message) {
 			hash_map::Entry::Vacant(vacant) => {
@@ -241,6 +283,10 @@ impl PeerKnowledge {
 	fn contains(&self, message: &MessageSubject, kind: MessageKind) -> bool {
 		self.sent.contains(message, kind) || self.received.contains(message, kind)
 	}
+
+	fn contains_all(&self, messages: &[MessageSubject], kind: MessageKind) -> bool {
+		messages.iter().all(|message| self.contains(message, kind))
+	}
 }
 
 /// Information about blocks in our current view as well as whether peers know of them.
@@ -260,24 +306,34 @@ struct BlockEntry {
 	session: SessionIndex,
 }
 
+// The assignment cert is stored along with the candidates it claims, the approval signature
+// along with the candidates it covers. Both may cover more candidates than the one the state
+// is stored for.
 #[derive(Debug)]
 enum ApprovalState {
-	Assigned(AssignmentCert),
-	Approved(AssignmentCert, ValidatorSignature),
+	Assigned(AssignmentCertV2, CandidateBitfield),
+	Approved(AssignmentCertV2, CandidateBitfield, CandidateBitfield, ValidatorSignature),
 }
 
 impl ApprovalState {
-	fn assignment_cert(&self) -> &AssignmentCert {
+	fn assignment_cert(&self) -> &AssignmentCertV2 {
+		match *self {
+			ApprovalState::Assigned(ref cert, _) => cert,
+			ApprovalState::Approved(ref cert, _, _, _) => cert,
+		}
+	}
+
+	fn assignment_candidates(&self) -> &CandidateBitfield {
 		match *self {
-			ApprovalState::Assigned(ref cert) => cert,
-			ApprovalState::Approved(ref cert, _) => cert,
+			ApprovalState::Assigned(_, ref candidates) => candidates,
+			ApprovalState::Approved(_, ref candidates, _, _) => candidates,
 		}
 	}
 
-	fn approval_signature(&self) -> Option<ValidatorSignature> {
+	fn approval(&self) -> Option<(&CandidateBitfield, &ValidatorSignature)> {
 		match *self {
-			ApprovalState::Assigned(_) => None,
-			ApprovalState::Approved(_, ref sig) => Some(sig.clone()),
+			ApprovalState::Assigned(_, _) => None,
+			ApprovalState::Approved(_, _, ref candidates, ref sig) => Some((candidates, sig)),
 		}
 	}
 }
@@ -317,8 +373,8 @@ impl MessageSource {
 }
 
 enum PendingMessage {
-	Assignment(IndirectAssignmentCert, CandidateIndex),
-	Approval(IndirectSignedApprovalVote),
+	Assignment(IndirectAssignmentCertV2, CandidateBitfield),
+	Approval(IndirectSignedApprovalVoteV2),
 }
 
 #[overseer::contextbounds(ApprovalDistribution, prefix = self::overseer)]
@@ -331,10 +387,12 @@ impl State {
 		rng: &mut (impl CryptoRng + Rng),
 	) {
 		match event {
-			NetworkBridgeEvent::PeerConnected(peer_id, role, _, _) => {
+			NetworkBridgeEvent::PeerConnected(peer_id, role, version, _) => {
 				// insert a blank view if none already present
-				gum::trace!(target: LOG_TARGET, ?peer_id, ?role, "Peer connected");
-				self.peer_views.entry(peer_id).or_default();
+				gum::trace!(target: LOG_TARGET, ?peer_id, ?role, %version, "Peer connected");
+				self.peer_views
+					.entry(peer_id)
+					.or_insert(PeerEntry { view: View::default(), version });
 			},
 			NetworkBridgeEvent::PeerDisconnected(peer_id) => {
 				gum::trace!(target: LOG_TARGET, ?peer_id, "Peer disconnected");
@@ -376,6 +434,9 @@ impl State {
 				});
 			},
 			NetworkBridgeEvent::PeerMessage(peer_id, Versioned::V1(msg)) => {
+				self.process_incoming_peer_message(ctx, metrics, peer_id, msg.into(), rng).await;
+			},
+			NetworkBridgeEvent::PeerMessage(peer_id, Versioned::V2(msg)) => {
 				self.process_incoming_peer_message(ctx, metrics, peer_id, msg, rng).await;
 			},
 		}
@@ -425,7 +486,7 @@ impl State {
 
 		{
 			let sender = ctx.sender();
-			for (peer_id, view) in self.peer_views.iter() {
+			for (peer_id, PeerEntry { view, version }) in self.peer_views.iter() {
 				let intersection = view.iter().filter(|h| new_hashes.contains(h));
 				let view_intersection = View::new(intersection.cloned(), view.finalized_number);
 				Self::unify_with_peer(
@@ -435,6 +496,7 @@ impl State {
 					&self.topologies,
 					self.peer_views.len(),
 					*peer_id,
+					*version,
 					view_intersection,
 					rng,
 				)
@@ -472,13 +534,13 @@ impl State {
 
 				for (peer_id, message) in to_import {
 					match message {
-						PendingMessage::Assignment(assignment, claimed_index) => {
+						PendingMessage::Assignment(assignment, claimed_indices) => {
 							self.import_and_circulate_assignment(
 								ctx,
 								metrics,
 								MessageSource::Peer(peer_id),
 								assignment,
-								claimed_index,
+								claimed_indices,
 								rng,
 							)
 							.await;
@@ -519,6 +581,7 @@ impl State {
 			ctx,
 			&mut self.blocks,
 			&self.topologies,
+			&self.peer_views,
 			|block_entry| block_entry.session == session,
 			|required_routing, local, validator_index| {
 				if *required_routing == RequiredRouting::PendingTopology {
@@ -536,36 +599,38 @@ impl State {
 		ctx: &mut Context,
 		metrics: &Metrics,
 		peer_id: PeerId,
-		msg: protocol_v1::ApprovalDistributionMessage,
+		msg: protocol_v2::ApprovalDistributionMessage,
 		rng: &mut R,
 	) where
 		R: CryptoRng + Rng,
 	{
 		match msg {
-			protocol_v1::ApprovalDistributionMessage::Assignments(assignments) => {
+			protocol_v2::ApprovalDistributionMessage::Assignments(assignments) => {
 				gum::trace!(
 					target: LOG_TARGET,
 					peer_id = %peer_id,
 					num = assignments.len(),
 					"Processing assignments from a peer",
 				);
-				for (assignment, claimed_index) in assignments.into_iter() {
+				for (assignment, claimed_indices) in assignments.into_iter() {
 					if let Some(pending) = self.pending_known.get_mut(&assignment.block_hash) {
-						let message_subject = MessageSubject(
+						let message_subjects = message_subjects(
 							assignment.block_hash,
-							claimed_index,
+							&claimed_indices,
 							assignment.validator,
 						);
 
 						gum::trace!(
 							target: LOG_TARGET,
 							%peer_id,
-							?message_subject,
+							?message_subjects,
 							"Pending assignment",
 						);
 
-						pending
-							.push((peer_id, PendingMessage::Assignment(assignment, claimed_index)));
+						pending.push((
+							peer_id,
+							PendingMessage::Assignment(assignment, claimed_indices),
+						));
 
 						continue
 					}
@@ -575,13 +640,13 @@ impl State {
 						metrics,
 						MessageSource::Peer(peer_id),
 						assignment,
-						claimed_index,
+						claimed_indices,
 						rng,
 					)
 					.await;
 				}
 			},
-			protocol_v1::ApprovalDistributionMessage::Approvals(approvals) => {
+			protocol_v2::ApprovalDistributionMessage::Approvals(approvals) => {
 				gum::trace!(
 					target: LOG_TARGET,
 					peer_id = %peer_id,
@@ -590,16 +655,16 @@ impl State {
 				);
 				for approval_vote in approvals.into_iter() {
 					if let Some(pending) = self.pending_known.get_mut(&approval_vote.block_hash) {
-						let message_subject = MessageSubject(
+						let message_subjects = message_subjects(
 							approval_vote.block_hash,
-							approval_vote.candidate_index,
+							&approval_vote.candidate_indices,
 							approval_vote.validator,
 						);
 
 						gum::trace!(
 							target: LOG_TARGET,
 							%peer_id,
-							?message_subject,
+							?message_subjects,
 							"Pending approval",
 						);
 
@@ -634,9 +699,12 @@ impl State {
 	{
 		gum::trace!(target: LOG_TARGET, ?view, "Peer view change");
 		let finalized_number = view.finalized_number;
-		let old_view =
-			self.peer_views.get_mut(&peer_id).map(|d| std::mem::replace(d, view.clone()));
+		let old_view = self
+			.peer_views
+			.get_mut(&peer_id)
+			.map(|d| std::mem::replace(&mut d.view, view.clone()));
 		let old_finalized_number = old_view.map(|v| v.finalized_number).unwrap_or(0);
+		let version = peer_protocol_version(&self.peer_views, &peer_id);
 
 		// we want to prune every block known_by peer up to (including) view.finalized_number
 		let blocks = &mut self.blocks;
@@ -663,6 +731,7 @@ impl State {
 			&self.topologies,
 			self.peer_views.len(),
 			peer_id,
+			version,
 			view,
 			rng,
 		)
@@ -702,8 +771,8 @@ impl State {
 		ctx: &mut Context,
 		metrics: &Metrics,
 		source: MessageSource,
-		assignment: IndirectAssignmentCert,
-		claimed_candidate_index: CandidateIndex,
+		assignment: IndirectAssignmentCertV2,
+		claimed_candidate_indices: CandidateBitfield,
 		rng: &mut R,
 	) where
 		R: CryptoRng + Rng,
@@ -731,21 +800,36 @@ impl State {
 		};
 
 		// compute metadata on the assignment.
-		let message_subject = MessageSubject(block_hash, claimed_candidate_index, validator_index);
+		let message_subjects =
+			message_subjects(block_hash, &claimed_candidate_indices, validator_index);
 		let message_kind = MessageKind::Assignment;
 
+		if message_subjects.is_empty() {
+			if let Some(peer_id) = source.peer_id() {
+				gum::debug!(
+					target: LOG_TARGET,
+					?peer_id,
+					hash = ?block_hash,
+					?validator_index,
+					"Assignment claiming no candidates",
+				);
+				modify_reputation(ctx.sender(), peer_id, COST_INVALID_MESSAGE).await;
+			}
+			return
+		}
+
 		if let Some(peer_id) = source.peer_id() {
 			// check if our knowledge of the peer already contains this assignment
 			match entry.known_by.entry(peer_id) {
 				hash_map::Entry::Occupied(mut peer_knowledge) => {
 					let peer_knowledge = peer_knowledge.get_mut();
-					if peer_knowledge.contains(&message_subject, message_kind) {
+					if peer_knowledge.contains_all(&message_subjects, message_kind) {
 						// wasn't included before
-						if !peer_knowledge.received.insert(message_subject.clone(), message_kind) {
+						if !peer_knowledge.received.insert_all(&message_subjects, message_kind) {
 							gum::debug!(
 								target: LOG_TARGET,
 								?peer_id,
-								?message_subject,
+								?message_subjects,
 								"Duplicate assignment",
 							);
 							modify_reputation(ctx.sender(), peer_id, COST_DUPLICATE_MESSAGE).await;
@@ -757,7 +841,7 @@ impl State {
 					gum::debug!(
 						target: LOG_TARGET,
 						?peer_id,
-						?message_subject,
+						?message_subjects,
 						"Assignment from a peer is out of view",
 					);
 					modify_reputation(ctx.sender(), peer_id, COST_UNEXPECTED_MESSAGE).await;
@@ -765,11 +849,11 @@ impl State {
 			}
 
 			// if the assignment is known to be valid, reward the peer
-			if entry.knowledge.contains(&message_subject, message_kind) {
+			if entry.knowledge.contains_all(&message_subjects, message_kind) {
 				modify_reputation(ctx.sender(), peer_id, BENEFIT_VALID_MESSAGE).await;
 				if let Some(peer_knowledge) = entry.known_by.get_mut(&peer_id) {
-					gum::trace!(target: LOG_TARGET, ?peer_id, ?message_subject, "Known assignment");
-					peer_knowledge.received.insert(message_subject, message_kind);
+					gum::trace!(target: LOG_TARGET, ?peer_id, ?message_subjects, "Known assignment");
+					peer_knowledge.received.insert_all(&message_subjects, message_kind);
 				}
 				return
 			}
@@ -778,7 +862,7 @@ impl State {
 
 			ctx.send_message(ApprovalVotingMessage::CheckAndImportAssignment(
 				assignment.clone(),
-				claimed_candidate_index,
+				claimed_candidate_indices.clone(),
 				tx,
 			))
 			.await;
@@ -796,16 +880,16 @@ impl State {
 			gum::trace!(
 				target: LOG_TARGET,
 				?source,
-				?message_subject,
+				?message_subjects,
 				?result,
 				"Checked assignment",
 			);
 			match result {
 				AssignmentCheckResult::Accepted => {
 					modify_reputation(ctx.sender(), peer_id, BENEFIT_VALID_MESSAGE_FIRST).await;
-					entry.knowledge.known_messages.insert(message_subject.clone(), message_kind);
+					entry.knowledge.insert_all(&message_subjects, message_kind);
 					if let Some(peer_knowledge) = entry.known_by.get_mut(&peer_id) {
-						peer_knowledge.received.insert(message_subject.clone(), message_kind);
+						peer_knowledge.received.insert_all(&message_subjects, message_kind);
 					}
 				},
 				AssignmentCheckResult::AcceptedDuplicate => {
@@ -813,7 +897,7 @@ impl State {
 					// There is more than one way each validator can be assigned to each core.
 					// cf. https://github.com/paritytech/polkadot/pull/2160#discussion_r557628699
 					if let Some(peer_knowledge) = entry.known_by.get_mut(&peer_id) {
-						peer_knowledge.received.insert(message_subject.clone(), message_kind);
+						peer_knowledge.received.insert_all(&message_subjects, message_kind);
 					}
 					gum::debug!(
 						target: LOG_TARGET,
@@ -847,18 +931,18 @@ impl State {
 				},
 			}
 		} else {
-			if !entry.knowledge.insert(message_subject.clone(), message_kind) {
+			if !entry.knowledge.insert_all(&message_subjects, message_kind) {
 				// if we already imported an assignment, there is no need to distribute it again
 				gum::warn!(
 					target: LOG_TARGET,
-					?message_subject,
+					?message_subjects,
 					"Importing locally an already known assignment",
 				);
 				return
 			} else {
 				gum::debug!(
 					target: LOG_TARGET,
-					?message_subject,
+					?message_subjects,
 					"Importing locally a new assignment",
 				);
 			}
@@ -874,28 +958,45 @@ impl State {
 			t.local_grid_neighbors().required_routing_by_index(validator_index, local)
 		});
 
-		let message_state = match entry.candidates.get_mut(claimed_candidate_index as usize) {
-			Some(candidate_entry) => {
-				// set the approval state for validator_index to Assigned
-				// unless the approval state is set already
+		if claimed_candidate_indices
+			.iter_ones()
+			.any(|index| index >= entry.candidates.len())
+		{
+			gum::warn!(
+				target: LOG_TARGET,
+				hash = ?block_hash,
+				?claimed_candidate_indices,
+				"Expected a candidate entry on import_and_circulate_assignment",
+			);
+
+			return
+		}
+
+		// set the approval state for validator_index to Assigned for all claimed
+		// candidates unless the approval state is set already
+		let mut message_states = entry
+			.candidates
+			.iter_mut()
+			.enumerate()
+			.filter(|(candidate_index, _)| {
+				claimed_candidate_indices.bit_at(*candidate_index as CandidateIndex)
+			})
+			.map(|(_, candidate_entry)| {
 				candidate_entry.messages.entry(validator_index).or_insert_with(|| MessageState {
 					required_routing,
 					local,
 					random_routing: Default::default(),
-					approval_state: ApprovalState::Assigned(assignment.cert.clone()),
+					approval_state: ApprovalState::Assigned(
+						assignment.cert.clone(),
+						claimed_candidate_indices.clone(),
+					),
 				})
-			},
-			None => {
-				gum::warn!(
-					target: LOG_TARGET,
-					hash = ?block_hash,
-					?claimed_candidate_index,
-					"Expected a candidate entry on import_and_circulate_assignment",
-				);
+			})
+			.collect::<Vec<_>>();
 
-				return
-			},
-		};
+		// The message is routed once, based on the state of the first claimed candidate.
+		// `message_subjects` isn't empty, so neither is `message_states`.
+		let message_state = message_states.swap_remove(0);
 
 		// Dispatch the message to all peers in the routing set which
 		// know the block.
@@ -903,7 +1004,7 @@ impl State {
 		// If the topology isn't known yet (race with networking subsystems)
 		// then messages will be sent when we get it.
 
-		let assignments = vec![(assignment, claimed_candidate_index)];
+		let assignments = vec![(assignment, claimed_candidate_indices.clone())];
 		let n_peers_total = self.peer_views.len();
 		let source_peer = source.peer_id();
 
@@ -937,7 +1038,7 @@ impl State {
 		for peer in peers.iter() {
 			// we already filtered peers above, so this should always be Some
 			if let Some(peer_knowledge) = entry.known_by.get_mut(peer) {
-				peer_knowledge.sent.insert(message_subject.clone(), message_kind);
+				peer_knowledge.sent.insert_all(&message_subjects, message_kind);
 			}
 		}
 
@@ -945,19 +1046,14 @@ impl State {
 			gum::trace!(
 				target: LOG_TARGET,
 				?block_hash,
-				?claimed_candidate_index,
+				?claimed_candidate_indices,
 				local = source.peer_id().is_none(),
 				num_peers = peers.len(),
 				"Sending an assignment to peers",
 			);
 
-			ctx.send_message(NetworkBridgeTxMessage::SendValidationMessage(
-				peers,
-				Versioned::V1(protocol_v1::ValidationProtocol::ApprovalDistribution(
-					protocol_v1::ApprovalDistributionMessage::Assignments(assignments),
-				)),
-			))
-			.await;
+			let peers = with_protocol_versions(&self.peer_views, peers);
+			send_assignments_batched(ctx.sender(), assignments, &peers).await;
 		}
 	}
 
@@ -966,14 +1062,19 @@ impl State {
 		ctx: &mut Context,
 		metrics: &Metrics,
 		source: MessageSource,
-		vote: IndirectSignedApprovalVote,
+		vote: IndirectSignedApprovalVoteV2,
 	) {
 		let block_hash = vote.block_hash;
 		let validator_index = vote.validator;
-		let candidate_index = vote.candidate_index;
+		let candidate_indices = vote.candidate_indices.clone();
 
 		let entry = match self.blocks.get_mut(&block_hash) {
-			Some(entry) if entry.candidates.get(candidate_index as usize).is_some() => entry,
+			Some(entry)
+				if candidate_indices.count_ones() > 0 &&
+					candidate_indices
+						.iter_ones()
+						.all(|index| index < entry.candidates.len()) =>
+				entry,
 			_ => {
 				if let Some(peer_id) = source.peer_id() {
 					if !self.recent_outdated_blocks.is_recent_outdated(&block_hash) {
@@ -985,15 +1086,15 @@ impl State {
 		};
 
 		// compute metadata on the assignment.
-		let message_subject = MessageSubject(block_hash, candidate_index, validator_index);
+		let message_subjects = message_subjects(block_hash, &candidate_indices, validator_index);
 		let message_kind = MessageKind::Approval;
 
 		if let Some(peer_id) = source.peer_id() {
-			if !entry.knowledge.contains(&message_subject, MessageKind::Assignment) {
+			if !entry.knowledge.contains_all(&message_subjects, MessageKind::Assignment) {
 				gum::debug!(
 					target: LOG_TARGET,
 					?peer_id,
-					?message_subject,
+					?message_subjects,
 					"Unknown approval assignment",
 				);
 				modify_reputation(ctx.sender(), peer_id, COST_UNEXPECTED_MESSAGE).await;
@@ -1004,12 +1105,12 @@ impl State {
 			match entry.known_by.entry(peer_id) {
 				hash_map::Entry::Occupied(mut knowledge) => {
 					let peer_knowledge = knowledge.get_mut();
-					if peer_knowledge.contains(&message_subject, message_kind) {
-						if !peer_knowledge.received.insert(message_subject.clone(), message_kind) {
+					if peer_knowledge.contains_all(&message_subjects, message_kind) {
+						if !peer_knowledge.received.insert_all(&message_subjects, message_kind) {
 							gum::debug!(
 								target: LOG_TARGET,
 								?peer_id,
-								?message_subject,
+								?message_subjects,
 								"Duplicate approval",
 							);
 
@@ -1022,7 +1123,7 @@ impl State {
 					gum::debug!(
 						target: LOG_TARGET,
 						?peer_id,
-						?message_subject,
+						?message_subjects,
 						"Approval from a peer is out of view",
 					);
 					modify_reputation(ctx.sender(), peer_id, COST_UNEXPECTED_MESSAGE).await;
@@ -1030,11 +1131,11 @@ impl State {
 			}
 
 			// if the approval is known to be valid, reward the peer
-			if entry.knowledge.contains(&message_subject, message_kind) {
-				gum::trace!(target: LOG_TARGET, ?peer_id, ?message_subject, "Known approval");
+			if entry.knowledge.contains_all(&message_subjects, message_kind) {
+				gum::trace!(target: LOG_TARGET, ?peer_id, ?message_subjects, "Known approval");
 				modify_reputation(ctx.sender(), peer_id, BENEFIT_VALID_MESSAGE).await;
 				if let Some(peer_knowledge) = entry.known_by.get_mut(&peer_id) {
-					peer_knowledge.received.insert(message_subject.clone(), message_kind);
+					peer_knowledge.received.insert_all(&message_subjects, message_kind);
 				}
 				return
 			}
@@ -1057,7 +1158,7 @@ impl State {
 			gum::trace!(
 				target: LOG_TARGET,
 				?peer_id,
-				?message_subject,
+				?message_subjects,
 				?result,
 				"Checked approval",
 			);
@@ -1065,9 +1166,9 @@ impl State {
 				ApprovalCheckResult::Accepted => {
 					modify_reputation(ctx.sender(), peer_id, BENEFIT_VALID_MESSAGE_FIRST).await;
 
-					entry.knowledge.insert(message_subject.clone(), message_kind);
+					entry.knowledge.insert_all(&message_subjects, message_kind);
 					if let Some(peer_knowledge) = entry.known_by.get_mut(&peer_id) {
-						peer_knowledge.received.insert(message_subject.clone(), message_kind);
+						peer_knowledge.received.insert_all(&message_subjects, message_kind);
 					}
 				},
 				ApprovalCheckResult::Bad(error) => {
@@ -1082,18 +1183,18 @@ impl State {
 				},
 			}
 		} else {
-			if !entry.knowledge.insert(message_subject.clone(), message_kind) {
+			if !entry.knowledge.insert_all(&message_subjects, message_kind) {
 				// if we already imported an approval, there is no need to distribute it again
 				gum::warn!(
 					target: LOG_TARGET,
-					?message_subject,
+					?message_subjects,
 					"Importing locally an already known approval",
 				);
 				return
 			} else {
 				gum::debug!(
 					target: LOG_TARGET,
-					?message_subject,
+					?message_subjects,
 					"Importing locally a new approval",
 				);
 			}
@@ -1102,71 +1203,61 @@ impl State {
 		// Invariant: to our knowledge, none of the peers except for the `source` know about the approval.
 		metrics.on_approval_imported();
 
-		let required_routing = match entry.candidates.get_mut(candidate_index as usize) {
-			Some(candidate_entry) => {
-				// set the approval state for validator_index to Approved
-				// it should be in assigned state already
-				match candidate_entry.messages.remove(&validator_index) {
-					Some(MessageState {
-						approval_state: ApprovalState::Assigned(cert),
-						required_routing,
-						local,
-						random_routing,
-					}) => {
-						candidate_entry.messages.insert(
-							validator_index,
-							MessageState {
-								approval_state: ApprovalState::Approved(
-									cert,
-									vote.signature.clone(),
-								),
-								required_routing,
-								local,
-								random_routing,
-							},
-						);
+		let mut required_routing = None;
+		for candidate_index in candidate_indices.iter_ones() {
+			// the candidate index was checked to be in range above.
+			let candidate_entry = &mut entry.candidates[candidate_index];
 
-						required_routing
-					},
-					Some(_) => {
-						unreachable!(
-							"we only insert it after the metadata, checked the metadata above; qed"
-						);
-					},
-					None => {
-						// this would indicate a bug in approval-voting
-						gum::warn!(
-							target: LOG_TARGET,
-							hash = ?block_hash,
-							?candidate_index,
-							?validator_index,
-							"Importing an approval we don't have an assignment for",
-						);
+			// set the approval state for validator_index to Approved
+			// it should be in assigned state already
+			let message_state = match candidate_entry.messages.get_mut(&validator_index) {
+				Some(message_state) => message_state,
+				None => {
+					// this would indicate a bug in approval-voting
+					gum::warn!(
+						target: LOG_TARGET,
+						hash = ?block_hash,
+						?candidate_index,
+						?validator_index,
+						"Importing an approval we don't have an assignment for",
+					);
 
-						return
-					},
-				}
-			},
-			None => {
-				gum::warn!(
-					target: LOG_TARGET,
-					hash = ?block_hash,
-					?candidate_index,
-					?validator_index,
-					"Expected a candidate entry on import_and_circulate_approval",
-				);
+					return
+				},
+			};
 
-				return
-			},
+			// The candidate may already be approved by an approval covering a
+			// different set of candidates.
+			let approved = match &message_state.approval_state {
+				ApprovalState::Assigned(cert, assigned_candidates) =>
+					Some(ApprovalState::Approved(
+						cert.clone(),
+						assigned_candidates.clone(),
+						candidate_indices.clone(),
+						vote.signature.clone(),
+					)),
+				ApprovalState::Approved(..) => None,
+			};
+			if let Some(approved) = approved {
+				message_state.approval_state = approved;
+			}
+
+			required_routing.get_or_insert(message_state.required_routing);
+		}
+
+		// `candidate_indices` was checked to have at least one bit set above.
+		let required_routing = match required_routing {
+			Some(required_routing) => required_routing,
+			None => return,
 		};
 
-		// Dispatch a ApprovalDistributionV1Message::Approval(vote)
+		// Dispatch a ApprovalDistributionV2Message::Approval(vote)
 		// to all peers required by the topology, with the exception of the source peer.
 
 		let topology = self.topologies.get_topology(entry.session);
 		let source_peer = source.peer_id();
 
-		let message_subject = &message_subject;
+		let message_subjects = &message_subjects;
 		let peer_filter = move |peer, knowledge: &PeerKnowledge| {
 			if Some(peer) == source_peer.as_ref() {
 				return false
@@ -1183,7 +1274,7 @@ impl State {
 			//   3. Any randomly selected peers have been sent the assignment already.
 			let in_topology = topology
 				.map_or(false, |t| t.local_grid_neighbors().route_to_peer(required_routing, peer));
-			in_topology || knowledge.sent.contains(message_subject, MessageKind::Assignment)
+			in_topology || knowledge.sent.contains_all(message_subjects, MessageKind::Assignment)
 		};
 
 		let peers = entry
@@ -1198,7 +1289,7 @@ impl State {
 		for peer in peers.iter() {
 			// we already filtered peers above, so this should always be Some
 			if let Some(entry) = entry.known_by.get_mut(peer) {
-				entry.sent.insert(message_subject.clone(), message_kind);
+				entry.sent.insert_all(message_subjects, message_kind);
 			}
 		}
 
@@ -1207,27 +1298,24 @@ impl State {
 			gum::trace!(
 				target: LOG_TARGET,
 				?block_hash,
-				?candidate_index,
+				?candidate_indices,
 				local = source.peer_id().is_none(),
 				num_peers = peers.len(),
 				"Sending an approval to peers",
 			);
 
-			ctx.send_message(NetworkBridgeTxMessage::SendValidationMessage(
-				peers,
-				Versioned::V1(protocol_v1::ValidationProtocol::ApprovalDistribution(
-					protocol_v1::ApprovalDistributionMessage::Approvals(approvals),
-				)),
-			))
-			.await;
+			let peers = with_protocol_versions(&self.peer_views, peers);
+			send_approvals_batched(ctx.sender(), approvals, &peers).await;
 		}
 	}
 
 	/// Retrieve approval signatures from state for the given relay block/indices:
+	///
+	/// Each signature comes with the indices of all candidates of the block it covers.
 	fn get_approval_signatures(
 		&mut self,
 		indices: HashSet<(Hash, CandidateIndex)>,
-	) -> HashMap<ValidatorIndex, ValidatorSignature> {
+	) -> HashMap<ValidatorIndex, (Hash, Vec<CandidateIndex>, ValidatorSignature)> {
 		let mut all_sigs = HashMap::new();
 		for (hash, index) in indices {
 			let block_entry = match self.blocks.get(&hash) {
@@ -1245,21 +1333,22 @@ impl State {
 			let candidate_entry = match block_entry.candidates.get(index as usize) {
 				None => {
 					gum::debug!(
-						target: LOG_TARGET,
-						?hash,
-						?index,
-						"`get_approval_signatures`: could not find candidate entry for given hash and index!"
-						);
+					target: LOG_TARGET,
+					?hash,
+					?index,
+					"`get_approval_signatures`: could not find candidate entry for given hash and index!"
+					);
 					continue
 				},
 				Some(e) => e,
 			};
 			let sigs =
 				candidate_entry.messages.iter().filter_map(|(validator_index, message_state)| {
-					match &message_state.approval_state {
-						ApprovalState::Approved(_, sig) => Some((*validator_index, sig.clone())),
-						ApprovalState::Assigned(_) => None,
-					}
+					message_state.approval_state.approval().map(|(candidates, sig)| {
+						let candidate_indices =
+							candidates.iter_ones().map(|index| index as CandidateIndex).collect();
+						(*validator_index, (hash, candidate_indices, sig.clone()))
+					})
 				});
 			all_sigs.extend(sigs);
 		}
@@ -1273,6 +1362,7 @@ impl State {
 		topologies: &SessionGridTopologies,
 		total_peers: usize,
 		peer_id: PeerId,
+		protocol_version: ProtocolVersion,
 		view: View,
 		rng: &mut (impl CryptoRng + Rng),
 	) {
@@ -1302,11 +1392,14 @@ impl State {
 
 				let topology = topologies.get_topology(entry.session);
 
+				// Approvals may cover several candidates, they are only sent once the
+				// assignments for all of them have been.
+				let mut approvals_in_block = Vec::new();
+
 				// Iterate all messages in all candidates.
-				for (candidate_index, validator, message_state) in
-					entry.candidates.iter_mut().enumerate().flat_map(|(c_i, c)| {
-						c.messages.iter_mut().map(move |(k, v)| (c_i as _, k, v))
-					}) {
+				for (validator, message_state) in
+					entry.candidates.iter_mut().flat_map(|c| c.messages.iter_mut())
+				{
 					// Propagate the message to all peers in the required routing set OR
 					// randomly sample peers.
 					{
@@ -1332,41 +1425,47 @@ impl State {
 						}
 					}
 
-					let message_subject = MessageSubject(block, candidate_index, *validator);
+					let assignment_candidates =
+						message_state.approval_state.assignment_candidates();
+					let message_subjects =
+						message_subjects(block, assignment_candidates, *validator);
 
-					let assignment_message = (
-						IndirectAssignmentCert {
-							block_hash: block,
-							validator: *validator,
-							cert: message_state.approval_state.assignment_cert().clone(),
-						},
-						candidate_index,
-					);
-
-					let approval_message =
-						message_state.approval_state.approval_signature().map(|signature| {
-							IndirectSignedApprovalVote {
+					if !peer_knowledge.contains_all(&message_subjects, MessageKind::Assignment) {
+						peer_knowledge.sent.insert_all(&message_subjects, MessageKind::Assignment);
+						assignments_to_send.push((
+							IndirectAssignmentCertV2 {
 								block_hash: block,
 								validator: *validator,
-								candidate_index,
-								signature,
-							}
-						});
+								cert: message_state.approval_state.assignment_cert().clone(),
+							},
+							assignment_candidates.clone(),
+						));
+					}
 
-					if !peer_knowledge.contains(&message_subject, MessageKind::Assignment) {
-						peer_knowledge
-							.sent
-							.insert(message_subject.clone(), MessageKind::Assignment);
-						assignments_to_send.push(assignment_message);
+					if let Some((candidate_indices, signature)) =
+						message_state.approval_state.approval()
+					{
+						approvals_in_block.push(IndirectSignedApprovalVoteV2 {
+							block_hash: block,
+							candidate_indices: candidate_indices.clone(),
+							validator: *validator,
+							signature: signature.clone(),
+						});
 					}
+				}
 
-					if let Some(approval_message) = approval_message {
-						if !peer_knowledge.contains(&message_subject, MessageKind::Approval) {
-							peer_knowledge
-								.sent
-								.insert(message_subject.clone(), MessageKind::Approval);
-							approvals_to_send.push(approval_message);
-						}
+				for approval_message in approvals_in_block {
+					let message_subjects = message_subjects(
+						block,
+						&approval_message.candidate_indices,
+						approval_message.validator,
+					);
+
+					if peer_knowledge.contains_all(&message_subjects, MessageKind::Assignment) &&
+						!peer_knowledge.contains_all(&message_subjects, MessageKind::Approval)
+					{
+						peer_knowledge.sent.insert_all(&message_subjects, MessageKind::Approval);
+						approvals_to_send.push(approval_message);
 					}
 				}
 
@@ -1374,6 +1473,8 @@ impl State {
 			}
 		}
 
+		let peers = [(peer_id, protocol_version)];
+
 		if !assignments_to_send.is_empty() {
 			gum::trace!(
 				target: LOG_TARGET,
@@ -1382,7 +1483,7 @@ impl State {
 				"Sending assignments to unified peer",
 			);
 
-			send_assignments_batched(sender, assignments_to_send, peer_id).await;
+			send_assignments_batched(sender, assignments_to_send, &peers).await;
 		}
 
 		if !approvals_to_send.is_empty() {
@@ -1393,7 +1494,7 @@ impl State {
 				"Sending approvals to unified peer",
 			);
 
-			send_approvals_batched(sender, approvals_to_send, peer_id).await;
+			send_approvals_batched(sender, approvals_to_send, &peers).await;
 		}
 	}
 
@@ -1421,6 +1522,7 @@ impl State {
 			ctx,
 			&mut self.blocks,
 			&self.topologies,
+			&self.peer_views,
 			|block_entry| {
 				let block_age = max_age - block_entry.number;
 
@@ -1448,6 +1550,7 @@ impl State {
 			ctx,
 			&mut self.blocks,
 			&self.topologies,
+			&self.peer_views,
 			|block_entry| {
 				// Ramp up aggression only for the very oldest block(s).
 				// Approval voting can get stuck on a single block preventing
@@ -1505,6 +1608,7 @@ async fn adjust_required_routing_and_propagate<Context, BlockFilter, RoutingModi
 	ctx: &mut Context,
 	blocks: &mut HashMap<Hash, BlockEntry>,
 	topologies: &SessionGridTopologies,
+	peer_views: &HashMap<PeerId, PeerEntry>,
 	block_filter: BlockFilter,
 	routing_modifier: RoutingModifier,
 ) where
@@ -1521,12 +1625,13 @@ async fn adjust_required_routing_and_propagate<Context, BlockFilter, RoutingModi
 			continue
 		}
 
+		// Approvals may cover several candidates, they are only sent to a peer once the
+		// assignments for all of them have been.
+		let mut approvals_in_block = Vec::new();
+
 		// Iterate all messages in all candidates.
-		for (candidate_index, validator, message_state) in block_entry
-			.candidates
-			.iter_mut()
-			.enumerate()
-			.flat_map(|(c_i, c)| c.messages.iter_mut().map(move |(k, v)| (c_i as _, k, v)))
+		for (validator, message_state) in
+			block_entry.candidates.iter_mut().flat_map(|c| c.messages.iter_mut())
 		{
 			routing_modifier(&mut message_state.required_routing, message_state.local, validator);
 
@@ -1540,23 +1645,24 @@ async fn adjust_required_routing_and_propagate<Context, BlockFilter, RoutingModi
 			};
 
 			// Propagate the message to all peers in the required routing set.
-			let message_subject = MessageSubject(*block_hash, candidate_index, *validator);
+			let assignment_candidates = message_state.approval_state.assignment_candidates();
+			let message_subjects = message_subjects(*block_hash, assignment_candidates, *validator);
 
 			let assignment_message = (
-				IndirectAssignmentCert {
+				IndirectAssignmentCertV2 {
 					block_hash: *block_hash,
 					validator: *validator,
 					cert: message_state.approval_state.assignment_cert().clone(),
 				},
-				candidate_index,
+				assignment_candidates.clone(),
 			);
 			let approval_message =
-				message_state.approval_state.approval_signature().map(|signature| {
-					IndirectSignedApprovalVote {
+				message_state.approval_state.approval().map(|(candidate_indices, signature)| {
+					IndirectSignedApprovalVoteV2 {
 						block_hash: *block_hash,
 						validator: *validator,
-						candidate_index,
-						signature,
+						candidate_indices: candidate_indices.clone(),
+						signature: signature.clone(),
 					}
 				});
 
@@ -1568,8 +1674,8 @@ async fn adjust_required_routing_and_propagate<Context, BlockFilter, RoutingModi
 					continue
 				}
 
-				if !peer_knowledge.contains(&message_subject, MessageKind::Assignment) {
-					peer_knowledge.sent.insert(message_subject.clone(), MessageKind::Assignment);
+				if !peer_knowledge.contains_all(&message_subjects, MessageKind::Assignment) {
+					peer_knowledge.sent.insert_all(&message_subjects, MessageKind::Assignment);
 					peer_assignments
 						.entry(*peer)
 						.or_insert_with(Vec::new)
@@ -1577,29 +1683,67 @@ async fn adjust_required_routing_and_propagate<Context, BlockFilter, RoutingModi
 				}
 
 				if let Some(approval_message) = approval_message.as_ref() {
-					if !peer_knowledge.contains(&message_subject, MessageKind::Approval) {
-						peer_knowledge.sent.insert(message_subject.clone(), MessageKind::Approval);
-						peer_approvals
-							.entry(*peer)
-							.or_insert_with(Vec::new)
-							.push(approval_message.clone());
-					}
+					approvals_in_block.push((*peer, approval_message.clone()));
 				}
 			}
 		}
+
+		for (peer, approval_message) in approvals_in_block {
+			let peer_knowledge = match block_entry.known_by.get_mut(&peer) {
+				Some(peer_knowledge) => peer_knowledge,
+				None => continue,
+			};
+			let message_subjects = message_subjects(
+				*block_hash,
+				&approval_message.candidate_indices,
+				approval_message.validator,
+			);
+
+			if peer_knowledge.contains_all(&message_subjects, MessageKind::Assignment) &&
+				!peer_knowledge.contains_all(&message_subjects, MessageKind::Approval)
+			{
+				peer_knowledge.sent.insert_all(&message_subjects, MessageKind::Approval);
+				peer_approvals.entry(peer).or_insert_with(Vec::new).push(approval_message);
+			}
+		}
 	}
 
 	// Send messages in accumulated packets, assignments preceding approvals.
 
 	for (peer, assignments_packet) in peer_assignments {
-		send_assignments_batched(ctx.sender(), assignments_packet, peer).await;
+		let peers = [(peer, peer_protocol_version(peer_views, &peer))];
+		send_assignments_batched(ctx.sender(), assignments_packet, &peers).await;
 	}
 
 	for (peer, approvals_packet) in peer_approvals {
-		send_approvals_batched(ctx.sender(), approvals_packet, peer).await;
+		let peers = [(peer, peer_protocol_version(peer_views, &peer))];
+		send_approvals_batched(ctx.sender(), approvals_packet, &peers).await;
 	}
 }
 
+/// The protocol version `peer` is connected with.
+///
+/// Peers we don't know about are assumed to use the oldest version.
+fn peer_protocol_version(
+	peer_views: &HashMap<PeerId, PeerEntry>,
+	peer: &PeerId,
+) -> ProtocolVersion {
+	peer_views.get(peer).map_or(ValidationVersion::V1.into(), |entry| entry.version)
+}
+
+fn with_protocol_versions(
+	peer_views: &HashMap<PeerId, PeerEntry>,
+	peers: Vec<PeerId>,
+) -> Vec<(PeerId, ProtocolVersion)> {
+	peers
+		.into_iter()
+		.map(|peer| {
+			let version = peer_protocol_version(peer_views, &peer);
+			(peer, version)
+		})
+		.collect()
+}
+
 /// Modify the reputation of a peer based on its behavior.
 async fn modify_reputation(
 	sender: &mut impl overseer::ApprovalDistributionSenderTrait,
@@ -1681,12 +1825,12 @@ impl ApprovalDistribution {
 			ApprovalDistributionMessage::NewBlocks(metas) => {
 				state.handle_new_blocks(ctx, metrics, metas, rng).await;
 			},
-			ApprovalDistributionMessage::DistributeAssignment(cert, candidate_index) => {
+			ApprovalDistributionMessage::DistributeAssignment(cert, candidate_indices) => {
 				gum::debug!(
 					target: LOG_TARGET,
-					"Distributing our assignment on candidate (block={}, index={})",
+					"Distributing our assignment on candidates (block={}, indices={:?})",
 					cert.block_hash,
-					candidate_index,
+					candidate_indices,
 				);
 
 				state
@@ -1695,7 +1839,7 @@ impl ApprovalDistribution {
 						&metrics,
 						MessageSource::Local,
 						cert,
-						candidate_index,
+						candidate_indices,
 						rng,
 					)
 					.await;
@@ -1703,9 +1847,9 @@ impl ApprovalDistribution {
 			ApprovalDistributionMessage::DistributeApproval(vote) => {
 				gum::debug!(
 					target: LOG_TARGET,
-					"Distributing our approval vote on candidate (block={}, index={})",
+					"Distributing our approval vote on candidates (block={}, indices={:?})",
 					vote.block_hash,
-					vote.candidate_index,
+					vote.candidate_indices,
 				);
 
 				state
@@ -1758,50 +1902,159 @@ pub const MAX_APPROVAL_BATCH_SIZE: usize = ensure_size_not_zero(
 	MAX_NOTIFICATION_SIZE as usize / std::mem::size_of::<IndirectSignedApprovalVote>() / 3,
 );
 
+/// The maximum encoded size of the messages in a batch is 33% of maximum allowed by protocol.
+///
+/// The batch sizes above are derived from the size of the version 1 messages, which is fixed.
+/// Version 2 messages carry bitfields of arbitrary length, so their batches are also bounded by
+/// their encoded size.
+const MAX_BATCH_ENCODED_SIZE: usize = MAX_NOTIFICATION_SIZE as usize / 3;
+
+// Takes at most `max_count` messages with a total encoded size of at most
+// `MAX_BATCH_ENCODED_SIZE`. The batch contains at least one message if any are left.
+fn next_batch<T: Encode>(
+	messages: &mut Peekable<impl Iterator<Item = T>>,
+	max_count: usize,
+) -> Vec<T> {
+	let mut batch = Vec::new();
+	let mut encoded_size = 0;
+
+	while batch.len() < max_count {
+		let fits = |message: &T| {
+			batch.is_empty() || encoded_size + message.encoded_size() <= MAX_BATCH_ENCODED_SIZE
+		};
+
+		match messages.next_if(fits) {
+			Some(message) => {
+				encoded_size += message.encoded_size();
+				batch.push(message);
+			},
+			None => break,
+		}
+	}
+
+	batch
+}
+
+// Peers connected with the given protocol version.
+fn peers_with_version(
+	peers: &[(PeerId, ProtocolVersion)],
+	version: ValidationVersion,
+) -> Vec<PeerId> {
+	let version = ProtocolVersion::from(version);
+	peers.iter().filter(|(_, v)| *v == version).map(|(peer, _)| *peer).collect()
+}
+
+// Assignments of compact certificates or claiming more than one candidate have no
+// version 1 representation.
+fn assignment_into_v1(
+	(assignment, candidate_indices): (IndirectAssignmentCertV2, CandidateBitfield),
+) -> Option<(IndirectAssignmentCert, CandidateIndex)> {
+	if candidate_indices.count_ones() != 1 {
+		return None
+	}
+
+	let candidate_index = candidate_indices.first_one()? as CandidateIndex;
+	Some((assignment.try_into().ok()?, candidate_index))
+}
+
 /// Send assignments while honoring the `max_notification_size` of the protocol.
 ///
 /// Splitting the messages into multiple notifications allows more granular processing at the
 /// destination, such that the subsystem doesn't get stuck for long processing a batch
 /// of assignments and can `select!` other tasks.
+///
+/// Peers connected with version 1 of the protocol are only sent the assignments which can be
+/// expressed in version 1.
 pub(crate) async fn send_assignments_batched(
 	sender: &mut impl overseer::ApprovalDistributionSenderTrait,
-	assignments: Vec<(IndirectAssignmentCert, CandidateIndex)>,
-	peer: PeerId,
+	assignments: Vec<(IndirectAssignmentCertV2, CandidateBitfield)>,
+	peers: &[(PeerId, ProtocolVersion)],
 ) {
-	let mut batches = assignments.into_iter().peekable();
+	let v1_peers = peers_with_version(peers, ValidationVersion::V1);
+	let v2_peers = peers_with_version(peers, ValidationVersion::V2);
+
+	if !v1_peers.is_empty() {
+		let mut batches = assignments.iter().cloned().filter_map(assignment_into_v1).peekable();
+
+		while batches.peek().is_some() {
+			let batch: Vec<_> = batches.by_ref().take(MAX_ASSIGNMENT_BATCH_SIZE).collect();
+
+			sender
+				.send_message(NetworkBridgeTxMessage::SendValidationMessage(
+					v1_peers.clone(),
+					Versioned::V1(protocol_v1::ValidationProtocol::ApprovalDistribution(
+						protocol_v1::ApprovalDistributionMessage::Assignments(batch),
+					)),
+				))
+				.await;
+		}
+	}
 
-	while batches.peek().is_some() {
-		let batch: Vec<_> = batches.by_ref().take(MAX_ASSIGNMENT_BATCH_SIZE).collect();
+	if !v2_peers.is_empty() {
+		let mut batches = assignments.into_iter().peekable();
 
-		sender
-			.send_message(NetworkBridgeTxMessage::SendValidationMessage(
-				vec![peer],
-				Versioned::V1(protocol_v1::ValidationProtocol::ApprovalDistribution(
-					protocol_v1::ApprovalDistributionMessage::Assignments(batch),
-				)),
-			))
-			.await;
+		while batches.peek().is_some() {
+			let batch = next_batch(&mut batches, MAX_ASSIGNMENT_BATCH_SIZE);
+
+			sender
+				.send_message(NetworkBridgeTxMessage::SendValidationMessage(
+					v2_peers.clone(),
+					Versioned::V2(protocol_v2::ValidationProtocol::ApprovalDistribution(
+						protocol_v2::ApprovalDistributionMessage::Assignments(batch),
+					)),
+				))
+				.await;
+		}
 	}
 }
 
 /// Send approvals while honoring the `max_notification_size` of the protocol.
+///
+/// Peers connected with version 1 of the protocol are only sent the approvals covering a
+/// single candidate.
 pub(crate) async fn send_approvals_batched(
 	sender: &mut impl overseer::ApprovalDistributionSenderTrait,
-	approvals: Vec<IndirectSignedApprovalVote>,
-	peer: PeerId,
+	approvals: Vec<IndirectSignedApprovalVoteV2>,
+	peers: &[(PeerId, ProtocolVersion)],
 ) {
-	let mut batches = approvals.into_iter().peekable();
+	let v1_peers = peers_with_version(peers, ValidationVersion::V1);
+	let v2_peers = peers_with_version(peers, ValidationVersion::V2);
+
+	if !v1_peers.is_empty() {
+		let mut batches = approvals
+			.iter()
+			.cloned()
+			.filter_map(|approval| IndirectSignedApprovalVote::try_from(approval).ok())
+			.peekable();
+
+		while batches.peek().is_some() {
+			let batch: Vec<_> = batches.by_ref().take(MAX_APPROVAL_BATCH_SIZE).collect();
+
+			sender
+				.send_message(NetworkBridgeTxMessage::SendValidationMessage(
+					v1_peers.clone(),
+					Versioned::V1(protocol_v1::ValidationProtocol::ApprovalDistribution(
+						protocol_v1::ApprovalDistributionMessage::Approvals(batch),
+					)),
+				))
+				.await;
+		}
+	}
 
-	while batches.peek().is_some() {
-		let batch: Vec<_> = batches.by_ref().take(MAX_APPROVAL_BATCH_SIZE).collect();
+	if !v2_peers.is_empty() {
+		let mut batches = approvals.into_iter().peekable();
 
-		sender
-			.send_message(NetworkBridgeTxMessage::SendValidationMessage(
-				vec![peer],
-				Versioned::V1(protocol_v1::ValidationProtocol::ApprovalDistribution(
-					protocol_v1::ApprovalDistributionMessage::Approvals(batch),
-				)),
-			))
-			.await;
+		while batches.peek().is_some() {
+			let batch = next_batch(&mut batches, MAX_APPROVAL_BATCH_SIZE);
+
+			sender
+				.send_message(NetworkBridgeTxMessage::SendValidationMessage(
+					v2_peers.clone(),
+					Versioned::V2(protocol_v2::ValidationProtocol::ApprovalDistribution(
+						protocol_v2::ApprovalDistributionMessage::Approvals(batch),
+					)),
+				))
+				.await;
+		}
 	}
 }
@@ -335,10 +335,11 @@ fn try_import_the_same_assignment() {
 			overseer_recv(overseer).await,
 			AllMessages::ApprovalVoting(ApprovalVotingMessage::CheckAndImportAssignment(
 				assignment,
-				0u32,
+				claimed_candidate_indices,
 				tx,
 			)) => {
-				assert_eq!(assignment, cert);
+				assert_eq!(assignment, cert.clone().into());
+				assert_eq!(claimed_candidate_indices, 0u32.into());
 				tx.send(AssignmentCheckResult::Accepted).unwrap();
 			}
 		);
@@ -427,8 +428,8 @@ fn spam_attack_results_in_negative_reputation_change() {
 					claimed_candidate_index,
 					tx,
 				)) => {
-					assert_eq!(assignment, assignments[i].0);
-					assert_eq!(claimed_candidate_index, assignments[i].1);
+					assert_eq!(assignment, assignments[i].0.clone().into());
+					assert_eq!(claimed_candidate_index, assignments[i].1.into());
 					tx.send(AssignmentCheckResult::Accepted).unwrap();
 				}
 			);
@@ -492,7 +493,10 @@ fn peer_sending_us_the_same_we_just_sent_them_is_ok() {
 		let cert = fake_assignment_cert(hash, validator_index);
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert.clone(), candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(
+				cert.clone().into(),
+				candidate_index.into(),
+			),
 		)
 		.await;
 
@@ -570,7 +574,7 @@ fn import_approval_happy_path() {
 		let cert = fake_assignment_cert(hash, validator_index);
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert, candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(cert.into(), candidate_index.into()),
 		)
 		.await;
 
@@ -603,7 +607,7 @@ fn import_approval_happy_path() {
 				vote,
 				tx,
 			)) => {
-				assert_eq!(vote, approval);
+				assert_eq!(vote, approval.clone().into());
 				tx.send(ApprovalCheckResult::Accepted).unwrap();
 			}
 		);
@@ -679,8 +683,8 @@ fn import_approval_bad() {
 				i,
 				tx,
 			)) => {
-				assert_eq!(assignment, cert);
-				assert_eq!(i, candidate_index);
+				assert_eq!(assignment, cert.clone().into());
+				assert_eq!(i, candidate_index.into());
 				tx.send(AssignmentCheckResult::Accepted).unwrap();
 			}
 		);
@@ -697,7 +701,7 @@ fn import_approval_bad() {
 				vote,
 				tx,
 			)) => {
-				assert_eq!(vote, approval);
+				assert_eq!(vote, approval.clone().into());
 				tx.send(ApprovalCheckResult::Bad(ApprovalCheckError::UnknownBlock(hash))).unwrap();
 			}
 		);
@@ -825,9 +829,17 @@ fn update_peer_view() {
 		let cert_a = fake_assignment_cert(hash_a, ValidatorIndex(0));
 		let cert_b = fake_assignment_cert(hash_b, ValidatorIndex(0));
 
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeAssignment(cert_a, 0)).await;
+		overseer_send(
+			overseer,
+			ApprovalDistributionMessage::DistributeAssignment(cert_a.into(), 0u32.into()),
+		)
+		.await;
 
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeAssignment(cert_b, 0)).await;
+		overseer_send(
+			overseer,
+			ApprovalDistributionMessage::DistributeAssignment(cert_b.into(), 0u32.into()),
+		)
+		.await;
 
 		// connect a peer
 		setup_peer_with_view(overseer, peer, view![hash_a]).await;
@@ -848,7 +860,7 @@ fn update_peer_view() {
 		virtual_overseer
 	});
 
-	assert_eq!(state.peer_views.get(peer).map(|v| v.finalized_number), Some(0));
+	assert_eq!(state.peer_views.get(peer).map(|v| v.view.finalized_number), Some(0));
 	assert_eq!(
 		state
 			.blocks
@@ -879,7 +891,7 @@ fn update_peer_view() {
 
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert_c.clone(), 0),
+			ApprovalDistributionMessage::DistributeAssignment(cert_c.clone().into(), 0u32.into()),
 		)
 		.await;
 
@@ -900,7 +912,7 @@ fn update_peer_view() {
 		virtual_overseer
 	});
 
-	assert_eq!(state.peer_views.get(peer).map(|v| v.finalized_number), Some(2));
+	assert_eq!(state.peer_views.get(peer).map(|v| v.view.finalized_number), Some(2));
 	assert_eq!(
 		state
 			.blocks
@@ -930,7 +942,7 @@ fn update_peer_view() {
 		virtual_overseer
 	});
 
-	assert_eq!(state.peer_views.get(peer).map(|v| v.finalized_number), Some(finalized_number));
+	assert_eq!(state.peer_views.get(peer).map(|v| v.view.finalized_number), Some(finalized_number));
 	assert!(state.blocks.get(&hash_c).unwrap().known_by.get(peer).is_none());
 }
 
@@ -975,8 +987,8 @@ fn import_remotely_then_locally() {
 				i,
 				tx,
 			)) => {
-				assert_eq!(assignment, cert);
-				assert_eq!(i, candidate_index);
+				assert_eq!(assignment, cert.clone().into());
+				assert_eq!(i, candidate_index.into());
 				tx.send(AssignmentCheckResult::Accepted).unwrap();
 			}
 		);
@@ -986,7 +998,7 @@ fn import_remotely_then_locally() {
 		// import the same assignment locally
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert, candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(cert.into(), candidate_index.into()),
 		)
 		.await;
 
@@ -1008,14 +1020,15 @@ fn import_remotely_then_locally() {
 				vote,
 				tx,
 			)) => {
-				assert_eq!(vote, approval);
+				assert_eq!(vote, approval.clone().into());
 				tx.send(ApprovalCheckResult::Accepted).unwrap();
 			}
 		);
 		expect_reputation_change(overseer, peer, BENEFIT_VALID_MESSAGE_FIRST).await;
 
 		// import the same approval locally
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeApproval(approval)).await;
+		overseer_send(overseer, ApprovalDistributionMessage::DistributeApproval(approval.into()))
+			.await;
 
 		assert!(overseer.recv().timeout(TIMEOUT).await.is_none(), "no message should be sent");
 		virtual_overseer
@@ -1058,12 +1071,18 @@ fn sends_assignments_even_when_state_is_approved() {
 
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert.clone(), candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(
+				cert.clone().into(),
+				candidate_index.into(),
+			),
 		)
 		.await;
 
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeApproval(approval.clone()))
-			.await;
+		overseer_send(
+			overseer,
+			ApprovalDistributionMessage::DistributeApproval(approval.clone().into()),
+		)
+		.await;
 
 		// connect the peer.
 		setup_peer_with_view(overseer, peer, view![hash]).await;
@@ -1168,8 +1187,8 @@ fn race_condition_in_local_vs_remote_view_update() {
 					claimed_candidate_index,
 					tx,
 				)) => {
-					assert_eq!(assignment, assignments[i].0);
-					assert_eq!(claimed_candidate_index, assignments[i].1);
+					assert_eq!(assignment, assignments[i].0.clone().into());
+					assert_eq!(claimed_candidate_index, assignments[i].1.into());
 					tx.send(AssignmentCheckResult::Accepted).unwrap();
 				}
 			);
@@ -1236,12 +1255,18 @@ fn propagates_locally_generated_assignment_to_both_dimensions() {
 
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert.clone(), candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(
+				cert.clone().into(),
+				candidate_index.into(),
+			),
 		)
 		.await;
 
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeApproval(approval.clone()))
-			.await;
+		overseer_send(
+			overseer,
+			ApprovalDistributionMessage::DistributeApproval(approval.clone().into()),
+		)
+		.await;
 
 		let assignments = vec![(cert.clone(), candidate_index)];
 		let approvals = vec![approval.clone()];
@@ -1484,12 +1509,18 @@ fn propagates_to_required_after_connect() {
 
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert.clone(), candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(
+				cert.clone().into(),
+				candidate_index.into(),
+			),
 		)
 		.await;
 
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeApproval(approval.clone()))
-			.await;
+		overseer_send(
+			overseer,
+			ApprovalDistributionMessage::DistributeApproval(approval.clone().into()),
+		)
+		.await;
 
 		let assignments = vec![(cert.clone(), candidate_index)];
 		let approvals = vec![approval.clone()];
@@ -1609,12 +1640,18 @@ fn sends_to_more_peers_after_getting_topology() {
 
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert.clone(), candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(
+				cert.clone().into(),
+				candidate_index.into(),
+			),
 		)
 		.await;
 
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeApproval(approval.clone()))
-			.await;
+		overseer_send(
+			overseer,
+			ApprovalDistributionMessage::DistributeApproval(approval.clone().into()),
+		)
+		.await;
 
 		let assignments = vec![(cert.clone(), candidate_index)];
 		let approvals = vec![approval.clone()];
@@ -1768,12 +1805,18 @@ fn originator_aggression_l1() {
 
 		overseer_send(
 			overseer,
-			ApprovalDistributionMessage::DistributeAssignment(cert.clone(), candidate_index),
+			ApprovalDistributionMessage::DistributeAssignment(
+				cert.clone().into(),
+				candidate_index.into(),
+			),
 		)
 		.await;
 
-		overseer_send(overseer, ApprovalDistributionMessage::DistributeApproval(approval.clone()))
-			.await;
+		overseer_send(
+			overseer,
+			ApprovalDistributionMessage::DistributeApproval(approval.clone().into()),
+		)
+		.await;
 
 		let assignments = vec![(cert.clone(), candidate_index)];
 		let approvals = vec![approval.clone()];
@@ -2293,7 +2336,7 @@ fn batch_test_round(message_count: usize) {
 		let validators = 0..message_count;
 		let assignments: Vec<_> = validators
 			.clone()
-			.map(|index| (fake_assignment_cert(Hash::zero(), ValidatorIndex(index as u32)), 0))
+			.map(|index| (fake_assignment_cert(Hash::zero(), ValidatorIndex(index as u32)), 0u32))
 			.collect();
 
 		let approvals: Vec<_> = validators
@@ -2305,9 +2348,22 @@ fn batch_test_round(message_count: usize) {
 			})
 			.collect();
 
-		let peer = PeerId::random();
-		send_assignments_batched(&mut sender, assignments.clone(), peer).await;
-		send_approvals_batched(&mut sender, approvals.clone(), peer).await;
+		let peers = [(PeerId::random(), ValidationVersion::V1.into())];
+		send_assignments_batched(
+			&mut sender,
+			assignments
+				.iter()
+				.map(|(cert, index)| (cert.clone().into(), (*index).into()))
+				.collect(),
+			&peers,
+		)
+		.await;
+		send_approvals_batched(
+			&mut sender,
+			approvals.iter().cloned().map(Into::into).collect(),
+			&peers,
+		)
+		.await;
 
 		// Check expected assignments batches.
 		for assignment_index in (0..assignments.len()).step_by(super::MAX_ASSIGNMENT_BATCH_SIZE) {
@@ -2380,6 +2436,86 @@ fn batch_test_round(message_count: usize) {
 	));
 }
 
+#[test]
+fn batch_sending_skips_multi_candidate_messages_for_v1_peers() {
+	use polkadot_node_subsystem::SubsystemContext;
+	let pool = sp_core::testing::TaskExecutor::new();
+	let mut state = State::default();
+
+	let (mut context, mut virtual_overseer) = test_helpers::make_subsystem_context(pool.clone());
+	let subsystem = ApprovalDistribution::new(Default::default());
+	let mut rng = rand_chacha::ChaCha12Rng::seed_from_u64(12345);
+	let mut sender = context.sender().clone();
+	let subsystem = subsystem.run_inner(context, &mut state, &mut rng);
+
+	let test_fut = async move {
+		let overseer = &mut virtual_overseer;
+		let v1_peer = PeerId::random();
+		let v2_peer = PeerId::random();
+		let peers =
+			[(v1_peer, ValidationVersion::V1.into()), (v2_peer, ValidationVersion::V2.into())];
+
+		let single: IndirectSignedApprovalVoteV2 = IndirectSignedApprovalVote {
+			block_hash: Hash::zero(),
+			candidate_index: 0,
+			validator: ValidatorIndex(0),
+			signature: dummy_signature(),
+		}
+		.into();
+		let multiple = IndirectSignedApprovalVoteV2 {
+			block_hash: Hash::zero(),
+			candidate_indices: vec![0u32, 1].try_into().unwrap(),
+			validator: ValidatorIndex(1),
+			signature: dummy_signature(),
+		};
+
+		send_approvals_batched(&mut sender, vec![single.clone(), multiple.clone()], &peers).await;
+
+		assert_matches!(
+			overseer_recv(overseer).await,
+			AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendValidationMessage(
+				peers,
+				Versioned::V1(protocol_v1::ValidationProtocol::ApprovalDistribution(
+					protocol_v1::ApprovalDistributionMessage::Approvals(sent_approvals)
+				))
+			)) => {
+				assert_eq!(peers, vec![v1_peer]);
+				assert_eq!(sent_approvals.len(), 1);
+				assert_eq!(IndirectSignedApprovalVoteV2::from(sent_approvals[0].clone()), single);
+			}
+		);
+
+		assert_matches!(
+			overseer_recv(overseer).await,
+			AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendValidationMessage(
+				peers,
+				Versioned::V2(protocol_v2::ValidationProtocol::ApprovalDistribution(
+					protocol_v2::ApprovalDistributionMessage::Approvals(sent_approvals)
+				))
+			)) => {
+				assert_eq!(peers, vec![v2_peer]);
+				assert_eq!(sent_approvals, vec![single, multiple]);
+			}
+		);
+		virtual_overseer
+	};
+
+	futures::pin_mut!(test_fut);
+	futures::pin_mut!(subsystem);
+
+	executor::block_on(future::join(
+		async move {
+			let mut overseer = test_fut.await;
+			overseer
+				.send(FromOrchestra::Signal(OverseerSignal::Conclude))
+				.timeout(TIMEOUT)
+				.await
+				.expect("Conclude send timeout");
+		},
+		subsystem,
+	));
+}
+
 #[test]
 fn batch_sending_1_msg() {
 	batch_test_round(1);
@@ -2420,3 +2556,27 @@ fn const_ensure_size_not_zero() {
 	crate::ensure_size_not_zero(super::MAX_ASSIGNMENT_BATCH_SIZE);
 	crate::ensure_size_not_zero(super::MAX_APPROVAL_BATCH_SIZE);
 }
+
+#[test]
+fn batches_are_bounded_by_encoded_size() {
+	// Each approval covers enough candidates to make its encoding take a fifth of the budget.
+	let candidate_indices: Vec<CandidateIndex> =
+		(0..(super::MAX_BATCH_ENCODED_SIZE / 5 * 8) as CandidateIndex).collect();
+	let approval = IndirectSignedApprovalVoteV2 {
+		block_hash: Hash::repeat_byte(0x01),
+		candidate_indices: candidate_indices.try_into().unwrap(),
+		validator: ValidatorIndex(0),
+		signature: dummy_signature(),
+	};
+	assert!(approval.encoded_size() > super::MAX_BATCH_ENCODED_SIZE / 5);
+
+	let mut approvals = vec![approval; 10].into_iter().peekable();
+	let mut batches = Vec::new();
+	while approvals.peek().is_some() {
+		let batch = super::next_batch(&mut approvals, super::MAX_APPROVAL_BATCH_SIZE);
+		assert!(batch.encoded_size() <= super::MAX_BATCH_ENCODED_SIZE);
+		batches.push(batch.len());
+	}
+
+	assert_eq!(batches, vec![4, 4, 2]);
+}
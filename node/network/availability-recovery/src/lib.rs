@@ -37,7 +37,9 @@ use lru::LruCache;
 use rand::seq::SliceRandom;
 
 use fatality::Nested;
-use polkadot_erasure_coding::{branch_hash, branches, obtain_chunks_v1, recovery_threshold};
+use polkadot_erasure_coding::{
+	branch_hash, branches, obtain_chunks_v1, recovery_threshold, systematic_recovery_threshold,
+};
 #[cfg(not(test))]
 use polkadot_node_network_protocol::request_response::CHUNK_REQUEST_TIMEOUT;
 use polkadot_node_network_protocol::{
@@ -99,9 +101,23 @@ const TIMEOUT_START_NEW_REQUESTS: Duration = CHUNK_REQUEST_TIMEOUT;
 #[cfg(test)]
 const TIMEOUT_START_NEW_REQUESTS: Duration = Duration::from_millis(100);
 
+/// The order in which the sources of availability data are tried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecoveryStrategyKind {
+	/// Request the full data from the backing group first, fall back to chunks.
+	BackersFirst,
+	/// Request the full data from the backing group first, then the systematic chunks and
+	/// finally fall back to regular chunks.
+	BackersFirstThenSystematicChunks,
+	/// Request the systematic chunks first, fall back to regular chunks.
+	SystematicChunks,
+	/// Only request regular chunks.
+	ChunksOnly,
+}
+
 /// The Availability Recovery Subsystem.
 pub struct AvailabilityRecoverySubsystem {
-	fast_path: bool,
+	strategy: RecoveryStrategyKind,
 	/// Receiver for available data requests.
 	req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 	/// Metrics for this subsystem.
@@ -129,6 +145,19 @@ struct RequestChunksFromValidators {
 	requesting_chunks: FuturesUndead<Result<Option<ErasureChunk>, (ValidatorIndex, RequestError)>>,
 }
 
+/// Request the systematic chunks, which allows for recovering the data without decoding it.
+///
+/// The systematic chunks are held by the first `systematic_threshold` validators. As soon as any
+/// of them fails to provide a valid chunk, we give up and fall back to
+/// [`RequestChunksFromValidators`], reusing the chunks we have already received.
+struct RequestSystematicChunks {
+	/// The validators holding a systematic chunk, which we have not yet requested it from.
+	validators: VecDeque<ValidatorIndex>,
+	received_chunks: HashMap<ValidatorIndex, ErasureChunk>,
+	/// Pending chunk requests with soft timeout.
+	requesting_chunks: FuturesUndead<Result<Option<ErasureChunk>, (ValidatorIndex, RequestError)>>,
+}
+
 struct RecoveryParams {
 	/// Discovery ids of `validators`.
 	validator_authority_keys: Vec<AuthorityDiscoveryId>,
@@ -139,6 +168,9 @@ struct RecoveryParams {
 	/// The number of pieces needed.
 	threshold: usize,
 
+	/// The number of systematic pieces needed.
+	systematic_threshold: usize,
+
 	/// A hash of the relevant candidate.
 	candidate_hash: CandidateHash,
 
@@ -151,9 +183,10 @@ struct RecoveryParams {
 
 /// Source the availability data either by means
 /// of direct request response protocol to
-/// backers (a.k.a. fast-path), or recover from chunks.
+/// backers (a.k.a. fast-path), or recover from (systematic) chunks.
 enum Source {
 	RequestFromBackers(RequestFromBackers),
+	RequestSystematicChunks(RequestSystematicChunks),
 	RequestChunks(RequestChunksFromValidators),
 }
 
@@ -167,6 +200,9 @@ struct RecoveryTask<Sender> {
 
 	/// The source to obtain the availability data from.
 	source: Source,
+
+	/// Whether to try the systematic chunks once the backers could not provide the data.
+	systematic_after_backers: bool,
 }
 
 impl RequestFromBackers {
@@ -248,14 +284,25 @@ impl RequestFromBackers {
 
 impl RequestChunksFromValidators {
 	fn new(n_validators: u32) -> Self {
-		let mut shuffling: Vec<_> = (0..n_validators).map(ValidatorIndex).collect();
+		Self::with_received_chunks(n_validators, HashMap::new())
+	}
+
+	/// Create a new instance, which does not request the already `received_chunks` again.
+	fn with_received_chunks(
+		n_validators: u32,
+		received_chunks: HashMap<ValidatorIndex, ErasureChunk>,
+	) -> Self {
+		let mut shuffling: Vec<_> = (0..n_validators)
+			.map(ValidatorIndex)
+			.filter(|i| !received_chunks.contains_key(i))
+			.collect();
 		shuffling.shuffle(&mut rand::thread_rng());
 
 		RequestChunksFromValidators {
 			error_count: 0,
 			total_received_responses: 0,
 			shuffling: shuffling.into(),
-			received_chunks: HashMap::new(),
+			received_chunks,
 			requesting_chunks: FuturesUndead::new(),
 		}
 	}
@@ -587,6 +634,239 @@ impl RequestChunksFromValidators {
 	}
 }
 
+impl RequestSystematicChunks {
+	fn new(systematic_threshold: usize) -> Self {
+		RequestSystematicChunks {
+			validators: (0..systematic_threshold as u32).map(ValidatorIndex).collect(),
+			received_chunks: HashMap::new(),
+			requesting_chunks: FuturesUndead::new(),
+		}
+	}
+
+	fn launch_parallel_requests(&mut self, params: &RecoveryParams) -> Vec<Requests> {
+		let num_requests = std::cmp::min(
+			N_PARALLEL,
+			params.systematic_threshold.saturating_sub(self.received_chunks.len()),
+		);
+		let candidate_hash = &params.candidate_hash;
+		let mut requests = Vec::new();
+
+		while self.requesting_chunks.len() < num_requests {
+			let validator_index = match self.validators.pop_front() {
+				Some(validator_index) => validator_index,
+				None => break,
+			};
+			let validator = params.validator_authority_keys[validator_index.0 as usize].clone();
+			gum::trace!(
+				target: LOG_TARGET,
+				?validator,
+				?validator_index,
+				?candidate_hash,
+				"Requesting systematic chunk",
+			);
+
+			let raw_request = req_res::v1::ChunkFetchingRequest {
+				candidate_hash: params.candidate_hash,
+				index: validator_index,
+			};
+
+			let (req, res) = OutgoingRequest::new(Recipient::Authority(validator), raw_request);
+			requests.push(Requests::ChunkFetchingV1(req));
+
+			params.metrics.on_chunk_request_issued();
+			let timer = params.metrics.time_chunk_request();
+
+			self.requesting_chunks.push(Box::pin(async move {
+				let _timer = timer;
+				match res.await {
+					Ok(req_res::v1::ChunkFetchingResponse::Chunk(chunk)) =>
+						Ok(Some(chunk.recombine_into_chunk(&raw_request))),
+					Ok(req_res::v1::ChunkFetchingResponse::NoSuchChunk) => Ok(None),
+					Err(e) => Err((validator_index, e)),
+				}
+			}));
+		}
+
+		requests
+	}
+
+	/// Wait for the pending requests, returns `false` as soon as any of them fails.
+	async fn wait_for_chunks(&mut self, params: &RecoveryParams) -> bool {
+		let metrics = &params.metrics;
+
+		while let Some(request_result) =
+			self.requesting_chunks.next_with_timeout(TIMEOUT_START_NEW_REQUESTS).await
+		{
+			match request_result {
+				Ok(Some(chunk)) if is_chunk_valid(params, &chunk) => {
+					metrics.on_chunk_request_succeeded();
+					gum::trace!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						validator_index = ?chunk.index,
+						"Received valid systematic chunk",
+					);
+					self.received_chunks.insert(chunk.index, chunk);
+				},
+				Ok(Some(_)) => {
+					metrics.on_chunk_request_invalid();
+					return false
+				},
+				Ok(None) => {
+					metrics.on_chunk_request_no_such_chunk();
+					return false
+				},
+				Err((validator_index, e)) => {
+					if let RequestError::NetworkError(RequestFailure::Network(
+						OutboundFailure::Timeout,
+					)) = e
+					{
+						metrics.on_chunk_request_timeout();
+					} else {
+						metrics.on_chunk_request_error();
+					}
+
+					gum::debug!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						err = ?e,
+						?validator_index,
+						"Failure requesting systematic chunk",
+					);
+					return false
+				},
+			}
+
+			if self.received_chunks.len() >= params.systematic_threshold {
+				break
+			}
+		}
+
+		true
+	}
+
+	async fn run<Sender>(
+		&mut self,
+		params: &RecoveryParams,
+		sender: &mut Sender,
+	) -> Result<AvailableData, RecoveryError>
+	where
+		Sender: overseer::AvailabilityRecoverySenderTrait,
+	{
+		let metrics = &params.metrics;
+
+		// First query the store for any systematic chunk we've got.
+		{
+			let (tx, rx) = oneshot::channel();
+			sender
+				.send_message(AvailabilityStoreMessage::QueryAllChunks(params.candidate_hash, tx))
+				.await;
+
+			match rx.await {
+				Ok(chunks) =>
+					for chunk in chunks {
+						if (chunk.index.0 as usize) < params.systematic_threshold &&
+							is_chunk_valid(params, &chunk)
+						{
+							self.validators.retain(|i| *i != chunk.index);
+							self.received_chunks.insert(chunk.index, chunk);
+						}
+					},
+				Err(oneshot::Canceled) => {
+					gum::warn!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						"Failed to reach the availability store"
+					);
+				},
+			}
+		}
+
+		let _recovery_timer = metrics.time_full_recovery();
+
+		while self.received_chunks.len() < params.systematic_threshold {
+			let requests = self.launch_parallel_requests(params);
+			if !requests.is_empty() {
+				sender
+					.send_message(NetworkBridgeTxMessage::SendRequests(
+						requests,
+						IfDisconnected::TryConnect,
+					))
+					.await;
+			}
+
+			if !self.wait_for_chunks(params).await ||
+				(self.validators.is_empty() &&
+					self.requesting_chunks.total_len() == 0 &&
+					self.received_chunks.len() < params.systematic_threshold)
+			{
+				gum::debug!(
+					target: LOG_TARGET,
+					candidate_hash = ?params.candidate_hash,
+					received = %self.received_chunks.len(),
+					systematic_threshold = %params.systematic_threshold,
+					"Systematic data recovery is not possible, falling back to regular chunks",
+				);
+				metrics.on_systematic_recovery_fallback();
+
+				return Err(RecoveryError::Unavailable)
+			}
+		}
+
+		let recovery_duration = metrics.time_erasure_recovery();
+
+		let mut chunks: Vec<_> = self.received_chunks.values().collect();
+		chunks.sort_unstable_by_key(|c| c.index);
+		let chunks = chunks.into_iter().map(|c| c.chunk.clone()).collect();
+
+		match polkadot_erasure_coding::reconstruct_from_systematic_v1(
+			params.validators.len(),
+			chunks,
+		) {
+			Ok(data) =>
+				if reconstructed_data_matches_root(
+					params.validators.len(),
+					&params.erasure_root,
+					&data,
+				) {
+					gum::trace!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						erasure_root = ?params.erasure_root,
+						"Systematic data recovery complete",
+					);
+					metrics.on_recovery_succeeded();
+
+					Ok(data)
+				} else {
+					recovery_duration.map(|rd| rd.stop_and_discard());
+					gum::trace!(
+						target: LOG_TARGET,
+						candidate_hash = ?params.candidate_hash,
+						erasure_root = ?params.erasure_root,
+						"Systematic data recovery - root mismatch",
+					);
+					metrics.on_recovery_invalid();
+
+					Err(RecoveryError::Invalid)
+				},
+			Err(err) => {
+				recovery_duration.map(|rd| rd.stop_and_discard());
+				gum::trace!(
+					target: LOG_TARGET,
+					candidate_hash = ?params.candidate_hash,
+					erasure_root = ?params.erasure_root,
+					?err,
+					"Systematic data recovery error",
+				);
+				metrics.on_recovery_invalid();
+
+				Err(RecoveryError::Invalid)
+			},
+		}
+	}
+}
+
 const fn is_unavailable(
 	received_chunks: usize,
 	requesting_chunks: usize,
@@ -701,9 +981,30 @@ where
 						Ok(data) => break Ok(data),
 						Err(RecoveryError::Invalid) => break Err(RecoveryError::Invalid),
 						Err(RecoveryError::Unavailable) =>
-							self.source = Source::RequestChunks(RequestChunksFromValidators::new(
-								self.params.validators.len() as _,
-							)),
+							self.source = if self.systematic_after_backers {
+								Source::RequestSystematicChunks(RequestSystematicChunks::new(
+									self.params.systematic_threshold,
+								))
+							} else {
+								Source::RequestChunks(RequestChunksFromValidators::new(
+									self.params.validators.len() as _,
+								))
+							},
+					}
+				},
+				Source::RequestSystematicChunks(ref mut systematic) => {
+					match systematic.run(&self.params, &mut self.sender).await {
+						Ok(data) => break Ok(data),
+						Err(RecoveryError::Invalid) => break Err(RecoveryError::Invalid),
+						Err(RecoveryError::Unavailable) => {
+							let received_chunks = std::mem::take(&mut systematic.received_chunks);
+							self.source = Source::RequestChunks(
+								RequestChunksFromValidators::with_received_chunks(
+									self.params.validators.len() as _,
+									received_chunks,
+								),
+							)
+						},
 					}
 				},
 				Source::RequestChunks(ref mut from_all) =>
@@ -857,6 +1158,7 @@ async fn launch_recovery_task<Context>(
 	backing_group: Option<GroupIndex>,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
+	strategy: RecoveryStrategyKind,
 ) -> error::Result<()> {
 	let candidate_hash = receipt.hash();
 
@@ -864,19 +1166,44 @@ async fn launch_recovery_task<Context>(
 		validator_authority_keys: session_info.discovery_keys.clone(),
 		validators: session_info.validators.clone(),
 		threshold: recovery_threshold(session_info.validators.len())?,
+		systematic_threshold: systematic_recovery_threshold(session_info.validators.len())?,
 		candidate_hash,
 		erasure_root: receipt.descriptor.erasure_root,
 		metrics: metrics.clone(),
 	};
 
+	let use_backers = matches!(
+		strategy,
+		RecoveryStrategyKind::BackersFirst | RecoveryStrategyKind::BackersFirstThenSystematicChunks
+	);
+	let use_systematic = matches!(
+		strategy,
+		RecoveryStrategyKind::SystematicChunks |
+			RecoveryStrategyKind::BackersFirstThenSystematicChunks
+	);
+
 	let phase = backing_group
+		.filter(|_| use_backers)
 		.and_then(|g| session_info.validator_groups.get(g))
 		.map(|group| Source::RequestFromBackers(RequestFromBackers::new(group.clone())))
 		.unwrap_or_else(|| {
-			Source::RequestChunks(RequestChunksFromValidators::new(params.validators.len() as _))
+			if use_systematic {
+				Source::RequestSystematicChunks(RequestSystematicChunks::new(
+					params.systematic_threshold,
+				))
+			} else {
+				Source::RequestChunks(
+					RequestChunksFromValidators::new(params.validators.len() as _),
+				)
+			}
 		});
 
-	let recovery_task = RecoveryTask { sender: ctx.sender().clone(), params, source: phase };
+	let recovery_task = RecoveryTask {
+		sender: ctx.sender().clone(),
+		params,
+		source: phase,
+		systematic_after_backers: use_systematic,
+	};
 
 	let (remote, remote_handle) = recovery_task.run().remote_handle();
 
@@ -907,6 +1234,7 @@ async fn handle_recover<Context>(
 	backing_group: Option<GroupIndex>,
 	response_sender: oneshot::Sender<Result<AvailableData, RecoveryError>>,
 	metrics: &Metrics,
+	strategy: RecoveryStrategyKind,
 ) -> error::Result<()> {
 	let candidate_hash = receipt.hash();
 
@@ -950,6 +1278,7 @@ async fn handle_recover<Context>(
 				backing_group,
 				response_sender,
 				metrics,
+				strategy,
 			)
 			.await,
 		None => {
@@ -983,7 +1312,30 @@ impl AvailabilityRecoverySubsystem {
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { fast_path: true, req_receiver, metrics }
+		Self { strategy: RecoveryStrategyKind::BackersFirst, req_receiver, metrics }
+	}
+
+	/// Create a new instance of `AvailabilityRecoverySubsystem` which starts with a fast path to
+	/// request data from backers and then tries to recover from the systematic chunks, before
+	/// falling back to regular chunks.
+	pub fn with_fast_path_then_systematic_chunks(
+		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		metrics: Metrics,
+	) -> Self {
+		Self {
+			strategy: RecoveryStrategyKind::BackersFirstThenSystematicChunks,
+			req_receiver,
+			metrics,
+		}
+	}
+
+	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests the systematic
+	/// chunks first, which avoids decoding, and falls back to regular chunks.
+	pub fn with_systematic_chunks(
+		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
+		metrics: Metrics,
+	) -> Self {
+		Self { strategy: RecoveryStrategyKind::SystematicChunks, req_receiver, metrics }
 	}
 
 	/// Create a new instance of `AvailabilityRecoverySubsystem` which requests only chunks
@@ -991,12 +1343,12 @@ impl AvailabilityRecoverySubsystem {
 		req_receiver: IncomingRequestReceiver<request_v1::AvailableDataFetchingRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { fast_path: false, req_receiver, metrics }
+		Self { strategy: RecoveryStrategyKind::ChunksOnly, req_receiver, metrics }
 	}
 
 	async fn run<Context>(self, mut ctx: Context) -> SubsystemResult<()> {
 		let mut state = State::default();
-		let Self { fast_path, mut req_receiver, metrics } = self;
+		let Self { strategy, mut req_receiver, metrics } = self;
 
 		loop {
 			let recv_req = req_receiver.recv(|| vec![COST_INVALID_REQUEST]).fuse();
@@ -1023,9 +1375,10 @@ impl AvailabilityRecoverySubsystem {
 										&mut ctx,
 										receipt,
 										session_index,
-										maybe_backing_group.filter(|_| fast_path),
+										maybe_backing_group,
 										response_sender,
 										&metrics,
+										strategy,
 									).await {
 										gum::warn!(
 											target: LOG_TARGET,
@@ -58,6 +58,9 @@ struct MetricsInner {
 	/// Note: Those are only recoveries which could not get served locally already - so in other
 	/// words: Only real recoveries.
 	full_recoveries_started: Counter<U64>,
+
+	/// Number of systematic recoveries that had to fall back to recovering from regular chunks.
+	systematic_recoveries_fallback: Counter<U64>,
 }
 
 impl Metrics {
@@ -150,6 +153,13 @@ impl Metrics {
 			metrics.full_recoveries_started.inc()
 		}
 	}
+
+	/// A systematic recovery failed and we fell back to regular chunks.
+	pub fn on_systematic_recovery_fallback(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.systematic_recoveries_fallback.inc()
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -210,6 +220,13 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			systematic_recoveries_fallback: prometheus::register(
+				Counter::new(
+					"polkadot_parachain_availability_recovery_systematic_recoveries_fallback",
+					"Total number of systematic recoveries that fell back to regular chunks.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
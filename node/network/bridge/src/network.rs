@@ -70,7 +70,11 @@ pub(crate) fn send_message<M>(
 	// network used `Bytes` this would not be necessary.
 	let last_peer = peers.pop();
 	// optimization: generate the protocol name once.
-	let protocol_name = protocol_names.get_name(peer_set, version);
+	//
+	// Notifications are always written on the main protocol name, the networking layer
+	// maps it to the fallback negotiated with each peer. `version` only determines the
+	// encoding of `message`.
+	let protocol_name = protocol_names.get_main_name(peer_set);
 	peers.into_iter().for_each(|peer| {
 		net.write_notification(peer, protocol_name.clone(), message.clone());
 	});
@@ -32,7 +32,8 @@ use polkadot_node_network_protocol::{
 		CollationVersion, PeerSet, PeerSetProtocolNames, PerPeerSet, ProtocolVersion,
 		ValidationVersion,
 	},
-	v1 as protocol_v1, ObservedRole, OurView, PeerId, UnifiedReputationChange as Rep, View,
+	v1 as protocol_v1, v2 as protocol_v2, ObservedRole, OurView, PeerId,
+	UnifiedReputationChange as Rep, View,
 };
 
 use polkadot_node_subsystem::{
@@ -416,30 +417,39 @@ where
 				);
 
 				if !v_messages.is_empty() {
-					let (events, reports) =
-						if expected_versions[PeerSet::Validation] ==
-							Some(ValidationVersion::V1.into())
-						{
-							handle_v1_peer_messages::<protocol_v1::ValidationProtocol, _>(
-								remote,
-								PeerSet::Validation,
-								&mut shared.0.lock().validation_peers,
-								v_messages,
-								&metrics,
-							)
-						} else {
-							gum::warn!(
-								target: LOG_TARGET,
-								version = ?expected_versions[PeerSet::Validation],
-								"Major logic bug. Peer somehow has unsupported validation protocol version."
-							);
+					let (events, reports) = if expected_versions[PeerSet::Validation] ==
+						Some(ValidationVersion::V1.into())
+					{
+						handle_peer_messages::<protocol_v1::ValidationProtocol, _>(
+							remote,
+							PeerSet::Validation,
+							&mut shared.0.lock().validation_peers,
+							v_messages,
+							&metrics,
+						)
+					} else if expected_versions[PeerSet::Validation] ==
+						Some(ValidationVersion::V2.into())
+					{
+						handle_peer_messages::<protocol_v2::ValidationProtocol, _>(
+							remote,
+							PeerSet::Validation,
+							&mut shared.0.lock().validation_peers,
+							v_messages,
+							&metrics,
+						)
+					} else {
+						gum::warn!(
+							target: LOG_TARGET,
+							version = ?expected_versions[PeerSet::Validation],
+							"Major logic bug. Peer somehow has unsupported validation protocol version."
+						);
 
-							never!("Only version 1 is supported; peer set connection checked above; qed");
+						never!("Only versions 1 and 2 are supported; peer set connection checked above; qed");
 
-							// If a peer somehow triggers this, we'll disconnect them
-							// eventually.
-							(Vec::new(), vec![UNCONNECTED_PEERSET_COST])
-						};
+						// If a peer somehow triggers this, we'll disconnect them
+						// eventually.
+						(Vec::new(), vec![UNCONNECTED_PEERSET_COST])
+					};
 
 					for report in reports {
 						network_service.report_peer(remote, report);
@@ -453,7 +463,7 @@ where
 						if expected_versions[PeerSet::Collation] ==
 							Some(CollationVersion::V1.into())
 						{
-							handle_v1_peer_messages::<protocol_v1::CollationProtocol, _>(
+							handle_peer_messages::<protocol_v1::CollationProtocol, _>(
 								remote,
 								PeerSet::Collation,
 								&mut shared.0.lock().collation_peers,
@@ -721,6 +731,7 @@ fn update_our_view<Net, Context>(
 		)
 	};
 
+	// View updates are encoded the same way in all versions of the validation protocol.
 	send_validation_message_v1(
 		net,
 		validation_peers,
@@ -753,9 +764,9 @@ fn update_our_view<Net, Context>(
 	);
 }
 
-// Handle messages on a specific v1 peer-set. The peer is expected to be connected on that
-// peer-set.
-fn handle_v1_peer_messages<RawMessage: Decode, OutMessage: From<RawMessage>>(
+// Handle messages on a specific peer-set, decoded as `RawMessage` of the version the peer is
+// connected with. The peer is expected to be connected on that peer-set.
+fn handle_peer_messages<RawMessage: Decode, OutMessage: From<RawMessage>>(
 	peer: PeerId,
 	peer_set: PeerSet,
 	peers: &mut HashMap<PeerId, PeerData>,
@@ -189,11 +189,23 @@ impl TestNetworkHandle {
 		v
 	}
 
-	async fn connect_peer(&mut self, peer: PeerId, peer_set: PeerSet, role: ObservedRole) {
+	async fn connect_peer(
+		&mut self,
+		peer: PeerId,
+		version: ProtocolVersion,
+		peer_set: PeerSet,
+		role: ObservedRole,
+	) {
+		let negotiated_fallback = if version == peer_set.get_main_version() {
+			None
+		} else {
+			Some(self.protocol_names.get_name(peer_set, version))
+		};
+
 		self.send_network_event(NetworkEvent::NotificationStreamOpened {
 			remote: peer,
 			protocol: self.protocol_names.get_main_name(peer_set),
-			negotiated_fallback: None,
+			negotiated_fallback,
 			role: role.into(),
 		})
 		.await;
@@ -404,10 +416,20 @@ fn send_our_view_upon_connection() {
 		handle.await_mode_switch().await;
 
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Collation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				CollationVersion::V1.into(),
+				PeerSet::Collation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		let view = view![head];
@@ -451,10 +473,20 @@ fn sends_view_updates_to_peers() {
 		handle.await_mode_switch().await;
 
 		network_handle
-			.connect_peer(peer_a.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer_a.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 		network_handle
-			.connect_peer(peer_b.clone(), PeerSet::Collation, ObservedRole::Full)
+			.connect_peer(
+				peer_b.clone(),
+				CollationVersion::V1.into(),
+				PeerSet::Collation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		let actions = network_handle.next_network_actions(2).await;
@@ -512,10 +544,20 @@ fn do_not_send_view_update_until_synced() {
 		assert_ne!(peer_a, peer_b);
 
 		network_handle
-			.connect_peer(peer_a.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer_a.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 		network_handle
-			.connect_peer(peer_b.clone(), PeerSet::Collation, ObservedRole::Full)
+			.connect_peer(
+				peer_b.clone(),
+				CollationVersion::V1.into(),
+				PeerSet::Collation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		{
@@ -605,10 +647,20 @@ fn do_not_send_view_update_when_only_finalized_block_changed() {
 		let peer_b = PeerId::random();
 
 		network_handle
-			.connect_peer(peer_a.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer_a.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 		network_handle
-			.connect_peer(peer_b.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer_b.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		let hash_a = Hash::repeat_byte(1);
@@ -664,7 +716,12 @@ fn peer_view_updates_sent_via_overseer() {
 		let peer = PeerId::random();
 
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		let view = view![Hash::repeat_byte(1)];
@@ -714,7 +771,12 @@ fn peer_messages_sent_via_overseer() {
 		let peer = PeerId::random();
 
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		// bridge will inform about all connected peers.
@@ -778,6 +840,71 @@ fn peer_messages_sent_via_overseer() {
 	});
 }
 
+#[test]
+fn v2_peer_messages_sent_via_overseer() {
+	test_harness(done_syncing_oracle(), |test_harness| async move {
+		let TestHarness { mut network_handle, mut virtual_overseer } = test_harness;
+
+		let peer = PeerId::random();
+
+		network_handle
+			.connect_peer(
+				peer.clone(),
+				ValidationVersion::V2.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
+			.await;
+
+		// bridge will inform about all connected peers.
+		{
+			assert_sends_validation_event_to_all(
+				NetworkBridgeEvent::PeerConnected(
+					peer.clone(),
+					ObservedRole::Full,
+					ValidationVersion::V2.into(),
+					None,
+				),
+				&mut virtual_overseer,
+			)
+			.await;
+
+			assert_sends_validation_event_to_all(
+				NetworkBridgeEvent::PeerViewChange(peer.clone(), View::default()),
+				&mut virtual_overseer,
+			)
+			.await;
+		}
+
+		let approval_distribution_message =
+			protocol_v2::ApprovalDistributionMessage::Approvals(Vec::new());
+
+		let message_v2 = protocol_v2::ValidationProtocol::ApprovalDistribution(
+			approval_distribution_message.clone(),
+		);
+
+		network_handle
+			.peer_message(
+				peer.clone(),
+				PeerSet::Validation,
+				WireMessage::ProtocolMessage(message_v2.clone()).encode(),
+			)
+			.await;
+
+		assert_matches!(
+			virtual_overseer.recv().await,
+			AllMessages::ApprovalDistribution(
+				ApprovalDistributionMessage::NetworkBridgeUpdate(
+					NetworkBridgeEvent::PeerMessage(p, Versioned::V2(m))
+				)
+			) => {
+				assert_eq!(p, peer);
+				assert_eq!(m, approval_distribution_message);
+			}
+		);
+		virtual_overseer
+	});
+}
 #[test]
 fn peer_disconnect_from_just_one_peerset() {
 	test_harness(done_syncing_oracle(), |test_harness| async move {
@@ -786,10 +913,20 @@ fn peer_disconnect_from_just_one_peerset() {
 		let peer = PeerId::random();
 
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Collation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				CollationVersion::V1.into(),
+				PeerSet::Collation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		// bridge will inform about all connected peers.
@@ -879,10 +1016,20 @@ fn relays_collation_protocol_messages() {
 		let peer_b = PeerId::random();
 
 		network_handle
-			.connect_peer(peer_a.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer_a.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 		network_handle
-			.connect_peer(peer_b.clone(), PeerSet::Collation, ObservedRole::Full)
+			.connect_peer(
+				peer_b.clone(),
+				CollationVersion::V1.into(),
+				PeerSet::Collation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		// bridge will inform about all connected peers.
@@ -982,10 +1129,20 @@ fn different_views_on_different_peer_sets() {
 		let peer = PeerId::random();
 
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Collation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				CollationVersion::V1.into(),
+				PeerSet::Collation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		// bridge will inform about all connected peers.
@@ -1069,7 +1226,12 @@ fn sent_views_include_finalized_number_update() {
 		let peer_a = PeerId::random();
 
 		network_handle
-			.connect_peer(peer_a.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer_a.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		let hash_a = Hash::repeat_byte(1);
@@ -1114,7 +1276,12 @@ fn view_finalized_number_can_not_go_down() {
 		let peer_a = PeerId::random();
 
 		network_handle
-			.connect_peer(peer_a.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer_a.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.await;
 
 		network_handle
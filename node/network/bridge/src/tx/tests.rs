@@ -25,7 +25,7 @@ use std::collections::HashSet;
 use sc_network::{Event as NetworkEvent, IfDisconnected, ProtocolName};
 
 use polkadot_node_network_protocol::{
-	peer_set::PeerSetProtocolNames,
+	peer_set::{PeerSetProtocolNames, ProtocolVersion},
 	request_response::{outgoing::Requests, ReqProtocolNames},
 	ObservedRole, Versioned,
 };
@@ -167,11 +167,23 @@ impl TestNetworkHandle {
 		self.action_rx.next().await.expect("subsystem concluded early")
 	}
 
-	async fn connect_peer(&mut self, peer: PeerId, peer_set: PeerSet, role: ObservedRole) {
+	async fn connect_peer(
+		&mut self,
+		peer: PeerId,
+		version: ProtocolVersion,
+		peer_set: PeerSet,
+		role: ObservedRole,
+	) {
+		let negotiated_fallback = if version == peer_set.get_main_version() {
+			None
+		} else {
+			Some(self.peerset_protocol_names.get_name(peer_set, version))
+		};
+
 		self.send_network_event(NetworkEvent::NotificationStreamOpened {
 			remote: peer,
 			protocol: self.peerset_protocol_names.get_main_name(peer_set),
-			negotiated_fallback: None,
+			negotiated_fallback,
 			role: role.into(),
 		})
 		.await;
@@ -235,7 +247,12 @@ fn send_messages_to_peers() {
 		let peer = PeerId::random();
 
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Validation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				ValidationVersion::V1.into(),
+				PeerSet::Validation,
+				ObservedRole::Full,
+			)
 			.timeout(TIMEOUT)
 			.await
 			.expect("Timeout does not occur");
@@ -244,7 +261,12 @@ fn send_messages_to_peers() {
 		// so the single item sink has to be free explicitly
 
 		network_handle
-			.connect_peer(peer.clone(), PeerSet::Collation, ObservedRole::Full)
+			.connect_peer(
+				peer.clone(),
+				CollationVersion::V1.into(),
+				PeerSet::Collation,
+				ObservedRole::Full,
+			)
 			.timeout(TIMEOUT)
 			.await
 			.expect("Timeout does not occur");
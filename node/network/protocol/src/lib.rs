@@ -251,22 +251,25 @@ impl View {
 
 /// A protocol-versioned type.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Versioned<V1> {
+pub enum Versioned<V1, V2> {
 	/// V1 type.
 	V1(V1),
+	/// V2 type.
+	V2(V2),
 }
 
-impl<V1: Clone> Versioned<&'_ V1> {
+impl<V1: Clone, V2: Clone> Versioned<&'_ V1, &'_ V2> {
 	/// Convert to a fully-owned version of the message.
-	pub fn clone_inner(&self) -> Versioned<V1> {
+	pub fn clone_inner(&self) -> Versioned<V1, V2> {
 		match *self {
 			Versioned::V1(inner) => Versioned::V1(inner.clone()),
+			Versioned::V2(inner) => Versioned::V2(inner.clone()),
 		}
 	}
 }
 
 /// All supported versions of the validation protocol message.
-pub type VersionedValidationProtocol = Versioned<v1::ValidationProtocol>;
+pub type VersionedValidationProtocol = Versioned<v1::ValidationProtocol, v2::ValidationProtocol>;
 
 impl From<v1::ValidationProtocol> for VersionedValidationProtocol {
 	fn from(v1: v1::ValidationProtocol) -> Self {
@@ -274,8 +277,16 @@ impl From<v1::ValidationProtocol> for VersionedValidationProtocol {
 	}
 }
 
+impl From<v2::ValidationProtocol> for VersionedValidationProtocol {
+	fn from(v2: v2::ValidationProtocol) -> Self {
+		VersionedValidationProtocol::V2(v2)
+	}
+}
+
 /// All supported versions of the collation protocol message.
-pub type VersionedCollationProtocol = Versioned<v1::CollationProtocol>;
+///
+/// The collation protocol is unchanged in v2, so both variants carry the same type.
+pub type VersionedCollationProtocol = Versioned<v1::CollationProtocol, v2::CollationProtocol>;
 
 impl From<v1::CollationProtocol> for VersionedCollationProtocol {
 	fn from(v1: v1::CollationProtocol) -> Self {
@@ -289,6 +300,7 @@ macro_rules! impl_versioned_full_protocol_from {
 			fn from(versioned_from: $from) -> $out {
 				match versioned_from {
 					Versioned::V1(x) => Versioned::V1(x.into()),
+					Versioned::V2(x) => Versioned::V2(x.into()),
 				}
 			}
 		}
@@ -298,7 +310,7 @@ macro_rules! impl_versioned_full_protocol_from {
 /// Implement `TryFrom` for one versioned enum variant into the inner type.
 /// `$m_ty::$variant(inner) -> Ok(inner)`
 macro_rules! impl_versioned_try_from {
-	($from:ty, $out:ty, $v1_pat:pat => $v1_out:expr) => {
+	($from:ty, $out:ty, $v1_pat:pat => $v1_out:expr, $v2_pat:pat => $v2_out:expr) => {
 		impl TryFrom<$from> for $out {
 			type Error = crate::WrongVariant;
 
@@ -306,6 +318,7 @@ macro_rules! impl_versioned_try_from {
 				#[allow(unreachable_patterns)] // when there is only one variant
 				match x {
 					Versioned::V1($v1_pat) => Ok(Versioned::V1($v1_out)),
+					Versioned::V2($v2_pat) => Ok(Versioned::V2($v2_out)),
 					_ => Err(crate::WrongVariant),
 				}
 			}
@@ -318,6 +331,7 @@ macro_rules! impl_versioned_try_from {
 				#[allow(unreachable_patterns)] // when there is only one variant
 				match x {
 					Versioned::V1($v1_pat) => Ok(Versioned::V1($v1_out.clone())),
+					Versioned::V2($v2_pat) => Ok(Versioned::V2($v2_out.clone())),
 					_ => Err(crate::WrongVariant),
 				}
 			}
@@ -326,7 +340,8 @@ macro_rules! impl_versioned_try_from {
 }
 
 /// Version-annotated messages used by the bitfield distribution subsystem.
-pub type BitfieldDistributionMessage = Versioned<v1::BitfieldDistributionMessage>;
+pub type BitfieldDistributionMessage =
+	Versioned<v1::BitfieldDistributionMessage, v2::BitfieldDistributionMessage>;
 impl_versioned_full_protocol_from!(
 	BitfieldDistributionMessage,
 	VersionedValidationProtocol,
@@ -335,11 +350,13 @@ impl_versioned_full_protocol_from!(
 impl_versioned_try_from!(
 	VersionedValidationProtocol,
 	BitfieldDistributionMessage,
-	v1::ValidationProtocol::BitfieldDistribution(x) => x
+	v1::ValidationProtocol::BitfieldDistribution(x) => x,
+	v2::ValidationProtocol::BitfieldDistribution(x) => x
 );
 
 /// Version-annotated messages used by the statement distribution subsystem.
-pub type StatementDistributionMessage = Versioned<v1::StatementDistributionMessage>;
+pub type StatementDistributionMessage =
+	Versioned<v1::StatementDistributionMessage, v2::StatementDistributionMessage>;
 impl_versioned_full_protocol_from!(
 	StatementDistributionMessage,
 	VersionedValidationProtocol,
@@ -348,11 +365,13 @@ impl_versioned_full_protocol_from!(
 impl_versioned_try_from!(
 	VersionedValidationProtocol,
 	StatementDistributionMessage,
-	v1::ValidationProtocol::StatementDistribution(x) => x
+	v1::ValidationProtocol::StatementDistribution(x) => x,
+	v2::ValidationProtocol::StatementDistribution(x) => x
 );
 
 /// Version-annotated messages used by the approval distribution subsystem.
-pub type ApprovalDistributionMessage = Versioned<v1::ApprovalDistributionMessage>;
+pub type ApprovalDistributionMessage =
+	Versioned<v1::ApprovalDistributionMessage, v2::ApprovalDistributionMessage>;
 impl_versioned_full_protocol_from!(
 	ApprovalDistributionMessage,
 	VersionedValidationProtocol,
@@ -361,11 +380,13 @@ impl_versioned_full_protocol_from!(
 impl_versioned_try_from!(
 	VersionedValidationProtocol,
 	ApprovalDistributionMessage,
-	v1::ValidationProtocol::ApprovalDistribution(x) => x
+	v1::ValidationProtocol::ApprovalDistribution(x) => x,
+	v2::ValidationProtocol::ApprovalDistribution(x) => x
 );
 
 /// Version-annotated messages used by the gossip-support subsystem (this is void).
-pub type GossipSupportNetworkMessage = Versioned<v1::GossipSupportNetworkMessage>;
+pub type GossipSupportNetworkMessage =
+	Versioned<v1::GossipSupportNetworkMessage, v2::GossipSupportNetworkMessage>;
 // This is a void enum placeholder, so never gets sent over the wire.
 impl TryFrom<VersionedValidationProtocol> for GossipSupportNetworkMessage {
 	type Error = WrongVariant;
@@ -382,7 +403,8 @@ impl<'a> TryFrom<&'a VersionedValidationProtocol> for GossipSupportNetworkMessag
 }
 
 /// Version-annotated messages used by the bitfield distribution subsystem.
-pub type CollatorProtocolMessage = Versioned<v1::CollatorProtocolMessage>;
+pub type CollatorProtocolMessage =
+	Versioned<v1::CollatorProtocolMessage, v2::CollatorProtocolMessage>;
 impl_versioned_full_protocol_from!(
 	CollatorProtocolMessage,
 	VersionedCollationProtocol,
@@ -391,7 +413,8 @@ impl_versioned_full_protocol_from!(
 impl_versioned_try_from!(
 	VersionedCollationProtocol,
 	CollatorProtocolMessage,
-	v1::CollationProtocol::CollatorProtocol(x) => x
+	v1::CollationProtocol::CollatorProtocol(x) => x,
+	v2::CollationProtocol::CollatorProtocol(x) => x
 );
 
 /// v1 notification protocol types.
@@ -551,3 +574,70 @@ pub mod v1 {
 		payload
 	}
 }
+
+/// v2 notification protocol types.
+///
+/// Only the approval distribution messages differ from v1, allowing assignments and
+/// approvals to cover several candidates of the same block.
+pub mod v2 {
+	use parity_scale_codec::{Decode, Encode};
+
+	use polkadot_node_primitives::approval::v2::{
+		CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2,
+	};
+
+	pub use super::v1::{
+		declare_signature_payload, BitfieldDistributionMessage, CollationProtocol,
+		CollatorProtocolMessage, GossipSupportNetworkMessage, StatementDistributionMessage,
+		StatementMetadata,
+	};
+
+	/// Network messages used by the approval distribution subsystem.
+	#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+	pub enum ApprovalDistributionMessage {
+		/// Assignments for candidates in recent, unfinalized blocks, along with the
+		/// candidates of the block each assignment claims.
+		///
+		/// Actually checking the assignment may yield a different result.
+		#[codec(index = 0)]
+		Assignments(Vec<(IndirectAssignmentCertV2, CandidateBitfield)>),
+		/// Approvals for candidates in some recent, unfinalized block.
+		#[codec(index = 1)]
+		Approvals(Vec<IndirectSignedApprovalVoteV2>),
+	}
+
+	impl From<super::v1::ApprovalDistributionMessage> for ApprovalDistributionMessage {
+		fn from(message: super::v1::ApprovalDistributionMessage) -> Self {
+			match message {
+				super::v1::ApprovalDistributionMessage::Assignments(assignments) =>
+					ApprovalDistributionMessage::Assignments(
+						assignments
+							.into_iter()
+							.map(|(cert, candidate_index)| (cert.into(), candidate_index.into()))
+							.collect(),
+					),
+				super::v1::ApprovalDistributionMessage::Approvals(approvals) =>
+					ApprovalDistributionMessage::Approvals(
+						approvals.into_iter().map(Into::into).collect(),
+					),
+			}
+		}
+	}
+
+	/// All network messages on the validation peer-set.
+	#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq, derive_more::From)]
+	pub enum ValidationProtocol {
+		/// Bitfield distribution messages
+		#[codec(index = 1)]
+		#[from]
+		BitfieldDistribution(BitfieldDistributionMessage),
+		/// Statement distribution messages
+		#[codec(index = 3)]
+		#[from]
+		StatementDistribution(StatementDistributionMessage),
+		/// Approval distribution messages
+		#[codec(index = 4)]
+		#[from]
+		ApprovalDistribution(ApprovalDistributionMessage),
+	}
+}
@@ -72,7 +72,7 @@ impl PeerSet {
 		// Networking layer relies on `get_main_name()` being the main name of the protocol
 		// for peersets and connection management.
 		let protocol = peerset_protocol_names.get_main_name(self);
-		let fallback_names = PeerSetProtocolNames::get_fallback_names(self);
+		let fallback_names = peerset_protocol_names.get_fallback_names(self);
 		let max_notification_size = self.get_max_notification_size(is_authority);
 
 		match self {
@@ -118,7 +118,7 @@ impl PeerSet {
 	/// of the main protocol name reported by [`PeerSetProtocolNames::get_main_name()`].
 	pub fn get_main_version(self) -> ProtocolVersion {
 		match self {
-			PeerSet::Validation => ValidationVersion::V1.into(),
+			PeerSet::Validation => ValidationVersion::V2.into(),
 			PeerSet::Collation => CollationVersion::V1.into(),
 		}
 	}
@@ -144,6 +144,8 @@ impl PeerSet {
 			PeerSet::Validation =>
 				if version == ValidationVersion::V1.into() {
 					Some("validation/1")
+				} else if version == ValidationVersion::V2.into() {
+					Some("validation/2")
 				} else {
 					None
 				},
@@ -211,6 +213,8 @@ impl From<ProtocolVersion> for u32 {
 pub enum ValidationVersion {
 	/// The first version.
 	V1 = 1,
+	/// The second version, adding multi-candidate approval distribution messages.
+	V2 = 2,
 }
 
 /// Supported collation protocol versions. Only versions defined here must be used in the codebase.
@@ -374,17 +378,30 @@ impl PeerSetProtocolNames {
 		.into()
 	}
 
-	/// Get the protocol fallback names. Currently only holds the legacy name
-	/// for `LEGACY_PROTOCOL_VERSION` = 1.
-	fn get_fallback_names(protocol: PeerSet) -> Vec<ProtocolName> {
-		std::iter::once(Self::get_legacy_name(protocol)).collect()
+	/// Get the protocol fallback names: the names of all versions older than the main
+	/// version, newest first, followed by the legacy name for `LEGACY_PROTOCOL_VERSION` = 1.
+	fn get_fallback_names(&self, protocol: PeerSet) -> Vec<ProtocolName> {
+		let main_version: u32 = protocol.get_main_version().into();
+		let mut older_versions: Vec<ProtocolVersion> = match protocol {
+			PeerSet::Validation => ValidationVersion::iter().map(Into::into).collect(),
+			PeerSet::Collation => CollationVersion::iter().map(Into::into).collect(),
+		};
+		older_versions.retain(|version| u32::from(*version) < main_version);
+		older_versions.sort_by_key(|version| std::cmp::Reverse(u32::from(*version)));
+
+		older_versions
+			.into_iter()
+			.map(|version| self.get_name(protocol, version))
+			.chain(std::iter::once(Self::get_legacy_name(protocol)))
+			.collect()
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::{
-		CollationVersion, Hash, PeerSet, PeerSetProtocolNames, ProtocolVersion, ValidationVersion,
+		CollationVersion, Hash, PeerSet, PeerSetProtocolNames, ProtocolName, ProtocolVersion,
+		ValidationVersion,
 	};
 	use strum::IntoEnumIterator;
 
@@ -459,6 +476,13 @@ mod tests {
 			Some((PeerSet::Validation, TestVersion(1).into())),
 		);
 
+		let validation_v2 =
+			"/7ac8741de8b7146d8a5617fd462914557fe63c265a7f1c10e7dae32858eebb80/validation/2";
+		assert_eq!(
+			protocol_names.try_get_protocol(&validation_v2.into()),
+			Some((PeerSet::Validation, TestVersion(2).into())),
+		);
+
 		let validation_legacy = "/polkadot/validation/1";
 		assert_eq!(
 			protocol_names.try_get_protocol(&validation_legacy.into()),
@@ -536,4 +560,26 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn fallback_names_cover_older_versions() {
+		let genesis_hash = Hash::from([
+			122, 200, 116, 29, 232, 183, 20, 109, 138, 86, 23, 253, 70, 41, 20, 85, 127, 230, 60,
+			38, 90, 127, 28, 16, 231, 218, 227, 40, 88, 238, 187, 128,
+		]);
+		let protocol_names = PeerSetProtocolNames::new(genesis_hash, None);
+
+		assert_eq!(
+			protocol_names.get_main_name(PeerSet::Validation),
+			"/7ac8741de8b7146d8a5617fd462914557fe63c265a7f1c10e7dae32858eebb80/validation/2".into(),
+		);
+		let expected: Vec<ProtocolName> = vec![
+			"/7ac8741de8b7146d8a5617fd462914557fe63c265a7f1c10e7dae32858eebb80/validation/1".into(),
+			"/polkadot/validation/1".into(),
+		];
+		assert_eq!(protocol_names.get_fallback_names(PeerSet::Validation), expected);
+
+		let expected: Vec<ProtocolName> = vec!["/polkadot/collation/1".into()];
+		assert_eq!(protocol_names.get_fallback_names(PeerSet::Collation), expected);
+	}
 }
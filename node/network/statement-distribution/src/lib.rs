@@ -1700,7 +1700,7 @@ async fn handle_network_update<Context, R>(
 				}
 			}
 		},
-		NetworkBridgeEvent::PeerMessage(peer, Versioned::V1(message)) => {
+		NetworkBridgeEvent::PeerMessage(peer, Versioned::V1(message) | Versioned::V2(message)) => {
 			handle_incoming_message_and_circulate(
 				peer,
 				topology_storage,
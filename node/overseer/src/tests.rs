@@ -30,8 +30,8 @@ use polkadot_node_subsystem_types::{
 	ActivatedLeaf, LeafStatus,
 };
 use polkadot_primitives::{
-	CandidateHash, CandidateReceipt, CollatorPair, InvalidDisputeStatementKind, SessionIndex,
-	ValidDisputeStatementKind, ValidatorIndex,
+	vstaging::ValidDisputeStatementKind, CandidateHash, CandidateReceipt, CollatorPair,
+	InvalidDisputeStatementKind, SessionIndex, ValidatorIndex,
 };
 
 use crate::{
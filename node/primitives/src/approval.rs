@@ -114,6 +114,283 @@ pub struct IndirectSignedApprovalVote {
 	pub signature: ValidatorSignature,
 }
 
+/// Version 2 of the approval types, which allow a single assignment certificate or approval
+/// vote to cover several candidates of the same relay chain block.
+pub mod v2 {
+	use super::{
+		AssignmentCert, AssignmentCertKind, IndirectAssignmentCert, IndirectSignedApprovalVote,
+		VRFOutput, VRFProof,
+	};
+	use bitvec::{order::Lsb0, vec::BitVec};
+	use parity_scale_codec::{Decode, Encode};
+	use polkadot_primitives::{
+		CandidateIndex, CoreIndex, Hash, ValidatorIndex, ValidatorSignature,
+	};
+	use std::marker::PhantomData;
+
+	/// A static context used for compact relay-vrf-modulo VRFs, which claim several
+	/// cores with a single VRF output.
+	pub const RELAY_VRF_MODULO_COMPACT_CONTEXT: &[u8] = b"A&V MOD v2";
+
+	/// Types which can be used as the index of a bit in a [`Bitfield`].
+	pub trait AsBitIndex {
+		/// Returns the position of the bit.
+		fn as_bit_index(&self) -> usize;
+	}
+
+	impl AsBitIndex for CandidateIndex {
+		fn as_bit_index(&self) -> usize {
+			*self as usize
+		}
+	}
+
+	impl AsBitIndex for CoreIndex {
+		fn as_bit_index(&self) -> usize {
+			self.0 as usize
+		}
+	}
+
+	/// A bitfield of indices, e.g. candidate or core indices within a relay chain block.
+	#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq, Hash)]
+	pub struct Bitfield<T>(BitVec<u8, Lsb0>, PhantomData<T>);
+
+	/// A bitfield of candidate indices.
+	pub type CandidateBitfield = Bitfield<CandidateIndex>;
+
+	/// A bitfield of core indices.
+	pub type CoreBitfield = Bitfield<CoreIndex>;
+
+	/// Errors which can occur when building a [`Bitfield`].
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+	pub enum BitfieldError {
+		/// The bitfield would have no bit set.
+		#[error("A bitfield must have at least one bit set")]
+		NullAssignment,
+	}
+
+	impl<T: AsBitIndex> Bitfield<T> {
+		/// Returns `true` if the bit at `index` is set.
+		pub fn bit_at(&self, index: T) -> bool {
+			let index = index.as_bit_index();
+			self.0.len() > index && self.0[index]
+		}
+
+		/// The number of bits in the bitfield.
+		pub fn len(&self) -> usize {
+			self.0.len()
+		}
+
+		/// Whether the bitfield has no bits at all.
+		pub fn is_empty(&self) -> bool {
+			self.0.is_empty()
+		}
+
+		/// The number of bits set.
+		pub fn count_ones(&self) -> usize {
+			self.0.count_ones()
+		}
+
+		/// The position of the first bit set, if any.
+		pub fn first_one(&self) -> Option<usize> {
+			self.0.first_one()
+		}
+
+		/// An iterator over the positions of all bits set.
+		pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+			self.0.iter_ones()
+		}
+	}
+
+	impl<T: AsBitIndex> From<T> for Bitfield<T> {
+		fn from(index: T) -> Self {
+			let index = index.as_bit_index();
+			let mut bits = BitVec::repeat(false, index + 1);
+			bits.set(index, true);
+			Bitfield(bits, PhantomData)
+		}
+	}
+
+	impl<T: AsBitIndex> TryFrom<Vec<T>> for Bitfield<T> {
+		type Error = BitfieldError;
+
+		fn try_from(indices: Vec<T>) -> Result<Self, Self::Error> {
+			let max_index = indices
+				.iter()
+				.map(AsBitIndex::as_bit_index)
+				.max()
+				.ok_or(BitfieldError::NullAssignment)?;
+
+			let mut bits = BitVec::repeat(false, max_index + 1);
+			for index in indices {
+				bits.set(index.as_bit_index(), true);
+			}
+
+			Ok(Bitfield(bits, PhantomData))
+		}
+	}
+
+	/// Errors converting version 2 approval types into their version 1 equivalents.
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+	pub enum IntoV1Error {
+		/// Compact assignments cannot be expressed in version 1.
+		#[error("A compact assignment has no version 1 representation")]
+		CompactAssignment,
+		/// Approvals covering more than one candidate cannot be expressed in version 1.
+		#[error("An approval covering {0} candidates has no version 1 representation")]
+		MultipleCandidates(usize),
+	}
+
+	/// Different kinds of input data or criteria that can prove a validator's assignment
+	/// to check one or more parachain candidates.
+	///
+	/// The first two variants encode exactly like [`AssignmentCertKind`].
+	#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+	pub enum AssignmentCertKindV2 {
+		/// See [`AssignmentCertKind::RelayVRFModulo`].
+		#[codec(index = 0)]
+		RelayVRFModulo {
+			/// The sample number used in this cert.
+			sample: u32,
+		},
+		/// See [`AssignmentCertKind::RelayVRFDelay`].
+		#[codec(index = 1)]
+		RelayVRFDelay {
+			/// The core index chosen in this cert.
+			core_index: CoreIndex,
+		},
+		/// An assignment story based on the VRF that authorized the relay-chain block where the
+		/// candidates were included, claiming all cores the VRF output assigns to the
+		/// validator at once.
+		///
+		/// The context is [`RELAY_VRF_MODULO_COMPACT_CONTEXT`]
+		#[codec(index = 2)]
+		RelayVRFModuloCompact {
+			/// The cores claimed by this cert.
+			core_bitfield: CoreBitfield,
+		},
+	}
+
+	/// A certification of assignment to one or more cores.
+	#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+	pub struct AssignmentCertV2 {
+		/// The criterion which is claimed to be met by this cert.
+		pub kind: AssignmentCertKindV2,
+		/// The VRF showing the criterion is met.
+		pub vrf: (VRFOutput, VRFProof),
+	}
+
+	impl From<AssignmentCert> for AssignmentCertV2 {
+		fn from(cert: AssignmentCert) -> Self {
+			let kind = match cert.kind {
+				AssignmentCertKind::RelayVRFModulo { sample } =>
+					AssignmentCertKindV2::RelayVRFModulo { sample },
+				AssignmentCertKind::RelayVRFDelay { core_index } =>
+					AssignmentCertKindV2::RelayVRFDelay { core_index },
+			};
+
+			AssignmentCertV2 { kind, vrf: cert.vrf }
+		}
+	}
+
+	impl TryFrom<AssignmentCertV2> for AssignmentCert {
+		type Error = IntoV1Error;
+
+		fn try_from(cert: AssignmentCertV2) -> Result<Self, Self::Error> {
+			let kind = match cert.kind {
+				AssignmentCertKindV2::RelayVRFModulo { sample } =>
+					AssignmentCertKind::RelayVRFModulo { sample },
+				AssignmentCertKindV2::RelayVRFDelay { core_index } =>
+					AssignmentCertKind::RelayVRFDelay { core_index },
+				AssignmentCertKindV2::RelayVRFModuloCompact { .. } =>
+					return Err(IntoV1Error::CompactAssignment),
+			};
+
+			Ok(AssignmentCert { kind, vrf: cert.vrf })
+		}
+	}
+
+	/// An assignment certificate which refers to the candidates it claims by block hash.
+	#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+	pub struct IndirectAssignmentCertV2 {
+		/// A block hash where the candidates appear.
+		pub block_hash: Hash,
+		/// The validator index.
+		pub validator: ValidatorIndex,
+		/// The cert itself.
+		pub cert: AssignmentCertV2,
+	}
+
+	impl From<IndirectAssignmentCert> for IndirectAssignmentCertV2 {
+		fn from(indirect: IndirectAssignmentCert) -> Self {
+			IndirectAssignmentCertV2 {
+				block_hash: indirect.block_hash,
+				validator: indirect.validator,
+				cert: indirect.cert.into(),
+			}
+		}
+	}
+
+	impl TryFrom<IndirectAssignmentCertV2> for IndirectAssignmentCert {
+		type Error = IntoV1Error;
+
+		fn try_from(indirect: IndirectAssignmentCertV2) -> Result<Self, Self::Error> {
+			Ok(IndirectAssignmentCert {
+				block_hash: indirect.block_hash,
+				validator: indirect.validator,
+				cert: indirect.cert.try_into()?,
+			})
+		}
+	}
+
+	/// A signed approval vote covering one or more candidates of the same block, which
+	/// references the candidates indirectly via the block.
+	///
+	/// The signature is over [`polkadot_primitives::vstaging::ApprovalVoteMultipleCandidates`] for the
+	/// candidate hashes in ascending candidate index order.
+	#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+	pub struct IndirectSignedApprovalVoteV2 {
+		/// A block hash where the candidates appear.
+		pub block_hash: Hash,
+		/// The indices of the candidates in the list of candidates fully included as-of the
+		/// block.
+		pub candidate_indices: CandidateBitfield,
+		/// The validator index.
+		pub validator: ValidatorIndex,
+		/// The signature by the validator.
+		pub signature: ValidatorSignature,
+	}
+
+	impl From<IndirectSignedApprovalVote> for IndirectSignedApprovalVoteV2 {
+		fn from(vote: IndirectSignedApprovalVote) -> Self {
+			IndirectSignedApprovalVoteV2 {
+				block_hash: vote.block_hash,
+				candidate_indices: vote.candidate_index.into(),
+				validator: vote.validator,
+				signature: vote.signature,
+			}
+		}
+	}
+
+	impl TryFrom<IndirectSignedApprovalVoteV2> for IndirectSignedApprovalVote {
+		type Error = IntoV1Error;
+
+		fn try_from(vote: IndirectSignedApprovalVoteV2) -> Result<Self, Self::Error> {
+			let count = vote.candidate_indices.count_ones();
+			let candidate_index = match vote.candidate_indices.first_one() {
+				Some(index) if count == 1 => index as CandidateIndex,
+				_ => return Err(IntoV1Error::MultipleCandidates(count)),
+			};
+
+			Ok(IndirectSignedApprovalVote {
+				block_hash: vote.block_hash,
+				candidate_index,
+				validator: vote.validator,
+				signature: vote.signature,
+			})
+		}
+	}
+}
+
 /// Metadata about a block which is now live in the approval protocol.
 #[derive(Debug)]
 pub struct BlockApprovalMeta {
@@ -233,6 +233,8 @@ pub enum InvalidCandidate {
 	InvalidOutputs,
 	/// Execution timeout.
 	Timeout,
+	/// Execution consumed more fuel than allowed by the executor parameters.
+	OutOfFuel,
 	/// Validation input is over the limit.
 	ParamsTooLarge(u64),
 	/// Code size is over the limit.
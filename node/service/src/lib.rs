@@ -59,6 +59,9 @@ use polkadot_node_subsystem_util::database::Database;
 
 #[cfg(feature = "full-node")]
 pub use {
+	polkadot_node_core_av_store::PruningConfig as AvailabilityPruningConfig,
+	polkadot_node_core_chain_selection::{StagnantCheckMode, ViabilityRule},
+	polkadot_node_core_dispute_coordinator::AuditLogConfig as DisputeAuditLogConfig,
 	polkadot_overseer::{Handle, Overseer, OverseerConnector, OverseerHandle},
 	polkadot_primitives::runtime_api::ParachainHost,
 	relay_chain_selection::SelectRelayChain,
@@ -447,6 +450,7 @@ fn new_partial<RuntimeApi, ExecutorDispatch, ChainSelection>(
 		ExecutorDispatch,
 	>,
 	select_chain: ChainSelection,
+	overseer_handle: Option<Handle>,
 ) -> Result<
 	service::PartialComponents<
 		FullClient<RuntimeApi, ExecutorDispatch>,
@@ -596,6 +600,7 @@ where
 					beefy_best_block_stream: beefy_rpc_links.from_voter_best_beefy_stream.clone(),
 					subscription_executor,
 				},
+				overseer_handle: overseer_handle.clone(),
 			};
 
 			polkadot_rpc::create_full(deps, backend.clone()).map_err(Into::into)
@@ -639,6 +644,31 @@ impl<C> NewFull<C> {
 	}
 }
 
+/// Constructs the local rules marking blocks as non-viable for chain selection. The rules can
+/// query other subsystems through the given overseer handle.
+#[cfg(feature = "full-node")]
+pub type ViabilityRulesFactory = Box<dyn FnOnce(Handle) -> Vec<Box<dyn ViabilityRule>> + Send>;
+
+/// Options of the approval voting subsystem which can be set by the node operator.
+#[cfg(feature = "full-node")]
+#[derive(Debug, Clone, Copy)]
+pub struct ApprovalVotingOptions {
+	/// The maximum number of local approvals of candidates in the same block which are signed
+	/// and distributed as a single vote. `1` disables coalescing.
+	pub max_approval_coalesce_count: u32,
+	/// Whether to produce compact assignment certificates.
+	pub enable_compact_assignments: bool,
+}
+
+#[cfg(feature = "full-node")]
+impl Default for ApprovalVotingOptions {
+	fn default() -> Self {
+		// Coalescing approvals and compact assignments stay disabled until enough of the
+		// network understands version 2 of the approval distribution messages.
+		ApprovalVotingOptions { max_approval_coalesce_count: 1, enable_compact_assignments: false }
+	}
+}
+
 /// Is this node a collator?
 #[cfg(feature = "full-node")]
 #[derive(Clone)]
@@ -721,6 +751,7 @@ where
 pub const AVAILABILITY_CONFIG: AvailabilityConfig = AvailabilityConfig {
 	col_data: parachains_db::REAL_COLUMNS.col_availability_data,
 	col_meta: parachains_db::REAL_COLUMNS.col_availability_meta,
+	pruning: polkadot_node_core_av_store::PruningConfig::DEFAULT,
 };
 
 /// Create a new full node of arbitrary runtime and executor.
@@ -740,6 +771,12 @@ pub fn new_full<RuntimeApi, ExecutorDispatch, OverseerGenerator>(
 	jaeger_agent: Option<std::net::SocketAddr>,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
 	program_path: Option<std::path::PathBuf>,
+	enable_pvf_sandbox: bool,
+	stagnant_check: Option<(StagnantCheckMode, Duration)>,
+	availability_pruning: AvailabilityPruningConfig,
+	viability_rules: Option<ViabilityRulesFactory>,
+	dispute_audit_log: Option<DisputeAuditLogConfig>,
+	approval_voting: ApprovalVotingOptions,
 	overseer_enable_anyways: bool,
 	overseer_gen: OverseerGenerator,
 	overseer_message_channel_capacity_override: Option<usize>,
@@ -806,6 +843,7 @@ where
 	let local_keystore = basics.keystore_container.local_keystore();
 	let auth_or_collator = role.is_authority() || is_collator.is_collator();
 	let requires_overseer_for_chain_sel = local_keystore.is_some() && auth_or_collator;
+	let runs_overseer = local_keystore.is_some() && (auth_or_collator || overseer_enable_anyways);
 
 	let pvf_checker_enabled = role.is_authority() && !is_collator.is_collator();
 
@@ -835,6 +873,7 @@ where
 		&mut config,
 		basics,
 		select_chain,
+		runs_overseer.then(|| overseer_handle.clone()),
 	)?;
 
 	let shared_voter_state = rpc_setup;
@@ -947,6 +986,8 @@ where
 		col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
 		col_session_data: parachains_db::REAL_COLUMNS.col_session_window_data,
 		slot_duration_millis: slot_duration.as_millis() as u64,
+		max_approval_coalesce_count: approval_voting.max_approval_coalesce_count,
+		enable_compact_assignments: approval_voting.enable_compact_assignments,
 	};
 
 	let candidate_validation_config = CandidateValidationConfig {
@@ -959,17 +1000,27 @@ where
 			None => std::env::current_exe()?,
 			Some(p) => p,
 		},
+		enable_pvf_sandbox,
 	};
 
+	let (stagnant_check_mode, stagnant_timeout) = stagnant_check.unwrap_or((
+		chain_selection_subsystem::StagnantCheckMode::PruneOnly,
+		chain_selection_subsystem::DEFAULT_STAGNANT_TIMEOUT,
+	));
 	let chain_selection_config = ChainSelectionConfig {
 		col_data: parachains_db::REAL_COLUMNS.col_chain_selection_data,
 		stagnant_check_interval: Default::default(),
-		stagnant_check_mode: chain_selection_subsystem::StagnantCheckMode::PruneOnly,
+		stagnant_check_mode,
+		stagnant_timeout,
 	};
 
+	let chain_selection_viability_rules =
+		viability_rules.map_or_else(Vec::new, |rules| rules(overseer_handle.clone()));
+
 	let dispute_coordinator_config = DisputeCoordinatorConfig {
 		col_dispute_data: parachains_db::REAL_COLUMNS.col_dispute_coordinator_data,
 		col_session_data: parachains_db::REAL_COLUMNS.col_session_window_data,
+		audit_log: dispute_audit_log,
 	};
 
 	let rpc_handlers = service::spawn_tasks(service::SpawnTasksParams {
@@ -1084,9 +1135,13 @@ where
 					spawner,
 					is_collator,
 					approval_voting_config,
-					availability_config: AVAILABILITY_CONFIG,
+					availability_config: AvailabilityConfig {
+						pruning: availability_pruning,
+						..AVAILABILITY_CONFIG
+					},
 					candidate_validation_config,
 					chain_selection_config,
+					chain_selection_viability_rules,
 					dispute_coordinator_config,
 					pvf_checker_enabled,
 					overseer_message_channel_capacity_override,
@@ -1335,6 +1390,7 @@ macro_rules! chain_ops {
 				&mut config,
 				basics,
 				chain_selection,
+				None,
 			)?;
 		Ok((Arc::new(Client::$variant(client)), backend, import_queue, task_manager))
 	}};
@@ -1404,6 +1460,12 @@ pub fn build_full(
 	enable_beefy: bool,
 	jaeger_agent: Option<std::net::SocketAddr>,
 	telemetry_worker_handle: Option<TelemetryWorkerHandle>,
+	enable_pvf_sandbox: bool,
+	stagnant_check: Option<(StagnantCheckMode, Duration)>,
+	availability_pruning: AvailabilityPruningConfig,
+	viability_rules: Option<ViabilityRulesFactory>,
+	dispute_audit_log: Option<DisputeAuditLogConfig>,
+	approval_voting: ApprovalVotingOptions,
 	overseer_enable_anyways: bool,
 	overseer_gen: impl OverseerGen,
 	overseer_message_channel_override: Option<usize>,
@@ -1423,6 +1485,12 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			stagnant_check,
+			availability_pruning,
+			viability_rules,
+			dispute_audit_log,
+			approval_voting,
 			overseer_enable_anyways,
 			overseer_gen,
 			overseer_message_channel_override,
@@ -1442,6 +1510,12 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			stagnant_check,
+			availability_pruning,
+			viability_rules,
+			dispute_audit_log,
+			approval_voting,
 			overseer_enable_anyways,
 			overseer_gen,
 			overseer_message_channel_override,
@@ -1461,6 +1535,12 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			stagnant_check,
+			availability_pruning,
+			viability_rules,
+			dispute_audit_log,
+			approval_voting,
 			overseer_enable_anyways,
 			overseer_gen,
 			overseer_message_channel_override,
@@ -1480,6 +1560,12 @@ pub fn build_full(
 			jaeger_agent,
 			telemetry_worker_handle,
 			None,
+			enable_pvf_sandbox,
+			stagnant_check,
+			availability_pruning,
+			viability_rules,
+			dispute_audit_log,
+			approval_voting,
 			overseer_enable_anyways,
 			overseer_gen,
 			overseer_message_channel_override.map(|capacity| {
@@ -1500,6 +1586,12 @@ pub fn build_full(
 		let _ = enable_beefy;
 		let _ = jaeger_agent;
 		let _ = telemetry_worker_handle;
+		let _ = enable_pvf_sandbox;
+		let _ = stagnant_check;
+		let _ = availability_pruning;
+		let _ = viability_rules;
+		let _ = dispute_audit_log;
+		let _ = approval_voting;
 		let _ = overseer_enable_anyways;
 		let _ = overseer_gen;
 		let _ = overseer_message_channel_override;
@@ -1555,9 +1647,11 @@ fn revert_chain_selection(db: Arc<dyn Database>, hash: Hash) -> sp_blockchain::R
 		col_data: parachains_db::REAL_COLUMNS.col_chain_selection_data,
 		stagnant_check_interval: chain_selection_subsystem::StagnantCheckInterval::never(),
 		stagnant_check_mode: chain_selection_subsystem::StagnantCheckMode::PruneOnly,
+		stagnant_timeout: chain_selection_subsystem::DEFAULT_STAGNANT_TIMEOUT,
 	};
 
-	let chain_selection = chain_selection_subsystem::ChainSelectionSubsystem::new(config, db);
+	let chain_selection =
+		chain_selection_subsystem::ChainSelectionSubsystem::new(config, db, Default::default());
 
 	chain_selection
 		.revert_to(hash)
@@ -1569,6 +1663,8 @@ fn revert_approval_voting(db: Arc<dyn Database>, hash: Hash) -> sp_blockchain::R
 		col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
 		col_session_data: parachains_db::REAL_COLUMNS.col_session_window_data,
 		slot_duration_millis: Default::default(),
+		max_approval_coalesce_count: 1,
+		enable_compact_assignments: false,
 	};
 
 	let approval_voting = approval_voting_subsystem::ApprovalVotingSubsystem::with_config(
@@ -22,7 +22,7 @@ use polkadot_availability_distribution::IncomingRequestReceivers;
 use polkadot_node_core_approval_voting::Config as ApprovalVotingConfig;
 use polkadot_node_core_av_store::Config as AvailabilityConfig;
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
-use polkadot_node_core_chain_selection::Config as ChainSelectionConfig;
+use polkadot_node_core_chain_selection::{Config as ChainSelectionConfig, ViabilityRule};
 use polkadot_node_core_dispute_coordinator::Config as DisputeCoordinatorConfig;
 use polkadot_node_network_protocol::{
 	peer_set::PeerSetProtocolNames,
@@ -115,6 +115,8 @@ where
 	pub candidate_validation_config: CandidateValidationConfig,
 	/// Configuration for the chain selection subsystem.
 	pub chain_selection_config: ChainSelectionConfig,
+	/// Local rules marking blocks as non-viable for chain selection.
+	pub chain_selection_viability_rules: Vec<Box<dyn ViabilityRule>>,
 	/// Configuration for the dispute coordinator subsystem.
 	pub dispute_coordinator_config: DisputeCoordinatorConfig,
 	/// Enable PVF pre-checking
@@ -150,6 +152,7 @@ pub fn prepared_overseer_builder<Spawner, RuntimeClient>(
 		availability_config,
 		candidate_validation_config,
 		chain_selection_config,
+		chain_selection_viability_rules,
 		dispute_coordinator_config,
 		pvf_checker_enabled,
 		overseer_message_channel_capacity_override,
@@ -224,7 +227,7 @@ where
 			IncomingRequestReceivers { pov_req_receiver, chunk_req_receiver },
 			Metrics::register(registry)?,
 		))
-		.availability_recovery(AvailabilityRecoverySubsystem::with_chunks_only(
+		.availability_recovery(AvailabilityRecoverySubsystem::with_systematic_chunks(
 			available_data_req_receiver,
 			Metrics::register(registry)?,
 		))
@@ -308,7 +311,14 @@ where
 			authority_discovery_service.clone(),
 			Metrics::register(registry)?,
 		))
-		.chain_selection(ChainSelectionSubsystem::new(chain_selection_config, parachains_db))
+		.chain_selection(
+			ChainSelectionSubsystem::new(
+				chain_selection_config,
+				parachains_db,
+				Metrics::register(registry)?,
+			)
+			.with_viability_rules(chain_selection_viability_rules),
+		)
 		.leaves(Vec::from_iter(
 			leaves
 				.into_iter()
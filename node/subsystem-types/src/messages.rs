@@ -33,7 +33,10 @@ use polkadot_node_network_protocol::{
 	UnifiedReputationChange,
 };
 use polkadot_node_primitives::{
-	approval::{BlockApprovalMeta, IndirectAssignmentCert, IndirectSignedApprovalVote},
+	approval::{
+		v2::{CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2},
+		BlockApprovalMeta, DelayTranche,
+	},
 	AvailableData, BabeEpoch, BlockWeight, CandidateVotes, CollationGenerationConfig,
 	CollationSecondedSignal, DisputeMessage, DisputeStatus, ErasureChunk, PoV,
 	SignedDisputeStatement, SignedFullStatement, ValidationResult,
@@ -41,7 +44,7 @@ use polkadot_node_primitives::{
 use polkadot_primitives::{
 	vstaging::ExecutorParams, AuthorityDiscoveryId, BackedCandidate, BlockNumber, CandidateEvent,
 	CandidateHash, CandidateIndex, CandidateReceipt, CollatorId, CommittedCandidateReceipt,
-	CoreState, DisputeState, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader,
+	CoreIndex, CoreState, DisputeState, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader,
 	Id as ParaId, InboundDownwardMessage, InboundHrmpMessage, MultiDisputeStatementSet,
 	OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, SessionIndex, SessionInfo,
 	SignedAvailabilityBitfield, SignedAvailabilityBitfields, ValidationCode, ValidationCodeHash,
@@ -265,6 +268,25 @@ pub enum DisputeCoordinatorMessage {
 		/// The block to vote on, might be base in case there is no better.
 		tx: oneshot::Sender<(BlockNumber, Hash)>,
 	},
+	/// Fetch a snapshot of the internal state of the dispute coordinator: spam slot occupancy and
+	/// the state of the participation queues. Meant for introspection by node operators.
+	Status(oneshot::Sender<DisputeCoordinatorStatus>),
+}
+
+/// The result of `DisputeCoordinatorMessage::Status`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisputeCoordinatorStatus {
+	/// Occupied spam slots as `(session, validator, count)`, each count being at most
+	/// `max_spam_votes`.
+	pub spam_slots: Vec<(SessionIndex, ValidatorIndex, u32)>,
+	/// How many unconfirmed disputes a validator may raise per session.
+	pub max_spam_votes: u32,
+	/// Number of participations waiting in the priority queue.
+	pub priority_queue_len: usize,
+	/// Number of participations waiting in the best-effort queue.
+	pub best_effort_queue_len: usize,
+	/// Number of participations currently running.
+	pub running_participations: usize,
 }
 
 /// The result of `DisputeCoordinatorMessage::ImportStatements`.
@@ -522,6 +544,51 @@ pub enum ChainSelectionMessage {
 	/// The passed blocks must be marked as reverted, and their children must be marked
 	/// as non-viable.
 	RevertBlocks(Vec<(BlockNumber, Hash)>),
+	/// Add the given block to the operator blacklist. The block and all its descendants
+	/// are marked as non-viable, right away or once the block is imported.
+	BlacklistBlock(Hash),
+	/// Remove the given block from the operator blacklist.
+	UnblacklistBlock(Hash),
+	/// Request the blocks in the operator blacklist.
+	Blacklist(oneshot::Sender<Vec<Hash>>),
+	/// Consult the operator blacklist and the viability rules again for all unfinalized
+	/// blocks, e.g. because the state the rules are based on changed.
+	ReevaluateViability,
+	/// Request all leaves of the tree of unfinalized blocks, including the non-viable ones.
+	/// Viable leaves come first, each group in descending order by score.
+	LeafDetails(oneshot::Sender<Vec<LeafDetails>>),
+}
+
+/// The approval status of a block as tracked by chain selection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockApprovalStatus {
+	/// The block is approved.
+	Approved,
+	/// The block is not approved yet.
+	Unapproved,
+	/// The block has not been approved in time and is stagnant.
+	Stagnant,
+}
+
+/// A leaf of the tree of unfinalized blocks, along with the reasons for its viability.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeafDetails {
+	/// The hash of the block.
+	pub block_hash: Hash,
+	/// The number of the block.
+	pub block_number: BlockNumber,
+	/// The weight of the block, used to order leaves.
+	pub weight: BlockWeight,
+	/// Whether the block can be built upon.
+	pub viable: bool,
+	/// The approval status of the block itself.
+	pub approval: BlockApprovalStatus,
+	/// Whether the block has been reverted, e.g. due to a dispute.
+	pub explicitly_reverted: bool,
+	/// Whether the block is blacklisted by the operator or non-viable by a local rule.
+	pub locally_blocked: bool,
+	/// The earliest unfinalized ancestor of the block which is non-viable itself, if any.
+	pub earliest_unviable_ancestor: Option<Hash>,
 }
 
 /// A sender for the result of a runtime API request.
@@ -614,6 +681,11 @@ impl RuntimeApiRequest {
 
 	/// `ExecutorParams`
 	pub const EXECUTOR_PARAMS_RUNTIME_REQUIREMENT: u32 = 4;
+
+	/// Approval votes of multiple candidates as dispute statements, see
+	/// `polkadot_primitives::vstaging::ValidDisputeStatementKind`. Not supported by any runtime
+	/// yet.
+	pub const MULTIPLE_CANDIDATE_APPROVALS_RUNTIME_REQUIREMENT: u32 = 6;
 }
 
 /// A message to the Runtime API subsystem.
@@ -731,6 +803,8 @@ pub enum ApprovalCheckError {
 	UnknownSessionIndex(SessionIndex),
 	#[error("Invalid candidate index: {0}")]
 	InvalidCandidateIndex(CandidateIndex),
+	#[error("Approval covers no candidates")]
+	NoCandidates,
 	#[error("Invalid validator index: {0:?}")]
 	InvalidValidatorIndex(ValidatorIndex),
 	#[error("Invalid candidate {0}: {1:?}")]
@@ -774,16 +848,18 @@ pub struct HighestApprovedAncestorBlock {
 pub enum ApprovalVotingMessage {
 	/// Check if the assignment is valid and can be accepted by our view of the protocol.
 	/// Should not be sent unless the block hash is known.
+	///
+	/// The bitfield contains the indices of all candidates claimed by the assignment.
 	CheckAndImportAssignment(
-		IndirectAssignmentCert,
-		CandidateIndex,
+		IndirectAssignmentCertV2,
+		CandidateBitfield,
 		oneshot::Sender<AssignmentCheckResult>,
 	),
 	/// Check if the approval vote is valid and can be accepted by our view of the
-	/// protocol.
+	/// protocol. The vote may cover multiple candidates of the same block.
 	///
 	/// Should not be sent unless the block hash within the indirect vote is known.
-	CheckAndImportApproval(IndirectSignedApprovalVote, oneshot::Sender<ApprovalCheckResult>),
+	CheckAndImportApproval(IndirectSignedApprovalVoteV2, oneshot::Sender<ApprovalCheckResult>),
 	/// Returns the highest possible ancestor hash of the provided block hash which is
 	/// acceptable to vote on finality for.
 	/// The `BlockNumber` provided is the number of the block's ancestor which is the
@@ -797,10 +873,98 @@ pub enum ApprovalVotingMessage {
 	///
 	/// This message involves a linear search for candidates on each relay chain fork and also
 	/// requires calling into `approval-distribution`: Calls should be infrequent and bounded.
+	///
+	/// Each signature comes with the hashes of all candidates it covers, in the order they
+	/// were signed.
 	GetApprovalSignaturesForCandidate(
 		CandidateHash,
-		oneshot::Sender<HashMap<ValidatorIndex, ValidatorSignature>>,
+		oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 	),
+
+	/// Request the approval state of all candidates included by the given relay block, to
+	/// inspect why the block is not approved yet. Returns `None` if the block is unknown.
+	BlockApprovalDetails(Hash, oneshot::Sender<Option<BlockApprovalDetails>>),
+}
+
+/// The tranches of assignments required to approve a candidate, as computed by approval voting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequiredTranchesDetails {
+	/// All validators appear to be required.
+	All,
+	/// More assignments are awaited.
+	Pending {
+		/// The highest considered delay tranche when counting assignments.
+		considered: DelayTranche,
+		/// The tick at which the next no-show, of the assignments counted, would occur.
+		next_no_show: Option<u64>,
+		/// The highest tranche to consider when looking to broadcast an own assignment.
+		maximum_broadcast: DelayTranche,
+		/// The clock drift, in ticks, caused by covering no-shows.
+		clock_drift: u64,
+	},
+	/// Enough assignments are known, and all of them up to `needed` are required to approve.
+	Exact {
+		/// The tranche to inspect up to.
+		needed: DelayTranche,
+		/// The amount of missing votes that are tolerated.
+		tolerated_missing: usize,
+		/// The tick at which the next no-show would occur, if any.
+		next_no_show: Option<u64>,
+		/// The last tick at which a needed assignment was received.
+		last_assignment_tick: Option<u64>,
+	},
+}
+
+/// An assignment of a validator to check a candidate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssignmentDetails {
+	/// The assigned validator.
+	pub validator: ValidatorIndex,
+	/// The tranche of the assignment.
+	pub tranche: DelayTranche,
+	/// The tick at which the assignment was received.
+	pub tick: u64,
+}
+
+/// The approval state of a candidate under a relay block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CandidateApprovalDetails {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The core the candidate occupies.
+	pub core_index: CoreIndex,
+	/// The group which backed the candidate.
+	pub backing_group: GroupIndex,
+	/// Whether the candidate is approved under the block.
+	pub approved: bool,
+	/// The required tranches of assignments, `None` if the session of the block is unknown.
+	pub required_tranches: Option<RequiredTranchesDetails>,
+	/// The known assignments, ordered by tranche.
+	pub assignments: Vec<AssignmentDetails>,
+	/// The validators which approved the candidate.
+	pub approvals: Vec<ValidatorIndex>,
+	/// The assigned validators which didn't approve the candidate in time.
+	pub no_shows: Vec<ValidatorIndex>,
+	/// The validators, assigned in the required tranches, whose approvals are still needed
+	/// for the candidate to be approved. Empty if the candidate is approved.
+	pub missing_approvals: Vec<ValidatorIndex>,
+}
+
+/// The approval state of all candidates included by a relay block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockApprovalDetails {
+	/// The hash of the block.
+	pub block_hash: Hash,
+	/// The number of the block.
+	pub block_number: BlockNumber,
+	/// The session of the block.
+	pub session: SessionIndex,
+	/// The current tranche relative to the slot of the block.
+	pub tranche_now: DelayTranche,
+	/// Whether all candidates are approved.
+	pub approved: bool,
+	/// The candidates included by the block, in order.
+	pub candidates: Vec<CandidateApprovalDetails>,
 }
 
 /// Message to the Approval Distribution subsystem.
@@ -811,19 +975,22 @@ pub enum ApprovalDistributionMessage {
 	NewBlocks(Vec<BlockApprovalMeta>),
 	/// Distribute an assignment cert from the local validator. The cert is assumed
 	/// to be valid, relevant, and for the given relay-parent and validator index.
-	DistributeAssignment(IndirectAssignmentCert, CandidateIndex),
+	/// The bitfield contains the indices of all candidates claimed by the assignment.
+	DistributeAssignment(IndirectAssignmentCertV2, CandidateBitfield),
 	/// Distribute an approval vote for the local validator. The approval vote is assumed to be
 	/// valid, relevant, and the corresponding approval already issued.
 	/// If not, the subsystem is free to drop the message.
-	DistributeApproval(IndirectSignedApprovalVote),
+	DistributeApproval(IndirectSignedApprovalVoteV2),
 	/// An update from the network bridge.
 	#[from]
 	NetworkBridgeUpdate(NetworkBridgeEvent<net_protocol::ApprovalDistributionMessage>),
 
 	/// Get all approval signatures for all chains a candidate appeared in.
+	///
+	/// Each signature comes with the block and the indices of all candidates it covers.
 	GetApprovalSignatures(
 		HashSet<(Hash, CandidateIndex)>,
-		oneshot::Sender<HashMap<ValidatorIndex, ValidatorSignature>>,
+		oneshot::Sender<HashMap<ValidatorIndex, (Hash, Vec<CandidateIndex>, ValidatorSignature)>>,
 	),
 }
 
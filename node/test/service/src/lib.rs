@@ -97,6 +97,12 @@ pub fn new_full(
 		None,
 		worker_program_path,
 		false,
+		None,
+		Default::default(),
+		None,
+		None,
+		Default::default(),
+		false,
 		polkadot_service::RealOverseerGen,
 		None,
 		None,
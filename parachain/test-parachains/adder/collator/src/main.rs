@@ -64,6 +64,12 @@ fn main() -> Result<()> {
 					None,
 					None,
 					false,
+					None,
+					Default::default(),
+					None,
+					None,
+					Default::default(),
+					false,
 					polkadot_service::RealOverseerGen,
 					None,
 					None,
@@ -222,5 +222,10 @@ sp_api::decl_runtime_apis! {
 		/// Returns execution parameters for the session.
 		#[api_version(4)]
 		fn session_executor_params(session_index: SessionIndex) -> Option<vstaging::ExecutorParams>;
+
+		/// Returns the state of the upward and downward message queues of the given para, along
+		/// with its overweight upward messages.
+		#[api_version(5)]
+		fn message_queues_status(para_id: ppp::Id) -> vstaging::MessageQueuesStatus;
 	}
 }
@@ -39,6 +39,9 @@ pub enum ExecutorParam {
 	/// Max. amount of memory the preparation worker is allowed to use during
 	/// pre-checking, in bytes
 	PrecheckingMaxMemory(u64),
+	/// Max. amount of fuel a PVF may consume during execution. The execution is metered in wasm
+	/// instructions, so exceeding the budget fails deterministically, unlike the execution timeout.
+	ExecutionMaxFuel(u64),
 }
 
 /// Unit type wrapper around [`type@Hash`] that represents an execution parameter set hash.
@@ -18,5 +18,256 @@
 
 // Put any primitives used by staging APIs functions here
 
+use crate::{
+	ApprovalVote, CandidateHash, CompactStatement, ExplicitDisputeStatement,
+	InvalidDisputeStatementKind, SessionIndex, SigningContext, ValidatorId, ValidatorSignature,
+};
+use parity_scale_codec::{Decode, Encode};
+use polkadot_core_primitives::Hash;
+use primitives::RuntimeDebug;
+use runtime_primitives::traits::AppVerify;
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+use sp_weights::Weight;
+
 pub mod executor_params;
 pub use executor_params::{ExecutorParam, ExecutorParams, ExecutorParamsHash};
+
+/// A vote of approval for multiple candidates of the same relay chain block.
+#[derive(Clone, RuntimeDebug)]
+pub struct ApprovalVoteMultipleCandidates<'a>(pub &'a [CandidateHash]);
+
+impl<'a> ApprovalVoteMultipleCandidates<'a> {
+	/// Yields the signing payload for this approval vote.
+	pub fn signing_payload(&self, session_index: SessionIndex) -> Vec<u8> {
+		const MAGIC: [u8; 4] = *b"APPR";
+		// A vote for a single candidate yields the same payload as `ApprovalVote`, so it
+		// remains valid for consumers which only know about single candidate approvals.
+		if self.0.len() == 1 {
+			(MAGIC, self.0.first().expect("QED: we just checked"), session_index).encode()
+		} else {
+			(MAGIC, &self.0, session_index).encode()
+		}
+	}
+}
+
+/// A statement about a candidate, to be used within the dispute resolution process.
+///
+/// Unlike [`crate::v2::DisputeStatement`], valid statements may be approvals covering multiple
+/// candidates. Those are not understood by runtimes which don't support them, see
+/// [`DisputeStatement::into_v2`].
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum DisputeStatement {
+	/// A valid statement, of the given kind.
+	#[codec(index = 0)]
+	Valid(ValidDisputeStatementKind),
+	/// An invalid statement, of the given kind.
+	#[codec(index = 1)]
+	Invalid(InvalidDisputeStatementKind),
+}
+
+impl DisputeStatement {
+	/// Get the payload data for this type of dispute statement.
+	///
+	/// Fails if the statement is an approval of multiple candidates which doesn't cover
+	/// `candidate_hash`.
+	pub fn payload_data(
+		&self,
+		candidate_hash: CandidateHash,
+		session: SessionIndex,
+	) -> Result<Vec<u8>, ()> {
+		let payload = match self {
+			DisputeStatement::Valid(ValidDisputeStatementKind::Explicit) =>
+				ExplicitDisputeStatement { valid: true, candidate_hash, session }.signing_payload(),
+			DisputeStatement::Valid(ValidDisputeStatementKind::BackingSeconded(
+				inclusion_parent,
+			)) => CompactStatement::Seconded(candidate_hash).signing_payload(&SigningContext {
+				session_index: session,
+				parent_hash: *inclusion_parent,
+			}),
+			DisputeStatement::Valid(ValidDisputeStatementKind::BackingValid(inclusion_parent)) =>
+				CompactStatement::Valid(candidate_hash).signing_payload(&SigningContext {
+					session_index: session,
+					parent_hash: *inclusion_parent,
+				}),
+			DisputeStatement::Valid(ValidDisputeStatementKind::ApprovalChecking) =>
+				ApprovalVote(candidate_hash).signing_payload(session),
+			DisputeStatement::Valid(
+				ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(candidate_hashes),
+			) =>
+				if candidate_hashes.contains(&candidate_hash) {
+					ApprovalVoteMultipleCandidates(candidate_hashes).signing_payload(session)
+				} else {
+					return Err(())
+				},
+			DisputeStatement::Invalid(InvalidDisputeStatementKind::Explicit) =>
+				ExplicitDisputeStatement { valid: false, candidate_hash, session }.signing_payload(),
+		};
+
+		Ok(payload)
+	}
+
+	/// Check the signature on a dispute statement.
+	pub fn check_signature(
+		&self,
+		validator_public: &ValidatorId,
+		candidate_hash: CandidateHash,
+		session: SessionIndex,
+		validator_signature: &ValidatorSignature,
+	) -> Result<(), ()> {
+		let payload = self.payload_data(candidate_hash, session)?;
+
+		if validator_signature.verify(&payload[..], &validator_public) {
+			Ok(())
+		} else {
+			Err(())
+		}
+	}
+
+	/// Whether the statement indicates validity.
+	pub fn indicates_validity(&self) -> bool {
+		match *self {
+			DisputeStatement::Valid(_) => true,
+			DisputeStatement::Invalid(_) => false,
+		}
+	}
+
+	/// Whether the statement indicates invalidity.
+	pub fn indicates_invalidity(&self) -> bool {
+		match *self {
+			DisputeStatement::Valid(_) => false,
+			DisputeStatement::Invalid(_) => true,
+		}
+	}
+
+	/// Statement is backing statement.
+	pub fn is_backing(&self) -> bool {
+		match *self {
+			Self::Valid(ValidDisputeStatementKind::BackingSeconded(_)) |
+			Self::Valid(ValidDisputeStatementKind::BackingValid(_)) => true,
+			Self::Valid(ValidDisputeStatementKind::Explicit) |
+			Self::Valid(ValidDisputeStatementKind::ApprovalChecking) |
+			Self::Valid(ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(_)) |
+			Self::Invalid(_) => false,
+		}
+	}
+
+	/// Convert into a statement understood by runtimes without support for approvals of
+	/// multiple candidates.
+	///
+	/// Returns `None` for such approvals.
+	pub fn into_v2(self) -> Option<crate::v2::DisputeStatement> {
+		match self {
+			Self::Valid(kind) => kind.into_v2().map(crate::v2::DisputeStatement::Valid),
+			Self::Invalid(kind) => Some(crate::v2::DisputeStatement::Invalid(kind)),
+		}
+	}
+}
+
+impl From<crate::v2::DisputeStatement> for DisputeStatement {
+	fn from(statement: crate::v2::DisputeStatement) -> Self {
+		match statement {
+			crate::v2::DisputeStatement::Valid(kind) => Self::Valid(kind.into()),
+			crate::v2::DisputeStatement::Invalid(kind) => Self::Invalid(kind),
+		}
+	}
+}
+
+/// Different kinds of statements of validity on a candidate.
+///
+/// Encodes like [`crate::v2::ValidDisputeStatementKind`] for the kinds both have in common.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum ValidDisputeStatementKind {
+	/// An explicit statement issued as part of a dispute.
+	#[codec(index = 0)]
+	Explicit,
+	/// A seconded statement on a candidate from the backing phase.
+	#[codec(index = 1)]
+	BackingSeconded(Hash),
+	/// A valid statement on a candidate from the backing phase.
+	#[codec(index = 2)]
+	BackingValid(Hash),
+	/// An approval vote from the approval checking phase.
+	#[codec(index = 3)]
+	ApprovalChecking,
+	/// An approval vote from the approval checking phase covering multiple candidates of the
+	/// same relay chain block, which must include the candidate the statement is about.
+	#[codec(index = 4)]
+	ApprovalCheckingMultipleCandidates(Vec<CandidateHash>),
+}
+
+impl ValidDisputeStatementKind {
+	/// Convert into a kind understood by runtimes without support for approvals of multiple
+	/// candidates.
+	///
+	/// Returns `None` for such approvals.
+	pub fn into_v2(self) -> Option<crate::v2::ValidDisputeStatementKind> {
+		match self {
+			Self::Explicit => Some(crate::v2::ValidDisputeStatementKind::Explicit),
+			Self::BackingSeconded(hash) =>
+				Some(crate::v2::ValidDisputeStatementKind::BackingSeconded(hash)),
+			Self::BackingValid(hash) =>
+				Some(crate::v2::ValidDisputeStatementKind::BackingValid(hash)),
+			Self::ApprovalChecking => Some(crate::v2::ValidDisputeStatementKind::ApprovalChecking),
+			Self::ApprovalCheckingMultipleCandidates(_) => None,
+		}
+	}
+}
+
+impl From<crate::v2::ValidDisputeStatementKind> for ValidDisputeStatementKind {
+	fn from(kind: crate::v2::ValidDisputeStatementKind) -> Self {
+		match kind {
+			crate::v2::ValidDisputeStatementKind::Explicit => Self::Explicit,
+			crate::v2::ValidDisputeStatementKind::BackingSeconded(hash) =>
+				Self::BackingSeconded(hash),
+			crate::v2::ValidDisputeStatementKind::BackingValid(hash) => Self::BackingValid(hash),
+			crate::v2::ValidDisputeStatementKind::ApprovalChecking => Self::ApprovalChecking,
+		}
+	}
+}
+
+/// The state of the upward message queue of a para.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, RuntimeDebug)]
+pub struct UpwardQueueStatus {
+	/// The number of messages waiting to be dispatched.
+	pub count: u32,
+	/// The total size in bytes of the messages waiting to be dispatched.
+	pub total_size: u32,
+	/// The maximum number of messages the queue may hold, as per `max_upward_queue_count`.
+	pub max_count: u32,
+	/// The maximum total size in bytes the queue may hold, as per `max_upward_queue_size`.
+	pub max_total_size: u32,
+	/// Whether the para is scheduled for dispatch, i.e. it is in `NeedsDispatch`.
+	pub needs_dispatch: bool,
+}
+
+/// The state of the downward message queue of a para.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, RuntimeDebug)]
+pub struct DownwardQueueStatus {
+	/// The number of messages waiting to be processed by the para.
+	pub length: u32,
+	/// The head of the message queue chain.
+	pub mqc_head: Hash,
+}
+
+/// An upward message which exceeded the maximum individual weight and awaits manual servicing.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, RuntimeDebug)]
+pub struct OverweightUpwardMessage {
+	/// The index under which the message can be serviced.
+	pub index: u64,
+	/// The size of the message in bytes.
+	pub size: u32,
+	/// The weight the message required for its execution, if it was recorded.
+	pub required_weight: Option<Weight>,
+}
+
+/// The state of the message queues of a para.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, RuntimeDebug)]
+pub struct MessageQueuesStatus {
+	/// The queue of messages sent from the para to the relay chain.
+	pub upward: UpwardQueueStatus,
+	/// The queue of messages sent from the relay chain to the para.
+	pub downward: DownwardQueueStatus,
+	/// The upward messages of the para that are parked as overweight.
+	pub overweight: Vec<OverweightUpwardMessage>,
+}
@@ -0,0 +1,257 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC to inspect the state of approval voting for a relay block, e.g. to find out which
+//! approvals are missing when finality lags.
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use polkadot_node_subsystem_types::messages::{
+	ApprovalVotingMessage, AssignmentDetails, BlockApprovalDetails, CandidateApprovalDetails,
+	RequiredTranchesDetails,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{BlockNumber, Hash, SessionIndex};
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+
+/// The origin of the messages sent to the overseer.
+const ORIGIN: &str = "approvals-rpc";
+
+/// The errors of the approvals RPC.
+pub enum Error {
+	/// The approval voting subsystem didn't answer the request.
+	ApprovalVotingUnavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::ApprovalVotingUnavailable => 1,
+		}
+	}
+}
+
+/// The tranches of assignments required to approve a candidate. Ticks are half-second units
+/// since the unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum RequiredTranches {
+	/// All validators appear to be required.
+	All,
+	/// More assignments are awaited.
+	#[serde(rename_all = "camelCase")]
+	Pending {
+		/// The highest considered delay tranche when counting assignments.
+		considered: u32,
+		/// The tick at which the next no-show, of the assignments counted, would occur.
+		next_no_show: Option<u64>,
+		/// The highest tranche from which assignments are accepted.
+		maximum_broadcast: u32,
+		/// The clock drift, in ticks, caused by covering no-shows.
+		clock_drift: u64,
+	},
+	/// Enough assignments are known, and all of them up to `needed` are required to approve.
+	#[serde(rename_all = "camelCase")]
+	Exact {
+		/// The tranche to inspect up to.
+		needed: u32,
+		/// The amount of missing votes that are tolerated.
+		tolerated_missing: u32,
+		/// The tick at which the next no-show would occur, if any.
+		next_no_show: Option<u64>,
+		/// The last tick at which a needed assignment was received.
+		last_assignment_tick: Option<u64>,
+	},
+}
+
+impl From<RequiredTranchesDetails> for RequiredTranches {
+	fn from(required: RequiredTranchesDetails) -> Self {
+		match required {
+			RequiredTranchesDetails::All => RequiredTranches::All,
+			RequiredTranchesDetails::Pending {
+				considered,
+				next_no_show,
+				maximum_broadcast,
+				clock_drift,
+			} => RequiredTranches::Pending {
+				considered,
+				next_no_show,
+				maximum_broadcast,
+				clock_drift,
+			},
+			RequiredTranchesDetails::Exact {
+				needed,
+				tolerated_missing,
+				next_no_show,
+				last_assignment_tick,
+			} => RequiredTranches::Exact {
+				needed,
+				tolerated_missing: tolerated_missing as u32,
+				next_no_show,
+				last_assignment_tick,
+			},
+		}
+	}
+}
+
+/// An assignment of a validator to check a candidate.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Assignment {
+	/// The index of the validator in the session.
+	pub validator_index: u32,
+	/// The tranche of the assignment.
+	pub tranche: u32,
+	/// The tick at which the assignment was received.
+	pub tick: u64,
+}
+
+impl From<AssignmentDetails> for Assignment {
+	fn from(assignment: AssignmentDetails) -> Self {
+		Self {
+			validator_index: assignment.validator.0,
+			tranche: assignment.tranche,
+			tick: assignment.tick,
+		}
+	}
+}
+
+/// The approval state of a candidate under a relay block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateApproval {
+	/// The hash of the candidate.
+	pub candidate_hash: Hash,
+	/// The core the candidate occupies.
+	pub core_index: u32,
+	/// The group which backed the candidate.
+	pub backing_group: u32,
+	/// Whether the candidate is approved under the block.
+	pub approved: bool,
+	/// The required tranches of assignments, `None` if the session of the block is unknown.
+	pub required_tranches: Option<RequiredTranches>,
+	/// The known assignments, ordered by tranche.
+	pub assignments: Vec<Assignment>,
+	/// The validators which approved the candidate.
+	pub approvals: Vec<u32>,
+	/// The assigned validators which didn't approve the candidate in time.
+	pub no_shows: Vec<u32>,
+	/// The validators whose approvals are still needed for the candidate to be approved.
+	pub missing_approvals: Vec<u32>,
+}
+
+impl From<CandidateApprovalDetails> for CandidateApproval {
+	fn from(candidate: CandidateApprovalDetails) -> Self {
+		Self {
+			candidate_hash: candidate.candidate_hash.0,
+			core_index: candidate.core_index.0,
+			backing_group: candidate.backing_group.0,
+			approved: candidate.approved,
+			required_tranches: candidate.required_tranches.map(Into::into),
+			assignments: candidate.assignments.into_iter().map(Into::into).collect(),
+			approvals: candidate.approvals.into_iter().map(|v| v.0).collect(),
+			no_shows: candidate.no_shows.into_iter().map(|v| v.0).collect(),
+			missing_approvals: candidate.missing_approvals.into_iter().map(|v| v.0).collect(),
+		}
+	}
+}
+
+/// The approval state of all candidates included by a relay block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockApproval {
+	/// The hash of the block.
+	pub block_hash: Hash,
+	/// The number of the block.
+	pub block_number: BlockNumber,
+	/// The session of the block.
+	pub session: SessionIndex,
+	/// The current tranche relative to the slot of the block.
+	pub tranche_now: u32,
+	/// Whether all candidates are approved.
+	pub approved: bool,
+	/// The candidates included by the block, in order.
+	pub candidates: Vec<CandidateApproval>,
+}
+
+impl From<BlockApprovalDetails> for BlockApproval {
+	fn from(block: BlockApprovalDetails) -> Self {
+		Self {
+			block_hash: block.block_hash,
+			block_number: block.block_number,
+			session: block.session,
+			tranche_now: block.tranche_now,
+			approved: block.approved,
+			candidates: block.candidates.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Approvals RPC methods.
+#[rpc(client, server)]
+pub trait ApprovalsApi {
+	/// Returns the approval state of all candidates included by the given relay block: their
+	/// required tranches, the assignments and approvals received, the no-shows and the approvals
+	/// still missing. `None` if the block is unknown to approval voting, e.g. because it is
+	/// finalized.
+	#[method(name = "parachain_blockApprovals")]
+	async fn block_approvals(&self, block_hash: Hash) -> RpcResult<Option<BlockApproval>>;
+}
+
+/// Provides the approvals RPC.
+pub struct Approvals {
+	overseer_handle: Handle,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl Approvals {
+	/// Creates a new instance of the approvals RPC.
+	pub fn new(overseer_handle: Handle, deny_unsafe: DenyUnsafe) -> Self {
+		Self { overseer_handle, deny_unsafe }
+	}
+}
+
+#[async_trait]
+impl ApprovalsApiServer for Approvals {
+	async fn block_approvals(&self, block_hash: Hash) -> RpcResult<Option<BlockApproval>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle
+			.clone()
+			.send_msg(ApprovalVotingMessage::BlockApprovalDetails(block_hash, tx), ORIGIN)
+			.await;
+
+		let details = rx.await.map_err(|e| {
+			error(
+				Error::ApprovalVotingUnavailable,
+				"The approval voting subsystem didn't answer the request.",
+				e,
+			)
+		})?;
+
+		Ok(details.map(Into::into))
+	}
+}
+
+fn error(code: Error, message: &str, data: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(format!("{:?}", data))))
+}
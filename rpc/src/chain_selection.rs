@@ -0,0 +1,201 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC to inspect the leaves of the chain selection subsystem and to manage its operator
+//! blacklist, e.g. to steer the node off a bad fork during an incident.
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use polkadot_node_subsystem_types::messages::{
+	BlockApprovalStatus, ChainSelectionMessage, LeafDetails,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{BlockNumber, Hash};
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+
+/// The origin of the messages sent to the overseer.
+const ORIGIN: &str = "chain-selection-rpc";
+
+/// The errors of the chain selection RPC.
+pub enum Error {
+	/// The chain selection subsystem didn't answer the request.
+	ChainSelectionUnavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::ChainSelectionUnavailable => 1,
+		}
+	}
+}
+
+/// The approval status of a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApprovalStatus {
+	/// The block is approved.
+	Approved,
+	/// The block is not approved yet.
+	Unapproved,
+	/// The block has not been approved in time.
+	Stagnant,
+}
+
+impl From<BlockApprovalStatus> for ApprovalStatus {
+	fn from(status: BlockApprovalStatus) -> Self {
+		match status {
+			BlockApprovalStatus::Approved => ApprovalStatus::Approved,
+			BlockApprovalStatus::Unapproved => ApprovalStatus::Unapproved,
+			BlockApprovalStatus::Stagnant => ApprovalStatus::Stagnant,
+		}
+	}
+}
+
+/// A leaf of the tree of unfinalized blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Leaf {
+	/// The hash of the block.
+	pub block_hash: Hash,
+	/// The number of the block.
+	pub block_number: BlockNumber,
+	/// The weight of the block, used to order leaves.
+	pub weight: u32,
+	/// Whether the block can be built upon.
+	pub viable: bool,
+	/// The approval status of the block itself.
+	pub approval: ApprovalStatus,
+	/// Whether the block has been reverted, e.g. due to a dispute.
+	pub explicitly_reverted: bool,
+	/// Whether the block is blacklisted or non-viable by a local rule.
+	pub locally_blocked: bool,
+	/// The earliest unfinalized ancestor which is non-viable itself, if any.
+	pub earliest_unviable_ancestor: Option<Hash>,
+}
+
+impl From<LeafDetails> for Leaf {
+	fn from(leaf: LeafDetails) -> Self {
+		Self {
+			block_hash: leaf.block_hash,
+			block_number: leaf.block_number,
+			weight: leaf.weight,
+			viable: leaf.viable,
+			approval: leaf.approval.into(),
+			explicitly_reverted: leaf.explicitly_reverted,
+			locally_blocked: leaf.locally_blocked,
+			earliest_unviable_ancestor: leaf.earliest_unviable_ancestor,
+		}
+	}
+}
+
+/// Chain selection RPC methods.
+#[rpc(client, server)]
+pub trait ChainSelectionApi {
+	/// Returns all leaves of the tree of unfinalized blocks, including the non-viable ones.
+	/// Viable leaves come first, each group in descending order by weight.
+	#[method(name = "parachain_chainSelectionLeaves")]
+	async fn leaves(&self) -> RpcResult<Vec<Leaf>>;
+
+	/// Adds a block to the blacklist. The block and all its descendants are considered
+	/// non-viable, i.e. they are neither built upon nor finalized by this node. Blocks which are
+	/// not yet imported are marked upon import.
+	#[method(name = "parachain_blacklistBlock")]
+	async fn blacklist_block(&self, block_hash: Hash) -> RpcResult<()>;
+
+	/// Removes a block from the blacklist.
+	#[method(name = "parachain_unblacklistBlock")]
+	async fn unblacklist_block(&self, block_hash: Hash) -> RpcResult<()>;
+
+	/// Returns the blacklisted blocks.
+	#[method(name = "parachain_blacklistedBlocks")]
+	async fn blacklisted_blocks(&self) -> RpcResult<Vec<Hash>>;
+}
+
+/// Provides the chain selection RPC.
+pub struct ChainSelection {
+	overseer_handle: Handle,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl ChainSelection {
+	/// Creates a new instance of the chain selection RPC.
+	pub fn new(overseer_handle: Handle, deny_unsafe: DenyUnsafe) -> Self {
+		Self { overseer_handle, deny_unsafe }
+	}
+
+	/// Sends a message to the chain selection subsystem and waits for its answer.
+	async fn request<T>(
+		&self,
+		make_message: impl FnOnce(oneshot::Sender<T>) -> ChainSelectionMessage,
+	) -> RpcResult<T> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle.clone().send_msg(make_message(tx), ORIGIN).await;
+		rx.await.map_err(|e| {
+			error(
+				Error::ChainSelectionUnavailable,
+				"The chain selection subsystem didn't answer the request.",
+				e,
+			)
+			.into()
+		})
+	}
+}
+
+#[async_trait]
+impl ChainSelectionApiServer for ChainSelection {
+	async fn leaves(&self) -> RpcResult<Vec<Leaf>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let leaves = self.request(ChainSelectionMessage::LeafDetails).await?;
+		Ok(leaves.into_iter().map(Into::into).collect())
+	}
+
+	async fn blacklist_block(&self, block_hash: Hash) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+
+		self.overseer_handle
+			.clone()
+			.send_msg(ChainSelectionMessage::BlacklistBlock(block_hash), ORIGIN)
+			.await;
+		Ok(())
+	}
+
+	async fn unblacklist_block(&self, block_hash: Hash) -> RpcResult<()> {
+		self.deny_unsafe.check_if_safe()?;
+
+		self.overseer_handle
+			.clone()
+			.send_msg(ChainSelectionMessage::UnblacklistBlock(block_hash), ORIGIN)
+			.await;
+		Ok(())
+	}
+
+	async fn blacklisted_blocks(&self) -> RpcResult<Vec<Hash>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		self.request(ChainSelectionMessage::Blacklist).await
+	}
+}
+
+fn error(code: Error, message: &str, data: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(format!("{:?}", data))))
+}
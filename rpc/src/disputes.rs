@@ -0,0 +1,332 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC to inspect the state of the dispute coordinator of the node, e.g. to diagnose dispute
+//! storms.
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use polkadot_node_primitives::{CandidateVotes, DisputeStatus as NodeDisputeStatus, Timestamp};
+use polkadot_node_subsystem_types::messages::{
+	DisputeCoordinatorMessage, DisputeCoordinatorStatus,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{
+	vstaging::ValidDisputeStatementKind, CandidateHash, Hash, InvalidDisputeStatementKind,
+	SessionIndex,
+};
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+
+/// The origin of the messages sent to the overseer.
+const ORIGIN: &str = "dispute-rpc";
+
+/// The errors of the disputes RPC.
+pub enum Error {
+	/// The dispute coordinator didn't answer the request.
+	DisputeCoordinatorUnavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::DisputeCoordinatorUnavailable => 1,
+		}
+	}
+}
+
+/// The status of a dispute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum DisputeStatus {
+	/// The dispute is active and unconcluded.
+	Active,
+	/// The dispute has been confirmed, but is not yet concluded.
+	Confirmed,
+	/// The dispute has been concluded in favor of the candidate.
+	ConcludedFor {
+		/// Unix timestamp in seconds of the conclusion.
+		since: Timestamp,
+	},
+	/// The dispute has been concluded against the candidate.
+	ConcludedAgainst {
+		/// Unix timestamp in seconds of the conclusion.
+		since: Timestamp,
+	},
+}
+
+impl From<NodeDisputeStatus> for DisputeStatus {
+	fn from(status: NodeDisputeStatus) -> Self {
+		match status {
+			NodeDisputeStatus::Active => DisputeStatus::Active,
+			NodeDisputeStatus::Confirmed => DisputeStatus::Confirmed,
+			NodeDisputeStatus::ConcludedFor(since) => DisputeStatus::ConcludedFor { since },
+			NodeDisputeStatus::ConcludedAgainst(since) => DisputeStatus::ConcludedAgainst { since },
+		}
+	}
+}
+
+/// A dispute known to the dispute coordinator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dispute {
+	/// The session the disputed candidate appeared in.
+	pub session: SessionIndex,
+	/// The hash of the disputed candidate.
+	pub candidate_hash: Hash,
+	/// The status of the dispute.
+	#[serde(flatten)]
+	pub status: DisputeStatus,
+}
+
+/// The kind of a dispute vote.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VoteKind {
+	/// An explicit statement issued as part of a dispute.
+	Explicit,
+	/// A seconded statement on the candidate from the backing phase.
+	BackingSeconded,
+	/// A valid statement on the candidate from the backing phase.
+	BackingValid,
+	/// An approval vote from the approval checking phase.
+	ApprovalChecking,
+}
+
+impl From<ValidDisputeStatementKind> for VoteKind {
+	fn from(kind: ValidDisputeStatementKind) -> Self {
+		match kind {
+			ValidDisputeStatementKind::Explicit => VoteKind::Explicit,
+			ValidDisputeStatementKind::BackingSeconded(_) => VoteKind::BackingSeconded,
+			ValidDisputeStatementKind::BackingValid(_) => VoteKind::BackingValid,
+			ValidDisputeStatementKind::ApprovalChecking |
+			ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(_) => VoteKind::ApprovalChecking,
+		}
+	}
+}
+
+impl From<InvalidDisputeStatementKind> for VoteKind {
+	fn from(kind: InvalidDisputeStatementKind) -> Self {
+		match kind {
+			InvalidDisputeStatementKind::Explicit => VoteKind::Explicit,
+		}
+	}
+}
+
+/// The vote of a single validator on a disputed candidate.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Vote {
+	/// The index of the validator in the session.
+	pub validator_index: u32,
+	/// Whether the validator considers the candidate valid.
+	pub valid: bool,
+	/// The kind of statement the vote comes from.
+	pub kind: VoteKind,
+}
+
+/// All votes on a disputed candidate, sorted by validator index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisputeVotes {
+	/// The session the candidate appeared in.
+	pub session: SessionIndex,
+	/// The hash of the candidate.
+	pub candidate_hash: Hash,
+	/// The votes on the candidate.
+	pub votes: Vec<Vote>,
+}
+
+impl DisputeVotes {
+	fn new(session: SessionIndex, candidate_hash: CandidateHash, votes: CandidateVotes) -> Self {
+		let mut flattened: Vec<Vote> = votes
+			.valid
+			.raw()
+			.iter()
+			.map(|(index, (kind, _))| Vote {
+				validator_index: index.0,
+				valid: true,
+				kind: kind.clone().into(),
+			})
+			.chain(votes.invalid.iter().map(|(index, (kind, _))| Vote {
+				validator_index: index.0,
+				valid: false,
+				kind: (*kind).into(),
+			}))
+			.collect();
+		flattened.sort_by_key(|vote| (vote.validator_index, !vote.valid));
+
+		Self { session, candidate_hash: candidate_hash.0, votes: flattened }
+	}
+}
+
+/// Spam slots occupied by a validator in a session.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpamSlots {
+	/// The session the slots belong to.
+	pub session: SessionIndex,
+	/// The index of the validator in the session.
+	pub validator_index: u32,
+	/// The number of unconfirmed disputes raised by the validator.
+	pub occupied: u32,
+}
+
+/// The internal state of the dispute coordinator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoordinatorStatus {
+	/// Occupied spam slots.
+	pub spam_slots: Vec<SpamSlots>,
+	/// How many unconfirmed disputes a validator may raise per session.
+	pub max_spam_votes: u32,
+	/// Number of participations waiting in the priority queue.
+	pub priority_queue_len: u32,
+	/// Number of participations waiting in the best-effort queue.
+	pub best_effort_queue_len: u32,
+	/// Number of participations currently running.
+	pub running_participations: u32,
+}
+
+impl From<DisputeCoordinatorStatus> for CoordinatorStatus {
+	fn from(status: DisputeCoordinatorStatus) -> Self {
+		Self {
+			spam_slots: status
+				.spam_slots
+				.into_iter()
+				.map(|(session, validator_index, occupied)| SpamSlots {
+					session,
+					validator_index: validator_index.0,
+					occupied,
+				})
+				.collect(),
+			max_spam_votes: status.max_spam_votes,
+			priority_queue_len: status.priority_queue_len as u32,
+			best_effort_queue_len: status.best_effort_queue_len as u32,
+			running_participations: status.running_participations as u32,
+		}
+	}
+}
+
+/// Disputes RPC methods.
+#[rpc(client, server)]
+pub trait DisputesApi {
+	/// Returns the disputes of the recent sessions known to the node, including the concluded
+	/// ones unless `active_only` is set.
+	#[method(name = "parachain_disputes")]
+	async fn disputes(&self, active_only: Option<bool>) -> RpcResult<Vec<Dispute>>;
+
+	/// Returns the votes the node knows of on the given candidate, `None` if the candidate is
+	/// unknown.
+	#[method(name = "parachain_disputeVotes")]
+	async fn dispute_votes(
+		&self,
+		session: SessionIndex,
+		candidate_hash: Hash,
+	) -> RpcResult<Option<DisputeVotes>>;
+
+	/// Returns the spam slot occupancy and the participation queue lengths of the dispute
+	/// coordinator.
+	#[method(name = "parachain_disputeCoordinatorStatus")]
+	async fn coordinator_status(&self) -> RpcResult<CoordinatorStatus>;
+}
+
+/// Provides the disputes RPC.
+pub struct Disputes {
+	overseer_handle: Handle,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl Disputes {
+	/// Creates a new instance of the disputes RPC.
+	pub fn new(overseer_handle: Handle, deny_unsafe: DenyUnsafe) -> Self {
+		Self { overseer_handle, deny_unsafe }
+	}
+
+	/// Sends a message to the dispute coordinator and waits for its answer.
+	async fn request<T>(
+		&self,
+		make_message: impl FnOnce(oneshot::Sender<T>) -> DisputeCoordinatorMessage,
+	) -> RpcResult<T> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle.clone().send_msg(make_message(tx), ORIGIN).await;
+		rx.await.map_err(|e| {
+			error(
+				Error::DisputeCoordinatorUnavailable,
+				"The dispute coordinator didn't answer the request.",
+				e,
+			)
+			.into()
+		})
+	}
+}
+
+#[async_trait]
+impl DisputesApiServer for Disputes {
+	async fn disputes(&self, active_only: Option<bool>) -> RpcResult<Vec<Dispute>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let disputes = if active_only.unwrap_or(false) {
+			self.request(DisputeCoordinatorMessage::ActiveDisputes).await?
+		} else {
+			self.request(DisputeCoordinatorMessage::RecentDisputes).await?
+		};
+
+		Ok(disputes
+			.into_iter()
+			.map(|(session, candidate_hash, status)| Dispute {
+				session,
+				candidate_hash: candidate_hash.0,
+				status: status.into(),
+			})
+			.collect())
+	}
+
+	async fn dispute_votes(
+		&self,
+		session: SessionIndex,
+		candidate_hash: Hash,
+	) -> RpcResult<Option<DisputeVotes>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let votes = self
+			.request(|tx| {
+				DisputeCoordinatorMessage::QueryCandidateVotes(
+					vec![(session, CandidateHash(candidate_hash))],
+					tx,
+				)
+			})
+			.await?;
+
+		Ok(votes.into_iter().next().map(|(session, candidate_hash, votes)| {
+			DisputeVotes::new(session, candidate_hash, votes)
+		}))
+	}
+
+	async fn coordinator_status(&self) -> RpcResult<CoordinatorStatus> {
+		self.deny_unsafe.check_if_safe()?;
+
+		Ok(self.request(DisputeCoordinatorMessage::Status).await?.into())
+	}
+}
+
+fn error(code: Error, message: &str, data: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(format!("{:?}", data))))
+}
@@ -37,6 +37,17 @@ use sp_consensus_babe::BabeApi;
 use sp_keystore::SyncCryptoStorePtr;
 use txpool_api::TransactionPool;
 
+mod approvals;
+mod chain_selection;
+mod disputes;
+mod message_queues;
+mod xcm_dry_run;
+pub use approvals::{Approvals, ApprovalsApiServer};
+pub use chain_selection::{ChainSelection, ChainSelectionApiServer};
+pub use disputes::{Disputes, DisputesApiServer};
+pub use message_queues::{MessageQueues, MessageQueuesApiServer};
+pub use xcm_dry_run::{XcmDryRun, XcmDryRunApiServer};
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = RpcModule<()>;
 
@@ -92,6 +103,8 @@ pub struct FullDeps<C, P, SC, B> {
 	pub grandpa: GrandpaDeps<B>,
 	/// BEEFY specific dependencies.
 	pub beefy: BeefyDeps,
+	/// Handle to the overseer, if it runs on this node.
+	pub overseer_handle: Option<polkadot_overseer::Handle>,
 }
 
 /// Instantiate all RPC extensions.
@@ -112,6 +125,8 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: pallet_xcm::dry_run::XcmDryRunApi<Block>,
+	C::Api: polkadot_primitives::runtime_api::ParachainHost<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -127,8 +142,17 @@ where
 	use substrate_state_trie_migration_rpc::{StateMigration, StateMigrationApiServer};
 
 	let mut io = RpcModule::new(());
-	let FullDeps { client, pool, select_chain, chain_spec, deny_unsafe, babe, grandpa, beefy } =
-		deps;
+	let FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		deny_unsafe,
+		babe,
+		grandpa,
+		beefy,
+		overseer_handle,
+	} = deps;
 	let BabeDeps { keystore, babe_config, shared_epoch_changes } = babe;
 	let GrandpaDeps {
 		shared_voter_state,
@@ -142,6 +166,13 @@ where
 	io.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 	io.merge(Mmr::new(client.clone()).into_rpc())?;
+	io.merge(XcmDryRun::new(client.clone(), deny_unsafe).into_rpc())?;
+	io.merge(MessageQueues::new(client.clone(), deny_unsafe).into_rpc())?;
+	if let Some(overseer_handle) = overseer_handle {
+		io.merge(Approvals::new(overseer_handle.clone(), deny_unsafe).into_rpc())?;
+		io.merge(ChainSelection::new(overseer_handle.clone(), deny_unsafe).into_rpc())?;
+		io.merge(Disputes::new(overseer_handle, deny_unsafe).into_rpc())?;
+	}
 	io.merge(
 		Babe::new(
 			client.clone(),
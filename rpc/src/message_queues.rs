@@ -0,0 +1,217 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC to inspect the upward and downward message queues of a para, e.g. to alert on
+//! backpressure.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use polkadot_primitives::{
+	runtime_api::ParachainHost,
+	vstaging::{
+		DownwardQueueStatus, MessageQueuesStatus, OverweightUpwardMessage, UpwardQueueStatus,
+	},
+	Block, Hash, Id as ParaId,
+};
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+
+/// The version of the `ParachainHost` API which introduced `message_queues_status`.
+const MESSAGE_QUEUES_STATUS_API_VERSION: u32 = 5;
+
+/// The errors of the message queues RPC.
+pub enum Error {
+	/// The runtime API call failed.
+	RuntimeError,
+	/// The runtime doesn't provide the state of the message queues.
+	NotSupported,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::NotSupported => 2,
+		}
+	}
+}
+
+/// The state of the queue of messages sent from a para to the relay chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpwardQueue {
+	/// The number of messages waiting to be dispatched.
+	pub count: u32,
+	/// The total size in bytes of the messages waiting to be dispatched.
+	pub total_size: u32,
+	/// The maximum number of messages the queue may hold.
+	pub max_count: u32,
+	/// The maximum total size in bytes the queue may hold.
+	pub max_total_size: u32,
+	/// Whether the para is scheduled for dispatch.
+	pub needs_dispatch: bool,
+}
+
+impl From<UpwardQueueStatus> for UpwardQueue {
+	fn from(status: UpwardQueueStatus) -> Self {
+		UpwardQueue {
+			count: status.count,
+			total_size: status.total_size,
+			max_count: status.max_count,
+			max_total_size: status.max_total_size,
+			needs_dispatch: status.needs_dispatch,
+		}
+	}
+}
+
+/// The state of the queue of messages sent from the relay chain to a para.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownwardQueue {
+	/// The number of messages waiting to be processed by the para.
+	pub length: u32,
+	/// The head of the message queue chain.
+	pub mqc_head: Hash,
+}
+
+impl From<DownwardQueueStatus> for DownwardQueue {
+	fn from(status: DownwardQueueStatus) -> Self {
+		DownwardQueue { length: status.length, mqc_head: status.mqc_head }
+	}
+}
+
+/// The weight an overweight message required for its execution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredWeight {
+	/// The computational time required.
+	pub ref_time: u64,
+	/// The size of the proof required.
+	pub proof_size: u64,
+}
+
+/// An upward message awaiting manual servicing because it exceeded the maximum individual
+/// weight.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverweightMessage {
+	/// The index under which the message can be serviced.
+	pub index: u64,
+	/// The size of the message in bytes.
+	pub size: u32,
+	/// The weight the message required, if it was recorded.
+	pub required_weight: Option<RequiredWeight>,
+}
+
+impl From<OverweightUpwardMessage> for OverweightMessage {
+	fn from(message: OverweightUpwardMessage) -> Self {
+		OverweightMessage {
+			index: message.index,
+			size: message.size,
+			required_weight: message.required_weight.map(|weight| RequiredWeight {
+				ref_time: weight.ref_time(),
+				proof_size: weight.proof_size(),
+			}),
+		}
+	}
+}
+
+/// The state of the message queues of a para.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageQueuesState {
+	/// The queue of messages sent from the para to the relay chain.
+	pub upward: UpwardQueue,
+	/// The queue of messages sent from the relay chain to the para.
+	pub downward: DownwardQueue,
+	/// The upward messages of the para that are parked as overweight.
+	pub overweight: Vec<OverweightMessage>,
+}
+
+impl From<MessageQueuesStatus> for MessageQueuesState {
+	fn from(status: MessageQueuesStatus) -> Self {
+		MessageQueuesState {
+			upward: status.upward.into(),
+			downward: status.downward.into(),
+			overweight: status.overweight.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Message queues RPC methods.
+#[rpc(client, server)]
+pub trait MessageQueuesApi<BlockHash> {
+	/// Returns the state of the upward and downward message queues of the given para, along with
+	/// its overweight upward messages, at the given block or the best one.
+	#[method(name = "parachain_messageQueues")]
+	fn message_queues(&self, para_id: u32, at: Option<BlockHash>) -> RpcResult<MessageQueuesState>;
+}
+
+/// Provides the message queues RPC.
+pub struct MessageQueues<C> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C> MessageQueues<C> {
+	/// Creates a new instance of the message queues RPC.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe }
+	}
+}
+
+impl<C> MessageQueuesApiServer<Hash> for MessageQueues<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ParachainHost<Block>,
+{
+	fn message_queues(&self, para_id: u32, at: Option<Hash>) -> RpcResult<MessageQueuesState> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let api = self.client.runtime_api();
+
+		let version = api
+			.api_version::<dyn ParachainHost<Block>>(at)
+			.map_err(|e| error(Error::RuntimeError, "Unable to query the runtime API version.", e))?
+			.unwrap_or_default();
+		if version < MESSAGE_QUEUES_STATUS_API_VERSION {
+			return Err(error(
+				Error::NotSupported,
+				"The runtime doesn't support inspecting message queues.",
+				version,
+			)
+			.into())
+		}
+
+		let status = api
+			.message_queues_status(at, ParaId::from(para_id))
+			.map_err(|e| error(Error::RuntimeError, "Unable to query the message queues.", e))?;
+
+		Ok(status.into())
+	}
+}
+
+fn error(code: Error, message: &str, data: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(format!("{:?}", data))))
+}
@@ -0,0 +1,109 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC to dry-run XCM programs, e.g. to estimate their fees before submitting them.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_xcm::dry_run::XcmDryRunApi as XcmDryRunRuntimeApi;
+use parity_scale_codec::{Decode, DecodeLimit, Encode};
+use polkadot_primitives::{Block, Hash};
+use sc_rpc::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use xcm::{VersionedMultiLocation, VersionedXcm, MAX_XCM_DECODE_DEPTH};
+
+/// The errors of the XCM dry-run RPC.
+pub enum Error {
+	/// The origin or the program couldn't be decoded.
+	DecodeError,
+	/// The runtime API call failed.
+	RuntimeError,
+	/// The runtime refused to dry-run the program.
+	DryRunError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::DecodeError => 1,
+			Error::RuntimeError => 2,
+			Error::DryRunError => 3,
+		}
+	}
+}
+
+/// XCM dry-run RPC methods.
+#[rpc(client, server)]
+pub trait XcmDryRunApi<BlockHash> {
+	/// Executes the SCALE-encoded `VersionedXcm` as if it was received from the SCALE-encoded
+	/// `VersionedMultiLocation` origin, on top of the given block or the best one, without
+	/// persisting any change.
+	///
+	/// Returns the SCALE-encoded `XcmDryRunEffects`: the outcome, the weight used, the trapped
+	/// assets, the emitted events and the forwarded messages.
+	#[method(name = "xcm_dryRun")]
+	fn dry_run(&self, origin: Bytes, xcm: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+}
+
+/// Provides the XCM dry-run RPC.
+pub struct XcmDryRun<C> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C> XcmDryRun<C> {
+	/// Creates a new instance of the XCM dry-run RPC.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe }
+	}
+}
+
+impl<C> XcmDryRunApiServer<Hash> for XcmDryRun<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: XcmDryRunRuntimeApi<Block>,
+{
+	fn dry_run(&self, origin: Bytes, xcm: Bytes, at: Option<Hash>) -> RpcResult<Bytes> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let origin = VersionedMultiLocation::decode(&mut &origin[..])
+			.map_err(|e| error(Error::DecodeError, "Unable to decode the origin.", e))?;
+		let xcm =
+			VersionedXcm::<()>::decode_all_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut &xcm[..])
+				.map_err(|e| error(Error::DecodeError, "Unable to decode the XCM program.", e))?;
+
+		let effects = self
+			.client
+			.runtime_api()
+			.dry_run_xcm(at, origin, xcm)
+			.map_err(|e| error(Error::RuntimeError, "Unable to dry-run the XCM program.", e))?
+			.map_err(|e| error(Error::DryRunError, "The XCM program can't be dry-run.", e))?;
+
+		Ok(effects.encode().into())
+	}
+}
+
+fn error(code: Error, message: &str, data: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(code.into(), message, Some(format!("{:?}", data))))
+}
@@ -215,6 +215,7 @@ parameter_types! {
 impl paras_registrar::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type OnSwap = (Crowdloan, Slots);
+	type LeasePeriodSwap = Slots;
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type Currency = Balances;
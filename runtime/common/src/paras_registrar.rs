@@ -32,7 +32,7 @@ use runtime_parachains::{
 };
 use sp_std::{prelude::*, result};
 
-use crate::traits::{OnSwap, Registrar};
+use crate::traits::{OnSwap, Registrar, SwapLeasePeriods};
 pub use pallet::*;
 use parity_scale_codec::{Decode, Encode};
 use runtime_parachains::paras::ParaKind;
@@ -63,6 +63,7 @@ pub trait WeightInfo {
 	fn swap() -> Weight;
 	fn schedule_code_upgrade(b: u32) -> Weight;
 	fn set_current_head(b: u32) -> Weight;
+	fn swap_lease_periods() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -88,6 +89,9 @@ impl WeightInfo for TestWeightInfo {
 	fn set_current_head(_b: u32) -> Weight {
 		Weight::zero()
 	}
+	fn swap_lease_periods() -> Weight {
+		Weight::zero()
+	}
 }
 
 #[frame_support::pallet]
@@ -120,6 +124,9 @@ pub mod pallet {
 		/// Runtime hook for when a parachain and parathread swap.
 		type OnSwap: crate::traits::OnSwap;
 
+		/// The means to swap some of the future lease periods of two paras.
+		type LeasePeriodSwap: SwapLeasePeriods<Self::BlockNumber>;
+
 		/// The deposit to be paid to run a parathread.
 		/// This should include the cost for storing the genesis head and validation code.
 		#[pallet::constant]
@@ -178,6 +185,12 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type PendingSwap<T> = StorageMap<_, Twox64Concat, ParaId, ParaId>;
 
+	/// Pending swap operations of some lease periods, as the other para, the first lease period
+	/// and the number of lease periods.
+	#[pallet::storage]
+	pub(super) type PendingLeasePeriodSwap<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, (ParaId, T::BlockNumber, T::BlockNumber)>;
+
 	/// Amount held on deposit for each para and the original depositor.
 	///
 	/// The given account ID is responsible for registering the code and initial head data, but may only do
@@ -405,6 +418,44 @@ pub mod pallet {
 			runtime_parachains::set_current_head::<T>(para, new_head);
 			Ok(())
 		}
+
+		/// Swap the leases of a para with another para for `period_count` lease periods starting
+		/// at `first_period`, which must all lie in the future. A lease period only one of the two
+		/// paras holds is thus transferred to the other one. Lease periods held by anyone but the
+		/// managers of the two paras, such as a crowdloan fund, can't be swapped.
+		///
+		/// The origin must be Root, the `id` owner, or the `id` itself.
+		///
+		/// Like `swap`, the swap will happen only if there is already an opposite swap of the same
+		/// lease periods pending. If there is not, the swap will be stored in the pending lease
+		/// period swaps map, ready for a later confirmatory swap.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::swap_lease_periods())]
+		pub fn swap_lease_periods(
+			origin: OriginFor<T>,
+			id: ParaId,
+			other: ParaId,
+			first_period: T::BlockNumber,
+			period_count: T::BlockNumber,
+		) -> DispatchResult {
+			Self::ensure_root_para_or_owner(origin, id)?;
+
+			// If `id` and `other` is the same id, we treat this as a "clear" function, and exit
+			// early, since swapping the same id would otherwise be a noop.
+			if id == other {
+				PendingLeasePeriodSwap::<T>::remove(id);
+				return Ok(())
+			}
+
+			if PendingLeasePeriodSwap::<T>::get(other) == Some((id, first_period, period_count)) {
+				T::LeasePeriodSwap::swap_lease_periods(id, other, first_period, period_count)?;
+				PendingLeasePeriodSwap::<T>::remove(other);
+			} else {
+				PendingLeasePeriodSwap::<T>::insert(id, (other, first_period, period_count));
+			}
+
+			Ok(())
+		}
 	}
 }
 
@@ -613,6 +664,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		PendingSwap::<T>::remove(id);
+		PendingLeasePeriodSwap::<T>::remove(id);
 		Self::deposit_event(Event::<T>::Deregistered { para_id: id });
 		Ok(())
 	}
@@ -783,6 +835,7 @@ mod tests {
 		type RuntimeEvent = RuntimeEvent;
 		type Currency = Balances;
 		type OnSwap = MockSwap;
+		type LeasePeriodSwap = MockLeasePeriodSwap;
 		type ParaDeposit = ParaDeposit;
 		type DataDepositPerByte = DataDepositPerByte;
 		type WeightInfo = TestWeightInfo;
@@ -826,6 +879,29 @@ mod tests {
 		}
 	}
 
+	parameter_types! {
+		pub static LeasePeriodSwaps: Vec<(ParaId, ParaId, BlockNumber, BlockNumber)> = Vec::new();
+	}
+
+	pub struct MockLeasePeriodSwap;
+	impl SwapLeasePeriods<BlockNumber> for MockLeasePeriodSwap {
+		fn swap_lease_periods(
+			one: ParaId,
+			other: ParaId,
+			first_period: BlockNumber,
+			period_count: BlockNumber,
+		) -> DispatchResult {
+			let mut swaps = LeasePeriodSwaps::get();
+			swaps.push((one, other, first_period, period_count));
+			LeasePeriodSwaps::set(swaps);
+			Ok(())
+		}
+
+		fn setup_worst_case_swap(_: ParaId, _: ParaId) -> (BlockNumber, BlockNumber) {
+			(1, 1)
+		}
+	}
+
 	const BLOCKS_PER_SESSION: u32 = 3;
 
 	fn run_to_block(n: BlockNumber) {
@@ -1279,6 +1355,77 @@ mod tests {
 			assert!(Parachains::is_parathread(para_2));
 		});
 	}
+
+	#[test]
+	fn swap_lease_periods_needs_both_paras() {
+		new_test_ext().execute_with(|| {
+			let para_1 = LOWEST_PUBLIC_ID;
+			let para_2 = LOWEST_PUBLIC_ID + 1;
+			run_to_block(1);
+
+			assert_ok!(Registrar::reserve(RuntimeOrigin::signed(1)));
+			assert_ok!(Registrar::reserve(RuntimeOrigin::signed(2)));
+			assert_ok!(Registrar::register(
+				RuntimeOrigin::signed(1),
+				para_1,
+				test_genesis_head(32),
+				test_validation_code(32),
+			));
+			assert_ok!(Registrar::register(
+				RuntimeOrigin::signed(2),
+				para_2,
+				test_genesis_head(32),
+				test_validation_code(32),
+			));
+
+			// Only the owner can request a swap for a para.
+			assert_noop!(
+				Registrar::swap_lease_periods(RuntimeOrigin::signed(2), para_1, para_2, 1, 2),
+				BadOrigin
+			);
+
+			assert_ok!(Registrar::swap_lease_periods(
+				RuntimeOrigin::signed(1),
+				para_1,
+				para_2,
+				1,
+				2
+			));
+			assert_eq!(PendingLeasePeriodSwap::<Test>::get(para_1), Some((para_2, 1, 2)));
+			assert!(LeasePeriodSwaps::get().is_empty());
+
+			// Swapping other lease periods does not confirm the swap.
+			assert_ok!(Registrar::swap_lease_periods(
+				RuntimeOrigin::signed(2),
+				para_2,
+				para_1,
+				1,
+				3
+			));
+			assert!(LeasePeriodSwaps::get().is_empty());
+
+			// Clear the request.
+			assert_ok!(Registrar::swap_lease_periods(
+				RuntimeOrigin::signed(2),
+				para_2,
+				para_2,
+				1,
+				3
+			));
+			assert_eq!(PendingLeasePeriodSwap::<Test>::get(para_2), None);
+
+			assert_ok!(Registrar::swap_lease_periods(
+				RuntimeOrigin::signed(2),
+				para_2,
+				para_1,
+				1,
+				2
+			));
+			assert_eq!(LeasePeriodSwaps::get(), vec![(para_2, para_1, 1, 2)]);
+			assert_eq!(PendingLeasePeriodSwap::<Test>::get(para_1), None);
+			assert_eq!(PendingLeasePeriodSwap::<Test>::get(para_2), None);
+		});
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1404,6 +1551,39 @@ mod benchmarking {
 			assert_eq!(paras::Pallet::<T>::lifecycle(parathread), Some(ParaLifecycle::Parachain));
 		}
 
+		// Worst case scenario, both paras hold leases of different managers, which both have to
+		// reserve more after the swap.
+		swap_lease_periods {
+			let para = register_para::<T>(LOWEST_PUBLIC_ID.into());
+			let other = ParaId::from(LOWEST_PUBLIC_ID + 1);
+			let other_manager: T::AccountId = account("manager", 0, 0);
+			T::Currency::make_free_balance_be(&other_manager, BalanceOf::<T>::max_value());
+			assert_ok!(Registrar::<T>::reserve(RawOrigin::Signed(other_manager.clone()).into()));
+			assert_ok!(Registrar::<T>::register(
+				RawOrigin::Signed(other_manager.clone()).into(),
+				other,
+				Registrar::<T>::worst_head_data(),
+				Registrar::<T>::worst_validation_code(),
+			));
+			next_scheduled_session::<T>();
+
+			// Make sure the lease periods have started.
+			frame_system::Pallet::<T>::set_block_number(1_000_000u32.into());
+			let (first_period, period_count) =
+				T::LeasePeriodSwap::setup_worst_case_swap(para, other);
+			let caller: T::AccountId = whitelisted_caller();
+			Registrar::<T>::swap_lease_periods(
+				RawOrigin::Signed(other_manager).into(),
+				other,
+				para,
+				first_period,
+				period_count,
+			)?;
+		}: _(RawOrigin::Signed(caller), para, other, first_period, period_count)
+		verify {
+			assert_eq!(PendingLeasePeriodSwap::<T>::get(other), None);
+		}
+
 		schedule_code_upgrade {
 			let b in 1 .. MAX_CODE_SIZE;
 			let new_code = ValidationCode(vec![0; b as usize]);
@@ -20,10 +20,14 @@
 //!
 //! This doesn't handle the mechanics of determining which para ID actually ends up with a parachain lease. This
 //! must handled by a separately, through the trait interface that this pallet provides or the root dispatchables.
+//!
+//! The only exception are lease extensions: the manager of a para that holds a lease for the current lease period
+//! may extend it by further lease periods directly, for a deposit given by a price curve set by governance. Like any
+//! other lease deposit, it is only reserved, not charged, and returned once the lease periods have ended.
 
 pub mod migration;
 
-use crate::traits::{LeaseError, Leaser, Registrar};
+use crate::traits::{LeaseError, Leaser, Registrar, SwapLeasePeriods};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{Currency, ReservableCurrency},
@@ -32,18 +36,36 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use primitives::Id as ParaId;
-use sp_runtime::traits::{CheckedConversion, CheckedSub, Saturating, Zero};
+use sp_runtime::traits::{CheckedConversion, CheckedSub, One, Saturating, Zero};
 use sp_std::prelude::*;
 
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type LeasePeriodOf<T> = <T as frame_system::Config>::BlockNumber;
 
+/// The price curve of lease extensions.
+///
+/// The deposit required for an extension is `base + per_period * n`, where `n` is the number of
+/// lease periods between the current lease period and the last lease period of the extension.
+///
+/// This is a deposit rather than a fee: it is reserved in full on top of whatever the leaser
+/// already holds for the para, and returned once the extended lease periods have ended.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LeaseExtensionPrice<Balance, LeasePeriod> {
+	/// The deposit required for an extension ending in the current lease period.
+	pub base: Balance,
+	/// The deposit added for each lease period an extension ends further in the future.
+	pub per_period: Balance,
+	/// The maximum number of lease periods an extension may end after the current one.
+	pub max_periods_ahead: LeasePeriod,
+}
+
 pub trait WeightInfo {
 	fn force_lease() -> Weight;
 	fn manage_lease_period_start(c: u32, t: u32) -> Weight;
 	fn clear_all_leases() -> Weight;
 	fn trigger_onboard() -> Weight;
+	fn extend_lease() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -60,6 +82,9 @@ impl WeightInfo for TestWeightInfo {
 	fn trigger_onboard() -> Weight {
 		Weight::zero()
 	}
+	fn extend_lease() -> Weight {
+		Weight::zero()
+	}
 }
 
 #[frame_support::pallet]
@@ -118,6 +143,11 @@ pub mod pallet {
 	pub type Leases<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, Vec<Option<(T::AccountId, BalanceOf<T>)>>, ValueQuery>;
 
+	/// The price curve of lease extensions. Lease extensions are disabled if this is `None`.
+	#[pallet::storage]
+	pub type ExtensionPrice<T: Config> =
+		StorageValue<_, LeaseExtensionPrice<BalanceOf<T>, LeasePeriodOf<T>>>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -134,6 +164,15 @@ pub mod pallet {
 			extra_reserved: BalanceOf<T>,
 			total_amount: BalanceOf<T>,
 		},
+		/// The price curve of lease extensions was changed.
+		ExtensionPriceSet { price: Option<LeaseExtensionPrice<BalanceOf<T>, LeasePeriodOf<T>>> },
+		/// Two paras swapped their leases for some future lease periods.
+		LeasePeriodsSwapped {
+			one: ParaId,
+			other: ParaId,
+			first_period: LeasePeriodOf<T>,
+			period_count: LeasePeriodOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -142,6 +181,24 @@ pub mod pallet {
 		ParaNotOnboarding,
 		/// There was an error with the lease.
 		LeaseError,
+		/// Lease extensions are disabled.
+		ExtensionsDisabled,
+		/// The caller is not the manager of the para.
+		NotParaManager,
+		/// The para does not hold a lease for the current lease period.
+		NoCurrentLease,
+		/// No lease periods were given.
+		NoLeasePeriods,
+		/// The lease extension would end too far in the future.
+		ExtensionTooFarAhead,
+		/// The deposit required for the lease extension exceeds the maximum the caller is willing
+		/// to place.
+		DepositExceedsMaxAmount,
+		/// A lease period to be swapped is not in the future.
+		LeasePeriodNotInFuture,
+		/// A lease period to be swapped is held by a leaser who is not the manager of either para,
+		/// such as a crowdloan fund.
+		LeaserNotParaManager,
 	}
 
 	#[pallet::hooks]
@@ -221,6 +278,54 @@ pub mod pallet {
 			};
 			Ok(())
 		}
+
+		/// Extend the lease of a para by `period_count` lease periods, following directly on the
+		/// last lease period it currently holds.
+		///
+		/// The origin must be signed by the manager of the para, which must hold a lease for the
+		/// current lease period. The deposit given by [`Pallet::lease_extension_deposit`] is
+		/// reserved from the caller in addition to any deposit it already holds for the para, but
+		/// only if it does not exceed `max_deposit`. Nothing is charged: all of it is held until
+		/// the extended lease periods have ended.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::extend_lease())]
+		pub fn extend_lease(
+			origin: OriginFor<T>,
+			para: ParaId,
+			period_count: LeasePeriodOf<T>,
+			max_deposit: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				T::Registrar::manager_of(para) == Some(who.clone()),
+				Error::<T>::NotParaManager
+			);
+
+			let (period_begin, deposit) = Self::lease_extension_deposit(para, period_count)?;
+			ensure!(deposit <= max_deposit, Error::<T>::DepositExceedsMaxAmount);
+
+			// A leaser only holds the largest of its deposits for a para, so the extended lease
+			// periods have to hold the existing deposit as well for all of `deposit` to be reserved.
+			let amount = Self::deposit_held(para, &who).saturating_add(deposit);
+			Self::lease_out(para, &who, amount, period_begin, period_count)
+				.map_err(|_| Error::<T>::LeaseError)?;
+			Ok(())
+		}
+
+		/// Set the price curve of lease extensions, or disable them with `None`.
+		///
+		/// The dispatch origin for this call must match `T::ForceOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_extension_price(
+			origin: OriginFor<T>,
+			price: Option<LeaseExtensionPrice<BalanceOf<T>, LeasePeriodOf<T>>>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ExtensionPrice::<T>::set(price.clone());
+			Self::deposit_event(Event::<T>::ExtensionPriceSet { price });
+			Ok(())
+		}
 	}
 }
 
@@ -306,6 +411,39 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// The first lease period and the deposit of a lease extension of `para` by `period_count`
+	/// lease periods, if it would be accepted right now.
+	///
+	/// The deposit is reserved on top of what the leaser already holds for `para`, see
+	/// [`LeaseExtensionPrice`].
+	pub fn lease_extension_deposit(
+		para: ParaId,
+		period_count: LeasePeriodOf<T>,
+	) -> Result<(LeasePeriodOf<T>, BalanceOf<T>), Error<T>> {
+		let price = ExtensionPrice::<T>::get().ok_or(Error::<T>::ExtensionsDisabled)?;
+		ensure!(!period_count.is_zero(), Error::<T>::NoLeasePeriods);
+
+		let leases = Leases::<T>::get(para);
+		ensure!(matches!(leases.first(), Some(Some(_))), Error::<T>::NoCurrentLease);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let (current_lease_period, _) =
+			Self::lease_period_index(now).ok_or(Error::<T>::NoCurrentLease)?;
+		// The extension follows the last lease period of the para, which is always leased.
+		let period_begin = current_lease_period.saturating_add((leases.len() as u32).into());
+		let periods_ahead = period_begin
+			.saturating_add(period_count)
+			.saturating_sub(One::one())
+			.saturating_sub(current_lease_period);
+		ensure!(periods_ahead <= price.max_periods_ahead, Error::<T>::ExtensionTooFarAhead);
+
+		let periods_ahead: u32 =
+			periods_ahead.checked_into().ok_or(Error::<T>::ExtensionTooFarAhead)?;
+		let deposit =
+			price.base.saturating_add(price.per_period.saturating_mul(periods_ahead.into()));
+		Ok((period_begin, deposit))
+	}
+
 	// Return a vector of (user, balance) for all deposits for a parachain.
 	// Useful when trying to clean up a parachain leases, as this would tell
 	// you all the balances you need to unreserve.
@@ -328,6 +466,160 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	// Return the deposit `leaser` has to hold for the given leases of a para.
+	fn deposit_held_in(
+		leases: &[Option<(T::AccountId, BalanceOf<T>)>],
+		leaser: &T::AccountId,
+	) -> BalanceOf<T> {
+		leases
+			.iter()
+			.map(|lease| match lease {
+				Some((who, amount)) =>
+					if who == leaser {
+						*amount
+					} else {
+						Zero::zero()
+					},
+				None => Zero::zero(),
+			})
+			.max()
+			.unwrap_or_else(Zero::zero)
+	}
+}
+
+impl<T: Config> SwapLeasePeriods<LeasePeriodOf<T>> for Pallet<T> {
+	fn swap_lease_periods(
+		one: ParaId,
+		other: ParaId,
+		first_period: LeasePeriodOf<T>,
+		period_count: LeasePeriodOf<T>,
+	) -> DispatchResult {
+		ensure!(!period_count.is_zero(), Error::<T>::NoLeasePeriods);
+		let now = frame_system::Pallet::<T>::block_number();
+		let (current_lease_period, _) =
+			Self::lease_period_index(now).ok_or(Error::<T>::LeasePeriodNotInFuture)?;
+		ensure!(first_period > current_lease_period, Error::<T>::LeasePeriodNotInFuture);
+
+		let offset = (first_period - current_lease_period)
+			.checked_into::<usize>()
+			.ok_or(Error::<T>::LeaseError)?;
+		let end = period_count
+			.checked_into::<usize>()
+			.and_then(|count| offset.checked_add(count))
+			.ok_or(Error::<T>::LeaseError)?;
+
+		let old_one = Leases::<T>::get(one);
+		let old_other = Leases::<T>::get(other);
+		let (mut new_one, mut new_other) = (old_one.clone(), old_other.clone());
+		// Only swap as far as any of the two paras holds leases.
+		let end = end.min(new_one.len().max(new_other.len()));
+
+		// Only the two managers agreed to the swap, so no one else's deposit may be moved to
+		// another para. In particular, a crowdloan fund was only contributed to for its own para.
+		let managers = [T::Registrar::manager_of(one), T::Registrar::manager_of(other)];
+		for leases in [&old_one, &old_other] {
+			for (who, _) in leases.iter().take(end).skip(offset).flatten() {
+				ensure!(
+					managers.iter().any(|manager| manager.as_ref() == Some(who)),
+					Error::<T>::LeaserNotParaManager
+				);
+			}
+		}
+		if new_one.len() < end {
+			new_one.resize_with(end, || None);
+		}
+		if new_other.len() < end {
+			new_other.resize_with(end, || None);
+		}
+		for i in offset..end {
+			sp_std::mem::swap(&mut new_one[i], &mut new_other[i]);
+		}
+		// It is illegal for a `None` value to trail in the list.
+		for leases in [&mut new_one, &mut new_other] {
+			while let Some(None) = leases.last() {
+				leases.pop();
+			}
+		}
+
+		// Deposits are held per para, so the leasers of swapped lease periods may need to hold
+		// more or less in total now.
+		let mut leasers = Vec::new();
+		for (who, _) in old_one.iter().chain(old_other.iter()).flatten() {
+			if !leasers.contains(who) {
+				leasers.push(who.clone());
+			}
+		}
+		let (mut increases, mut decreases) = (Vec::new(), Vec::new());
+		for who in leasers {
+			let held = Self::deposit_held_in(&old_one, &who)
+				.saturating_add(Self::deposit_held_in(&old_other, &who));
+			let needed = Self::deposit_held_in(&new_one, &who)
+				.saturating_add(Self::deposit_held_in(&new_other, &who));
+			if needed > held {
+				increases.push((who, needed - held));
+			} else if held > needed {
+				decreases.push((who, held - needed));
+			}
+		}
+
+		let mut reserved = Vec::new();
+		for (who, amount) in increases {
+			if T::Currency::reserve(&who, amount).is_err() {
+				for (who, amount) in reserved {
+					T::Currency::unreserve(&who, amount);
+				}
+				return Err(Error::<T>::LeaseError.into())
+			}
+			reserved.push((who, amount));
+		}
+		for (who, amount) in decreases {
+			let err_amount = T::Currency::unreserve(&who, amount);
+			debug_assert!(err_amount.is_zero());
+		}
+
+		for (para, leases) in [(one, new_one), (other, new_other)] {
+			if leases.is_empty() {
+				Leases::<T>::remove(para);
+			} else {
+				Leases::<T>::insert(para, leases);
+			}
+		}
+
+		Self::deposit_event(Event::<T>::LeasePeriodsSwapped {
+			one,
+			other,
+			first_period,
+			period_count,
+		});
+		Ok(())
+	}
+
+	#[cfg(any(feature = "runtime-benchmarks", test))]
+	fn setup_worst_case_swap(one: ParaId, other: ParaId) -> (LeasePeriodOf<T>, LeasePeriodOf<T>) {
+		// As many lease periods as a slot won in an auction.
+		let period_count = 8u32;
+		let now = frame_system::Pallet::<T>::block_number();
+		let (current_lease_period, _) =
+			Self::lease_period_index(now).expect("the first lease period has started");
+
+		// Each manager leases its own para for the current and all swapped lease periods, with
+		// different deposits, so that both have to reserve more after the swap.
+		let amount = T::Currency::minimum_balance();
+		for (para, deposit) in [(one, amount), (other, amount.saturating_add(amount))] {
+			let manager = T::Registrar::manager_of(para).expect("the para is registered");
+			let _ = T::Currency::deposit_creating(&manager, deposit.saturating_mul(3u32.into()));
+			T::Currency::reserve(&manager, deposit).expect("the manager was just funded");
+			Leases::<T>::insert(
+				para,
+				sp_std::vec![Some((manager, deposit)); period_count as usize + 1],
+			);
+		}
+
+		(current_lease_period.saturating_add(One::one()), period_count.into())
+	}
+}
+
 impl<T: Config> crate::traits::OnSwap for Pallet<T> {
 	fn on_swap(one: ParaId, other: ParaId) {
 		Leases::<T>::mutate(one, |x| Leases::<T>::mutate(other, |y| sp_std::mem::swap(x, y)))
@@ -427,19 +719,7 @@ impl<T: Config> Leaser<T::BlockNumber> for Pallet<T> {
 		para: ParaId,
 		leaser: &Self::AccountId,
 	) -> <Self::Currency as Currency<Self::AccountId>>::Balance {
-		Leases::<T>::get(para)
-			.into_iter()
-			.map(|lease| match lease {
-				Some((who, amount)) =>
-					if &who == leaser {
-						amount
-					} else {
-						Zero::zero()
-					},
-				None => Zero::zero(),
-			})
-			.max()
-			.unwrap_or_else(Zero::zero)
+		Self::deposit_held_in(&Leases::<T>::get(para), leaser)
 	}
 
 	#[cfg(any(feature = "runtime-benchmarks", test))]
@@ -503,7 +783,7 @@ mod tests {
 
 	use crate::{mock::TestRegistrar, slots};
 	use ::test_helpers::{dummy_head_data, dummy_validation_code};
-	use frame_support::{assert_noop, assert_ok, parameter_types};
+	use frame_support::{assert_noop, assert_ok, error::BadOrigin, parameter_types};
 	use frame_system::EnsureRoot;
 	use pallet_balances;
 	use primitives::{BlockNumber, Header};
@@ -978,6 +1258,208 @@ mod tests {
 			assert_eq!(Slots::lease_period_index(2 * lpl + offset + 1), Some((2, false)));
 		});
 	}
+
+	#[test]
+	fn extend_lease_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			assert_ok!(TestRegistrar::<Test>::register(
+				1,
+				ParaId::from(1_u32),
+				dummy_head_data(),
+				dummy_validation_code()
+			));
+			assert_ok!(Slots::lease_out(1.into(), &1, 1, 0, 1));
+
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), 1.into(), 2, 10),
+				Error::<Test>::ExtensionsDisabled
+			);
+
+			let price = LeaseExtensionPrice { base: 1, per_period: 1, max_periods_ahead: 3 };
+			assert_noop!(
+				Slots::set_extension_price(RuntimeOrigin::signed(1), Some(price.clone())),
+				BadOrigin
+			);
+			assert_ok!(Slots::set_extension_price(RuntimeOrigin::root(), Some(price.clone())));
+			System::assert_last_event(
+				Event::<Test>::ExtensionPriceSet { price: Some(price) }.into(),
+			);
+
+			// The extension starts after the current lease period and ends two periods ahead.
+			assert_eq!(Slots::lease_extension_deposit(1.into(), 2), Ok((1, 3)));
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(2), 1.into(), 2, 10),
+				Error::<Test>::NotParaManager
+			);
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), 1.into(), 0, 10),
+				Error::<Test>::NoLeasePeriods
+			);
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), 1.into(), 2, 2),
+				Error::<Test>::DepositExceedsMaxAmount
+			);
+			// The whole deposit is reserved on top of the one for the current lease period.
+			assert_ok!(Slots::extend_lease(RuntimeOrigin::signed(1), 1.into(), 2, 3));
+			assert_eq!(
+				Slots::lease(ParaId::from(1_u32)),
+				vec![Some((1, 1)), Some((1, 4)), Some((1, 4))]
+			);
+			assert_eq!(Balances::reserved_balance(1), 4);
+
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(1), 1.into(), 2, 10),
+				Error::<Test>::ExtensionTooFarAhead
+			);
+			// Even when the leaser already holds as much as the extension deposit.
+			assert_ok!(Slots::extend_lease(RuntimeOrigin::signed(1), 1.into(), 1, 4));
+			assert_eq!(Slots::deposit_held(1.into(), &1), 8);
+			assert_eq!(Balances::reserved_balance(1), 8);
+
+			// A para without a lease for the current lease period can't extend.
+			assert_ok!(TestRegistrar::<Test>::register(
+				2,
+				ParaId::from(2_u32),
+				dummy_head_data(),
+				dummy_validation_code()
+			));
+			assert_ok!(Slots::lease_out(2.into(), &2, 1, 1, 1));
+			assert_noop!(
+				Slots::extend_lease(RuntimeOrigin::signed(2), 2.into(), 1, 10),
+				Error::<Test>::NoCurrentLease
+			);
+		});
+	}
+
+	#[test]
+	fn swap_lease_periods_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			for i in 1u32..=2 {
+				assert_ok!(TestRegistrar::<Test>::register(
+					i as u64,
+					ParaId::from(i),
+					dummy_head_data(),
+					dummy_validation_code()
+				));
+			}
+			assert_ok!(Slots::lease_out(1.into(), &1, 3, 0, 3));
+			assert_ok!(Slots::lease_out(2.into(), &2, 5, 2, 2));
+
+			assert_noop!(
+				Slots::swap_lease_periods(1.into(), 2.into(), 0, 2),
+				Error::<Test>::LeasePeriodNotInFuture
+			);
+			assert_noop!(
+				Slots::swap_lease_periods(1.into(), 2.into(), 1, 0),
+				Error::<Test>::NoLeasePeriods
+			);
+
+			assert_ok!(Slots::swap_lease_periods(1.into(), 2.into(), 1, 2));
+			System::assert_last_event(
+				Event::<Test>::LeasePeriodsSwapped {
+					one: 1.into(),
+					other: 2.into(),
+					first_period: 1,
+					period_count: 2,
+				}
+				.into(),
+			);
+			assert_eq!(Slots::lease(ParaId::from(1_u32)), vec![Some((1, 3)), None, Some((2, 5))]);
+			assert_eq!(
+				Slots::lease(ParaId::from(2_u32)),
+				vec![None, Some((1, 3)), Some((1, 3)), Some((2, 5))]
+			);
+			// Both leasers now hold deposits for both paras.
+			assert_eq!(Balances::reserved_balance(1), 6);
+			assert_eq!(Balances::reserved_balance(2), 10);
+
+			run_to_block(40);
+			assert!(Slots::lease(ParaId::from(1_u32)).is_empty());
+			assert!(Slots::lease(ParaId::from(2_u32)).is_empty());
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::reserved_balance(2), 0);
+
+			assert_eq!(
+				TestRegistrar::<Test>::operations(),
+				vec![
+					(1.into(), 1, true),
+					(2.into(), 10, true),
+					(1.into(), 10, false),
+					(1.into(), 20, true),
+					(1.into(), 30, false),
+					(2.into(), 40, false),
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn swap_lease_periods_fails_without_deposit() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			for i in 1u32..=3 {
+				assert_ok!(TestRegistrar::<Test>::register(
+					i as u64,
+					ParaId::from(i),
+					dummy_head_data(),
+					dummy_validation_code()
+				));
+			}
+			assert_ok!(Slots::lease_out(1.into(), &1, 6, 1, 2));
+			assert_ok!(Slots::lease_out(2.into(), &2, 5, 1, 1));
+
+			// Leaser 1 would need to hold its deposit for both paras.
+			assert_noop!(
+				Slots::swap_lease_periods(1.into(), 2.into(), 2, 1),
+				Error::<Test>::LeaseError
+			);
+
+			// Lease periods can be moved to a para without any leases.
+			assert_ok!(Slots::swap_lease_periods(2.into(), 3.into(), 1, 1));
+			assert!(Slots::lease(ParaId::from(2_u32)).is_empty());
+			assert_eq!(Slots::lease(ParaId::from(3_u32)), vec![None, Some((2, 5))]);
+			assert_eq!(Balances::reserved_balance(2), 5);
+		});
+	}
+
+	#[test]
+	fn swap_lease_periods_refuses_other_leasers() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			for i in 1u32..=2 {
+				assert_ok!(TestRegistrar::<Test>::register(
+					i as u64,
+					ParaId::from(i),
+					dummy_head_data(),
+					dummy_validation_code()
+				));
+			}
+			// Leaser 3 is not a manager, like the fund account of a crowdloan.
+			assert_ok!(Slots::lease_out(1.into(), &1, 1, 1, 1));
+			assert_ok!(Slots::lease_out(1.into(), &3, 3, 2, 1));
+			assert_ok!(Slots::lease_out(2.into(), &2, 2, 1, 2));
+
+			assert_noop!(
+				Slots::swap_lease_periods(1.into(), 2.into(), 1, 2),
+				Error::<Test>::LeaserNotParaManager
+			);
+			assert_noop!(
+				Slots::swap_lease_periods(2.into(), 1.into(), 2, 1),
+				Error::<Test>::LeaserNotParaManager
+			);
+
+			// Lease periods held by the managers can still be swapped.
+			assert_ok!(Slots::swap_lease_periods(1.into(), 2.into(), 1, 1));
+			assert_eq!(Slots::lease(ParaId::from(1_u32)), vec![None, Some((2, 2)), Some((3, 3))]);
+			assert_eq!(Slots::lease(ParaId::from(2_u32)), vec![None, Some((1, 1)), Some((2, 2))]);
+		});
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1146,6 +1628,37 @@ mod benchmarking {
 			assert!(T::Registrar::is_parachain(para));
 		}
 
+		extend_lease {
+			// If there is an offset, we need to be on that block to be able to do lease things.
+			frame_system::Pallet::<T>::set_block_number(T::LeaseOffset::get() + One::one());
+			let (para, leaser) = register_a_parathread::<T>(1);
+			let now = frame_system::Pallet::<T>::block_number();
+			let (current_lease_period, _) = Slots::<T>::lease_period_index(now)
+				.expect("the first lease period has started");
+
+			let amount = T::Currency::minimum_balance();
+			assert!(Slots::<T>::lease_out(para, &leaser, amount, current_lease_period, One::one()).is_ok());
+			ExtensionPrice::<T>::put(LeaseExtensionPrice {
+				base: amount,
+				per_period: amount,
+				max_periods_ahead: 8u32.into(),
+			});
+
+			let period_count: LeasePeriodOf<T> = 4u32.into();
+			let (period_begin, deposit) = Slots::<T>::lease_extension_deposit(para, period_count)
+				.expect("lease extensions are enabled");
+		}: _(RawOrigin::Signed(leaser.clone()), para, period_count, deposit)
+		verify {
+			assert_last_event::<T>(Event::<T>::Leased {
+				para_id: para,
+				leaser,
+				period_begin,
+				period_count,
+				extra_reserved: deposit,
+				total_amount: amount + deposit,
+			}.into());
+		}
+
 		impl_benchmark_test_suite!(
 			Slots,
 			crate::integration_tests::new_test_ext(),
@@ -17,7 +17,7 @@
 //! Traits used across pallets for Polkadot.
 
 use frame_support::{
-	dispatch::DispatchResult,
+	dispatch::{DispatchError, DispatchResult},
 	traits::{Currency, ReservableCurrency},
 };
 use primitives::{HeadData, Id as ParaId, ValidationCode};
@@ -258,3 +258,36 @@ pub trait OnSwap {
 	/// such as leases, deposits held and thread/chain nature are swapped.
 	fn on_swap(one: ParaId, other: ParaId);
 }
+
+/// Something that can swap some of the future lease periods of two paras.
+pub trait SwapLeasePeriods<LeasePeriod> {
+	/// Exchange the leases `one` and `other` hold for the `period_count` lease periods starting at
+	/// `first_period`, which must all lie in the future. A lease period only one of them holds is
+	/// thus transferred to the other. Lease periods held by anyone but the managers of the two
+	/// paras can't be swapped.
+	///
+	/// Nothing is changed if an error is returned.
+	fn swap_lease_periods(
+		one: ParaId,
+		other: ParaId,
+		first_period: LeasePeriod,
+		period_count: LeasePeriod,
+	) -> DispatchResult;
+
+	/// Give the registered paras `one` and `other` the leases that are the most expensive to swap,
+	/// and return the first lease period and the number of lease periods to swap.
+	/// This is only used in benchmarking.
+	#[cfg(any(feature = "runtime-benchmarks", test))]
+	fn setup_worst_case_swap(one: ParaId, other: ParaId) -> (LeasePeriod, LeasePeriod);
+}
+
+impl<LeasePeriod: Default> SwapLeasePeriods<LeasePeriod> for () {
+	fn swap_lease_periods(_: ParaId, _: ParaId, _: LeasePeriod, _: LeasePeriod) -> DispatchResult {
+		Err(DispatchError::Other("Swapping lease periods is not supported"))
+	}
+
+	#[cfg(any(feature = "runtime-benchmarks", test))]
+	fn setup_worst_case_swap(_: ParaId, _: ParaId) -> (LeasePeriod, LeasePeriod) {
+		Default::default()
+	}
+}
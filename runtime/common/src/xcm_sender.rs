@@ -17,14 +17,16 @@
 //! XCM sender for relay chain.
 
 use frame_support::traits::Get;
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 use primitives::Id as ParaId;
 use runtime_parachains::{
 	configuration::{self, HostConfiguration},
-	dmp,
+	dmp::{self, FeeTracker},
 };
+use sp_runtime::FixedPointNumber;
 use sp_std::{marker::PhantomData, prelude::*};
 use xcm::prelude::*;
+use xcm_executor::traits::InspectMessageQueues;
 use SendError::*;
 
 /// Simple value-bearing trait for determining/expressing the assets required to be paid for a
@@ -47,6 +49,24 @@ impl<T: Get<MultiAssets>> PriceForParachainDelivery for ConstantPrice<T> {
 	}
 }
 
+/// Implementation of `PriceForParachainDelivery` which returns an exponentially increasing price.
+///
+/// The price is the sum of a base fee `B` and a fee of `M` per byte of the encoded message,
+/// multiplied by the fee factor `F` tracks for the destination para, and paid in the asset `A`:
+///
+/// `F * (B + encoded_msg_len * M)`
+pub struct ExponentialPrice<A, B, M, F>(sp_std::marker::PhantomData<(A, B, M, F)>);
+impl<A: Get<AssetId>, B: Get<u128>, M: Get<u128>, F: FeeTracker> PriceForParachainDelivery
+	for ExponentialPrice<A, B, M, F>
+{
+	fn price_for_parachain_delivery(para: ParaId, msg: &Xcm<()>) -> MultiAssets {
+		let msg_fee = (msg.encoded_size() as u128).saturating_mul(M::get());
+		let fee_sum = B::get().saturating_add(msg_fee);
+		let amount = F::get_fee_factor(para).saturating_mul_int(fee_sum);
+		(A::get(), amount).into()
+	}
+}
+
 /// XCM sender for relay chain. It only sends downward message.
 pub struct ChildParachainRouter<T, W, P>(PhantomData<(T, W, P)>);
 
@@ -88,3 +108,69 @@ impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion, P: PriceForPar
 			.map_err(|_| SendError::Transport(&"Error placing into DMP queue"))
 	}
 }
+
+impl<T: dmp::Config, W, P> InspectMessageQueues for ChildParachainRouter<T, W, P> {
+	fn clear_messages() {
+		// Best effort, as the queues are only cleared to be inspected in a dry-run.
+		let _ = dmp::DownwardMessageQueuePages::<T>::clear(u32::MAX, None);
+		let _ = dmp::DownwardMessageQueueIndices::<T>::clear(u32::MAX, None);
+	}
+
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		dmp::DownwardMessageQueueIndices::<T>::iter_keys()
+			.map(|para| {
+				let destination = MultiLocation::new(0, X1(Parachain(para.into())));
+				let messages = <dmp::Pallet<T>>::dmq_contents(para)
+					.iter()
+					.filter_map(|message| VersionedXcm::<()>::decode(&mut &message.msg[..]).ok())
+					.collect();
+				(destination.into(), messages)
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::parameter_types;
+	use sp_runtime::FixedU128;
+
+	parameter_types! {
+		pub FeeAssetId: AssetId = Concrete(Here.into());
+		pub const BaseDeliveryFee: u128 = 300_000_000;
+		pub const TransactionByteFee: u128 = 1_000_000;
+	}
+
+	struct TestFeeTracker;
+	impl FeeTracker for TestFeeTracker {
+		fn get_fee_factor(_: ParaId) -> FixedU128 {
+			FixedU128::from_rational(2, 1)
+		}
+	}
+
+	type TestExponentialPrice =
+		ExponentialPrice<FeeAssetId, BaseDeliveryFee, TransactionByteFee, TestFeeTracker>;
+
+	#[test]
+	fn exponential_price_correct_price_calculation() {
+		let id: ParaId = 123.into();
+		let b: u128 = BaseDeliveryFee::get();
+		let m: u128 = TransactionByteFee::get();
+
+		// F * (B + msg_length * M)
+		// message_length = 1
+		let result: u128 = TestFeeTracker::get_fee_factor(id).saturating_mul_int(b + m);
+		assert_eq!(
+			TestExponentialPrice::price_for_parachain_delivery(id, &Xcm(vec![])),
+			(FeeAssetId::get(), result).into()
+		);
+
+		// message size = 2
+		let result: u128 = TestFeeTracker::get_fee_factor(id).saturating_mul_int(b + (2 * m));
+		assert_eq!(
+			TestExponentialPrice::price_for_parachain_delivery(id, &Xcm(vec![ClearOrigin])),
+			(FeeAssetId::get(), result).into()
+		);
+	}
+}
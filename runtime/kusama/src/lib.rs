@@ -148,11 +148,15 @@ pub fn native_version() -> NativeVersion {
 	NativeVersion { runtime_version: VERSION, can_author_with: Default::default() }
 }
 
-/// We currently allow all calls.
+/// Calls whose weights have not been benchmarked yet are filtered out.
 pub struct BaseFilter;
 impl Contains<RuntimeCall> for BaseFilter {
-	fn contains(_c: &RuntimeCall) -> bool {
-		true
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(
+			call,
+			RuntimeCall::Slots(slots::Call::extend_lease { .. }) |
+				RuntimeCall::Registrar(paras_registrar::Call::swap_lease_periods { .. })
+		)
 	}
 }
 
@@ -162,7 +166,7 @@ parameter_types! {
 }
 
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = BlockWeights;
 	type BlockLength = BlockLength;
 	type RuntimeOrigin = RuntimeOrigin;
@@ -1139,6 +1143,8 @@ impl parachains_paras::Config for Runtime {
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
+	pub const RetryOverweightOnIdle: bool = false;
+	pub const OverweightExpiry: Option<BlockNumber> = None;
 }
 
 impl parachains_ump::Config for Runtime {
@@ -1147,6 +1153,9 @@ impl parachains_ump::Config for Runtime {
 		crate::parachains_ump::XcmSink<xcm_executor::XcmExecutor<xcm_config::XcmConfig>, Runtime>;
 	type FirstMessageFactorPercent = FirstMessageFactorPercent;
 	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+	type RetryOverweightOnIdle = RetryOverweightOnIdle;
+	type OverweightExpiry = OverweightExpiry;
+	type OnOverweightExpired = ();
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
@@ -1187,6 +1196,7 @@ impl paras_registrar::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OnSwap = (Crowdloan, Slots);
+	type LeasePeriodSwap = Slots;
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
@@ -1485,7 +1495,10 @@ impl Get<&'static str> for StakingMigrationV11OldPallet {
 /// All migrations that will run on the next runtime upgrade.
 ///
 /// Should be cleared after every release.
-pub type Migrations = ();
+pub type Migrations = (
+	parachains_dmp::migration::v1::MigrateToV1<Runtime>,
+	parachains_ump::migration::v2::MigrateToV2<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -1933,6 +1946,23 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<()>,
+		) -> Result<pallet_xcm::dry_run::XcmDryRunEffects, pallet_xcm::dry_run::XcmDryRunError> {
+			XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin, xcm)
+		}
+	}
+
+	impl pallet_xcm::trapped_assets::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(Hash, xcm::VersionedMultiAssets)> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
 	impl pallet_nomination_pools_runtime_api::NominationPoolsApi<
 		Block,
 		AccountId,
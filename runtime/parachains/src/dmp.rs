@@ -20,17 +20,51 @@ use crate::{
 };
 use frame_support::pallet_prelude::*;
 use primitives::{DownwardMessage, Hash, Id as ParaId, InboundDownwardMessage};
-use sp_runtime::traits::{BlakeTwo256, Hash as HashT, SaturatedConversion};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash as HashT, Saturating},
+	FixedU128,
+};
 use sp_std::{fmt, prelude::*};
 use xcm::latest::SendError;
 
 pub use pallet::*;
 
+pub mod migration;
+
 #[cfg(test)]
 mod tests;
 
+const LOG_TARGET: &str = "runtime::dmp";
+
 pub const MAX_MESSAGE_QUEUE_SIZE: usize = 1024;
 
+/// The maximum number of messages stored in a single page of a downward message queue.
+pub const QUEUE_PAGE_CAPACITY: u32 = 16;
+
+/// The base of the delivery fee factor, which is raised to the power of the queue length: 1.05.
+pub const EXPONENTIAL_FEE_BASE: FixedU128 = FixedU128::from_inner(1_050_000_000_000_000_000);
+
+/// The index of a page of a downward message queue.
+pub type PageIndex = u32;
+
+/// The pages a downward message queue is stored in.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueueIndices {
+	/// The index of the page holding the oldest messages.
+	pub head: PageIndex,
+	/// The index following the page holding the newest messages. The queue has no pages if it
+	/// equals `head`.
+	pub tail: PageIndex,
+	/// The number of messages in the queue.
+	pub length: u32,
+}
+
+/// Something that tracks how much more than usual it costs to deliver a message to a para.
+pub trait FeeTracker {
+	/// The factor by which the price of delivering a message to `para` is multiplied.
+	fn get_fee_factor(para: ParaId) -> FixedU128;
+}
+
 /// An error sending a downward message.
 #[cfg_attr(test, derive(Debug))]
 pub enum QueueDownwardMessageError {
@@ -76,22 +110,32 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config + configuration::Config {}
 
-	/// The downward messages addressed for a certain para.
+	/// The pages of the downward messages addressed for a certain para, oldest first.
+	///
+	/// Each page holds at most `QUEUE_PAGE_CAPACITY` messages.
 	#[pallet::storage]
-	pub(crate) type DownwardMessageQueues<T: Config> = StorageMap<
+	pub type DownwardMessageQueuePages<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
 		ParaId,
+		Twox64Concat,
+		PageIndex,
 		Vec<InboundDownwardMessage<T::BlockNumber>>,
 		ValueQuery,
 	>;
 
+	/// The head and tail pages and the length of the downward message queue of a certain para.
+	#[pallet::storage]
+	pub type DownwardMessageQueueIndices<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, QueueIndices, ValueQuery>;
+
 	/// A mapping that stores the downward message queue MQC head for each para.
 	///
 	/// Each link in this chain has a form:
@@ -135,7 +179,9 @@ impl<T: Config> Pallet<T> {
 
 	/// Remove all relevant storage items for an outgoing parachain.
 	fn clean_dmp_after_outgoing(outgoing_para: &ParaId) {
-		<Self as Store>::DownwardMessageQueues::remove(outgoing_para);
+		let _ =
+			<Self as Store>::DownwardMessageQueuePages::clear_prefix(outgoing_para, u32::MAX, None);
+		<Self as Store>::DownwardMessageQueueIndices::remove(outgoing_para);
 		<Self as Store>::DownwardMessageQueueHeads::remove(outgoing_para);
 	}
 
@@ -152,9 +198,7 @@ impl<T: Config> Pallet<T> {
 			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
 		}
 
-		if <Self as Store>::DownwardMessageQueues::decode_len(para).unwrap_or(0) >
-			MAX_MESSAGE_QUEUE_SIZE
-		{
+		if Self::dmq_length(*para) as usize > MAX_MESSAGE_QUEUE_SIZE {
 			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
 		}
 
@@ -179,9 +223,7 @@ impl<T: Config> Pallet<T> {
 			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
 		}
 
-		if <Self as Store>::DownwardMessageQueues::decode_len(para).unwrap_or(0) >
-			MAX_MESSAGE_QUEUE_SIZE
-		{
+		if Self::dmq_length(para) as usize > MAX_MESSAGE_QUEUE_SIZE {
 			return Err(QueueDownwardMessageError::ExceedsMaxMessageSize)
 		}
 
@@ -195,9 +237,22 @@ impl<T: Config> Pallet<T> {
 			*head = new_head;
 		});
 
-		<Self as Store>::DownwardMessageQueues::mutate(para, |v| {
-			v.push(inbound);
-		});
+		// append to the newest page, or start a new one if it's full.
+		let mut indices = <Self as Store>::DownwardMessageQueueIndices::get(para);
+		let newest_page = indices.tail.wrapping_sub(1);
+		let newest_page_len =
+			<Self as Store>::DownwardMessageQueuePages::decode_len(para, newest_page).unwrap_or(0);
+		let page = if indices.head != indices.tail && newest_page_len < QUEUE_PAGE_CAPACITY as usize
+		{
+			newest_page
+		} else {
+			let page = indices.tail;
+			indices.tail = indices.tail.wrapping_add(1);
+			page
+		};
+		<Self as Store>::DownwardMessageQueuePages::append(para, page, inbound);
+		indices.length.saturating_inc();
+		<Self as Store>::DownwardMessageQueueIndices::insert(para, indices);
 
 		Ok(())
 	}
@@ -223,24 +278,46 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Prunes the specified number of messages from the downward message queue of the given para.
+	///
+	/// Only the pages holding pruned messages are touched: pages that are pruned completely are
+	/// removed, and the remaining messages of a partially pruned page are kept in place.
 	pub(crate) fn prune_dmq(para: ParaId, processed_downward_messages: u32) -> Weight {
-		<Self as Store>::DownwardMessageQueues::mutate(para, |q| {
-			let processed_downward_messages = processed_downward_messages as usize;
-			if processed_downward_messages > q.len() {
-				// reaching this branch is unexpected due to the constraint established by
-				// `check_processed_downward_messages`. But better be safe than sorry.
-				q.clear();
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		let mut indices = <Self as Store>::DownwardMessageQueueIndices::get(para);
+		// pruning more messages than queued is unexpected due to the constraint established by
+		// `check_processed_downward_messages`. But better be safe than sorry.
+		let mut to_prune = processed_downward_messages;
+
+		while to_prune > 0 && indices.head != indices.tail {
+			let page_len =
+				<Self as Store>::DownwardMessageQueuePages::decode_len(para, indices.head)
+					.unwrap_or(0) as u32;
+			if page_len <= to_prune {
+				<Self as Store>::DownwardMessageQueuePages::remove(para, indices.head);
+				indices.head = indices.head.wrapping_add(1);
+				to_prune -= page_len;
+				indices.length = indices.length.saturating_sub(page_len);
 			} else {
-				*q = q.split_off(processed_downward_messages);
+				<Self as Store>::DownwardMessageQueuePages::mutate(para, indices.head, |page| {
+					page.drain(..to_prune as usize);
+				});
+				indices.length = indices.length.saturating_sub(to_prune);
+				to_prune = 0;
 			}
-		});
-		T::DbWeight::get().reads_writes(1, 1)
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+		}
+
+		if indices.head == indices.tail {
+			<Self as Store>::DownwardMessageQueueIndices::remove(para);
+		} else {
+			<Self as Store>::DownwardMessageQueueIndices::insert(para, indices);
+		}
+		weight
 	}
 
 	/// Returns the Head of Message Queue Chain for the given para or `None` if there is none
 	/// associated with it.
-	#[cfg(test)]
-	fn dmq_mqc_head(para: ParaId) -> Hash {
+	pub(crate) fn dmq_mqc_head(para: ParaId) -> Hash {
 		<Self as Store>::DownwardMessageQueueHeads::get(&para)
 	}
 
@@ -248,15 +325,28 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Returns 0 if the para doesn't have an associated downward message queue.
 	pub(crate) fn dmq_length(para: ParaId) -> u32 {
-		<Self as Store>::DownwardMessageQueues::decode_len(&para)
-			.unwrap_or(0)
-			.saturated_into::<u32>()
+		<Self as Store>::DownwardMessageQueueIndices::get(&para).length
 	}
 
 	/// Returns the downward message queue contents for the given para.
 	///
 	/// The most recent messages are the latest in the vector.
-	pub(crate) fn dmq_contents(recipient: ParaId) -> Vec<InboundDownwardMessage<T::BlockNumber>> {
-		<Self as Store>::DownwardMessageQueues::get(&recipient)
+	pub fn dmq_contents(recipient: ParaId) -> Vec<InboundDownwardMessage<T::BlockNumber>> {
+		let indices = <Self as Store>::DownwardMessageQueueIndices::get(&recipient);
+		let mut contents = Vec::with_capacity(indices.length as usize);
+		let mut page = indices.head;
+		while page != indices.tail {
+			contents.extend(<Self as Store>::DownwardMessageQueuePages::get(&recipient, page));
+			page = page.wrapping_add(1);
+		}
+		contents
+	}
+}
+
+impl<T: Config> FeeTracker for Pallet<T> {
+	/// The fee factor grows exponentially with the number of messages waiting in the queue, so
+	/// that filling up the queue of a para quickly becomes prohibitively expensive.
+	fn get_fee_factor(para: ParaId) -> FixedU128 {
+		EXPONENTIAL_FEE_BASE.saturating_pow(Self::dmq_length(para) as usize)
 	}
 }
@@ -0,0 +1,130 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::dmp::{
+	Config, DownwardMessageQueueIndices, DownwardMessageQueuePages, Pallet, QueueIndices,
+	LOG_TARGET, QUEUE_PAGE_CAPACITY,
+};
+use frame_support::{
+	pallet_prelude::*,
+	storage_alias,
+	traits::{OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use primitives::{Id as ParaId, InboundDownwardMessage};
+use sp_std::prelude::*;
+
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+pub mod v1 {
+	use super::*;
+
+	/// The downward message queues as stored before they were split into pages.
+	#[storage_alias]
+	pub(crate) type DownwardMessageQueues<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		ParaId,
+		Vec<InboundDownwardMessage<<T as frame_system::Config>::BlockNumber>>,
+		ValueQuery,
+	>;
+
+	/// Moves every downward message queue into pages of at most `QUEUE_PAGE_CAPACITY` messages.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() == 0 {
+				let mut weight = T::DbWeight::get().reads(1);
+
+				let mut queues = 0u64;
+				let mut pages = 0u64;
+				for (para, messages) in DownwardMessageQueues::<T>::drain() {
+					let length = messages.len() as u32;
+					let mut tail = 0;
+					for page in messages.chunks(QUEUE_PAGE_CAPACITY as usize) {
+						DownwardMessageQueuePages::<T>::insert(para, tail, page.to_vec());
+						tail += 1;
+					}
+					if length > 0 {
+						DownwardMessageQueueIndices::<T>::insert(
+							para,
+							QueueIndices { head: 0, tail, length },
+						);
+					}
+
+					queues += 1;
+					pages += tail as u64;
+				}
+				log::info!(
+					target: LOG_TARGET,
+					"Moved {} downward message queues into {} pages",
+					queues,
+					pages
+				);
+
+				weight
+					.saturating_accrue(T::DbWeight::get().reads_writes(queues, queues * 2 + pages));
+
+				StorageVersion::new(1).put::<Pallet<T>>();
+
+				weight.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!(target: LOG_TARGET, "skipping v1, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			let queues = DownwardMessageQueues::<T>::iter()
+				.map(|(para, messages)| {
+					(para, messages.len() as u32, Pallet::<T>::dmq_mqc_head(para))
+				})
+				.collect::<Vec<_>>();
+			log::trace!(
+				target: LOG_TARGET,
+				"Moving {} downward message queues into pages",
+				queues.len()
+			);
+			Ok(queues.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let queues = Vec::<(ParaId, u32, primitives::Hash)>::decode(&mut &state[..])
+				.map_err(|_| "Cannot decode the downward message queues")?;
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() == 1,
+				"Storage version should be `1` after the migration",
+			);
+			ensure!(
+				DownwardMessageQueues::<T>::iter_keys().next().is_none(),
+				"The downward message queues should have been moved into pages",
+			);
+			for (para, length, head) in queues {
+				ensure!(
+					Pallet::<T>::dmq_length(para) == length,
+					"Every downward message queue should keep its messages",
+				);
+				ensure!(
+					Pallet::<T>::dmq_mqc_head(para) == head,
+					"Every downward message queue should keep its MQC head",
+				);
+			}
+			Ok(())
+		}
+	}
+}
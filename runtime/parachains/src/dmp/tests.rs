@@ -15,10 +15,11 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::mock::{new_test_ext, Configuration, Dmp, MockGenesisConfig, Paras, System};
+use crate::mock::{new_test_ext, Configuration, Dmp, MockGenesisConfig, Paras, System, Test};
 use hex_literal::hex;
 use parity_scale_codec::Encode;
 use primitives::BlockNumber;
+use sp_runtime::FixedPointNumber;
 
 pub(crate) fn run_to_block(to: BlockNumber, new_session: Option<Vec<BlockNumber>>) {
 	while System::block_number() < to {
@@ -73,9 +74,11 @@ fn clean_dmp_works() {
 		let outgoing_paras = vec![a, b];
 		Dmp::initializer_on_new_session(&notification, &outgoing_paras);
 
-		assert!(<Dmp as Store>::DownwardMessageQueues::get(&a).is_empty());
-		assert!(<Dmp as Store>::DownwardMessageQueues::get(&b).is_empty());
-		assert!(!<Dmp as Store>::DownwardMessageQueues::get(&c).is_empty());
+		assert!(Dmp::dmq_contents(a).is_empty());
+		assert!(Dmp::dmq_contents(b).is_empty());
+		assert!(!Dmp::dmq_contents(c).is_empty());
+		assert_eq!(<Dmp as Store>::DownwardMessageQueuePages::iter_prefix(&a).count(), 0);
+		assert_eq!(<Dmp as Store>::DownwardMessageQueueIndices::get(&a), Default::default());
 	});
 }
 
@@ -160,6 +163,116 @@ fn dmq_pruning() {
 	});
 }
 
+#[test]
+fn dmq_is_stored_in_pages() {
+	let a = ParaId::from(1312);
+	let capacity = QUEUE_PAGE_CAPACITY;
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		for i in 0..capacity * 2 + 1 {
+			queue_downward_message(a, i.encode()).unwrap();
+		}
+
+		assert_eq!(
+			<Dmp as Store>::DownwardMessageQueueIndices::get(&a),
+			QueueIndices { head: 0, tail: 3, length: capacity * 2 + 1 },
+		);
+		assert_eq!(<Dmp as Store>::DownwardMessageQueuePages::get(&a, 0).len(), capacity as usize);
+		assert_eq!(<Dmp as Store>::DownwardMessageQueuePages::get(&a, 2).len(), 1);
+
+		let contents = Dmp::dmq_contents(a);
+		assert_eq!(contents.len(), (capacity * 2 + 1) as usize);
+		for (i, message) in contents.into_iter().enumerate() {
+			assert_eq!(message.msg, (i as u32).encode());
+		}
+	});
+}
+
+#[test]
+fn dmq_pruning_across_pages() {
+	let a = ParaId::from(1312);
+	let capacity = QUEUE_PAGE_CAPACITY;
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		for i in 0..capacity * 2 + 1 {
+			queue_downward_message(a, i.encode()).unwrap();
+		}
+
+		// prune the first page and part of the second one.
+		Dmp::prune_dmq(a, capacity + 2);
+		assert_eq!(Dmp::dmq_length(a), capacity - 1);
+		assert_eq!(
+			<Dmp as Store>::DownwardMessageQueueIndices::get(&a),
+			QueueIndices { head: 1, tail: 3, length: capacity - 1 },
+		);
+		assert!(!<Dmp as Store>::DownwardMessageQueuePages::contains_key(&a, 0));
+		assert_eq!(Dmp::dmq_contents(a)[0].msg, (capacity + 2).encode());
+
+		// new messages are appended to the newest page.
+		queue_downward_message(a, vec![1, 2, 3]).unwrap();
+		assert_eq!(<Dmp as Store>::DownwardMessageQueueIndices::get(&a).tail, 3);
+		assert_eq!(<Dmp as Store>::DownwardMessageQueuePages::get(&a, 2).len(), 2);
+
+		// pruning more than there is empties the queue.
+		Dmp::prune_dmq(a, capacity * 2);
+		assert_eq!(Dmp::dmq_length(a), 0);
+		assert!(Dmp::dmq_contents(a).is_empty());
+		assert_eq!(<Dmp as Store>::DownwardMessageQueuePages::iter_prefix(&a).count(), 0);
+		assert!(!<Dmp as Store>::DownwardMessageQueueIndices::contains_key(&a));
+	});
+}
+
+#[test]
+fn fee_factor_grows_exponentially_with_queue_length() {
+	let a = ParaId::from(1312);
+	let b = ParaId::from(228);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		assert_eq!(Dmp::get_fee_factor(a), FixedU128::saturating_from_integer(1));
+
+		queue_downward_message(a, vec![1, 2, 3]).unwrap();
+		assert_eq!(Dmp::get_fee_factor(a), EXPONENTIAL_FEE_BASE);
+
+		queue_downward_message(a, vec![4, 5, 6]).unwrap();
+		assert_eq!(Dmp::get_fee_factor(a), EXPONENTIAL_FEE_BASE * EXPONENTIAL_FEE_BASE);
+		assert_eq!(Dmp::get_fee_factor(b), FixedU128::saturating_from_integer(1));
+
+		// the fee factor drops again once the para processed its messages.
+		Dmp::prune_dmq(a, 2);
+		assert_eq!(Dmp::get_fee_factor(a), FixedU128::saturating_from_integer(1));
+	});
+}
+
+#[test]
+fn migrate_to_v1_pages_queues() {
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+	let a = ParaId::from(1312);
+	let b = ParaId::from(228);
+	let capacity = QUEUE_PAGE_CAPACITY;
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		StorageVersion::new(0).put::<Dmp>();
+		let messages = (0..capacity + 1)
+			.map(|i| InboundDownwardMessage { msg: i.encode(), sent_at: 1 })
+			.collect::<Vec<_>>();
+		migration::v1::DownwardMessageQueues::<Test>::insert(a, messages.clone());
+		migration::v1::DownwardMessageQueues::<Test>::insert(b, Vec::new());
+
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Dmp>(), 1);
+		assert_eq!(migration::v1::DownwardMessageQueues::<Test>::iter().count(), 0);
+		assert_eq!(
+			<Dmp as Store>::DownwardMessageQueueIndices::get(&a),
+			QueueIndices { head: 0, tail: 2, length: capacity + 1 },
+		);
+		assert_eq!(Dmp::dmq_contents(a), messages);
+		assert!(!<Dmp as Store>::DownwardMessageQueueIndices::contains_key(&b));
+		assert!(Dmp::dmq_contents(b).is_empty());
+	});
+}
+
 #[test]
 fn queue_downward_message_critical() {
 	let a = ParaId::from(1312);
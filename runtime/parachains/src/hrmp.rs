@@ -21,7 +21,7 @@ use crate::{
 use frame_support::{pallet_prelude::*, traits::ReservableCurrency};
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
-use polkadot_parachain::primitives::HorizontalMessages;
+use polkadot_parachain::primitives::{HorizontalMessages, IsSystem};
 use primitives::{
 	Balance, Hash, HrmpChannelId, Id as ParaId, InboundHrmpMessage, OutboundHrmpMessage,
 	SessionIndex,
@@ -277,6 +277,14 @@ pub mod pallet {
 		/// An HRMP channel was opened via Root origin.
 		/// `[sender, recipient, proposed_max_capacity, proposed_max_message_size]`
 		HrmpChannelForceOpened(ParaId, ParaId, u32, u32),
+		/// Opening of HRMP channels in both directions requested.
+		/// `[sender, recipient, proposed_max_capacity, proposed_max_message_size]`
+		BidirectionalChannelRequested(ParaId, ParaId, u32, u32),
+		/// A request to open HRMP channels in both directions was accepted. `[sender, recipient]`
+		BidirectionalChannelAccepted(ParaId, ParaId),
+		/// HRMP channels in both directions between a para and a system chain were established.
+		/// `[para, system_chain, max_capacity, max_message_size]`
+		SystemChannelEstablished(ParaId, ParaId, u32, u32),
 	}
 
 	#[pallet::error]
@@ -319,6 +327,10 @@ pub mod pallet {
 		OpenHrmpChannelAlreadyConfirmed,
 		/// The provided witness data is wrong.
 		WrongWitness,
+		/// There is no pending request to open channels in both directions from the sender.
+		BidirectionalOpenRequestDoesntExist,
+		/// The target of the channel is not a system chain.
+		ChannelCreationNotAuthorized,
 	}
 
 	/// The set of pending HRMP open channel requests.
@@ -338,6 +350,25 @@ pub mod pallet {
 	pub type HrmpOpenChannelRequestsList<T: Config> =
 		StorageValue<_, Vec<HrmpChannelId>, ValueQuery>;
 
+	/// The set of pending open channel requests for which the sender also asked to open the channel
+	/// in the opposite direction, keyed by the `sender -> recipient` channel.
+	///
+	/// Invariant:
+	/// - each key should also be present in `HrmpOpenChannelRequests`.
+	#[pallet::storage]
+	pub type HrmpBidirectionalOpenRequests<T: Config> =
+		StorageMap<_, Twox64Concat, HrmpChannelId, ()>;
+
+	/// The set of confirmed open channel requests whose recipient is a system chain that accepted
+	/// them without a deposit, via
+	/// [`establish_channel_with_system`](Pallet::establish_channel_with_system).
+	///
+	/// Invariant:
+	/// - each key should also be present in `HrmpOpenChannelRequests` as a confirmed request.
+	#[pallet::storage]
+	pub type HrmpSystemAcceptedOpenRequests<T: Config> =
+		StorageMap<_, Twox64Concat, HrmpChannelId, ()>;
+
 	/// This mapping tracks how many open channel requests are initiated by a given sender para.
 	/// Invariant: `HrmpOpenChannelRequests` should contain the same number of items that has
 	/// `(X, _)` as the number of `HrmpOpenChannelRequestCount` for `X`.
@@ -619,6 +650,137 @@ pub mod pallet {
 			));
 			Ok(())
 		}
+
+		/// Initiate opening channels in both directions between the origin parachain and the given
+		/// recipient with the given channel parameters.
+		///
+		/// The request is the same as the one made by
+		/// [`hrmp_init_open_channel`](Pallet::hrmp_init_open_channel), but the recipient can accept
+		/// it with [`hrmp_accept_open_bidirectional_channel`](
+		/// Pallet::hrmp_accept_open_bidirectional_channel), which also opens the channel in the
+		/// opposite direction with the same parameters.
+		#[pallet::call_index(8)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::hrmp_init_open_channel()
+				.saturating_add(T::DbWeight::get().writes(1))
+		)]
+		pub fn hrmp_init_open_bidirectional_channel(
+			origin: OriginFor<T>,
+			recipient: ParaId,
+			proposed_max_capacity: u32,
+			proposed_max_message_size: u32,
+		) -> DispatchResult {
+			let origin = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			Self::init_open_channel(
+				origin,
+				recipient,
+				proposed_max_capacity,
+				proposed_max_message_size,
+			)?;
+			<Self as Store>::HrmpBidirectionalOpenRequests::insert(
+				HrmpChannelId { sender: origin, recipient },
+				(),
+			);
+			Self::deposit_event(Event::BidirectionalChannelRequested(
+				origin,
+				recipient,
+				proposed_max_capacity,
+				proposed_max_message_size,
+			));
+			Ok(())
+		}
+
+		/// Accept a pending request from the given sender to open channels in both directions.
+		///
+		/// This accepts the `sender -> origin` channel, requests the `origin -> sender` channel
+		/// with the same parameters and accepts it on behalf of the sender. The deposits of both
+		/// sides are reserved within this call, so either both channels get opened or neither.
+		///
+		/// The channels will be opened only on the next session boundary.
+		#[pallet::call_index(9)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::hrmp_accept_open_channel()
+				.saturating_mul(2)
+				.saturating_add(<T as Config>::WeightInfo::hrmp_init_open_channel())
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn hrmp_accept_open_bidirectional_channel(
+			origin: OriginFor<T>,
+			sender: ParaId,
+		) -> DispatchResult {
+			let origin = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			let channel_id = HrmpChannelId { sender, recipient: origin };
+			ensure!(
+				<Self as Store>::HrmpBidirectionalOpenRequests::contains_key(&channel_id),
+				Error::<T>::BidirectionalOpenRequestDoesntExist,
+			);
+			let request = <Self as Store>::HrmpOpenChannelRequests::get(&channel_id)
+				.ok_or(Error::<T>::AcceptHrmpChannelDoesntExist)?;
+
+			Self::accept_open_channel(origin, sender)?;
+			Self::init_open_channel(
+				origin,
+				sender,
+				request.max_capacity,
+				request.max_message_size,
+			)?;
+			Self::accept_open_channel(sender, origin)?;
+			<Self as Store>::HrmpBidirectionalOpenRequests::remove(&channel_id);
+
+			Self::deposit_event(Event::BidirectionalChannelAccepted(sender, origin));
+			Ok(())
+		}
+
+		/// Establish channels in both directions between the origin parachain and a system chain.
+		///
+		/// Both channels are requested and accepted at once, using the maximum capacity and message
+		/// size allowed by the relay-chain configuration. The usual deposits are reserved from the
+		/// origin. As the system chain doesn't consent to the channels, it reserves no deposits and
+		/// the channels don't count against its channel limits.
+		///
+		/// The channels will be opened only on the next session boundary.
+		#[pallet::call_index(10)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::hrmp_init_open_channel()
+				.saturating_add(<T as Config>::WeightInfo::hrmp_accept_open_channel())
+				.saturating_mul(2)
+		)]
+		pub fn establish_channel_with_system(
+			origin: OriginFor<T>,
+			target_system_chain: ParaId,
+		) -> DispatchResult {
+			let origin = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			ensure!(target_system_chain.is_system(), Error::<T>::ChannelCreationNotAuthorized);
+
+			let config = <configuration::Pallet<T>>::config();
+			let max_capacity = config.hrmp_channel_max_capacity;
+			let max_message_size = config.hrmp_channel_max_message_size;
+
+			Self::do_init_open_channel(
+				origin,
+				target_system_chain,
+				max_capacity,
+				max_message_size,
+				false,
+			)?;
+			Self::do_accept_open_channel(target_system_chain, origin, true)?;
+			Self::do_init_open_channel(
+				target_system_chain,
+				origin,
+				max_capacity,
+				max_message_size,
+				true,
+			)?;
+			Self::do_accept_open_channel(origin, target_system_chain, false)?;
+
+			Self::deposit_event(Event::SystemChannelEstablished(
+				origin,
+				target_system_chain,
+				max_capacity,
+				max_message_size,
+			));
+			Ok(())
+		}
 	}
 }
 
@@ -669,7 +831,7 @@ impl<T: Config> Pallet<T> {
 			outgoing_paras.len() as u32
 		))
 		.saturating_add(<T as Config>::WeightInfo::force_process_hrmp_close(
-			outgoing_paras.len() as u32
+			outgoing_paras.len() as u32,
 		))
 	}
 
@@ -725,6 +887,8 @@ impl<T: Config> Pallet<T> {
 					continue
 				},
 			};
+			<Self as Store>::HrmpBidirectionalOpenRequests::remove(&req_id);
+			let recipient_deposit = Self::take_recipient_deposit(&req_id, config);
 
 			// Return the deposit of the sender, but only if it is not the para being offboarded.
 			if !outgoing.contains(&req_id.sender) {
@@ -736,14 +900,14 @@ impl<T: Config> Pallet<T> {
 
 			// If the request was confirmed, then it means it was confirmed in the finished session.
 			// Therefore, the config's hrmp_recipient_deposit represents the actual value of the
-			// deposit.
+			// deposit, unless it was waived for a system chain.
 			//
 			// We still want to refund the deposit only if the para is not being offboarded.
 			if req_data.confirmed {
 				if !outgoing.contains(&req_id.recipient) {
 					T::Currency::unreserve(
 						&req_id.recipient.into_account_truncating(),
-						config.hrmp_recipient_deposit.unique_saturated_into(),
+						recipient_deposit.unique_saturated_into(),
 					);
 				}
 				Self::decrease_accepted_channel_request_count(req_id.recipient);
@@ -799,6 +963,7 @@ impl<T: Config> Pallet<T> {
 			);
 
 			if request.confirmed {
+				let recipient_deposit = Self::take_recipient_deposit(&channel_id, config);
 				if <paras::Pallet<T>>::is_valid_para(channel_id.sender) &&
 					<paras::Pallet<T>>::is_valid_para(channel_id.recipient)
 				{
@@ -806,7 +971,7 @@ impl<T: Config> Pallet<T> {
 						&channel_id,
 						HrmpChannel {
 							sender_deposit: request.sender_deposit,
-							recipient_deposit: config.hrmp_recipient_deposit,
+							recipient_deposit,
 							max_capacity: request.max_capacity,
 							max_total_size: request.max_total_size,
 							max_message_size: request.max_message_size,
@@ -833,12 +998,26 @@ impl<T: Config> Pallet<T> {
 
 				let _ = open_req_channels.swap_remove(idx);
 				<Self as Store>::HrmpOpenChannelRequests::remove(&channel_id);
+				<Self as Store>::HrmpBidirectionalOpenRequests::remove(&channel_id);
 			}
 		}
 
 		<Self as Store>::HrmpOpenChannelRequestsList::put(open_req_channels);
 	}
 
+	/// Returns the deposit the recipient reserved when confirming the given open channel request,
+	/// and forgets whether it was waived.
+	fn take_recipient_deposit(
+		channel_id: &HrmpChannelId,
+		config: &HostConfiguration<T::BlockNumber>,
+	) -> Balance {
+		if <Self as Store>::HrmpSystemAcceptedOpenRequests::take(channel_id).is_some() {
+			0
+		} else {
+			config.hrmp_recipient_deposit
+		}
+	}
+
 	/// Iterate over all close channel requests unconditionally closing the channels.
 	fn process_hrmp_close_channel_requests() {
 		let close_reqs = <Self as Store>::HrmpCloseChannelRequestsList::take();
@@ -1134,6 +1313,25 @@ impl<T: Config> Pallet<T> {
 		proposed_max_capacity: u32,
 		proposed_max_message_size: u32,
 	) -> DispatchResult {
+		Self::do_init_open_channel(
+			origin,
+			recipient,
+			proposed_max_capacity,
+			proposed_max_message_size,
+			false,
+		)
+	}
+
+	/// Initiate opening a channel. If `system_sender` is set, the sender is a system chain which
+	/// reserves no deposit and isn't held to its limit of outbound channels.
+	fn do_init_open_channel(
+		origin: ParaId,
+		recipient: ParaId,
+		proposed_max_capacity: u32,
+		proposed_max_message_size: u32,
+		system_sender: bool,
+	) -> DispatchResult {
+		debug_assert!(!system_sender || origin.is_system());
 		ensure!(origin != recipient, Error::<T>::OpenHrmpChannelToSelf);
 		ensure!(
 			<paras::Pallet<T>>::is_valid_para(recipient),
@@ -1171,13 +1369,14 @@ impl<T: Config> Pallet<T> {
 			config.hrmp_max_parachain_outbound_channels
 		};
 		ensure!(
-			egress_cnt + open_req_cnt < channel_num_limit,
+			system_sender || egress_cnt + open_req_cnt < channel_num_limit,
 			Error::<T>::OpenHrmpChannelLimitExceeded,
 		);
 
+		let sender_deposit = if system_sender { 0 } else { config.hrmp_sender_deposit };
 		T::Currency::reserve(
 			&origin.into_account_truncating(),
-			config.hrmp_sender_deposit.unique_saturated_into(),
+			sender_deposit.unique_saturated_into(),
 		)?;
 
 		// mutating storage directly now -- shall not bail henceforth.
@@ -1188,7 +1387,7 @@ impl<T: Config> Pallet<T> {
 			HrmpOpenChannelRequest {
 				confirmed: false,
 				_age: 0,
-				sender_deposit: config.hrmp_sender_deposit,
+				sender_deposit,
 				max_capacity: proposed_max_capacity,
 				max_message_size: proposed_max_message_size,
 				max_total_size: config.hrmp_channel_max_total_size,
@@ -1227,6 +1426,17 @@ impl<T: Config> Pallet<T> {
 	/// Basically the same as [`hrmp_accept_open_channel`](Pallet::hrmp_accept_open_channel) but
 	/// intended for calling directly from other pallets rather than dispatched.
 	pub fn accept_open_channel(origin: ParaId, sender: ParaId) -> DispatchResult {
+		Self::do_accept_open_channel(origin, sender, false)
+	}
+
+	/// Accept a pending open channel request. If `system_recipient` is set, the recipient is a
+	/// system chain which reserves no deposit and isn't held to its limit of inbound channels.
+	fn do_accept_open_channel(
+		origin: ParaId,
+		sender: ParaId,
+		system_recipient: bool,
+	) -> DispatchResult {
+		debug_assert!(!system_recipient || origin.is_system());
 		let channel_id = HrmpChannelId { sender, recipient: origin };
 		let mut channel_req = <Self as Store>::HrmpOpenChannelRequests::get(&channel_id)
 			.ok_or(Error::<T>::AcceptHrmpChannelDoesntExist)?;
@@ -1244,14 +1454,18 @@ impl<T: Config> Pallet<T> {
 			<Self as Store>::HrmpIngressChannelsIndex::decode_len(&origin).unwrap_or(0) as u32;
 		let accepted_cnt = <Self as Store>::HrmpAcceptedChannelRequestCount::get(&origin);
 		ensure!(
-			ingress_cnt + accepted_cnt < channel_num_limit,
+			system_recipient || ingress_cnt + accepted_cnt < channel_num_limit,
 			Error::<T>::AcceptHrmpChannelLimitExceeded,
 		);
 
-		T::Currency::reserve(
-			&origin.into_account_truncating(),
-			config.hrmp_recipient_deposit.unique_saturated_into(),
-		)?;
+		if system_recipient {
+			<Self as Store>::HrmpSystemAcceptedOpenRequests::insert(&channel_id, ());
+		} else {
+			T::Currency::reserve(
+				&origin.into_account_truncating(),
+				config.hrmp_recipient_deposit.unique_saturated_into(),
+			)?;
+		}
 
 		// persist the updated open channel request and then increment the number of accepted
 		// channels.
@@ -1290,6 +1504,7 @@ impl<T: Config> Pallet<T> {
 
 		// Remove the request by the channel id and sync the accompanying list with the set.
 		<Self as Store>::HrmpOpenChannelRequests::remove(&channel_id);
+		<Self as Store>::HrmpBidirectionalOpenRequests::remove(&channel_id);
 		<Self as Store>::HrmpOpenChannelRequestsList::mutate(|open_req_channels| {
 			if let Some(pos) = open_req_channels.iter().position(|x| x == &channel_id) {
 				open_req_channels.swap_remove(pos);
@@ -1446,6 +1661,22 @@ impl<T: Config> Pallet<T> {
 				.collect::<BTreeSet<_>>(),
 		);
 
+		for (channel_id, ()) in <Self as Store>::HrmpBidirectionalOpenRequests::iter() {
+			assert!(
+				<Self as Store>::HrmpOpenChannelRequests::contains_key(&channel_id),
+				"bidirectional open request {:?} has no corresponding open request",
+				channel_id,
+			);
+		}
+		for (channel_id, ()) in <Self as Store>::HrmpSystemAcceptedOpenRequests::iter() {
+			assert!(
+				<Self as Store>::HrmpOpenChannelRequests::get(&channel_id)
+					.map_or(false, |request| request.confirmed),
+				"system accepted open request {:?} has no corresponding confirmed open request",
+				channel_id,
+			);
+		}
+
 		// verify that the set of keys in `HrmpOpenChannelRequestCount` corresponds to the set
 		// of _senders_ in `HrmpOpenChannelRequests`.
 		//
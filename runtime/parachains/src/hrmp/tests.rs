@@ -657,3 +657,180 @@ fn cancel_pending_open_channel_request() {
 		Hrmp::assert_storage_consistency_exhaustive();
 	});
 }
+
+#[test]
+fn open_bidirectional_channel_works() {
+	let para_a = 2000.into();
+	let para_a_origin: crate::Origin = 2000.into();
+	let para_b = 2001.into();
+	let para_b_origin: crate::Origin = 2001.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+		run_to_block(5, Some(vec![4, 5]));
+
+		Hrmp::hrmp_init_open_bidirectional_channel(para_a_origin.into(), para_b, 2, 8).unwrap();
+		Hrmp::assert_storage_consistency_exhaustive();
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::BidirectionalChannelRequested(para_a, para_b, 2, 8))));
+
+		Hrmp::hrmp_accept_open_bidirectional_channel(para_b_origin.into(), para_a).unwrap();
+		Hrmp::assert_storage_consistency_exhaustive();
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::BidirectionalChannelAccepted(para_a, para_b))));
+		assert!(<Hrmp as Store>::HrmpBidirectionalOpenRequests::iter().next().is_none());
+
+		// Both sides have paid both the sender and the recipient deposits.
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()),
+			800
+		);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()),
+			800
+		);
+
+		// Both channels get opened on the session change.
+		run_to_block(8, Some(vec![8]));
+		assert!(channel_exists(para_a, para_b));
+		assert!(channel_exists(para_b, para_a));
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn accept_bidirectional_channel_requires_bidirectional_request() {
+	let para_a = 2000.into();
+	let para_b = 2001.into();
+	let para_b_origin: crate::Origin = 2001.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+		run_to_block(5, Some(vec![4, 5]));
+
+		// A plain open request can't be accepted in both directions.
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		assert_noop!(
+			Hrmp::hrmp_accept_open_bidirectional_channel(para_b_origin.into(), para_a),
+			Error::<Test>::BidirectionalOpenRequestDoesntExist
+		);
+	});
+}
+
+#[test]
+fn accept_bidirectional_channel_is_atomic() {
+	let para_a = 2000.into();
+	let para_a_origin: crate::Origin = 2000.into();
+	let para_b = 2001.into();
+	let para_b_origin: crate::Origin = 2001.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		// `para_b` can afford the recipient deposit but not the sender deposit on top of it.
+		register_parachain(para_a);
+		register_parachain_with_balance(para_b, 150);
+		run_to_block(5, Some(vec![4, 5]));
+
+		Hrmp::hrmp_init_open_bidirectional_channel(para_a_origin.into(), para_b, 2, 8).unwrap();
+		assert_noop!(
+			Hrmp::hrmp_accept_open_bidirectional_channel(para_b_origin.into(), para_a),
+			pallet_balances::Error::<Test, _>::InsufficientBalance
+		);
+
+		// Canceling the request also drops the bidirectional marker.
+		Hrmp::cancel_open_request(para_b, HrmpChannelId { sender: para_a, recipient: para_b })
+			.unwrap();
+		assert!(<Hrmp as Store>::HrmpBidirectionalOpenRequests::iter().next().is_none());
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()),
+			1000
+		);
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn establish_channel_with_system_works() {
+	let para_a = 2000.into();
+	let para_a_origin: crate::Origin = 2000.into();
+	let para_b = 2001.into();
+	let system_para = 1000.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+		register_parachain(system_para);
+		run_to_block(5, Some(vec![4, 5]));
+
+		assert_noop!(
+			Hrmp::establish_channel_with_system(para_a_origin.clone().into(), para_b),
+			Error::<Test>::ChannelCreationNotAuthorized
+		);
+
+		Hrmp::establish_channel_with_system(para_a_origin.into(), system_para).unwrap();
+		Hrmp::assert_storage_consistency_exhaustive();
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::SystemChannelEstablished(para_a, system_para, 2, 8))));
+
+		// Only the para has paid the sender and the recipient deposits.
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()),
+			800
+		);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&system_para.into_account_truncating()),
+			1000
+		);
+
+		run_to_block(8, Some(vec![8]));
+		assert!(channel_exists(para_a, system_para));
+		assert!(channel_exists(system_para, para_a));
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		// Closing the channels refunds the deposits of the para only.
+		let to_system = HrmpChannelId { sender: para_a, recipient: system_para };
+		let from_system = HrmpChannelId { sender: system_para, recipient: para_a };
+		Hrmp::close_channel(para_a, to_system).unwrap();
+		Hrmp::close_channel(para_a, from_system).unwrap();
+		run_to_block(10, Some(vec![10]));
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()),
+			1000
+		);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&system_para.into_account_truncating()),
+			1000
+		);
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn establish_channel_with_system_ignores_system_channel_limits() {
+	let paras: Vec<ParaId> = vec![2000.into(), 2001.into(), 2002.into()];
+	let system_para = 1000.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		for para in &paras {
+			register_parachain(*para);
+		}
+		register_parachain_with_balance(system_para, 0);
+		run_to_block(5, Some(vec![4, 5]));
+
+		// The system chain can neither afford the deposits nor accept more than two channels in
+		// each direction, yet every para gets its channels.
+		for para in &paras {
+			let origin: crate::Origin = (*para).into();
+			Hrmp::establish_channel_with_system(origin.into(), system_para).unwrap();
+		}
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		run_to_block(8, Some(vec![8]));
+		for para in &paras {
+			assert!(channel_exists(*para, system_para));
+			assert!(channel_exists(system_para, *para));
+		}
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
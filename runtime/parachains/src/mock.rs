@@ -17,8 +17,8 @@
 //! Mocks for all the traits.
 
 use crate::{
-	configuration, disputes, dmp, hrmp, inclusion, initializer, origin, paras, paras_inherent,
-	scheduler, session_info, shared,
+	configuration, disputes, dmp, hrmp, inclusion, initializer, on_demand, origin, paras,
+	paras_inherent, scheduler, session_info, shared,
 	ump::{self, MessageId, UmpSink},
 	ParaId,
 };
@@ -60,6 +60,7 @@ frame_support::construct_runtime!(
 		ParaInclusion: inclusion,
 		ParaInherent: paras_inherent,
 		Scheduler: scheduler,
+		OnDemand: on_demand,
 		Initializer: initializer,
 		Dmp: dmp,
 		Ump: ump,
@@ -225,6 +226,9 @@ impl crate::dmp::Config for Test {}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
+	pub static RetryOverweightOnIdle: bool = false;
+	pub static OverweightExpiry: Option<BlockNumber> = None;
+	pub static OverweightExpiredWeight: Weight = Weight::zero();
 }
 
 impl crate::ump::Config for Test {
@@ -232,6 +236,9 @@ impl crate::ump::Config for Test {
 	type UmpSink = TestUmpSink;
 	type FirstMessageFactorPercent = FirstMessageFactorPercent;
 	type ExecuteOverweightOrigin = frame_system::EnsureRoot<AccountId>;
+	type RetryOverweightOnIdle = RetryOverweightOnIdle;
+	type OverweightExpiry = OverweightExpiry;
+	type OnOverweightExpired = TestOnOverweightExpired;
 	type WeightInfo = crate::ump::TestWeightInfo;
 }
 
@@ -298,6 +305,20 @@ impl crate::disputes::SlashingHandler<BlockNumber> for Test {
 
 impl crate::scheduler::Config for Test {}
 
+parameter_types! {
+	pub const OnDemandBaseFee: Balance = 100;
+	pub const OnDemandFeeMultiplier: u32 = 3;
+}
+
+impl crate::on_demand::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = pallet_balances::Pallet<Test>;
+	type BaseFee = OnDemandBaseFee;
+	type FeeMultiplier = OnDemandFeeMultiplier;
+	type OnOrderFee = ();
+	type WeightInfo = crate::on_demand::TestWeightInfo;
+}
+
 impl crate::inclusion::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type DisputesHandler = Disputes;
@@ -414,6 +435,34 @@ impl UmpSink for TestUmpSink {
 	}
 }
 
+std::thread_local! {
+	static EXPIRED: RefCell<Vec<(ParaId, UpwardMessage)>> = RefCell::new(vec![]);
+}
+
+/// Return which overweight messages have expired and clear the buffer.
+pub fn take_expired() -> Vec<(ParaId, UpwardMessage)> {
+	EXPIRED.with(|expired| std::mem::take(&mut *expired.borrow_mut()))
+}
+
+/// Records the overweight messages which expired, each requiring `OverweightExpiredWeight`.
+pub struct TestOnOverweightExpired;
+impl ump::OnOverweightExpired for TestOnOverweightExpired {
+	fn on_overweight_expired(
+		origin: ParaId,
+		_: ump::OverweightIndex,
+		msg: Vec<u8>,
+		max_weight: Weight,
+	) -> Result<Weight, Weight> {
+		let required = OverweightExpiredWeight::get();
+		if required.any_gt(max_weight) {
+			return Err(required)
+		}
+
+		EXPIRED.with(|expired| expired.borrow_mut().push((origin, msg)));
+		Ok(required)
+	}
+}
+
 pub struct TestRewardValidators;
 
 impl inclusion::RewardValidators for TestRewardValidators {
@@ -0,0 +1,174 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The on-demand module for parathreads.
+//!
+//! Parathreads do not hold a slot lease and therefore are not assigned a core of their own.
+//! Instead, any account may pay to place a claim for the next block of a parathread. Claims are
+//! handed over to the scheduler's parathread claim queue and are assigned to one of the
+//! parathread cores from there.
+//!
+//! The price of an order depends on how full the claim queue is: an order placed into an empty
+//! queue costs `BaseFee`, and the price rises quadratically with the fill level, approaching
+//! `BaseFee * (1 + FeeMultiplier)` as the queue fills up. Callers provide the maximum amount they
+//! are willing to pay, so that an order never gets more expensive than expected.
+
+use crate::{configuration, paras, scheduler};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, OnUnbalanced, WithdrawReasons},
+};
+use frame_system::pallet_prelude::*;
+use primitives::{CollatorId, Id as ParaId, ParathreadClaim};
+use sp_runtime::{traits::Saturating, Perbill};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+pub trait WeightInfo {
+	fn place_order() -> Weight;
+}
+
+/// A weight info that is only suitable for testing.
+pub struct TestWeightInfo;
+
+impl WeightInfo for TestWeightInfo {
+	fn place_order() -> Weight {
+		Weight::MAX
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + configuration::Config + paras::Config + scheduler::Config
+	{
+		/// The outer event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency that orders are paid with.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The price of an order placed into an empty claim queue.
+		#[pallet::constant]
+		type BaseFee: Get<BalanceOf<Self>>;
+
+		/// How many times `BaseFee` is added on top of `BaseFee` for an order placed into a
+		/// completely full claim queue, scaled down by the square of the queue fill level.
+		#[pallet::constant]
+		type FeeMultiplier: Get<u32>;
+
+		/// What to do with the fees paid for orders.
+		type OnOrderFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Something that provides the weight of this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An order for the next block of a parathread was placed and handed to the scheduler.
+		OrderPlaced { para_id: ParaId, who: T::AccountId, fee: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The parathread claim queue is full.
+		QueueFull,
+		/// The current price of an order exceeds the maximum amount the caller is willing to pay.
+		FeeExceedsMaxAmount,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Place an order for the next block of the parathread `para_id`, to be produced by
+		/// `collator`.
+		///
+		/// Fails if the current price of an order, as given by [`Pallet::order_fee`], exceeds
+		/// `max_amount`. The claim is added to the scheduler's parathread claim queue, which fails
+		/// if the para is not a parathread or already has a claim queued, and only then is the
+		/// price withdrawn from the caller.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			para_id: ParaId,
+			collator: CollatorId,
+			max_amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let fee = Self::order_fee().ok_or(Error::<T>::QueueFull)?;
+			ensure!(fee <= max_amount, Error::<T>::FeeExceedsMaxAmount);
+
+			<scheduler::Pallet<T>>::add_parathread_claim(ParathreadClaim(para_id, collator))?;
+
+			let imbalance = T::Currency::withdraw(
+				&who,
+				fee,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			T::OnOrderFee::on_unbalanced(imbalance);
+
+			Self::deposit_event(Event::OrderPlaced { para_id, who, fee });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The price of an order placed right now.
+	///
+	/// Returns `None` if the parathread claim queue is full, including the case where there are
+	/// no parathread cores at all.
+	pub fn order_fee() -> Option<BalanceOf<T>> {
+		let (queued, capacity) = <scheduler::Pallet<T>>::parathread_queue_fill();
+		if queued >= capacity {
+			return None
+		}
+
+		Some(Self::fee_for_fill(queued, capacity))
+	}
+
+	/// The price of an order given `queued` claims in a queue of size `capacity`.
+	pub(crate) fn fee_for_fill(queued: u32, capacity: u32) -> BalanceOf<T> {
+		let base = T::BaseFee::get();
+		let fullness = Perbill::from_rational(queued, capacity);
+		let surcharge = base.saturating_mul(T::FeeMultiplier::get().into());
+
+		base.saturating_add(fullness.square() * surcharge)
+	}
+}
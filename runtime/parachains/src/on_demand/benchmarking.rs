@@ -0,0 +1,78 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	configuration::ActiveConfig,
+	on_demand::{Pallet as OnDemand, *},
+	paras::{Pallet as Paras, ParaGenesisArgs, ParaKind, ParachainsCache},
+	scheduler::Pallet as Scheduler,
+};
+use frame_benchmarking::whitelisted_caller;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, TrailingZeroInput};
+
+fn register_parathread<T: Config>(id: ParaId) {
+	let mut parachains = ParachainsCache::new();
+	Paras::<T>::initialize_para_now(
+		&mut parachains,
+		id,
+		&ParaGenesisArgs {
+			para_kind: ParaKind::Parathread,
+			genesis_head: vec![1].into(),
+			validation_code: vec![1].into(),
+		},
+	);
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	// compare to the last event record
+	let frame_system::EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+frame_benchmarking::benchmarks! {
+	place_order {
+		// make room for two claims, one of which is already taken, so that the order pays the
+		// surcharge.
+		ActiveConfig::<T>::mutate(|config| {
+			config.parathread_cores = 1;
+			config.scheduling_lookahead = 2;
+		});
+
+		let collator = CollatorId::decode(&mut TrailingZeroInput::zeroes()).unwrap();
+		let other: ParaId = 1u32.into();
+		register_parathread::<T>(other);
+		Scheduler::<T>::add_parathread_claim(ParathreadClaim(other, collator.clone()))?;
+
+		let para_id: ParaId = 2u32.into();
+		register_parathread::<T>(para_id);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let fee = OnDemand::<T>::order_fee().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), para_id, collator, fee)
+	verify {
+		assert_last_event::<T>(Event::<T>::OrderPlaced { para_id, who: caller, fee }.into());
+	}
+}
+
+frame_benchmarking::impl_benchmark_test_suite!(
+	OnDemand,
+	crate::mock::new_test_ext(Default::default()),
+	crate::mock::Test
+);
@@ -0,0 +1,157 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::{
+	configuration::HostConfiguration,
+	mock::{
+		assert_last_event, new_test_ext, Balances, MockGenesisConfig, OnDemand,
+		RuntimeEvent as MockEvent, RuntimeOrigin, Scheduler, System, Test,
+	},
+	paras::{ParaGenesisArgs, ParaKind},
+};
+use frame_support::{assert_noop, assert_ok, traits::Currency as _};
+use keyring::Sr25519Keyring;
+use primitives::{BlockNumber, CollatorId};
+
+const THREAD_A: ParaId = ParaId::new(100);
+const THREAD_B: ParaId = ParaId::new(101);
+const THREAD_C: ParaId = ParaId::new(102);
+const CHAIN: ParaId = ParaId::new(200);
+
+fn default_config() -> HostConfiguration<BlockNumber> {
+	// A claim queue with room for two claims.
+	HostConfiguration { parathread_cores: 2, scheduling_lookahead: 1, ..Default::default() }
+}
+
+fn genesis_config(config: HostConfiguration<BlockNumber>) -> MockGenesisConfig {
+	let para = |id, para_kind| {
+		(
+			id,
+			ParaGenesisArgs {
+				genesis_head: Vec::new().into(),
+				validation_code: vec![1, 2, 3].into(),
+				para_kind,
+			},
+		)
+	};
+
+	MockGenesisConfig {
+		paras: crate::paras::GenesisConfig {
+			paras: vec![
+				para(THREAD_A, ParaKind::Parathread),
+				para(THREAD_B, ParaKind::Parathread),
+				para(THREAD_C, ParaKind::Parathread),
+				para(CHAIN, ParaKind::Parachain),
+			],
+		},
+		configuration: crate::configuration::GenesisConfig { config, ..Default::default() },
+		..Default::default()
+	}
+}
+
+fn collator() -> CollatorId {
+	CollatorId::from(Sr25519Keyring::Alice.public())
+}
+
+#[test]
+fn order_fee_rises_with_queue_fill() {
+	new_test_ext(Default::default()).execute_with(|| {
+		// Base fee is 100 and the multiplier is 3.
+		assert_eq!(OnDemand::fee_for_fill(0, 2), 100);
+		assert_eq!(OnDemand::fee_for_fill(1, 2), 175);
+		assert_eq!(OnDemand::fee_for_fill(2, 2), 400);
+	});
+}
+
+#[test]
+fn order_fee_is_none_without_parathread_cores() {
+	let config = HostConfiguration { parathread_cores: 0, ..default_config() };
+	new_test_ext(genesis_config(config)).execute_with(|| {
+		assert_eq!(OnDemand::order_fee(), None);
+
+		Balances::make_free_balance_be(&1, 1_000);
+		assert_noop!(
+			OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_A, collator(), 1_000),
+			Error::<Test>::QueueFull,
+		);
+	});
+}
+
+#[test]
+fn place_order_works() {
+	new_test_ext(genesis_config(default_config())).execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&1, 1_000);
+
+		assert_eq!(OnDemand::order_fee(), Some(100));
+		assert_ok!(OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_A, collator(), 100));
+		assert_last_event(MockEvent::OnDemand(Event::OrderPlaced {
+			para_id: THREAD_A,
+			who: 1,
+			fee: 100,
+		}));
+
+		assert_eq!(Balances::free_balance(&1), 900);
+		assert!(Scheduler::has_parathread_claim(THREAD_A));
+		assert_eq!(Scheduler::parathread_queue_fill(), (1, 2));
+
+		// The second order pays more as the queue is half full.
+		assert_eq!(OnDemand::order_fee(), Some(175));
+		assert_ok!(OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_B, collator(), 200));
+		assert_eq!(Balances::free_balance(&1), 725);
+		assert!(Scheduler::has_parathread_claim(THREAD_B));
+		assert_eq!(Scheduler::parathread_queue_fill(), (2, 2));
+		assert_eq!(OnDemand::order_fee(), None);
+	});
+}
+
+#[test]
+fn place_order_rejects_invalid_orders() {
+	new_test_ext(genesis_config(default_config())).execute_with(|| {
+		Balances::make_free_balance_be(&1, 1_000);
+		Balances::make_free_balance_be(&2, 50);
+
+		assert_noop!(
+			OnDemand::place_order(RuntimeOrigin::signed(1), CHAIN, collator(), 1_000),
+			scheduler::Error::<Test>::NotParathread,
+		);
+		assert_noop!(
+			OnDemand::place_order(RuntimeOrigin::signed(1), ParaId::new(300), collator(), 1_000),
+			scheduler::Error::<Test>::NotParathread,
+		);
+		assert_noop!(
+			OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_A, collator(), 99),
+			Error::<Test>::FeeExceedsMaxAmount,
+		);
+		assert_noop!(
+			OnDemand::place_order(RuntimeOrigin::signed(2), THREAD_A, collator(), 1_000),
+			pallet_balances::Error::<Test, _>::InsufficientBalance,
+		);
+
+		assert_ok!(OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_A, collator(), 1_000));
+		assert_noop!(
+			OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_A, collator(), 1_000),
+			scheduler::Error::<Test>::ClaimAlreadyQueued,
+		);
+
+		assert_ok!(OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_B, collator(), 1_000));
+		assert_noop!(
+			OnDemand::place_order(RuntimeOrigin::signed(1), THREAD_C, collator(), 1_000),
+			Error::<Test>::QueueFull,
+		);
+	});
+}
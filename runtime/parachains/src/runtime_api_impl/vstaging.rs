@@ -16,8 +16,11 @@
 
 //! Put implementations of functions from staging APIs here.
 
-use crate::{disputes, session_info};
-use primitives::{vstaging::ExecutorParams, CandidateHash, DisputeState, SessionIndex};
+use crate::{disputes, dmp, session_info, ump};
+use primitives::{
+	vstaging::{DownwardQueueStatus, ExecutorParams, MessageQueuesStatus},
+	CandidateHash, DisputeState, Id as ParaId, SessionIndex,
+};
 use sp_std::prelude::*;
 
 /// Implementation for `get_session_disputes` function from the runtime API
@@ -40,3 +43,15 @@ pub fn session_executor_params<T: session_info::Config>(
 		None => Some(ExecutorParams::default()),
 	}
 }
+
+/// Implementation for the `message_queues_status` function of the runtime API.
+pub fn message_queues_status<T: ump::Config + dmp::Config>(para_id: ParaId) -> MessageQueuesStatus {
+	MessageQueuesStatus {
+		upward: <ump::Pallet<T>>::upward_queue_status(para_id),
+		downward: DownwardQueueStatus {
+			length: <dmp::Pallet<T>>::dmq_length(para_id),
+			mqc_head: <dmp::Pallet<T>>::dmq_mqc_head(para_id),
+		},
+		overweight: <ump::Pallet<T>>::overweight_messages(para_id),
+	}
+}
@@ -165,6 +165,16 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config + configuration::Config + paras::Config {}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The claim does not correspond to any live parathread.
+		NotParathread,
+		/// The parathread already has a claim queued or assigned to a core.
+		ClaimAlreadyQueued,
+		/// The parathread claim queue is full.
+		QueueFull,
+	}
+
 	/// All the validator groups. One for each core. Indices are into `ActiveValidators` - not the
 	/// broader set of Polkadot validators, but instead just the subset used for parachains during
 	/// this session.
@@ -346,40 +356,45 @@ impl<T: Config> Pallet<T> {
 	/// assigned to a core, this call will fail. This call will also fail if the queue is full.
 	///
 	/// Fails if the claim does not correspond to any live parathread.
-	#[allow(unused)]
-	pub fn add_parathread_claim(claim: ParathreadClaim) {
-		if !<paras::Pallet<T>>::is_parathread(claim.0) {
-			return
-		}
+	pub fn add_parathread_claim(claim: ParathreadClaim) -> DispatchResult {
+		ensure!(<paras::Pallet<T>>::is_parathread(claim.0), Error::<T>::NotParathread);
 
 		let config = <configuration::Pallet<T>>::config();
 		let queue_max_size = config.parathread_cores * config.scheduling_lookahead;
 
-		ParathreadQueue::<T>::mutate(|queue| {
-			if queue.queue.len() >= queue_max_size as usize {
-				return
-			}
+		ParathreadQueue::<T>::try_mutate(|queue| -> DispatchResult {
+			ensure!(queue.queue.len() < queue_max_size as usize, Error::<T>::QueueFull);
 
 			let para_id = claim.0;
 
-			let competes_with_another =
-				ParathreadClaimIndex::<T>::mutate(|index| match index.binary_search(&para_id) {
-					Ok(_) => true,
-					Err(i) => {
-						index.insert(i, para_id);
-						false
-					},
-				});
-
-			if competes_with_another {
-				return
-			}
+			ParathreadClaimIndex::<T>::try_mutate(|index| match index.binary_search(&para_id) {
+				Ok(_) => Err(Error::<T>::ClaimAlreadyQueued),
+				Err(i) => {
+					index.insert(i, para_id);
+					Ok(())
+				},
+			})?;
 
 			let entry = ParathreadEntry { claim, retries: 0 };
 			queue.enqueue_entry(entry, config.parathread_cores);
+			Ok(())
 		})
 	}
 
+	/// Returns the number of parathread claims currently in the queue along with the maximum
+	/// number of claims the queue can hold under the active configuration.
+	pub fn parathread_queue_fill() -> (u32, u32) {
+		let config = <configuration::Pallet<T>>::config();
+		let queue_max_size = config.parathread_cores * config.scheduling_lookahead;
+
+		(ParathreadQueue::<T>::get().queue.len() as u32, queue_max_size)
+	}
+
+	/// Whether the given parathread already has a claim either in the queue or assigned to a core.
+	pub fn has_parathread_claim(para_id: ParaId) -> bool {
+		ParathreadClaimIndex::<T>::get().binary_search(&para_id).is_ok()
+	}
+
 	/// Free unassigned cores. Provide a list of cores that should be considered newly-freed along with the reason
 	/// for them being freed. The list is assumed to be sorted in ascending order by core index.
 	pub(crate) fn free_cores(just_freed_cores: impl IntoIterator<Item = (CoreIndex, FreedReason)>) {
@@ -16,7 +16,7 @@
 
 use super::*;
 
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
 use keyring::Sr25519Keyring;
 use primitives::{BlockNumber, CollatorId, SessionIndex, ValidatorId};
 
@@ -131,7 +131,10 @@ fn add_parathread_claim_works() {
 		assert!(Paras::is_parathread(thread_id));
 
 		{
-			Scheduler::add_parathread_claim(ParathreadClaim(thread_id, collator.clone()));
+			assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(
+				thread_id,
+				collator.clone()
+			)));
 			let queue = ParathreadQueue::<Test>::get();
 			assert_eq!(queue.next_core_offset, 1);
 			assert_eq!(queue.queue.len(), 1);
@@ -150,7 +153,10 @@ fn add_parathread_claim_works() {
 		// due to the index, completing claims are not allowed.
 		{
 			let collator2 = CollatorId::from(Sr25519Keyring::Bob.public());
-			Scheduler::add_parathread_claim(ParathreadClaim(thread_id, collator2.clone()));
+			assert_noop!(
+				Scheduler::add_parathread_claim(ParathreadClaim(thread_id, collator2.clone())),
+				Error::<Test>::ClaimAlreadyQueued,
+			);
 			let queue = ParathreadQueue::<Test>::get();
 			assert_eq!(queue.next_core_offset, 1);
 			assert_eq!(queue.queue.len(), 1);
@@ -169,7 +175,10 @@ fn add_parathread_claim_works() {
 		// claims on non-live parathreads have no effect.
 		{
 			let thread_id2 = ParaId::from(11);
-			Scheduler::add_parathread_claim(ParathreadClaim(thread_id2, collator.clone()));
+			assert_noop!(
+				Scheduler::add_parathread_claim(ParathreadClaim(thread_id2, collator.clone())),
+				Error::<Test>::NotParathread,
+			);
 			let queue = ParathreadQueue::<Test>::get();
 			assert_eq!(queue.next_core_offset, 1);
 			assert_eq!(queue.queue.len(), 1);
@@ -211,7 +220,10 @@ fn cannot_add_claim_when_no_parathread_cores() {
 
 		assert!(Paras::is_parathread(thread_id));
 
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_id, collator.clone()));
+		assert_noop!(
+			Scheduler::add_parathread_claim(ParathreadClaim(thread_id, collator.clone())),
+			Error::<Test>::QueueFull,
+		);
 		assert_eq!(ParathreadQueue::<Test>::get(), Default::default());
 	});
 }
@@ -497,8 +509,8 @@ fn schedule_schedules() {
 		}
 
 		// add a couple of parathread claims.
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone()));
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_c, collator.clone()));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone())));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_c, collator.clone())));
 
 		run_to_block(2, |_| None);
 
@@ -601,8 +613,8 @@ fn schedule_schedules_including_just_freed() {
 		});
 
 		// add a couple of parathread claims now that the parathreads are live.
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone()));
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_c, collator.clone()));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone())));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_c, collator.clone())));
 
 		run_to_block(2, |_| None);
 
@@ -626,9 +638,9 @@ fn schedule_schedules_including_just_freed() {
 		// add a couple more parathread claims - the claim on `b` will go to the 3rd parathread core (4)
 		// and the claim on `d` will go back to the 1st parathread core (2). The claim on `e` then
 		// will go for core `3`.
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone()));
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_d, collator.clone()));
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_e, collator.clone()));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone())));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_d, collator.clone())));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_e, collator.clone())));
 
 		run_to_block(3, |_| None);
 
@@ -868,8 +880,8 @@ fn schedule_rotates_groups() {
 		let session_start_block = <Scheduler as Store>::SessionStartBlock::get();
 		assert_eq!(session_start_block, 1);
 
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone()));
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone()));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone())));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone())));
 
 		run_to_block(2, |_| None);
 
@@ -937,8 +949,8 @@ fn parathread_claims_are_pruned_after_retries() {
 			_ => None,
 		});
 
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone()));
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone()));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone())));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone())));
 
 		run_to_block(2, |_| None);
 		assert_eq!(Scheduler::scheduled().len(), 2);
@@ -1094,7 +1106,7 @@ fn next_up_on_available_uses_next_scheduled_or_none_for_thread() {
 		let thread_claim_a = ParathreadClaim(thread_a, collator.clone());
 		let thread_claim_b = ParathreadClaim(thread_b, collator.clone());
 
-		Scheduler::add_parathread_claim(thread_claim_a.clone());
+		assert_ok!(Scheduler::add_parathread_claim(thread_claim_a.clone()));
 
 		run_to_block(2, |_| None);
 
@@ -1112,7 +1124,7 @@ fn next_up_on_available_uses_next_scheduled_or_none_for_thread() {
 
 			assert!(Scheduler::next_up_on_available(CoreIndex(0)).is_none());
 
-			Scheduler::add_parathread_claim(thread_claim_b);
+			assert_ok!(Scheduler::add_parathread_claim(thread_claim_b));
 
 			let queue = ParathreadQueue::<Test>::get();
 			assert_eq!(
@@ -1166,7 +1178,7 @@ fn next_up_on_time_out_reuses_claim_if_nothing_queued() {
 		let thread_claim_a = ParathreadClaim(thread_a, collator.clone());
 		let thread_claim_b = ParathreadClaim(thread_b, collator.clone());
 
-		Scheduler::add_parathread_claim(thread_claim_a.clone());
+		assert_ok!(Scheduler::add_parathread_claim(thread_claim_a.clone()));
 
 		run_to_block(2, |_| None);
 
@@ -1189,7 +1201,7 @@ fn next_up_on_time_out_reuses_claim_if_nothing_queued() {
 				ScheduledCore { para_id: thread_a, collator: Some(collator.clone()) }
 			);
 
-			Scheduler::add_parathread_claim(thread_claim_b);
+			assert_ok!(Scheduler::add_parathread_claim(thread_claim_b));
 
 			let queue = ParathreadQueue::<Test>::get();
 			assert_eq!(
@@ -1425,8 +1437,8 @@ fn parathread_claims_are_pruned_after_deregistration() {
 			_ => None,
 		});
 
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone()));
-		Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone()));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_a, collator.clone())));
+		assert_ok!(Scheduler::add_parathread_claim(ParathreadClaim(thread_b, collator.clone())));
 
 		run_to_block(2, |_| None);
 		assert_eq!(Scheduler::scheduled().len(), 2);
@@ -20,13 +20,20 @@ use crate::{
 };
 use frame_support::{pallet_prelude::*, traits::EnsureOrigin};
 use frame_system::pallet_prelude::*;
+use parity_scale_codec::Compact;
 use polkadot_parachain::primitives::UpwardMessages;
-use primitives::{Id as ParaId, UpwardMessage};
+use primitives::{
+	vstaging::{OverweightUpwardMessage, UpwardQueueStatus},
+	Id as ParaId, UpwardMessage,
+};
+use sp_runtime::traits::Saturating;
 use sp_std::{collections::btree_map::BTreeMap, fmt, marker::PhantomData, mem, prelude::*};
 use xcm::latest::Outcome;
 
 pub use pallet::*;
 
+const LOG_TARGET: &str = "runtime::ump";
+
 /// Maximum value that `config.max_upward_message_size` can be set to
 ///
 /// This is used for benchmarking sanely bounding relevant storage items. It is expected from the `configurations`
@@ -81,6 +88,36 @@ impl UmpSink for () {
 	}
 }
 
+/// Handles overweight upward messages which expired before they could be executed.
+///
+/// This can be used to e.g. refund the assets the message carried to the para that sent it.
+pub trait OnOverweightExpired {
+	/// Handle the expired overweight message `msg` sent by `origin` which was stashed under
+	/// `index`, using no more than `max_weight`.
+	///
+	/// Returns the amount of weight consumed in the process. If handling the message requires more
+	/// than `max_weight`, nothing must be done and the required weight is returned as an error.
+	/// The message is then kept and handed over again later.
+	fn on_overweight_expired(
+		origin: ParaId,
+		index: OverweightIndex,
+		msg: Vec<u8>,
+		max_weight: Weight,
+	) -> Result<Weight, Weight>;
+}
+
+/// Drops expired overweight messages without any further action.
+impl OnOverweightExpired for () {
+	fn on_overweight_expired(
+		_: ParaId,
+		_: OverweightIndex,
+		_: Vec<u8>,
+		_: Weight,
+	) -> Result<Weight, Weight> {
+		Ok(Weight::zero())
+	}
+}
+
 /// Simple type used to identify messages for the purpose of reporting events. Secure if and only
 /// if the message content is unique.
 pub type MessageId = [u8; 32];
@@ -239,6 +276,19 @@ pub mod pallet {
 		/// Origin which is allowed to execute overweight messages.
 		type ExecuteOverweightOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// Whether the weight left over at the end of a block is used to retry overweight
+		/// messages, in the order they were stashed.
+		type RetryOverweightOnIdle: Get<bool>;
+
+		/// The number of blocks after which an overweight message that was not executed is
+		/// dropped, or `None` to keep overweight messages until they are serviced.
+		///
+		/// Expired messages are dropped using the weight left over at the end of a block.
+		type OverweightExpiry: Get<Option<Self::BlockNumber>>;
+
+		/// What to do with overweight messages that expired.
+		type OnOverweightExpired: OnOverweightExpired;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -273,6 +323,15 @@ pub mod pallet {
 		///
 		/// \[ overweight_index, used \]
 		OverweightServiced(OverweightIndex, Weight),
+		/// Upward message from the overweight queue was executed with the weight left over at the
+		/// end of a block.
+		///
+		/// \[ overweight_index, used \]
+		OverweightRetried(OverweightIndex, Weight),
+		/// Upward message from the overweight queue expired and was dropped.
+		///
+		/// \[ para, overweight_index \]
+		OverweightExpired(ParaId, OverweightIndex),
 	}
 
 	#[pallet::error]
@@ -333,11 +392,43 @@ pub mod pallet {
 	pub type Overweight<T: Config> =
 		CountedStorageMap<_, Twox64Concat, OverweightIndex, (ParaId, Vec<u8>), OptionQuery>;
 
+	/// The indices of the messages in `Overweight`, by the para that sent them.
+	#[pallet::storage]
+	pub type OverweightIndices<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ParaId, Twox64Concat, OverweightIndex, (), OptionQuery>;
+
+	/// The weight that the messages in `Overweight` required when they were found to be overweight.
+	///
+	/// Messages that were stashed before this map was introduced do not have an entry.
+	#[pallet::storage]
+	pub type OverweightRequiredWeight<T: Config> =
+		StorageMap<_, Twox64Concat, OverweightIndex, Weight, OptionQuery>;
+
+	/// The block number at which the messages in `Overweight` were stashed.
+	///
+	/// Messages that were stashed before this map was introduced are recorded at the block of the
+	/// upgrade to storage version 2.
+	#[pallet::storage]
+	pub type OverweightEnqueuedAt<T: Config> =
+		StorageMap<_, Twox64Concat, OverweightIndex, T::BlockNumber, OptionQuery>;
+
+	/// The lowest index that may still be present in `Overweight`. Overweight messages are retried
+	/// and expired in order, starting from this index.
+	#[pallet::storage]
+	pub type OverweightRetryCursor<T: Config> = StorageValue<_, OverweightIndex, ValueQuery>;
+
 	/// The number of overweight messages ever recorded in `Overweight` (and thus the lowest free
 	/// index).
 	#[pallet::storage]
 	pub type OverweightCount<T: Config> = StorageValue<_, OverweightIndex, ValueQuery>;
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::process_overweight_on_idle(now, remaining_weight)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Service a single overweight upward message.
@@ -365,7 +456,7 @@ pub mod pallet {
 				Overweight::<T>::get(index).ok_or(Error::<T>::UnknownMessageIndex)?;
 			let used = T::UmpSink::process_upward_message(sender, &data[..], weight_limit)
 				.map_err(|_| Error::<T>::WeightOverLimit)?;
-			Overweight::<T>::remove(index);
+			Self::remove_overweight(sender, index);
 			Self::deposit_event(Event::OverweightServiced(index, used));
 			Ok(Some(used.saturating_add(<T as Config>::WeightInfo::service_overweight())).into())
 		}
@@ -561,7 +652,8 @@ impl<T: Config> Pallet<T> {
 								if we get into this branch then `peek_front` returned `Some`;\
 								thus `upward_message` cannot be `None`; qed",
 							);
-							let index = Self::stash_overweight(dispatchee, upward_message);
+							let index =
+								Self::stash_overweight(dispatchee, upward_message, required);
 							Self::deposit_event(Event::OverweightEnqueued(
 								dispatchee, id, index, required,
 							));
@@ -591,7 +683,11 @@ impl<T: Config> Pallet<T> {
 
 	/// Puts a given upward message into the list of overweight messages allowing it to be executed
 	/// later.
-	fn stash_overweight(sender: ParaId, upward_message: Vec<u8>) -> OverweightIndex {
+	fn stash_overweight(
+		sender: ParaId,
+		upward_message: Vec<u8>,
+		required: Weight,
+	) -> OverweightIndex {
 		let index = <Self as Store>::OverweightCount::mutate(|count| {
 			let index = *count;
 			*count += 1;
@@ -599,8 +695,144 @@ impl<T: Config> Pallet<T> {
 		});
 
 		<Self as Store>::Overweight::insert(index, (sender, upward_message));
+		<Self as Store>::OverweightIndices::insert(sender, index, ());
+		<Self as Store>::OverweightRequiredWeight::insert(index, required);
+		<Self as Store>::OverweightEnqueuedAt::insert(
+			index,
+			<frame_system::Pallet<T>>::block_number(),
+		);
 		index
 	}
+
+	/// Removes the overweight message with the given index along with its metadata.
+	fn remove_overweight(sender: ParaId, index: OverweightIndex) {
+		<Self as Store>::Overweight::remove(index);
+		<Self as Store>::OverweightIndices::remove(sender, index);
+		<Self as Store>::OverweightRequiredWeight::remove(index);
+		<Self as Store>::OverweightEnqueuedAt::remove(index);
+	}
+
+	/// Expires and retries overweight messages, oldest first, using at most `limit` weight.
+	///
+	/// A message that is neither expired nor fits into the remaining weight, or whose expiry
+	/// can't be handled within the remaining weight, blocks all the messages stashed after it
+	/// until the next call. Messages that were stashed without recording the block number never
+	/// expire; `migration::v2` records the block number of the upgrade for them.
+	///
+	/// Does nothing if neither `RetryOverweightOnIdle` nor `OverweightExpiry` are enabled.
+	pub(crate) fn process_overweight_on_idle(now: T::BlockNumber, limit: Weight) -> Weight {
+		let retry = T::RetryOverweightOnIdle::get();
+		let expiry = T::OverweightExpiry::get();
+		if !retry && expiry.is_none() {
+			return Weight::zero()
+		}
+
+		// reading the cursor and the count, and writing the cursor back.
+		let mut weight_used = T::DbWeight::get().reads_writes(2, 1);
+		// reading a message along with its metadata, and removing them along with their index.
+		let step_weight = T::DbWeight::get().reads_writes(3, 5);
+		if weight_used.saturating_add(step_weight).any_gt(limit) {
+			return Weight::zero()
+		}
+
+		let end = <Self as Store>::OverweightCount::get();
+		let mut cursor = <Self as Store>::OverweightRetryCursor::get();
+		while cursor < end && !weight_used.saturating_add(step_weight).any_gt(limit) {
+			weight_used.saturating_accrue(step_weight);
+
+			let (sender, data) = match <Self as Store>::Overweight::get(cursor) {
+				Some(message) => message,
+				None => {
+					// already serviced.
+					cursor += 1;
+					continue
+				},
+			};
+
+			let is_expired = expiry.map_or(false, |expiry| {
+				<Self as Store>::OverweightEnqueuedAt::get(cursor)
+					.map_or(false, |enqueued_at| now.saturating_sub(enqueued_at) >= expiry)
+			});
+			if is_expired {
+				let max_weight = limit.saturating_sub(weight_used);
+				match T::OnOverweightExpired::on_overweight_expired(
+					sender, cursor, data, max_weight,
+				) {
+					Ok(used) => {
+						weight_used.saturating_accrue(used);
+						Self::remove_overweight(sender, cursor);
+						Self::deposit_event(Event::OverweightExpired(sender, cursor));
+						cursor += 1;
+						continue
+					},
+					Err(_) => break,
+				}
+			}
+
+			if !retry {
+				// the messages after this one were stashed later, so they can't be expired either.
+				break
+			}
+
+			let max_weight = limit.saturating_sub(weight_used);
+			let fits = <Self as Store>::OverweightRequiredWeight::get(cursor)
+				.map_or(true, |required| required.all_lte(max_weight));
+			if !fits {
+				break
+			}
+
+			match T::UmpSink::process_upward_message(sender, &data[..], max_weight) {
+				Ok(used) => {
+					weight_used.saturating_accrue(used);
+					Self::remove_overweight(sender, cursor);
+					Self::deposit_event(Event::OverweightRetried(cursor, used));
+					cursor += 1;
+				},
+				Err(_) => break,
+			}
+		}
+
+		<Self as Store>::OverweightRetryCursor::put(cursor);
+		weight_used
+	}
+
+	/// Returns the state of the dispatch queue of the given para.
+	pub(crate) fn upward_queue_status(para: ParaId) -> UpwardQueueStatus {
+		let config = <configuration::Pallet<T>>::config();
+		let (count, total_size) = <Self as Store>::RelayDispatchQueueSize::get(&para);
+
+		UpwardQueueStatus {
+			count,
+			total_size,
+			max_count: config.max_upward_queue_count,
+			max_total_size: config.max_upward_queue_size,
+			needs_dispatch: <Self as Store>::NeedsDispatch::get().binary_search(&para).is_ok(),
+		}
+	}
+
+	/// Returns the overweight messages sent by the given para, ordered by their index.
+	///
+	/// Only the messages of the para are visited, and only their length is read.
+	pub(crate) fn overweight_messages(para: ParaId) -> Vec<OverweightUpwardMessage> {
+		let mut messages = <Self as Store>::OverweightIndices::iter_key_prefix(para)
+			.map(|index| OverweightUpwardMessage {
+				index,
+				size: Self::overweight_message_size(index).unwrap_or_default(),
+				required_weight: <Self as Store>::OverweightRequiredWeight::get(index),
+			})
+			.collect::<Vec<_>>();
+		messages.sort_by_key(|message| message.index);
+		messages
+	}
+
+	/// Returns the size of the overweight message with the given index, decoding only the length
+	/// prefix of the message which follows its sender in the stored value.
+	fn overweight_message_size(index: OverweightIndex) -> Option<u32> {
+		let key = <Self as Store>::Overweight::hashed_key_for(index);
+		let mut len_prefix = [0u8; 5];
+		sp_io::storage::read(&key, &mut len_prefix, ParaId::max_encoded_len() as u32)?;
+		Compact::<u32>::decode(&mut &len_prefix[..]).ok().map(|len| len.0)
+	}
 }
 
 /// To avoid constant fetching, deserializing and serialization the queues are cached.
@@ -14,14 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::ump::{Config, Overweight, Pallet};
+use crate::ump::{Config, Overweight, OverweightEnqueuedAt, OverweightIndices, Pallet};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{OnRuntimeUpgrade, StorageVersion},
 	weights::Weight,
 };
 
-pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 pub mod v1 {
 	use super::*;
@@ -47,3 +47,75 @@ pub mod v1 {
 		}
 	}
 }
+
+pub mod v2 {
+	use super::*;
+	#[cfg(feature = "try-runtime")]
+	use sp_std::prelude::*;
+
+	/// Indexes the overweight messages by the para that sent them, and records the current block
+	/// as the one the messages without such a record were stashed at, so that they expire
+	/// `OverweightExpiry` blocks after the upgrade.
+	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() == 1 {
+				let mut weight = T::DbWeight::get().reads(1);
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				let mut overweight_messages = 0u64;
+				let mut stamped = 0u64;
+				for (index, (sender, _)) in Overweight::<T>::iter() {
+					OverweightIndices::<T>::insert(sender, index, ());
+					if !OverweightEnqueuedAt::<T>::contains_key(index) {
+						OverweightEnqueuedAt::<T>::insert(index, now);
+						stamped += 1;
+					}
+					overweight_messages += 1;
+				}
+				log::info!(
+					target: crate::ump::LOG_TARGET,
+					"Indexed {} overweight messages by sender, {} of which were stashed at an unknown block",
+					overweight_messages,
+					stamped,
+				);
+
+				weight.saturating_accrue(
+					T::DbWeight::get()
+						.reads_writes(overweight_messages * 2 + 1, overweight_messages + stamped),
+				);
+
+				StorageVersion::new(2).put::<Pallet<T>>();
+
+				weight.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!(target: crate::ump::LOG_TARGET, "skipping v2, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok((Overweight::<T>::count() as u64).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let overweight_messages = u64::decode(&mut &state[..])
+				.map_err(|_| "Cannot decode the number of overweight messages")?;
+			ensure!(
+				StorageVersion::get::<Pallet<T>>() == 2,
+				"Storage version should be `2` after the migration",
+			);
+			ensure!(
+				OverweightIndices::<T>::iter_keys().count() as u64 == overweight_messages,
+				"Every overweight message should be indexed by its sender",
+			);
+			ensure!(
+				Overweight::<T>::iter_keys().all(OverweightEnqueuedAt::<T>::contains_key),
+				"Every overweight message should have the block it was stashed at",
+			);
+			Ok(())
+		}
+	}
+}
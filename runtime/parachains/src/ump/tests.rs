@@ -16,10 +16,11 @@
 
 use super::*;
 use crate::mock::{
-	assert_last_event, new_test_ext, take_processed, Configuration, MockGenesisConfig,
-	RuntimeOrigin, System, Test, Ump,
+	assert_last_event, new_test_ext, take_expired, take_processed, Configuration,
+	MockGenesisConfig, OverweightExpiredWeight, OverweightExpiry, RetryOverweightOnIdle,
+	RuntimeEvent, RuntimeOrigin, System, Test, Ump,
 };
-use frame_support::{assert_noop, assert_ok, weights::Weight};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
 use std::collections::HashSet;
 
 pub(super) struct GenesisConfigBuilder {
@@ -362,3 +363,247 @@ fn overweight_queue_works() {
 		);
 	});
 }
+
+#[test]
+fn queue_status_and_overweight_messages_are_reported() {
+	let para_a = ParaId::from(2021);
+	let para_b = ParaId::from(2022);
+
+	let a_msg_1 = (500u32, "a_msg_1").encode();
+	let a_msg_2 = (500u32, "a_msg_2").encode();
+	let a_msg_3 = (100u32, "a_msg_3").encode();
+
+	new_test_ext(
+		GenesisConfigBuilder {
+			ump_service_total_weight: Weight::from_parts(900, 900),
+			ump_max_individual_weight: Weight::from_parts(300, 300),
+			..Default::default()
+		}
+		.build(),
+	)
+	.execute_with(|| {
+		// The first message fits the budget, the second one is stashed as overweight.
+		queue_upward_msg(para_a, a_msg_1.clone());
+		queue_upward_msg(para_a, a_msg_2.clone());
+		Ump::process_pending_upward_messages();
+		assert_eq!(take_processed(), vec![(para_a, a_msg_1)]);
+
+		queue_upward_msg(para_a, a_msg_3.clone());
+
+		assert_eq!(
+			Ump::upward_queue_status(para_a),
+			UpwardQueueStatus {
+				count: 1,
+				total_size: a_msg_3.len() as u32,
+				max_count: 4,
+				max_total_size: 64,
+				needs_dispatch: true,
+			}
+		);
+		assert_eq!(
+			Ump::overweight_messages(para_a),
+			vec![OverweightUpwardMessage {
+				index: 0,
+				size: a_msg_2.len() as u32,
+				required_weight: Some(Weight::from_parts(500, 500)),
+			}]
+		);
+
+		assert!(!Ump::upward_queue_status(para_b).needs_dispatch);
+		assert!(Ump::overweight_messages(para_b).is_empty());
+
+		// Servicing the message removes it along with its required weight.
+		assert_ok!(Ump::service_overweight(RuntimeOrigin::root(), 0, Weight::from_parts(500, 500)));
+		assert!(Ump::overweight_messages(para_a).is_empty());
+		assert_eq!(<Ump as Store>::OverweightRequiredWeight::get(0), None);
+		assert_eq!(<Ump as Store>::OverweightIndices::iter().count(), 0);
+	});
+}
+
+#[test]
+fn overweight_messages_are_retried_on_idle() {
+	let para_a = ParaId::from(2021);
+	let para_b = ParaId::from(2022);
+
+	let a_msg_1 = (500u32, "a_msg_1").encode();
+	let a_msg_2 = (500u32, "a_msg_2").encode();
+	let b_msg_1 = (500u32, "b_msg_1").encode();
+	let b_msg_2 = (450u32, "b_msg_2").encode();
+	let b_msg_3 = (500u32, "b_msg_3").encode();
+
+	new_test_ext(
+		GenesisConfigBuilder {
+			ump_service_total_weight: Weight::from_parts(900, 900),
+			ump_max_individual_weight: Weight::from_parts(300, 300),
+			..Default::default()
+		}
+		.build(),
+	)
+	.execute_with(|| {
+		System::set_block_number(1);
+
+		// Stash `a_msg_2`, `b_msg_2` and `b_msg_3` as overweight messages 0, 1 and 2.
+		queue_upward_msg(para_a, a_msg_1.clone());
+		queue_upward_msg(para_a, a_msg_2.clone());
+		Ump::process_pending_upward_messages();
+		queue_upward_msg(para_b, b_msg_1.clone());
+		queue_upward_msg(para_b, b_msg_2.clone());
+		queue_upward_msg(para_b, b_msg_3.clone());
+		Ump::process_pending_upward_messages();
+		assert_eq!(take_processed(), vec![(para_a, a_msg_1), (para_b, b_msg_1)]);
+		assert_eq!(Overweight::<Test>::count(), 3);
+
+		// The policy is disabled by default.
+		assert_eq!(Ump::on_idle(1, Weight::MAX), Weight::zero());
+		assert_eq!(Overweight::<Test>::count(), 3);
+
+		RetryOverweightOnIdle::set(true);
+
+		// Not enough weight for the oldest message blocks the others.
+		Ump::on_idle(1, Weight::from_parts(499, 499));
+		assert_eq!(take_processed(), vec![]);
+		assert_eq!(OverweightRetryCursor::<Test>::get(), 0);
+
+		// Messages which were serviced in the meantime are skipped.
+		assert_ok!(Ump::service_overweight(RuntimeOrigin::root(), 0, Weight::from_parts(500, 500)));
+		assert_eq!(take_processed(), vec![(para_a, a_msg_2)]);
+
+		Ump::on_idle(1, Weight::from_parts(900, 900));
+		assert_eq!(take_processed(), vec![(para_b, b_msg_2)]);
+		let retried = RuntimeEvent::Ump(Event::OverweightRetried(1, Weight::from_parts(450, 450)));
+		assert!(System::events().iter().any(|record| record.event == retried));
+		assert_eq!(OverweightRetryCursor::<Test>::get(), 2);
+
+		Ump::on_idle(1, Weight::from_parts(500, 500));
+		assert_eq!(take_processed(), vec![(para_b, b_msg_3)]);
+		assert_last_event(Event::OverweightRetried(2, Weight::from_parts(500, 500)).into());
+		assert_eq!(OverweightRetryCursor::<Test>::get(), 3);
+		assert_eq!(Overweight::<Test>::count(), 0);
+		assert_eq!(OverweightRequiredWeight::<Test>::iter().count(), 0);
+		assert_eq!(OverweightEnqueuedAt::<Test>::iter().count(), 0);
+		assert_eq!(OverweightIndices::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn overweight_messages_expire_on_idle() {
+	let para_a = ParaId::from(2021);
+
+	let a_msg_1 = (500u32, "a_msg_1").encode();
+	let a_msg_2 = (500u32, "a_msg_2").encode();
+
+	new_test_ext(
+		GenesisConfigBuilder {
+			ump_service_total_weight: Weight::from_parts(900, 900),
+			ump_max_individual_weight: Weight::from_parts(300, 300),
+			..Default::default()
+		}
+		.build(),
+	)
+	.execute_with(|| {
+		System::set_block_number(1);
+		OverweightExpiry::set(Some(10));
+
+		queue_upward_msg(para_a, a_msg_1.clone());
+		queue_upward_msg(para_a, a_msg_2.clone());
+		Ump::process_pending_upward_messages();
+		assert_eq!(take_processed(), vec![(para_a, a_msg_1)]);
+		assert_eq!(OverweightEnqueuedAt::<Test>::get(0), Some(1));
+
+		// Not expired yet, and retrying is disabled.
+		Ump::on_idle(10, Weight::MAX);
+		assert_eq!(take_expired(), vec![]);
+		assert_eq!(take_processed(), vec![]);
+		assert_eq!(Overweight::<Test>::count(), 1);
+
+		Ump::on_idle(11, Weight::MAX);
+		assert_eq!(take_expired(), vec![(para_a, a_msg_2)]);
+		assert_eq!(take_processed(), vec![]);
+		assert_last_event(Event::OverweightExpired(para_a, 0).into());
+		assert_noop!(
+			Ump::service_overweight(RuntimeOrigin::root(), 0, Weight::from_parts(500, 500)),
+			Error::<Test>::UnknownMessageIndex
+		);
+	});
+}
+
+#[test]
+fn migration_to_v2_indexes_overweight_messages_by_sender() {
+	use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+	let para_a = ParaId::from(2021);
+	let para_b = ParaId::from(2022);
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		StorageVersion::new(1).put::<Ump>();
+		Overweight::<Test>::insert(0, (para_a, vec![1, 2, 3]));
+		Overweight::<Test>::insert(1, (para_b, vec![4]));
+		Overweight::<Test>::insert(2, (para_a, vec![5, 6]));
+		OverweightEnqueuedAt::<Test>::insert(2, 1);
+		OverweightCount::<Test>::put(3);
+		assert!(Ump::overweight_messages(para_a).is_empty());
+
+		// Messages stashed at an unknown block never expire.
+		System::set_block_number(20);
+		OverweightExpiry::set(Some(10));
+		Ump::on_idle(20, Weight::MAX);
+		assert_eq!(take_expired(), vec![]);
+		assert_eq!(Overweight::<Test>::count(), 3);
+
+		migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Ump>(), 2);
+		assert_eq!(OverweightEnqueuedAt::<Test>::get(0), Some(20));
+		assert_eq!(OverweightEnqueuedAt::<Test>::get(1), Some(20));
+		assert_eq!(OverweightEnqueuedAt::<Test>::get(2), Some(1));
+		assert_eq!(
+			Ump::overweight_messages(para_a),
+			vec![
+				OverweightUpwardMessage { index: 0, size: 3, required_weight: None },
+				OverweightUpwardMessage { index: 2, size: 2, required_weight: None },
+			]
+		);
+		assert_eq!(
+			Ump::overweight_messages(para_b),
+			vec![OverweightUpwardMessage { index: 1, size: 1, required_weight: None }]
+		);
+	});
+}
+
+#[test]
+fn overweight_messages_expire_only_within_the_weight_limit() {
+	let para_a = ParaId::from(2021);
+
+	let a_msg_1 = (500u32, "a_msg_1").encode();
+	let a_msg_2 = (500u32, "a_msg_2").encode();
+
+	new_test_ext(
+		GenesisConfigBuilder {
+			ump_service_total_weight: Weight::from_parts(900, 900),
+			ump_max_individual_weight: Weight::from_parts(300, 300),
+			..Default::default()
+		}
+		.build(),
+	)
+	.execute_with(|| {
+		System::set_block_number(1);
+		OverweightExpiry::set(Some(10));
+		OverweightExpiredWeight::set(Weight::from_parts(200, 200));
+
+		queue_upward_msg(para_a, a_msg_1.clone());
+		queue_upward_msg(para_a, a_msg_2.clone());
+		Ump::process_pending_upward_messages();
+		assert_eq!(take_processed(), vec![(para_a, a_msg_1)]);
+
+		// Handling the expiry doesn't fit, so the message is kept.
+		assert_eq!(Ump::on_idle(11, Weight::from_parts(100, 100)), Weight::zero());
+		assert_eq!(take_expired(), vec![]);
+		assert_eq!(Overweight::<Test>::count(), 1);
+		assert_eq!(OverweightRetryCursor::<Test>::get(), 0);
+
+		assert_eq!(Ump::on_idle(12, Weight::from_parts(200, 200)), Weight::from_parts(200, 200));
+		assert_eq!(take_expired(), vec![(para_a, a_msg_2)]);
+		assert_eq!(Overweight::<Test>::count(), 0);
+		assert_eq!(OverweightRetryCursor::<Test>::get(), 1);
+	});
+}
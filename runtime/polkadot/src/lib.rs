@@ -41,7 +41,7 @@ use frame_election_provider_support::{generate_solution_type, onchain, Sequentia
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU32, EitherOfDiverse, InstanceFilter, KeyOwnerProofSystem, LockIdentifier,
+		ConstU32, Contains, EitherOfDiverse, InstanceFilter, KeyOwnerProofSystem, LockIdentifier,
 		PrivilegeCmp, WithdrawReasons,
 	},
 	weights::ConstantMultiplier,
@@ -142,13 +142,25 @@ type MoreThanHalfCouncil = EitherOfDiverse<
 	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
 >;
 
+/// Calls whose weights have not been benchmarked yet are filtered out.
+pub struct BaseFilter;
+impl Contains<RuntimeCall> for BaseFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(
+			call,
+			RuntimeCall::Slots(slots::Call::extend_lease { .. }) |
+				RuntimeCall::Registrar(paras_registrar::Call::swap_lease_periods { .. })
+		)
+	}
+}
+
 parameter_types! {
 	pub const Version: RuntimeVersion = VERSION;
 	pub const SS58Prefix: u8 = 0;
 }
 
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = BlockWeights;
 	type BlockLength = BlockLength;
 	type RuntimeOrigin = RuntimeOrigin;
@@ -1301,6 +1313,8 @@ impl parachains_paras::Config for Runtime {
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
+	pub const RetryOverweightOnIdle: bool = false;
+	pub const OverweightExpiry: Option<BlockNumber> = None;
 }
 
 impl parachains_ump::Config for Runtime {
@@ -1309,6 +1323,9 @@ impl parachains_ump::Config for Runtime {
 		crate::parachains_ump::XcmSink<xcm_executor::XcmExecutor<xcm_config::XcmConfig>, Runtime>;
 	type FirstMessageFactorPercent = FirstMessageFactorPercent;
 	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+	type RetryOverweightOnIdle = RetryOverweightOnIdle;
+	type OverweightExpiry = OverweightExpiry;
+	type OnOverweightExpired = ();
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Self>;
 }
 
@@ -1352,6 +1369,7 @@ impl paras_registrar::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OnSwap = (Crowdloan, Slots);
+	type LeasePeriodSwap = Slots;
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = ParaDataByteDeposit;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
@@ -1606,7 +1624,10 @@ impl Get<&'static str> for StakingMigrationV11OldPallet {
 /// All migrations that will run on the next runtime upgrade.
 ///
 /// Should be cleared after every release.
-pub type Migrations = ();
+pub type Migrations = (
+	parachains_dmp::migration::v1::MigrateToV1<Runtime>,
+	parachains_ump::migration::v2::MigrateToV2<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -2069,6 +2090,23 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<()>,
+		) -> Result<pallet_xcm::dry_run::XcmDryRunEffects, pallet_xcm::dry_run::XcmDryRunError> {
+			XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin, xcm)
+		}
+	}
+
+	impl pallet_xcm::trapped_assets::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(Hash, xcm::VersionedMultiAssets)> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
 	#[cfg(feature = "try-runtime")]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
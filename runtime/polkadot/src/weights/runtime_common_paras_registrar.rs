@@ -216,4 +216,21 @@ impl<T: frame_system::Config> runtime_common::paras_registrar::WeightInfo for We
 			.saturating_add(Weight::from_parts(969, 0).saturating_mul(b.into()))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Not yet generated on the reference hardware: the execution time is an upper estimate until
+	/// the weights are regenerated.
+	/// Storage: Registrar Paras (r:2 w:0)
+	/// Proof Skipped: Registrar Paras (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Registrar PendingLeasePeriodSwap (r:1 w:1)
+	/// Proof Skipped: Registrar PendingLeasePeriodSwap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Slots Leases (r:2 w:2)
+	/// Proof Skipped: Slots Leases (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Not measured: a placeholder until the benchmark is run. The call is filtered out of this runtime meanwhile.
+	fn swap_lease_periods() -> Weight {
+		Weight::from_parts(80_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 16000))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
 }
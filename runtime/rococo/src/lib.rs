@@ -1064,6 +1064,8 @@ impl parachains_paras::Config for Runtime {
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
+	pub const RetryOverweightOnIdle: bool = false;
+	pub const OverweightExpiry: Option<BlockNumber> = None;
 }
 
 impl parachains_ump::Config for Runtime {
@@ -1072,6 +1074,9 @@ impl parachains_ump::Config for Runtime {
 		crate::parachains_ump::XcmSink<xcm_executor::XcmExecutor<xcm_config::XcmConfig>, Runtime>;
 	type FirstMessageFactorPercent = FirstMessageFactorPercent;
 	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+	type RetryOverweightOnIdle = RetryOverweightOnIdle;
+	type OverweightExpiry = OverweightExpiry;
+	type OnOverweightExpired = ();
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
@@ -1129,6 +1134,7 @@ impl paras_registrar::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OnSwap = (Crowdloan, Slots);
+	type LeasePeriodSwap = Slots;
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
@@ -1509,7 +1515,10 @@ pub type UncheckedExtrinsic =
 /// All migrations that will run on the next runtime upgrade.
 ///
 /// Should be cleared after every release.
-pub type Migrations = ();
+pub type Migrations = (
+	parachains_dmp::migration::v1::MigrateToV1<Runtime>,
+	parachains_ump::migration::v2::MigrateToV2<Runtime>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -1664,7 +1673,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
-	#[api_version(4)]
+	#[api_version(5)]
 	impl primitives::runtime_api::ParachainHost<Block, Hash, BlockNumber> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -1770,6 +1779,10 @@ sp_api::impl_runtime_apis! {
 		fn disputes() -> Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)> {
 			runtime_parachains::runtime_api_impl::vstaging::get_session_disputes::<Runtime>()
 		}
+
+		fn message_queues_status(para_id: ParaId) -> primitives::vstaging::MessageQueuesStatus {
+			parachains_runtime_api_impl_staging::message_queues_status::<Runtime>(para_id)
+		}
 	}
 
 	#[api_version(2)]
@@ -1985,6 +1998,23 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<()>,
+		) -> Result<pallet_xcm::dry_run::XcmDryRunEffects, pallet_xcm::dry_run::XcmDryRunError> {
+			XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin, xcm)
+		}
+	}
+
+	impl pallet_xcm::trapped_assets::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(Hash, xcm::VersionedMultiAssets)> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
 	impl pallet_beefy_mmr::BeefyMmrApi<Block, Hash> for RuntimeApi {
 		fn authority_set_proof() -> beefy_primitives::mmr::BeefyAuthoritySet<Hash> {
 			MmrLeaf::authority_set_proof()
@@ -271,7 +271,7 @@ impl<RuntimeCall> XcmWeightInfo<RuntimeCall> for RococoXcmWeight<RuntimeCall> {
 		XcmGeneric::<Runtime>::clear_topic()
 	}
 	fn alias_origin(_: &MultiLocation) -> Weight {
-		// XCM Executor does not currently support alias origin operations
+		// Rococo does not currently allow aliasing origins
 		Weight::MAX
 	}
 	fn unpaid_execution(_: &WeightLimit, _: &Option<MultiLocation>) -> Weight {
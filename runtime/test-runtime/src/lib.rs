@@ -526,6 +526,8 @@ impl parachains_dmp::Config for Runtime {}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
+	pub const RetryOverweightOnIdle: bool = false;
+	pub const OverweightExpiry: Option<BlockNumber> = None;
 }
 
 impl parachains_ump::Config for Runtime {
@@ -533,6 +535,9 @@ impl parachains_ump::Config for Runtime {
 	type UmpSink = ();
 	type FirstMessageFactorPercent = FirstMessageFactorPercent;
 	type ExecuteOverweightOrigin = frame_system::EnsureRoot<AccountId>;
+	type RetryOverweightOnIdle = RetryOverweightOnIdle;
+	type OverweightExpiry = OverweightExpiry;
+	type OnOverweightExpired = ();
 	type WeightInfo = parachains_ump::TestWeightInfo;
 }
 
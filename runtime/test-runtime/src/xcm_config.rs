@@ -112,6 +112,8 @@ impl xcm_executor::Config for XcmConfig {
 	type FeeManager = ();
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
+	type Aliasers = Nothing;
+	type HrmpHandler = ();
 	type CallDispatcher = super::RuntimeCall;
 	type SafeCallFilter = Everything;
 }
@@ -923,6 +923,8 @@ impl parachains_paras::Config for Runtime {
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
+	pub const RetryOverweightOnIdle: bool = false;
+	pub const OverweightExpiry: Option<BlockNumber> = None;
 }
 
 impl parachains_ump::Config for Runtime {
@@ -931,6 +933,9 @@ impl parachains_ump::Config for Runtime {
 		crate::parachains_ump::XcmSink<xcm_executor::XcmExecutor<xcm_config::XcmConfig>, Runtime>;
 	type FirstMessageFactorPercent = FirstMessageFactorPercent;
 	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+	type RetryOverweightOnIdle = RetryOverweightOnIdle;
+	type OverweightExpiry = OverweightExpiry;
+	type OnOverweightExpired = ();
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
@@ -1010,6 +1015,7 @@ impl paras_registrar::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type OnSwap = (Crowdloan, Slots);
+	type LeasePeriodSwap = Slots;
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
@@ -1226,7 +1232,11 @@ impl Get<&'static str> for StakingMigrationV11OldPallet {
 /// All migrations that will run on the next runtime upgrade.
 ///
 /// Should be cleared after every release.
-pub type Migrations = (clean_state_migration::CleanMigrate,);
+pub type Migrations = (
+	clean_state_migration::CleanMigrate,
+	parachains_dmp::migration::v1::MigrateToV1<Runtime>,
+	parachains_ump::migration::v2::MigrateToV2<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -1354,7 +1364,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
-	#[api_version(4)]
+	#[api_version(5)]
 	impl primitives::runtime_api::ParachainHost<Block, Hash, BlockNumber> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			parachains_runtime_api_impl::validators::<Runtime>()
@@ -1460,6 +1470,10 @@ sp_api::impl_runtime_apis! {
 		fn disputes() -> Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)> {
 			runtime_parachains::runtime_api_impl::vstaging::get_session_disputes::<Runtime>()
 		}
+
+		fn message_queues_status(para_id: ParaId) -> primitives::vstaging::MessageQueuesStatus {
+			parachains_runtime_api_impl_staging::message_queues_status::<Runtime>(para_id)
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -1669,6 +1683,23 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::dry_run::XcmDryRunApi<Block> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<()>,
+		) -> Result<pallet_xcm::dry_run::XcmDryRunEffects, pallet_xcm::dry_run::XcmDryRunError> {
+			XcmPallet::dry_run_xcm::<xcm_config::XcmRouter>(origin, xcm)
+		}
+	}
+
+	impl pallet_xcm::trapped_assets::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(
+			origin: xcm::VersionedMultiLocation,
+		) -> Vec<(Hash, xcm::VersionedMultiAssets)> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
 	impl pallet_nomination_pools_runtime_api::NominationPoolsApi<
 		Block,
 		AccountId,
@@ -1858,8 +1889,8 @@ sp_api::impl_runtime_apis! {
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da95ecffd7b6c0f78751baa9d281e0bfa3a6d6f646c70792f74727372790000000000000000000000000000000000000000").to_vec().into(),
 				// Dmp DownwardMessageQueueHeads
 				hex_literal::hex!("63f78c98723ddc9073523ef3beefda0c4d7fefc408aac59dbfe80a72ac8e3ce5").to_vec().into(),
-				// Dmp DownwardMessageQueues
-				hex_literal::hex!("63f78c98723ddc9073523ef3beefda0ca95dac46c07a40d91506e7637ec4ba57").to_vec().into(),
+				// Dmp DownwardMessageQueuePages
+				hex_literal::hex!("63f78c98723ddc9073523ef3beefda0c34d894ec6fadffc214525dde845b9027").to_vec().into(),
 				// Configuration ActiveConfig
 				hex_literal::hex!("06de3d8a54d27e44a9d5ce189618f22db4b49d95320d9021994c850f25b8e385").to_vec().into(),
 				// The transactional storage limit.
@@ -127,4 +127,21 @@ impl<T: frame_system::Config> runtime_common::slots::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	/// Not yet generated on the reference hardware: the execution time is an upper estimate until
+	/// the weights are regenerated.
+	/// Storage: Registrar Paras (r:1 w:0)
+	/// Proof Skipped: Registrar Paras (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Slots ExtensionPrice (r:1 w:0)
+	/// Proof Skipped: Slots ExtensionPrice (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Slots Leases (r:1 w:1)
+	/// Proof Skipped: Slots Leases (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Not measured: a placeholder until the benchmark is run.
+	fn extend_lease() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 9000))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
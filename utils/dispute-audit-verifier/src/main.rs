@@ -0,0 +1,109 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Re-checks the records of a dispute audit log against the `SessionInfo`s of their sessions.
+//!
+//! Usage: `dispute-audit-verifier <AUDIT_LOG> [<SESSION>=<SESSION_INFO_FILE>]...`
+//!
+//! Each session info file contains the hex encoded result of the `ParachainHost_session_info`
+//! runtime API call for the session, e.g. as returned by the `state_call` RPC. Records of sessions
+//! without a session info file are skipped.
+//!
+//! Rotated files (`<AUDIT_LOG>.1`, `<AUDIT_LOG>.2`, ...) need to be checked one by one.
+
+use std::{collections::HashMap, fs, process};
+
+use parity_scale_codec::Decode;
+use polkadot_node_core_dispute_coordinator::audit_log::ConcludedDispute;
+use polkadot_primitives::{SessionIndex, SessionInfo};
+
+fn load_session_info(arg: &str) -> Result<(SessionIndex, SessionInfo), String> {
+	let (session, path) = arg
+		.split_once('=')
+		.ok_or_else(|| format!("Expected <SESSION>=<FILE>, got {}", arg))?;
+	let session = session.parse().map_err(|e| format!("Invalid session {}: {}", session, e))?;
+	let hex = fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+	let bytes = sp_core::bytes::from_hex(hex.trim())
+		.map_err(|e| format!("Invalid hex in {}: {}", path, e))?;
+	let session_info = Option::<SessionInfo>::decode(&mut &bytes[..])
+		.map_err(|e| format!("Can't decode the session info in {}: {}", path, e))?
+		.ok_or_else(|| format!("No session info for session {} in {}", session, path))?;
+	Ok((session, session_info))
+}
+
+fn run(args: Vec<String>) -> Result<bool, String> {
+	let (log, session_infos) = match args.split_first() {
+		Some((log, session_infos)) => (log, session_infos),
+		None =>
+			return Err(
+				"Usage: dispute-audit-verifier <AUDIT_LOG> [<SESSION>=<SESSION_INFO_FILE>]..."
+					.into(),
+			),
+	};
+	let session_infos = session_infos
+		.iter()
+		.map(|arg| load_session_info(arg))
+		.collect::<Result<HashMap<_, _>, _>>()?;
+
+	let log = fs::read_to_string(log).map_err(|e| format!("Can't read {}: {}", log, e))?;
+	let mut all_valid = true;
+	for (line_number, line) in log.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+		let line_number = line_number + 1;
+		let record: ConcludedDispute = match serde_json::from_str(line) {
+			Ok(record) => record,
+			Err(e) => {
+				println!("line {}: MALFORMED {}", line_number, e);
+				all_valid = false;
+				continue
+			},
+		};
+		let session_info = match session_infos.get(&record.session) {
+			Some(session_info) => session_info,
+			None => {
+				println!(
+					"line {}: SKIPPED candidate {:?} of session {}, no session info",
+					line_number, record.candidate_hash, record.session,
+				);
+				continue
+			},
+		};
+		match record.verify(&session_info.validators.to_vec()) {
+			Ok(()) => println!(
+				"line {}: OK candidate {:?} of session {}",
+				line_number, record.candidate_hash, record.session,
+			),
+			Err(e) => {
+				println!(
+					"line {}: INVALID candidate {:?} of session {}: {}",
+					line_number, record.candidate_hash, record.session, e,
+				);
+				all_valid = false;
+			},
+		}
+	}
+	Ok(all_valid)
+}
+
+fn main() {
+	match run(std::env::args().skip(1).collect()) {
+		Ok(true) => {},
+		Ok(false) => process::exit(1),
+		Err(e) => {
+			eprintln!("{}", e);
+			process::exit(2)
+		},
+	}
+}
@@ -20,7 +20,7 @@ use crate::{generic, mock::*, *};
 use codec::Decode;
 use frame_support::{
 	parameter_types,
-	traits::{Everything, OriginTrait},
+	traits::{Everything, Nothing, OriginTrait},
 	weights::Weight,
 };
 use sp_core::H256;
@@ -130,6 +130,8 @@ impl xcm_executor::Config for XcmConfig {
 	// No bridges yet...
 	type MessageExporter = ();
 	type UniversalAliases = TestUniversalAliases;
+	type Aliasers = Nothing;
+	type HrmpHandler = ();
 	type CallDispatcher = RuntimeCall;
 	type SafeCallFilter = Everything;
 }
@@ -0,0 +1,123 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dry-running XCM programs, to preview their effects before they are sent or executed.
+
+use crate::{Config, Event, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	weights::Weight,
+};
+use frame_system::Config as SysConfig;
+use scale_info::TypeInfo;
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_std::prelude::*;
+use xcm::prelude::*;
+use xcm_executor::traits::{InspectMessageQueues, WeightBounds};
+
+/// The effects of dry-running an XCM program.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct XcmDryRunEffects {
+	/// The outcome of the execution.
+	pub outcome: Outcome,
+	/// The weight used by the execution.
+	pub weight_used: Weight,
+	/// The assets which were trapped, e.g. because they were left in the holding register.
+	pub trapped_assets: Vec<VersionedMultiAssets>,
+	/// The events emitted during the execution, each one a SCALE-encoded `RuntimeEvent`.
+	pub emitted_events: Vec<Vec<u8>>,
+	/// The messages forwarded to the routers, grouped by destination.
+	pub forwarded_xcms: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>,
+}
+
+/// The reasons an XCM program can't be dry-run.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum XcmDryRunError {
+	/// The origin or the program are in an XCM version which isn't supported.
+	VersionedConversionFailed,
+	/// The program can't be weighed.
+	UnweighableMessage,
+	/// The dry-run couldn't be isolated in a storage transaction.
+	TransactionalLimitReached,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to preview what an XCM program does, e.g. to estimate its fees or catch its failures
+	/// before submitting it.
+	pub trait XcmDryRunApi {
+		/// Executes `xcm` as if it was received from `origin` and reports its effects.
+		///
+		/// The changes to the state are rolled back.
+		fn dry_run_xcm(
+			origin: VersionedMultiLocation,
+			xcm: VersionedXcm<()>,
+		) -> Result<XcmDryRunEffects, XcmDryRunError>;
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	<T as SysConfig>::RuntimeEvent: TryInto<Event<T>>,
+{
+	/// Executes `xcm` as if it was received from `origin` and reports its effects, including the
+	/// messages queued in `Router`, which should be the router of the executor.
+	///
+	/// The execution is limited to the weight of `xcm`, and its changes to the state are rolled
+	/// back.
+	pub fn dry_run_xcm<Router: InspectMessageQueues>(
+		origin: VersionedMultiLocation,
+		xcm: VersionedXcm<()>,
+	) -> Result<XcmDryRunEffects, XcmDryRunError> {
+		let origin: MultiLocation =
+			origin.try_into().map_err(|()| XcmDryRunError::VersionedConversionFailed)?;
+		let xcm: Xcm<()> =
+			xcm.try_into().map_err(|()| XcmDryRunError::VersionedConversionFailed)?;
+		let hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+		let mut xcm: Xcm<<T as SysConfig>::RuntimeCall> = xcm.into();
+		let weight_limit =
+			T::Weigher::weight(&mut xcm).map_err(|()| XcmDryRunError::UnweighableMessage)?;
+
+		with_transaction(|| {
+			frame_system::Pallet::<T>::reset_events();
+			Router::clear_messages();
+
+			let outcome = T::XcmExecutor::execute_xcm(origin, xcm, hash, weight_limit);
+
+			let events: Vec<_> = frame_system::Pallet::<T>::read_events_no_consensus()
+				.map(|record| record.event)
+				.collect();
+			let trapped_assets = events
+				.iter()
+				.filter_map(|event| match TryInto::<Event<T>>::try_into(event.clone()) {
+					Ok(Event::AssetsTrapped(_, _, assets)) => Some(assets),
+					_ => None,
+				})
+				.collect();
+			let effects = XcmDryRunEffects {
+				weight_used: outcome.weight_used(),
+				outcome,
+				trapped_assets,
+				emitted_events: events.iter().map(Encode::encode).collect(),
+				forwarded_xcms: Router::get_messages(),
+			};
+			// Routers which keep their queues outside of storage aren't rolled back.
+			Router::clear_messages();
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(effects))
+		})
+		.map_err(|_| XcmDryRunError::TransactionalLimitReached)
+	}
+}
@@ -25,7 +25,9 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod dry_run;
 pub mod migration;
+pub mod trapped_assets;
 
 use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
 use frame_support::traits::{
@@ -439,6 +441,8 @@ pub mod pallet {
 		LockNotFound,
 		/// The unlock operation cannot succeed because there are still users of the lock.
 		InUse,
+		/// The asset trap could not be found.
+		UnknownTrap,
 	}
 
 	impl<T: Config> From<SendError> for Error<T> {
@@ -515,6 +519,26 @@ pub mod pallet {
 	#[pallet::getter(fn asset_trap)]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// The assets trapped by each origin, keyed by the hash of the trap in `AssetTraps`.
+	///
+	/// Only the traps which were made after this index was introduced can be found in it, and at
+	/// most `MAX_INDEXED_TRAPS_PER_ORIGIN` traps are indexed for any one origin.
+	#[pallet::storage]
+	pub(super) type TrappedAssets<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VersionedMultiLocation,
+		Identity,
+		H256,
+		VersionedMultiAssets,
+		OptionQuery,
+	>;
+
+	/// The number of traps indexed in `TrappedAssets` for each origin.
+	#[pallet::storage]
+	pub(super) type TrappedAssetsCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, VersionedMultiLocation, u32, OptionQuery>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -1090,12 +1114,73 @@ pub mod pallet {
 				Some(weight_limit),
 			)
 		}
+
+		/// Claim several asset traps of `owner` at once, depositing their assets into
+		/// `beneficiary`.
+		///
+		/// This executes an XCM message claiming the traps as `owner`, e.g. to recover the assets
+		/// of a failed transfer on behalf of its sender. An event is deposited indicating whether
+		/// the message could be executed completely or only partially.
+		///
+		/// - `origin`: Must be Root.
+		/// - `owner`: The origin of the traps.
+		/// - `traps`: The hashes of the traps, as found in the `TrappedAssets` index.
+		/// - `beneficiary`: The location the assets are deposited into.
+		/// - `max_weight`: No more than this will be used in the execution of the message.
+		#[pallet::call_index(10)]
+		#[pallet::weight(
+			max_weight
+				.saturating_add(T::WeightInfo::execute())
+				.saturating_add(T::DbWeight::get().reads(traps.len() as u64))
+		)]
+		pub fn claim_trapped_assets(
+			origin: OriginFor<T>,
+			owner: Box<VersionedMultiLocation>,
+			traps: Vec<H256>,
+			beneficiary: Box<VersionedMultiLocation>,
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let owner: MultiLocation = (*owner).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let mut message = Vec::with_capacity(traps.len() + 1);
+			let mut assets_count = 0;
+			for trap in traps {
+				let versioned = TrappedAssets::<T>::get(LatestVersionedMultiLocation(&owner), trap)
+					.ok_or(Error::<T>::UnknownTrap)?;
+				// The ticket names the version the assets were trapped in, as it is in the hash.
+				let ticket = match versioned {
+					VersionedMultiAssets::V3(_) => Here.into(),
+					VersionedMultiAssets::V2(_) => GeneralIndex(2).into(),
+				};
+				let assets: MultiAssets =
+					versioned.try_into().map_err(|()| Error::<T>::BadVersion)?;
+				assets_count += assets.len() as u32;
+				message.push(ClaimAsset { assets, ticket });
+			}
+			message.push(DepositAsset { assets: Wild(AllCounted(assets_count)), beneficiary });
+
+			let message = Xcm(message);
+			let hash = message.using_encoded(sp_io::hashing::blake2_256);
+			let outcome =
+				T::XcmExecutor::execute_xcm_in_credit(owner, message, hash, max_weight, max_weight);
+			let result =
+				Ok(Some(outcome.weight_used().saturating_add(T::WeightInfo::execute())).into());
+			Self::deposit_event(Event::Attempted(outcome));
+			result
+		}
 	}
 }
 
 /// The maximum number of distinct assets allowed to be transferred in a single helper extrinsic.
 const MAX_ASSETS_FOR_TRANSFER: usize = 2;
 
+/// The maximum number of traps indexed in `TrappedAssets` for a single origin. Traps beyond this
+/// can still be claimed, they just can't be found through the index.
+const MAX_INDEXED_TRAPS_PER_ORIGIN: u32 = 64;
+
 impl<T: Config> Pallet<T> {
 	fn do_reserve_transfer_assets(
 		origin: OriginFor<T>,
@@ -1837,10 +1922,24 @@ impl<T: Config> DropAssets for Pallet<T> {
 		}
 		let versioned = VersionedMultiAssets::from(MultiAssets::from(assets));
 		let hash = BlakeTwo256::hash_of(&(&origin, &versioned));
-		AssetTraps::<T>::mutate(hash, |n| *n += 1);
+		let count = AssetTraps::<T>::mutate(hash, |n| {
+			*n += 1;
+			*n
+		});
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		if count == 1 {
+			// Only a new trap needs to be indexed.
+			let key = LatestVersionedMultiLocation(origin);
+			let indexed = TrappedAssetsCount::<T>::get(key).unwrap_or(0);
+			weight.saturating_accrue(T::DbWeight::get().reads(1));
+			if indexed < MAX_INDEXED_TRAPS_PER_ORIGIN {
+				TrappedAssets::<T>::insert(key, hash, versioned.clone());
+				TrappedAssetsCount::<T>::insert(key, indexed + 1);
+				weight.saturating_accrue(T::DbWeight::get().writes(2));
+			}
+		}
 		Self::deposit_event(Event::AssetsTrapped(hash, *origin, versioned));
-		// TODO #3735: Put the real weight in there.
-		Weight::zero()
+		weight
 	}
 }
 
@@ -1864,7 +1963,15 @@ impl<T: Config> ClaimAssets for Pallet<T> {
 		let hash = BlakeTwo256::hash_of(&(origin, versioned.clone()));
 		match AssetTraps::<T>::get(hash) {
 			0 => return false,
-			1 => AssetTraps::<T>::remove(hash),
+			1 => {
+				AssetTraps::<T>::remove(hash);
+				let key = LatestVersionedMultiLocation(origin);
+				if TrappedAssets::<T>::take(key, hash).is_some() {
+					TrappedAssetsCount::<T>::mutate_exists(key, |n| {
+						*n = n.and_then(|n| n.checked_sub(1)).filter(|n| *n > 0)
+					});
+				}
+			},
 			n => AssetTraps::<T>::insert(hash, n - 1),
 		}
 		Self::deposit_event(Event::AssetsClaimed(hash, *origin, versioned));
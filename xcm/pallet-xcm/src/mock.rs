@@ -33,7 +33,7 @@ use xcm_builder::{
 	FixedWeightBounds, IsConcrete, SignedAccountId32AsNative, SignedToAccountId32,
 	SovereignSignedViaLocation, TakeWeightCredit,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{traits::InspectMessageQueues, XcmExecutor};
 
 use crate::{self as pallet_xcm, TestWeightInfo};
 
@@ -174,6 +174,17 @@ impl SendXcm for TestSendXcm {
 		Ok(hash)
 	}
 }
+impl InspectMessageQueues for TestSendXcm {
+	fn clear_messages() {
+		take_sent_xcm();
+	}
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		sent_xcm()
+			.into_iter()
+			.map(|(dest, msg)| (dest.into(), vec![VersionedXcm::from(msg)]))
+			.collect()
+	}
+}
 /// Sender that returns error if `X8` junction and stops routing
 pub struct TestSendXcmErrX8;
 impl SendXcm for TestSendXcmErrX8 {
@@ -303,6 +314,8 @@ impl xcm_executor::Config for XcmConfig {
 	type FeeManager = ();
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
+	type Aliasers = Nothing;
+	type HrmpHandler = ();
 	type CallDispatcher = RuntimeCall;
 	type SafeCallFilter = Everything;
 }
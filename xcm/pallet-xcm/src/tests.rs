@@ -15,8 +15,9 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	mock::*, AssetTraps, CurrentMigration, Error, LatestVersionedMultiLocation, Queries,
-	QueryStatus, VersionDiscoveryQueue, VersionNotifiers, VersionNotifyTargets,
+	dry_run::XcmDryRunError, mock::*, AssetTraps, CurrentMigration, Error,
+	LatestVersionedMultiLocation, Queries, QueryStatus, TrappedAssets, TrappedAssetsCount,
+	VersionDiscoveryQueue, VersionNotifiers, VersionNotifyTargets, MAX_INDEXED_TRAPS_PER_ORIGIN,
 };
 use frame_support::{
 	assert_noop, assert_ok,
@@ -24,10 +25,16 @@ use frame_support::{
 	weights::Weight,
 };
 use polkadot_parachain::primitives::Id as ParaId;
-use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash};
+use sp_runtime::{
+	traits::{AccountIdConversion, BlakeTwo256, Hash},
+	DispatchError,
+};
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::AllowKnownQueryResponses;
-use xcm_executor::{traits::ShouldExecute, XcmExecutor};
+use xcm_executor::{
+	traits::{ClaimAssets, DropAssets, ShouldExecute},
+	XcmExecutor,
+};
 
 const ALICE: AccountId = AccountId::new([0u8; 32]);
 const BOB: AccountId = AccountId::new([1u8; 32]);
@@ -717,6 +724,253 @@ fn trapped_assets_can_be_claimed() {
 	});
 }
 
+#[test]
+fn dry_run_xcm_reports_trapped_assets_and_rolls_back() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let xcm = Xcm::<()>(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			Trap(0),
+		]);
+
+		let effects = XcmPallet::dry_run_xcm::<TestSendXcm>(origin.into(), xcm.into()).unwrap();
+
+		let vma = VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)));
+		let hash = BlakeTwo256::hash_of(&(origin, vma.clone()));
+		assert_eq!(
+			effects.outcome,
+			Outcome::Incomplete(BaseXcmWeight::get() * 3, XcmError::Trap(0))
+		);
+		assert_eq!(effects.weight_used, BaseXcmWeight::get() * 3);
+		assert_eq!(effects.trapped_assets, vec![vma.clone()]);
+		assert_eq!(
+			effects.emitted_events.last(),
+			Some(&codec::Encode::encode(&RuntimeEvent::XcmPallet(crate::Event::AssetsTrapped(
+				hash, origin, vma
+			))))
+		);
+		assert_eq!(effects.forwarded_xcms, vec![]);
+
+		// Nothing was actually withdrawn or trapped.
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert_eq!(AssetTraps::<Test>::iter().collect::<Vec<_>>(), vec![]);
+	});
+}
+
+#[test]
+fn dry_run_xcm_reports_forwarded_messages() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: MultiLocation = Parachain(PARA_ID).into();
+		let xcm = Xcm::<()>(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			DepositReserveAsset { assets: AllCounted(1).into(), dest, xcm: Xcm(vec![]) },
+		]);
+
+		let effects = XcmPallet::dry_run_xcm::<TestSendXcm>(origin.into(), xcm.into()).unwrap();
+
+		assert_eq!(effects.outcome, Outcome::Complete(BaseXcmWeight::get() * 3));
+		assert_eq!(effects.trapped_assets, vec![]);
+		assert_eq!(effects.forwarded_xcms.len(), 1);
+		assert_eq!(effects.forwarded_xcms[0].0, VersionedMultiLocation::from(dest));
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		// The message isn't left in the queue of the router.
+		assert_eq!(sent_xcm(), vec![]);
+	});
+}
+
+#[test]
+fn dry_run_xcm_rejects_unweighable_messages() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let xcm = Xcm::<()>(vec![ClearOrigin; MaxInstructions::get() as usize + 1]);
+
+		assert_eq!(
+			XcmPallet::dry_run_xcm::<TestSendXcm>(origin.into(), xcm.into()),
+			Err(XcmDryRunError::UnweighableMessage)
+		);
+	});
+}
+
+#[test]
+fn trapped_assets_are_indexed_by_origin() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+
+		assert_ok!(XcmPallet::execute(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(Xcm(vec![
+				WithdrawAsset((Here, SEND_AMOUNT).into()),
+				buy_execution((Here, SEND_AMOUNT)),
+				Trap(0),
+			]))),
+			BaseXcmWeight::get() * 3
+		));
+		let vma = VersionedMultiAssets::from(MultiAssets::from((Here, SEND_AMOUNT)));
+		let hash = BlakeTwo256::hash_of(&(source.clone(), vma.clone()));
+		assert_eq!(XcmPallet::trapped_assets(source.into()), vec![(hash, vma)]);
+		assert_eq!(XcmPallet::trapped_assets(dest.into()), vec![]);
+
+		assert_ok!(XcmPallet::execute(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(Xcm(vec![
+				ClaimAsset { assets: (Here, SEND_AMOUNT).into(), ticket: Here.into() },
+				buy_execution((Here, SEND_AMOUNT)),
+				DepositAsset { assets: AllCounted(1).into(), beneficiary: dest },
+			]))),
+			BaseXcmWeight::get() * 3
+		));
+		assert_eq!(XcmPallet::trapped_assets(source.into()), vec![]);
+		assert_eq!(TrappedAssets::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn trapped_assets_index_is_bounded_per_origin() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let context = XcmContext::with_message_hash([0; 32]);
+		let trap = |amount: u128| {
+			let assets: MultiAssets = (Here, amount).into();
+			<XcmPallet as DropAssets>::drop_assets(&origin, assets.clone().into(), &context);
+			BlakeTwo256::hash_of(&(origin, VersionedMultiAssets::from(assets)))
+		};
+
+		let first = trap(1);
+		// Trapping the same assets again only bumps the count of the existing trap.
+		trap(1);
+		for amount in 2..=MAX_INDEXED_TRAPS_PER_ORIGIN as u128 {
+			trap(amount);
+		}
+		let unindexed = trap(MAX_INDEXED_TRAPS_PER_ORIGIN as u128 + 1);
+		assert_eq!(
+			XcmPallet::trapped_assets(origin.into()).len(),
+			MAX_INDEXED_TRAPS_PER_ORIGIN as usize
+		);
+		assert_eq!(AssetTraps::<Test>::get(first), 2);
+		assert_eq!(AssetTraps::<Test>::get(unindexed), 1);
+		assert!(!TrappedAssets::<Test>::contains_key(
+			LatestVersionedMultiLocation(&origin),
+			unindexed
+		));
+
+		// Claiming an unindexed trap leaves the index alone.
+		let claim = |amount: u128| {
+			<XcmPallet as ClaimAssets>::claim_assets(
+				&origin,
+				&Here.into(),
+				&(Here, amount).into(),
+				&context,
+			)
+		};
+		assert!(claim(MAX_INDEXED_TRAPS_PER_ORIGIN as u128 + 1));
+		assert_eq!(
+			TrappedAssetsCount::<Test>::get(LatestVersionedMultiLocation(&origin)),
+			Some(MAX_INDEXED_TRAPS_PER_ORIGIN)
+		);
+
+		// Claiming an indexed trap makes room for a new one, but only once it is fully claimed.
+		assert!(claim(1));
+		assert_eq!(
+			TrappedAssetsCount::<Test>::get(LatestVersionedMultiLocation(&origin)),
+			Some(MAX_INDEXED_TRAPS_PER_ORIGIN)
+		);
+		assert!(claim(1));
+		assert_eq!(
+			TrappedAssetsCount::<Test>::get(LatestVersionedMultiLocation(&origin)),
+			Some(MAX_INDEXED_TRAPS_PER_ORIGIN - 1)
+		);
+		let hash = trap(MAX_INDEXED_TRAPS_PER_ORIGIN as u128 + 2);
+		assert!(TrappedAssets::<Test>::contains_key(LatestVersionedMultiLocation(&origin), hash));
+
+		for amount in 2..=MAX_INDEXED_TRAPS_PER_ORIGIN as u128 + 2 {
+			claim(amount);
+		}
+		assert_eq!(TrappedAssets::<Test>::iter().count(), 0);
+		assert_eq!(TrappedAssetsCount::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn claim_trapped_assets_works() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: MultiLocation =
+			Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let dest: MultiLocation = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+
+		for amount in [SEND_AMOUNT, SEND_AMOUNT * 2] {
+			assert_ok!(XcmPallet::execute(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(VersionedXcm::from(Xcm(vec![
+					WithdrawAsset((Here, amount).into()),
+					buy_execution((Here, amount)),
+					Trap(0),
+				]))),
+				BaseXcmWeight::get() * 3
+			));
+		}
+		let traps: Vec<_> = XcmPallet::trapped_assets(source.into())
+			.into_iter()
+			.map(|(hash, _)| hash)
+			.collect();
+		assert_eq!(traps.len(), 2);
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT * 3);
+
+		assert_noop!(
+			XcmPallet::claim_trapped_assets(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(source.into()),
+				traps.clone(),
+				Box::new(dest.into()),
+				BaseXcmWeight::get() * 3,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			XcmPallet::claim_trapped_assets(
+				RuntimeOrigin::root(),
+				Box::new(dest.into()),
+				traps.clone(),
+				Box::new(dest.into()),
+				BaseXcmWeight::get() * 3,
+			),
+			Error::<Test>::UnknownTrap
+		);
+
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::root(),
+			Box::new(source.into()),
+			traps,
+			Box::new(dest.into()),
+			BaseXcmWeight::get() * 3,
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(
+				BaseXcmWeight::get() * 3
+			)))
+		);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + SEND_AMOUNT * 3);
+		assert_eq!(XcmPallet::trapped_assets(source.into()), vec![]);
+		assert_eq!(AssetTraps::<Test>::iter().collect::<Vec<_>>(), vec![]);
+	});
+}
+
 #[test]
 fn fake_latest_versioned_multilocation_works() {
 	use codec::Encode;
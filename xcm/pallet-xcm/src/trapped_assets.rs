@@ -0,0 +1,43 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Finding the assets trapped by a location, e.g. to claim them back.
+
+use crate::{Config, LatestVersionedMultiLocation, Pallet, TrappedAssets};
+use sp_core::H256;
+use sp_std::prelude::*;
+use xcm::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	/// API to find the assets trapped by a location.
+	pub trait TrappedAssetsApi {
+		/// Returns the assets trapped by `origin`, along with the hash identifying each trap.
+		fn trapped_assets(origin: VersionedMultiLocation) -> Vec<(H256, VersionedMultiAssets)>;
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the assets trapped by `origin`, along with the hash identifying each trap.
+	///
+	/// Only the traps which are in the `TrappedAssets` index are returned.
+	pub fn trapped_assets(origin: VersionedMultiLocation) -> Vec<(H256, VersionedMultiAssets)> {
+		let origin: MultiLocation = match origin.try_into() {
+			Ok(origin) => origin,
+			Err(()) => return Vec::new(),
+		};
+		TrappedAssets::<T>::iter_prefix(LatestVersionedMultiLocation(&origin)).collect()
+	}
+}
@@ -0,0 +1,93 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Implementations of `HandleHrmp`, for use as the `HrmpHandler` of the XCM executor on a
+//! parachain.
+
+use frame_support::traits::{Contains, Get};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::HandleHrmp;
+
+/// Encodes the calls of the relay chain's HRMP pallet (`runtime/parachains/src/hrmp.rs`), as the
+/// relay chain's runtime would decode them.
+pub trait EncodeHrmpCall {
+	/// The encoded `hrmp_accept_open_channel { sender }` call.
+	fn hrmp_accept_open_channel(sender: u32) -> Vec<u8>;
+}
+
+/// Accepts the requests to open a channel from the paras in `Whitelist`, by asking the relay
+/// chain to dispatch `hrmp_accept_open_channel` as this parachain.
+///
+/// The call is paid for with `Fee`, an asset as seen from the relay chain, withdrawn from the
+/// sovereign account of this parachain on the relay chain; what is left of it is deposited back.
+/// `CallWeight` is the weight of the call on the relay chain.
+///
+/// Requests from the other paras are left pending, and the other notifications are ignored.
+pub struct AcceptHrmpChannelsFrom<Whitelist, Encoder, Sender, UniversalLocation, Fee, CallWeight>(
+	PhantomData<(Whitelist, Encoder, Sender, UniversalLocation, Fee, CallWeight)>,
+);
+impl<
+		Whitelist: Contains<u32>,
+		Encoder: EncodeHrmpCall,
+		Sender: SendXcm,
+		UniversalLocation: Get<InteriorMultiLocation>,
+		Fee: Get<MultiAsset>,
+		CallWeight: Get<Weight>,
+	> HandleHrmp
+	for AcceptHrmpChannelsFrom<Whitelist, Encoder, Sender, UniversalLocation, Fee, CallWeight>
+{
+	fn handle_new_channel_open_request(
+		sender: u32,
+		_max_message_size: u32,
+		_max_capacity: u32,
+	) -> XcmResult {
+		if !Whitelist::contains(&sender) {
+			log::trace!(
+				target: "xcm::hrmp",
+				"Leaving the request to open a channel from para {} pending",
+				sender,
+			);
+			return Ok(())
+		}
+		let beneficiary = MultiLocation::here()
+			.reanchored(&Parent.into(), UniversalLocation::get())
+			.map_err(|_| XcmError::LocationNotInvertible)?;
+		let fee = Fee::get();
+		let message = Xcm(vec![
+			WithdrawAsset(fee.clone().into()),
+			BuyExecution { fees: fee, weight_limit: Unlimited },
+			Transact {
+				origin_kind: OriginKind::Native,
+				require_weight_at_most: CallWeight::get(),
+				call: Encoder::hrmp_accept_open_channel(sender).into(),
+			},
+			RefundSurplus,
+			DepositAsset { assets: Wild(AllCounted(1)), beneficiary },
+		]);
+		log::debug!(target: "xcm::hrmp", "Accepting the channel from para {}", sender);
+		send_xcm::<Sender>(Parent.into(), message)?;
+		Ok(())
+	}
+
+	fn handle_channel_accepted(_recipient: u32) -> XcmResult {
+		Ok(())
+	}
+
+	fn handle_channel_closing(_initiator: u32, _sender: u32, _recipient: u32) -> XcmResult {
+		Ok(())
+	}
+}
@@ -40,6 +40,12 @@ pub use origin_conversion::{
 	SignedToAccountId32, SovereignSignedViaLocation,
 };
 
+mod hrmp;
+pub use hrmp::{AcceptHrmpChannelsFrom, EncodeHrmpCall};
+
+mod origin_aliases;
+pub use origin_aliases::{AliasChildLocation, AliasForeignAccountId32, AliasLocalAccountsFrom};
+
 mod asset_conversion;
 pub use asset_conversion::{
 	AsPrefixedGeneralIndex, ConvertedAbstractId, ConvertedConcreteId, MatchedConvertedConcreteId,
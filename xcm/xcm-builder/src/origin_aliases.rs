@@ -0,0 +1,93 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Various implementations of `ContainsPair<MultiLocation, MultiLocation>`, for use as the
+//! `Aliasers` of the XCM executor.
+
+use frame_support::traits::{Contains, ContainsPair};
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+
+/// Allows a location to alias any location in its interior, e.g. a parachain aliasing one of its
+/// accounts.
+///
+/// This grants no more than `DescendOrigin` does, but doesn't require the origin to be known
+/// relative to its interior.
+pub struct AliasChildLocation;
+impl ContainsPair<MultiLocation, MultiLocation> for AliasChildLocation {
+	fn contains(origin: &MultiLocation, target: &MultiLocation) -> bool {
+		log::trace!(
+			target: "xcm::contains",
+			"AliasChildLocation origin: {:?}, target: {:?}",
+			origin,
+			target,
+		);
+		target != origin && target.starts_with(origin)
+	}
+}
+
+/// Allows a foreign `AccountId32` to alias the local `AccountId32` with the same id, if the
+/// location of the foreign account is in `Prefix`, e.g. one of some trusted sibling parachains.
+pub struct AliasForeignAccountId32<Prefix>(PhantomData<Prefix>);
+impl<Prefix: Contains<MultiLocation>> ContainsPair<MultiLocation, MultiLocation>
+	for AliasForeignAccountId32<Prefix>
+{
+	fn contains(origin: &MultiLocation, target: &MultiLocation) -> bool {
+		log::trace!(
+			target: "xcm::contains",
+			"AliasForeignAccountId32 origin: {:?}, target: {:?}",
+			origin,
+			target,
+		);
+		let (prefix, account) = origin.split_last_interior();
+		match (account, target) {
+			(
+				Some(AccountId32 { network: None, id }),
+				MultiLocation {
+					parents: 0,
+					interior: X1(AccountId32 { network: None, id: target_id }),
+				},
+			) => id == *target_id && Prefix::contains(&prefix),
+			_ => false,
+		}
+	}
+}
+
+/// Allows the locations in `Origins` to alias any local account, e.g. a trusted system parachain
+/// acting on behalf of the accounts of this chain.
+///
+/// **Warning**: This hands the control over all accounts to `Origins`, only use it with locations
+/// which are trusted with that.
+pub struct AliasLocalAccountsFrom<Origins>(PhantomData<Origins>);
+impl<Origins: Contains<MultiLocation>> ContainsPair<MultiLocation, MultiLocation>
+	for AliasLocalAccountsFrom<Origins>
+{
+	fn contains(origin: &MultiLocation, target: &MultiLocation) -> bool {
+		log::trace!(
+			target: "xcm::contains",
+			"AliasLocalAccountsFrom origin: {:?}, target: {:?}",
+			origin,
+			target,
+		);
+		matches!(
+			target,
+			MultiLocation {
+				parents: 0,
+				interior: X1(AccountId32 { .. } | AccountKey20 { .. } | AccountIndex64 { .. })
+			}
+		) && Origins::contains(origin)
+	}
+}
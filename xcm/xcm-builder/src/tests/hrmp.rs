@@ -0,0 +1,88 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+#[test]
+fn hrmp_notifications_must_come_from_the_relay_chain() {
+	AllowUnpaidFrom::set(vec![Parent.into(), Parachain(1).into()]);
+	AcceptChannelsFrom::set(vec![1]);
+
+	let message =
+		Xcm(vec![HrmpNewChannelOpenRequest { sender: 1, max_message_size: 1024, max_capacity: 8 }]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Incomplete(Weight::from_parts(10, 10), XcmError::BadOrigin));
+	assert_eq!(sent_xcm(), vec![]);
+
+	let message = Xcm(vec![HrmpChannelAccepted { recipient: 1 }]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Incomplete(Weight::from_parts(10, 10), XcmError::BadOrigin));
+
+	let message = Xcm(vec![HrmpChannelClosing { initiator: 1, sender: 1, recipient: 42 }]);
+	let hash = fake_message_hash(&message);
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm(Parent, message, hash, Weight::from_parts(50, 50));
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(10, 10)));
+}
+
+#[test]
+fn accept_hrmp_channels_from_should_work() {
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+	AcceptChannelsFrom::set(vec![1]);
+
+	// A request from a para which isn't whitelisted is left pending.
+	let message =
+		Xcm(vec![HrmpNewChannelOpenRequest { sender: 2, max_message_size: 1024, max_capacity: 8 }]);
+	let hash = fake_message_hash(&message);
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm(Parent, message, hash, Weight::from_parts(50, 50));
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(10, 10)));
+	assert_eq!(sent_xcm(), vec![]);
+
+	// A request from a whitelisted para is accepted by asking the relay chain to dispatch the call.
+	let message =
+		Xcm(vec![HrmpNewChannelOpenRequest { sender: 1, max_message_size: 1024, max_capacity: 8 }]);
+	let hash = fake_message_hash(&message);
+	let r =
+		XcmExecutor::<TestConfig>::execute_xcm(Parent, message, hash, Weight::from_parts(50, 50));
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(10, 10)));
+	let fee: MultiAsset = (Here, 100u128).into();
+	let expected_msg = Xcm::<()>(vec![
+		WithdrawAsset(fee.clone().into()),
+		BuyExecution { fees: fee, weight_limit: Unlimited },
+		Transact {
+			origin_kind: OriginKind::Native,
+			require_weight_at_most: Weight::from_parts(1_000, 1_000),
+			call: (60u8, 1u8, 1u32).encode().into(),
+		},
+		RefundSurplus,
+		DepositAsset { assets: Wild(AllCounted(1)), beneficiary: Parachain(42).into() },
+	]);
+	let expected_hash = fake_message_hash(&expected_msg);
+	assert_eq!(sent_xcm(), vec![(Parent.into(), expected_msg, expected_hash)]);
+}
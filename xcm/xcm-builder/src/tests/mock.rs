@@ -16,8 +16,9 @@
 
 use crate::{barriers::AllowSubscriptionsFrom, test_utils::*};
 pub use crate::{
-	AllowExplicitUnpaidExecutionFrom, AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom,
-	AllowUnpaidExecutionFrom, FixedRateOfFungible, FixedWeightBounds, TakeWeightCredit,
+	AcceptHrmpChannelsFrom, AliasForeignAccountId32, AllowExplicitUnpaidExecutionFrom,
+	AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
+	EncodeHrmpCall, FixedRateOfFungible, FixedWeightBounds, TakeWeightCredit,
 };
 use frame_support::traits::{ContainsPair, Everything};
 pub use frame_support::{
@@ -352,6 +353,13 @@ impl Contains<(MultiLocation, Junction)> for TestUniversalAliases {
 	}
 }
 
+pub struct SiblingPrefix;
+impl Contains<MultiLocation> for SiblingPrefix {
+	fn contains(t: &MultiLocation) -> bool {
+		matches!(t, MultiLocation { parents: 1, interior: X1(Parachain(_)) })
+	}
+}
+
 pub enum ResponseSlot {
 	Expecting(MultiLocation),
 	Received(Response),
@@ -620,6 +628,28 @@ impl AssetExchange for TestAssetExchange {
 	}
 }
 
+parameter_types! {
+	pub static AcceptChannelsFrom: Vec<u32> = vec![];
+	pub HrmpFee: MultiAsset = (Here, 100u128).into();
+	pub HrmpCallWeight: Weight = Weight::from_parts(1_000, 1_000);
+}
+
+pub struct TestHrmpCalls;
+impl EncodeHrmpCall for TestHrmpCalls {
+	fn hrmp_accept_open_channel(sender: u32) -> Vec<u8> {
+		(60u8, 1u8, sender).encode()
+	}
+}
+
+pub type TestHrmpHandler = AcceptHrmpChannelsFrom<
+	IsInVec<AcceptChannelsFrom>,
+	TestHrmpCalls,
+	TestMessageSender,
+	ExecutorUniversalLocation,
+	HrmpFee,
+	HrmpCallWeight,
+>;
+
 pub struct TestConfig;
 impl Config for TestConfig {
 	type RuntimeCall = TestCall;
@@ -642,6 +672,8 @@ impl Config for TestConfig {
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
 	type FeeManager = TestFeeManager;
 	type UniversalAliases = TestUniversalAliases;
+	type Aliasers = AliasForeignAccountId32<SiblingPrefix>;
+	type HrmpHandler = TestHrmpHandler;
 	type MessageExporter = TestMessageExporter;
 	type CallDispatcher = TestCall;
 	type SafeCallFilter = Everything;
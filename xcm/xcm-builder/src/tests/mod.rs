@@ -31,6 +31,7 @@ mod barriers;
 mod basic;
 mod bridging;
 mod expecting;
+mod hrmp;
 mod locking;
 mod origins;
 mod querying;
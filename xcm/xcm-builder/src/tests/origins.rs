@@ -138,3 +138,80 @@ fn unpaid_execution_should_work() {
 	);
 	assert_eq!(r, Outcome::Complete(Weight::from_parts(10, 10)));
 }
+
+#[test]
+fn alias_origin_should_work() {
+	let account = AccountId32 { network: None, id: [0; 32] };
+	AllowUnpaidFrom::set(vec![
+		MultiLocation::new(1, X2(Parachain(1), account)),
+		MultiLocation::new(2, X2(Parachain(1), account)),
+	]);
+
+	// A sibling account may not alias a local account with a different id.
+	let message = Xcm(vec![AliasOrigin(AccountId32 { network: None, id: [1; 32] }.into())]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		MultiLocation::new(1, X2(Parachain(1), account)),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Incomplete(Weight::from_parts(10, 10), XcmError::NoPermission));
+
+	// An account of a parachain which isn't a sibling may not alias a local account.
+	let message = Xcm(vec![AliasOrigin(account.into())]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		MultiLocation::new(2, X2(Parachain(1), account)),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Incomplete(Weight::from_parts(10, 10), XcmError::NoPermission));
+
+	// A sibling account may alias the local account with the same id, which becomes the origin.
+	let message = Xcm(vec![
+		AliasOrigin(account.into()),
+		UnpaidExecution { weight_limit: Unlimited, check_origin: Some(account.into()) },
+	]);
+	let hash = fake_message_hash(&message);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		MultiLocation::new(1, X2(Parachain(1), account)),
+		message,
+		hash,
+		Weight::from_parts(50, 50),
+	);
+	assert_eq!(r, Outcome::Complete(Weight::from_parts(20, 20)));
+}
+
+#[test]
+fn alias_child_location_should_work() {
+	let para = MultiLocation::new(1, X1(Parachain(1)));
+	let account =
+		MultiLocation::new(1, X2(Parachain(1), AccountId32 { network: None, id: [0; 32] }));
+	assert!(AliasChildLocation::contains(&para, &account));
+	assert!(!AliasChildLocation::contains(&account, &para));
+	assert!(!AliasChildLocation::contains(&para, &para));
+	assert!(!AliasChildLocation::contains(&MultiLocation::new(1, X1(Parachain(2))), &account));
+}
+
+#[test]
+fn alias_local_accounts_from_should_work() {
+	struct TrustedPara;
+	impl Contains<MultiLocation> for TrustedPara {
+		fn contains(t: &MultiLocation) -> bool {
+			t == &MultiLocation::new(1, X1(Parachain(1000)))
+		}
+	}
+	type Aliaser = AliasLocalAccountsFrom<TrustedPara>;
+
+	let trusted = MultiLocation::new(1, X1(Parachain(1000)));
+	let untrusted = MultiLocation::new(1, X1(Parachain(1)));
+	let account: MultiLocation = AccountId32 { network: None, id: [0; 32] }.into();
+	let key: MultiLocation = AccountKey20 { network: None, key: [0; 20] }.into();
+	assert!(Aliaser::contains(&trusted, &account));
+	assert!(Aliaser::contains(&trusted, &key));
+	assert!(!Aliaser::contains(&untrusted, &account));
+	assert!(!Aliaser::contains(&trusted, &MultiLocation::here()));
+	assert!(!Aliaser::contains(&trusted, &MultiLocation::new(1, X1(Parachain(1)))));
+}
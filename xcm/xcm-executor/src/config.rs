@@ -16,8 +16,8 @@
 
 use crate::traits::{
 	AssetExchange, AssetLock, CallDispatcher, ClaimAssets, ConvertOrigin, DropAssets, ExportXcm,
-	FeeManager, OnResponse, ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds,
-	WeightTrader,
+	FeeManager, HandleHrmp, OnResponse, ShouldExecute, TransactAsset, VersionChangeNotifier,
+	WeightBounds, WeightTrader,
 };
 use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo, Parameter, PostDispatchInfo},
@@ -95,6 +95,13 @@ pub trait Config {
 	/// themselves.
 	type UniversalAliases: Contains<(MultiLocation, Junction)>;
 
+	/// The origin locations and the locations they are allowed to alias with `AliasOrigin`, as
+	/// `(origin, target)` pairs.
+	type Aliasers: ContainsPair<MultiLocation, MultiLocation>;
+
+	/// How to react to the HRMP channel management notifications sent by the relay chain.
+	type HrmpHandler: HandleHrmp;
+
 	/// The call dispatcher used by XCM.
 	///
 	/// XCM will use this to dispatch any calls. When no special call dispatcher is required,
@@ -31,8 +31,8 @@ use xcm::latest::prelude::*;
 pub mod traits;
 use traits::{
 	validate_export, AssetExchange, AssetLock, CallDispatcher, ClaimAssets, ConvertOrigin,
-	DropAssets, Enact, ExportXcm, FeeManager, FeeReason, OnResponse, ShouldExecute, TransactAsset,
-	VersionChangeNotifier, WeightBounds, WeightTrader,
+	DropAssets, Enact, ExportXcm, FeeManager, FeeReason, HandleHrmp, OnResponse, ShouldExecute,
+	TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
 };
 
 mod assets;
@@ -909,7 +909,15 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				self.context.topic = None;
 				Ok(())
 			},
-			AliasOrigin(_) => Err(XcmError::NoPermission),
+			AliasOrigin(target) => {
+				let origin = self.origin_ref().ok_or(XcmError::BadOrigin)?;
+				if Config::Aliasers::contains(origin, &target) {
+					self.context.origin = Some(target);
+					Ok(())
+				} else {
+					Err(XcmError::NoPermission)
+				}
+			},
 			UnpaidExecution { check_origin, .. } => {
 				ensure!(
 					check_origin.is_none() || self.context.origin == check_origin,
@@ -917,12 +925,32 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				);
 				Ok(())
 			},
-			HrmpNewChannelOpenRequest { .. } => Err(XcmError::Unimplemented),
-			HrmpChannelAccepted { .. } => Err(XcmError::Unimplemented),
-			HrmpChannelClosing { .. } => Err(XcmError::Unimplemented),
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } => {
+				self.ensure_relay_chain_origin()?;
+				Config::HrmpHandler::handle_new_channel_open_request(
+					sender,
+					max_message_size,
+					max_capacity,
+				)
+			},
+			HrmpChannelAccepted { recipient } => {
+				self.ensure_relay_chain_origin()?;
+				Config::HrmpHandler::handle_channel_accepted(recipient)
+			},
+			HrmpChannelClosing { initiator, sender, recipient } => {
+				self.ensure_relay_chain_origin()?;
+				Config::HrmpHandler::handle_channel_closing(initiator, sender, recipient)
+			},
 		}
 	}
 
+	/// Ensures the origin is the relay chain, the only one allowed to send HRMP notifications.
+	fn ensure_relay_chain_origin(&self) -> XcmResult {
+		let origin = self.origin_ref().ok_or(XcmError::BadOrigin)?;
+		ensure!(*origin == MultiLocation::parent(), XcmError::BadOrigin);
+		Ok(())
+	}
+
 	fn take_fee(&mut self, fee: MultiAssets, reason: FeeReason) -> XcmResult {
 		if Config::FeeManager::is_waived(self.origin_ref(), reason) {
 			return Ok(())
@@ -0,0 +1,51 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::latest::{Error as XcmError, Result as XcmResult};
+
+/// Handle the HRMP channel management notifications the relay chain sends to a parachain.
+///
+/// The executor only calls these once it has checked that the notification comes from the relay
+/// chain (i.e. the origin is the parent).
+pub trait HandleHrmp {
+	/// A para with ID `sender` requested to open a channel to this para.
+	fn handle_new_channel_open_request(
+		sender: u32,
+		max_message_size: u32,
+		max_capacity: u32,
+	) -> XcmResult;
+
+	/// The para with ID `recipient` accepted the channel this para requested to open to it.
+	fn handle_channel_accepted(recipient: u32) -> XcmResult;
+
+	/// The para with ID `initiator` closed the channel from `sender` to `recipient`, one of which
+	/// is this para.
+	fn handle_channel_closing(initiator: u32, sender: u32, recipient: u32) -> XcmResult;
+}
+
+impl HandleHrmp for () {
+	fn handle_new_channel_open_request(_: u32, _: u32, _: u32) -> XcmResult {
+		Err(XcmError::Unimplemented)
+	}
+
+	fn handle_channel_accepted(_: u32) -> XcmResult {
+		Err(XcmError::Unimplemented)
+	}
+
+	fn handle_channel_closing(_: u32, _: u32, _: u32) -> XcmResult {
+		Err(XcmError::Unimplemented)
+	}
+}
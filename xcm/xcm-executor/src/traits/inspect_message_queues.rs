@@ -0,0 +1,46 @@
+// Copyright 2023 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use sp_std::prelude::*;
+use xcm::{VersionedMultiLocation, VersionedXcm};
+
+/// Inspects the messages a router has queued for delivery, e.g. to report the messages which a
+/// dry-run of an XCM program forwarded.
+///
+/// Can be amalgamated into a tuple, in which case the messages of all the elements are reported.
+pub trait InspectMessageQueues {
+	/// Removes all the messages queued for delivery.
+	///
+	/// This is only meant to be used in a storage transaction which is later rolled back. A dry-run
+	/// also calls it once it's done, for the routers whose queues aren't kept in storage.
+	fn clear_messages();
+
+	/// Returns the messages queued for delivery, grouped by destination.
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl InspectMessageQueues for Tuple {
+	fn clear_messages() {
+		for_tuples!( #( Tuple::clear_messages(); )* );
+	}
+
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		let mut messages = Vec::new();
+		for_tuples!( #( messages.append(&mut Tuple::get_messages()); )* );
+		messages
+	}
+}
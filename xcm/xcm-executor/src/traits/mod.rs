@@ -30,6 +30,8 @@ mod export;
 pub use export::{export_xcm, validate_export, ExportXcm};
 mod fee_manager;
 pub use fee_manager::{FeeManager, FeeReason};
+mod hrmp;
+pub use hrmp::HandleHrmp;
 mod filter_asset_location;
 #[allow(deprecated)]
 pub use filter_asset_location::FilterAssetLocation;
@@ -37,6 +39,8 @@ mod token_matching;
 pub use token_matching::{
 	Error, MatchesFungible, MatchesFungibles, MatchesNonFungible, MatchesNonFungibles,
 };
+mod inspect_message_queues;
+pub use inspect_message_queues::InspectMessageQueues;
 mod on_response;
 pub use on_response::{OnResponse, VersionChangeNotifier};
 mod should_execute;
@@ -49,9 +53,9 @@ pub use weight::{WeightBounds, WeightTrader};
 pub mod prelude {
 	pub use super::{
 		export_xcm, validate_export, AssetExchange, AssetLock, ClaimAssets, Convert, ConvertOrigin,
-		Decoded, DropAssets, Enact, Encoded, Error, ExportXcm, FeeManager, FeeReason, Identity,
-		JustTry, LockError, MatchesFungible, MatchesFungibles, MatchesNonFungible,
-		MatchesNonFungibles, OnResponse, ShouldExecute, TransactAsset, VersionChangeNotifier,
-		WeightBounds, WeightTrader, WithOriginFilter,
+		Decoded, DropAssets, Enact, Encoded, Error, ExportXcm, FeeManager, FeeReason, HandleHrmp,
+		Identity, InspectMessageQueues, JustTry, LockError, MatchesFungible, MatchesFungibles,
+		MatchesNonFungible, MatchesNonFungibles, OnResponse, ShouldExecute, TransactAsset,
+		VersionChangeNotifier, WeightBounds, WeightTrader, WithOriginFilter,
 	};
 }
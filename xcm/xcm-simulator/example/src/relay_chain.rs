@@ -182,6 +182,8 @@ impl Config for XcmConfig {
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
+	type Aliasers = Nothing;
+	type HrmpHandler = ();
 	type CallDispatcher = RuntimeCall;
 	type SafeCallFilter = Everything;
 }
@@ -228,6 +230,9 @@ impl ump::Config for Runtime {
 	type UmpSink = ump::XcmSink<XcmExecutor<XcmConfig>, Runtime>;
 	type FirstMessageFactorPercent = FirstMessageFactorPercent;
 	type ExecuteOverweightOrigin = frame_system::EnsureRoot<AccountId>;
+	type RetryOverweightOnIdle = frame_support::traits::ConstBool<false>;
+	type OverweightExpiry = ();
+	type OnOverweightExpired = ();
 	type WeightInfo = ump::TestWeightInfo;
 }
 